@@ -12,7 +12,7 @@ fn iter_with_unchecked_loop(buf: &direct::SequentialSliceOfVecs<&[Vec<i32>]>) ->
             }
         }
     }
-    return sum;
+    sum
 }
 
 pub fn bench_with_unchecked_loop(c: &mut Criterion) {
@@ -31,7 +31,7 @@ fn iter_with_safe_loop(buf: &direct::SequentialSliceOfVecs<&[Vec<i32>]>) -> i32
             sum += buf.read_sample(channel, frame).unwrap();
         }
     }
-    return sum;
+    sum
 }
 
 pub fn bench_with_safe_loop(c: &mut Criterion) {
@@ -50,7 +50,7 @@ fn iter_with_iter_trait(buf: &direct::SequentialSliceOfVecs<&[Vec<i32>]>) -> i32
             sum += value;
         }
     }
-    return sum;
+    sum
 }
 
 pub fn bench_with_iter_trait(c: &mut Criterion) {
@@ -71,7 +71,7 @@ fn iter_with_i32le_float_conversion(
             sum += value;
         }
     }
-    return sum;
+    sum
 }
 
 pub fn bench_with_i32le_float_conversion(c: &mut Criterion) {
@@ -94,7 +94,7 @@ fn iter_with_i24le_float_conversion(
             sum += value;
         }
     }
-    return sum;
+    sum
 }
 
 pub fn bench_with_i24le_float_conversion(c: &mut Criterion) {
@@ -107,10 +107,122 @@ pub fn bench_with_i24le_float_conversion(c: &mut Criterion) {
     });
 }
 
+// write with format conversion
+fn write_with_float_conversion<'a>(buf: &mut dyn AdapterMut<'a, f32>, values: &[f32]) -> usize {
+    let mut nbr_clipped = 0;
+    for channel in 0..buf.channels() {
+        nbr_clipped += buf.write_from_slice_to_channel(channel, 0, values).1;
+    }
+    nbr_clipped
+}
+
+pub fn bench_float_to_i16le_conversion(c: &mut Criterion) {
+    let mut data = vec![0_u8; 40000];
+    let values = vec![0.5_f32; 10000];
+    let mut buffer = number_to_float::SequentialNumbers::<&mut [I16LE], f32>::new_from_bytes_mut(
+        &mut data, 2, 10000,
+    )
+    .unwrap();
+    c.bench_function("convert_float_to_i16le", |b| {
+        b.iter(|| black_box(write_with_float_conversion(&mut buffer, black_box(&values))))
+    });
+}
+
+pub fn bench_float_to_i24le_conversion(c: &mut Criterion) {
+    let mut data = vec![0_u8; 60000];
+    let values = vec![0.5_f32; 10000];
+    let mut buffer =
+        number_to_float::SequentialNumbers::<&mut [I24LE<3>], f32>::new_from_bytes_mut(
+            &mut data, 2, 10000,
+        )
+        .unwrap();
+    c.bench_function("convert_float_to_i24le", |b| {
+        b.iter(|| black_box(write_with_float_conversion(&mut buffer, black_box(&values))))
+    });
+}
+
+pub fn bench_float_to_i32le_conversion(c: &mut Criterion) {
+    let mut data = vec![0_u8; 80000];
+    let values = vec![0.5_f32; 10000];
+    let mut buffer = number_to_float::SequentialNumbers::<&mut [I32LE], f32>::new_from_bytes_mut(
+        &mut data, 2, 10000,
+    )
+    .unwrap();
+    c.bench_function("convert_float_to_i32le", |b| {
+        b.iter(|| black_box(write_with_float_conversion(&mut buffer, black_box(&values))))
+    });
+}
+
+pub fn bench_float_to_interleaved_i16le_conversion(c: &mut Criterion) {
+    let mut data = vec![0_u8; 40000];
+    let values = vec![0.5_f32; 10000];
+    let mut buffer = number_to_float::InterleavedNumbers::<&mut [I16LE], f32>::new_from_bytes_mut(
+        &mut data, 2, 10000,
+    )
+    .unwrap();
+    c.bench_function("convert_float_to_interleaved_i16le", |b| {
+        b.iter(|| black_box(write_with_float_conversion(&mut buffer, black_box(&values))))
+    });
+}
+
+// native iteration of an audio crate buffer
+fn iter_audio_native(buf: &::audio::buf::Sequential<i32>) -> i32 {
+    let mut sum = 0;
+    for channel in ::audio::Buf::iter_channels(buf) {
+        for value in ::audio::Channel::iter(&channel) {
+            sum += value;
+        }
+    }
+    sum
+}
+
+pub fn bench_audio_native_iter(c: &mut Criterion) {
+    let buffer = ::audio::buf::Sequential::<i32>::with_topology(2, 10000);
+    c.bench_function("audio_native_iter", |b| {
+        b.iter(|| black_box(iter_audio_native(black_box(&buffer))))
+    });
+}
+
+// read an audio crate buffer one channel at a time via the adapter traits
+fn iter_audio_channel_slices(buf: &dyn Adapter<i32>, values: &mut [i32]) -> i32 {
+    let mut sum = 0;
+    for channel in 0..buf.channels() {
+        buf.write_from_channel_to_slice(channel, 0, values);
+        sum += values.iter().sum::<i32>();
+    }
+    sum
+}
+
+pub fn bench_audio_channel_slices(c: &mut Criterion) {
+    let buffer = ::audio::buf::Sequential::<i32>::with_topology(2, 10000);
+    let mut values = vec![0; 10000];
+    c.bench_function("audio_channel_slices", |b| {
+        b.iter(|| black_box(iter_audio_channel_slices(black_box(&buffer), &mut values)))
+    });
+}
+
+// read an audio crate buffer one frame at a time via the adapter traits
+fn iter_audio_frame_slices(buf: &dyn Adapter<i32>, values: &mut [i32]) -> i32 {
+    let mut sum = 0;
+    for frame in 0..buf.frames() {
+        buf.write_from_frame_to_slice(frame, 0, values);
+        sum += values.iter().sum::<i32>();
+    }
+    sum
+}
+
+pub fn bench_audio_frame_slices(c: &mut Criterion) {
+    let buffer = ::audio::buf::Sequential::<i32>::with_topology(2, 10000);
+    let mut values = vec![0; 2];
+    c.bench_function("audio_frame_slices", |b| {
+        b.iter(|| black_box(iter_audio_frame_slices(black_box(&buffer), &mut values)))
+    });
+}
+
 // standard iteration of slices, for comparison
 fn iter_slice(buf: &[Vec<i32>]) -> i32 {
     let sum = buf.iter().map(|v| v.iter().sum::<i32>()).sum();
-    return sum;
+    sum
 }
 
 pub fn bench_slice_iter(c: &mut Criterion) {
@@ -127,6 +239,13 @@ criterion_group!(
     bench_with_iter_trait,
     bench_slice_iter,
     bench_with_i32le_float_conversion,
-    bench_with_i24le_float_conversion
+    bench_with_i24le_float_conversion,
+    bench_float_to_i16le_conversion,
+    bench_float_to_i24le_conversion,
+    bench_float_to_i32le_conversion,
+    bench_float_to_interleaved_i16le_conversion,
+    bench_audio_native_iter,
+    bench_audio_channel_slices,
+    bench_audio_frame_slices
 );
 criterion_main!(benches);
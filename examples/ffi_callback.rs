@@ -0,0 +1,114 @@
+// This example shows how to wrap the non-interleaved buffers
+// that C audio APIs pass to their process callbacks.
+//
+// PortAudio, with the paNonInterleaved flag, passes the input and output
+// as tables of channel pointers, `const float**` and `float**`.
+// JACK instead gives one pointer per port, that is fetched with
+// `jack_port_get_buffer()`, and these pointers can be collected into
+// a vector of slices.
+//
+// There are no real audio APIs here, the callbacks are called from `main()`
+// in the same way as a C library would call them.
+
+use audioadapter::direct::SequentialVecOfSlices;
+use audioadapter::{Adapter, AdapterMut};
+use std::os::raw::{c_int, c_ulong, c_void};
+
+// A callback with the same signature as a PortAudio stream callback.
+// The `input` and `output` arguments are `const float**` and `float**`.
+extern "C" fn portaudio_style_callback(
+    input: *const c_void,
+    output: *mut c_void,
+    frame_count: c_ulong,
+    _time_info: *const c_void,
+    _status_flags: c_ulong,
+    user_data: *mut c_void,
+) -> c_int {
+    let channels = unsafe { *(user_data as *const usize) };
+    let frames = frame_count as usize;
+
+    // Safety: the audio API guarantees that the tables hold one pointer per channel,
+    // that each channel holds `frames` samples,
+    // and that the buffers are valid until the callback returns.
+    let input = unsafe {
+        SequentialVecOfSlices::from_raw_parts(input as *const *const f32, channels, frames)
+    }
+    .unwrap();
+    let mut output = unsafe {
+        SequentialVecOfSlices::from_raw_parts_mut(output as *const *mut f32, channels, frames)
+    }
+    .unwrap();
+
+    // Copy the input to the output with half the volume.
+    for channel in 0..channels {
+        output.write_from_other_to_channel_with_gain(&input, channel, channel, 0, 0, frames, 0.5);
+    }
+    0
+}
+
+// A process function like one that would be called from a JACK process callback,
+// after fetching the buffer of each port with `jack_port_get_buffer()`.
+fn jack_style_process(in_ports: &[*const f32], out_ports: &[*mut f32], nframes: u32) {
+    let frames = nframes as usize;
+    // Safety: JACK guarantees that each port buffer holds `nframes` samples,
+    // and that the buffers of different ports do not overlap.
+    let inputs = in_ports
+        .iter()
+        .map(|ptr| unsafe { std::slice::from_raw_parts(*ptr, frames) })
+        .collect();
+    let outputs = out_ports
+        .iter()
+        .map(|ptr| unsafe { std::slice::from_raw_parts_mut(*ptr, frames) })
+        .collect();
+    let input = SequentialVecOfSlices::new(inputs, frames).unwrap();
+    let mut output = SequentialVecOfSlices::new_mut(outputs, frames).unwrap();
+
+    // Swap the left and right channels.
+    output.write_from_other_to_channel(&input, 0, 1, 0, 0, frames);
+    output.write_from_other_to_channel(&input, 1, 0, 0, 0, frames);
+}
+
+fn main() {
+    let channels: usize = 2;
+    let frames = 4;
+
+    // The buffers that the audio API would own.
+    let left_in = [0.1_f32, 0.2, 0.3, 0.4];
+    let right_in = [-0.1_f32, -0.2, -0.3, -0.4];
+    let mut left_out = vec![0.0_f32; frames];
+    let mut right_out = vec![0.0_f32; frames];
+
+    let input_table = [left_in.as_ptr(), right_in.as_ptr()];
+    let output_table = [left_out.as_mut_ptr(), right_out.as_mut_ptr()];
+    portaudio_style_callback(
+        input_table.as_ptr() as *const c_void,
+        output_table.as_ptr() as *mut c_void,
+        frames as c_ulong,
+        std::ptr::null(),
+        0,
+        &channels as *const usize as *mut c_void,
+    );
+    println!("PortAudio style, left: {:?}", left_out);
+    println!("PortAudio style, right: {:?}", right_out);
+    assert_eq!(left_out, [0.05, 0.1, 0.15, 0.2]);
+
+    jack_style_process(
+        &input_table,
+        &[left_out.as_mut_ptr(), right_out.as_mut_ptr()],
+        frames as u32,
+    );
+    println!("JACK style, left: {:?}", left_out);
+    println!("JACK style, right: {:?}", right_out);
+    assert_eq!(right_out, left_in);
+
+    // The same buffers, read via the trait methods.
+    let buffer = SequentialVecOfSlices::new(vec![&left_out[..], &right_out[..]], frames).unwrap();
+    for frame in 0..buffer.frames() {
+        println!(
+            "Frame {}: {:?}, {:?}",
+            frame,
+            buffer.read_sample(0, frame),
+            buffer.read_sample(1, frame)
+        );
+    }
+}
@@ -4,6 +4,13 @@
 //! that already implement the `audioadapter` traits.
 //! The wrappers enable reading and writing samples from/to another buffer
 //! with on-the-fly format conversion.
+//! [ConvertBytes] and [ConvertNumbers] give float access
+//! to buffers of samples stored as bytes or numbers,
+//! while [ConvertToNumbers] gives numerical access to a buffer of floats.
+//!
+//! The float type is a type parameter of the wrappers.
+//! With `f64`, the values do not pass through `f32`,
+//! and buffers of `f64` samples are read and written without any loss of precision.
 //!
 //! ## Example
 //! Wrap a `Vec<i16>` as an interleaved buffer,
@@ -35,12 +42,17 @@
 //! }
 //! ```
 
+use core::any::Any;
+use core::cell::Cell;
+use core::marker::PhantomData;
+
 use num_traits::Float;
 
+use crate::cliplog::ClipLog;
+use crate::layout::transfer_length;
 use crate::sample::BytesSample;
-use crate::sample::RawSample;
-use crate::sample::I16LE;
-use crate::{Adapter, AdapterMut};
+use crate::sample::{limit_to_ceiling, Ceiling, RawSample, RoundingMode};
+use crate::{Adapter, AdapterMut, IterationOrder};
 
 macro_rules! implement_wrapped_size_getters {
     () => {
@@ -51,116 +63,418 @@ macro_rules! implement_wrapped_size_getters {
         fn frames(&self) -> usize {
             self.buf.frames()
         }
+
+        fn is_channel_active(&self, channel: usize) -> bool {
+            self.buf.is_channel_active(channel)
+        }
+
+        fn preferred_iteration(&self) -> IterationOrder {
+            self.buf.preferred_iteration()
+        }
+
+        fn metadata(&self) -> Option<&dyn Any> {
+            self.buf.metadata()
+        }
+    };
+}
+
+/// Transfer values between slices and the channels and frames of the wrapper,
+/// with the bounds checks done by the shared functions of the `layout` module.
+macro_rules! implement_wrapped_slice_reads {
+    () => {
+        fn write_from_channel_to_slice(
+            &self,
+            channel: usize,
+            skip: usize,
+            slice: &mut [T],
+        ) -> usize {
+            let count =
+                match transfer_length(channel, self.channels(), skip, self.frames(), slice.len()) {
+                    Some(count) => count,
+                    None => return 0,
+                };
+            for (n, item) in slice[..count].iter_mut().enumerate() {
+                unsafe { *item = self.read_sample_unchecked(channel, skip + n) };
+            }
+            count
+        }
+
+        fn write_from_frame_to_slice(&self, frame: usize, skip: usize, slice: &mut [T]) -> usize {
+            let count =
+                match transfer_length(frame, self.frames(), skip, self.channels(), slice.len()) {
+                    Some(count) => count,
+                    None => return 0,
+                };
+            for (n, item) in slice[..count].iter_mut().enumerate() {
+                unsafe { *item = self.read_sample_unchecked(skip + n, frame) };
+            }
+            count
+        }
+    };
+}
+
+macro_rules! implement_wrapped_slice_writes {
+    () => {
+        fn write_from_slice_to_channel(
+            &mut self,
+            channel: usize,
+            skip: usize,
+            slice: &[T],
+        ) -> (usize, usize) {
+            let count =
+                match transfer_length(channel, self.channels(), skip, self.frames(), slice.len()) {
+                    Some(count) => count,
+                    None => return (0, 0),
+                };
+            let mut nbr_clipped = 0;
+            for (n, item) in slice[..count].iter().enumerate() {
+                unsafe {
+                    nbr_clipped += self.write_sample_unchecked(channel, skip + n, item) as usize
+                };
+            }
+            (count, nbr_clipped)
+        }
+
+        fn write_from_slice_to_frame(
+            &mut self,
+            frame: usize,
+            skip: usize,
+            slice: &[T],
+        ) -> (usize, usize) {
+            let count =
+                match transfer_length(frame, self.frames(), skip, self.channels(), slice.len()) {
+                    Some(count) => count,
+                    None => return (0, 0),
+                };
+            let mut nbr_clipped = 0;
+            for (n, item) in slice[..count].iter().enumerate() {
+                unsafe {
+                    nbr_clipped += self.write_sample_unchecked(skip + n, frame, item) as usize
+                };
+            }
+            (count, nbr_clipped)
+        }
     };
 }
 
 /// A wrapper for an [Adapter] or [AdapterMut] buffer containing samples
 /// stored as byte arrays.
 /// The wrapper enables reading and writing the samples as floats.
+///
+/// The byte arrays must have the size given by the sample type `U`,
+/// and using a buffer of arrays with another size fails to compile:
+/// ```compile_fail
+/// use audioadapter::adapter_to_float::ConvertBytes;
+/// use audioadapter::direct::InterleavedSlice;
+/// use audioadapter::sample::I32LE;
+/// use audioadapter::Adapter;
+///
+/// let data = [[0_u8; 2]; 4];
+/// let buffer = InterleavedSlice::new(&data, 2, 2).unwrap();
+/// let converter: ConvertBytes<f32, I32LE, _> =
+///     ConvertBytes::new(&buffer as &dyn Adapter<[u8; 2]>);
+/// ```
+///
+/// The wrapped buffer is accessed via a trait object without a [Send] or [Sync] bound,
+/// and therefore the wrapper is neither [Send] nor [Sync].
 pub struct ConvertBytes<T, U, V>
 where
     T: Float,
     U: BytesSample,
 {
-    _phantom: core::marker::PhantomData<T>,
-    _phantom_raw: core::marker::PhantomData<U>,
+    _phantom: core::marker::PhantomData<fn() -> T>,
+    _phantom_raw: core::marker::PhantomData<fn() -> U>,
+    rounding: RoundingMode,
+    ceiling: Option<Ceiling<T>>,
+    clip_log: ClipLog,
     buf: V,
 }
 
-macro_rules! byte_convert_traits_newtype {
-    ($typename:ident) => {
-        impl<'a, T, U> ConvertBytes<T, U, &'a dyn Adapter<'a, [u8; $typename::BYTES_PER_SAMPLE]>>
-            where
-                T: Float + 'a,
-                U: BytesSample + RawSample + 'a,
-            {
-                #[doc = "Create a new wrapper for an [Adapter] buffer of byte arrays, `[u8;  U::BYTES_PER_SAMPLE ]`,"]
-                #[doc = "containing samples of type ` $typename `."]
-                pub fn new(
-                    buf: &'a dyn Adapter<'a, [u8; $typename::BYTES_PER_SAMPLE]>,
-                ) -> Self {
-                    Self {
-                        _phantom: core::marker::PhantomData,
-                        _phantom_raw: core::marker::PhantomData,
-                        buf,
-                    }
-                }
-            }
+impl<T, U, V> ConvertBytes<T, U, V>
+where
+    T: Float,
+    U: BytesSample,
+{
+    /// Set the rounding mode used when writing float values to integer samples.
+    /// The default is [RoundingMode::Truncate].
+    pub fn set_rounding(&mut self, rounding: RoundingMode) {
+        self.rounding = rounding;
+    }
 
-            impl<'a, T, U> ConvertBytes<T, U, &'a mut dyn AdapterMut<'a, [u8; $typename::BYTES_PER_SAMPLE]>>
-            where
-                T: Float + 'a,
-                U: BytesSample + RawSample + 'a,
-            {
-                #[doc = "Create a new wrapper for an mutable [AdapterMut] buffer of byte arrays, `[u8;  $bytes ]`,"]
-                #[doc = "containing samples of type ` $typename `."]
-                pub fn new_mut(
-                    buf: &'a mut dyn AdapterMut<'a, [u8; $typename::BYTES_PER_SAMPLE]>,
-                ) -> Self {
-                    Self {
-                        _phantom: core::marker::PhantomData,
-                        _phantom_raw: core::marker::PhantomData,
-                        buf,
-                    }
-                }
-            }
+    /// Get the rounding mode used when writing float values to integer samples.
+    pub fn rounding(&self) -> RoundingMode {
+        self.rounding
+    }
 
-            impl<'a, T, U> Adapter<'a, T> for ConvertBytes<T, U, &'a dyn Adapter<'a, [u8; $typename::BYTES_PER_SAMPLE]>>
-            where
-            T: Float + 'a,
-            U: BytesSample + RawSample + 'a,
-            {
-                unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
-                    let raw = self.buf.read_sample_unchecked(channel, frame);
-                    let sample = U::from_slice(&raw);
-                    sample.to_scaled_float::<T>()
-                }
-
-                implement_wrapped_size_getters!();
-            }
+    /// Set a ceiling in dBFS for the values written to the buffer,
+    /// for example `-1.0` to keep the output 1 dB below full scale.
+    /// Written values above the ceiling are clamped to it,
+    /// and are counted as clipped.
+    /// Setting `None` removes the ceiling, which is the default.
+    pub fn set_ceiling_dbfs(&mut self, ceiling: Option<T>) {
+        self.ceiling = ceiling.map(Ceiling::from_dbfs);
+    }
 
-            impl<'a, T, U> Adapter<'a, T> for ConvertBytes<T, U, &'a mut dyn AdapterMut<'a, [u8; $typename::BYTES_PER_SAMPLE]>>
-            where
-            T: Float + 'a,
-            U: BytesSample + RawSample + 'a,
-            {
-                unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
-                    let raw = self.buf.read_sample_unchecked(channel, frame);
-                    let sample = U::from_slice(&raw);
-                    sample.to_scaled_float::<T>()
-                }
-
-                implement_wrapped_size_getters!();
-            }
+    /// Get the ceiling in dBFS for the values written to the buffer.
+    pub fn ceiling_dbfs(&self) -> Option<T> {
+        self.ceiling.map(|ceiling| ceiling.dbfs())
+    }
 
-            impl<'a, T, U> AdapterMut<'a, T> for ConvertBytes<T, U, &'a mut dyn AdapterMut<'a, [u8; $typename::BYTES_PER_SAMPLE]>>
-            where
-            T: Float + 'a,
-            U: BytesSample + RawSample + 'a,
-            {
-                unsafe fn write_sample_unchecked(&mut self, channel: usize, frame: usize, value: &T) -> bool {
-                    let converted = U::from_scaled_float(*value);
-                    self.buf.write_sample_unchecked(channel, frame, converted.value.as_slice().try_into().unwrap());
-                    converted.clipped
-                }
-
-                fn copy_frames_within(&mut self, src: usize, dest: usize, count: usize) -> Option<usize> {
-                    self.buf.copy_frames_within(src, dest, count)
-                }
-            }
+    /// Enable or disable logging a warning when values are clipped while writing.
+    /// Only the first clipping is logged, and calling this method rearms the warning.
+    /// Logging is enabled by default.
+    #[cfg(feature = "log")]
+    pub fn set_log_clipping(&mut self, enabled: bool) {
+        self.clip_log.set_enabled(enabled);
+    }
+
+    /// Check if logging of clipped values is enabled.
+    #[cfg(feature = "log")]
+    pub fn log_clipping(&self) -> bool {
+        self.clip_log.is_enabled()
+    }
+
+    /// Enable recording of the positions of clipped samples when writing.
+    /// For each channel, the frame indices of the first `max_per_channel`
+    /// clipped samples are recorded, and can be read with [ConvertBytes::clip_positions].
+    /// A value of zero disables the recording, which is the default.
+    /// This also clears any previously recorded positions.
+    #[cfg(feature = "std")]
+    pub fn set_record_clip_positions(&mut self, max_per_channel: usize) {
+        self.clip_log.set_max_positions(max_per_channel);
+    }
+
+    /// Get the recorded frame indices of the clipped samples of a channel,
+    /// in the order they were written.
+    #[cfg(feature = "std")]
+    pub fn clip_positions(&self, channel: usize) -> &[usize] {
+        self.clip_log.positions(channel)
+    }
+
+    /// Clear the recorded positions of clipped samples.
+    #[cfg(feature = "std")]
+    pub fn clear_clip_positions(&mut self) {
+        self.clip_log.clear_positions();
+    }
+}
+
+impl<'a, T, U> ConvertBytes<T, U, &'a dyn Adapter<'a, U::Raw>>
+where
+    T: Float + 'a,
+    U: BytesSample + RawSample + 'a,
+{
+    /// Create a new wrapper for an [Adapter] buffer of byte arrays, `[u8; U::BYTES_PER_SAMPLE]`,
+    /// containing samples of type `U`.
+    /// The size of the byte arrays is given by the sample type,
+    /// and a buffer of arrays with the wrong size is rejected at compile time.
+    pub fn new(buf: &'a dyn Adapter<'a, U::Raw>) -> Self {
+        Self {
+            _phantom: core::marker::PhantomData,
+            _phantom_raw: core::marker::PhantomData,
+            rounding: RoundingMode::Truncate,
+            ceiling: None,
+            clip_log: ClipLog::new(),
+            buf,
         }
+    }
 }
 
-byte_convert_traits_newtype!(I16LE);
+impl<'a, T, U> ConvertBytes<T, U, &'a mut dyn AdapterMut<'a, U::Raw>>
+where
+    T: Float + 'a,
+    U: BytesSample + RawSample + 'a,
+{
+    /// Create a new wrapper for a mutable [AdapterMut] buffer of byte arrays, `[u8; U::BYTES_PER_SAMPLE]`,
+    /// containing samples of type `U`.
+    /// The size of the byte arrays is given by the sample type,
+    /// and a buffer of arrays with the wrong size is rejected at compile time.
+    pub fn new_mut(buf: &'a mut dyn AdapterMut<'a, U::Raw>) -> Self {
+        Self {
+            _phantom: core::marker::PhantomData,
+            _phantom_raw: core::marker::PhantomData,
+            rounding: RoundingMode::Truncate,
+            ceiling: None,
+            clip_log: ClipLog::new(),
+            buf,
+        }
+    }
+}
+
+impl<'a, T, U> Adapter<'a, T> for ConvertBytes<T, U, &'a dyn Adapter<'a, U::Raw>>
+where
+    T: Float + 'a,
+    U: BytesSample + RawSample + 'a,
+{
+    unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
+        let raw = self.buf.read_sample_unchecked(channel, frame);
+        U::from_raw(raw).to_scaled_float::<T>()
+    }
+
+    implement_wrapped_size_getters!();
+    implement_wrapped_slice_reads!();
+}
+
+impl<'a, T, U> Adapter<'a, T> for ConvertBytes<T, U, &'a mut dyn AdapterMut<'a, U::Raw>>
+where
+    T: Float + 'a,
+    U: BytesSample + RawSample + 'a,
+{
+    unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
+        let raw = self.buf.read_sample_unchecked(channel, frame);
+        U::from_raw(raw).to_scaled_float::<T>()
+    }
+
+    implement_wrapped_size_getters!();
+    implement_wrapped_slice_reads!();
+}
+
+impl<'a, T, U> AdapterMut<'a, T> for ConvertBytes<T, U, &'a mut dyn AdapterMut<'a, U::Raw>>
+where
+    T: Float + 'a,
+    U: BytesSample + RawSample + 'a,
+{
+    unsafe fn write_sample_unchecked(&mut self, channel: usize, frame: usize, value: &T) -> bool {
+        let (value, limited) = limit_to_ceiling(*value, self.ceiling);
+        let converted = U::from_scaled_float(U::round_scaled(value, self.rounding));
+        #[cfg(feature = "verify-roundtrip")]
+        crate::roundtrip::verify_stored::<T, U>(&converted.value);
+        self.buf
+            .write_sample_unchecked(channel, frame, &converted.value.to_raw());
+        let clipped = limited || converted.clipped;
+        self.clip_log.report(clipped as usize, channel, frame);
+        if clipped {
+            self.clip_log.record(channel, frame);
+        }
+        clipped
+    }
+
+    implement_wrapped_slice_writes!();
+
+    fn copy_frames_within(&mut self, src: usize, dest: usize, count: usize) -> Option<usize> {
+        self.buf.copy_frames_within(src, dest, count)
+    }
+}
 
 /// A wrapper for an [Adapter] or [AdapterMut] buffer containing samples
 /// stored as numeric types.
 /// The wrapper enables reading and writing the samples as floats.
+///
+/// By default, full scale of the wrapped samples is mapped to the range -1.0 to +1.0.
+/// For wrapped buffers of floats where full scale is something else,
+/// such as ±2^23 for raw dumps of 24-bit DSP data,
+/// the full scale value can be set using [ConvertNumbers::set_full_scale].
+///
+/// Values written outside the range -1.0 to +1.0 are clipped to the nearest limit,
+/// also when the wrapped samples are floats.
+/// For wrapped float samples, this can be disabled using [ConvertNumbers::set_clipping].
+///
+/// The wrapped buffer is accessed via a trait object without a [Send] or [Sync] bound,
+/// and therefore the wrapper is neither [Send] nor [Sync].
 pub struct ConvertNumbers<U, V> {
-    _phantom: core::marker::PhantomData<V>,
+    full_scale: V,
+    inverse_full_scale: V,
+    clipping: bool,
+    rounding: RoundingMode,
+    ceiling: Option<Ceiling<V>>,
+    clip_log: ClipLog,
     buf: U,
 }
 
+impl<U, V> ConvertNumbers<U, V>
+where
+    V: Float,
+{
+    /// Set the value of the wrapped samples that corresponds to full scale.
+    /// Samples are divided by this value when reading,
+    /// and multiplied by it when writing.
+    /// For wrapped integer samples, the value applies after the normal
+    /// scaling of the integer range to -1.0 .. +1.0.
+    pub fn set_full_scale(&mut self, full_scale: V) {
+        self.full_scale = full_scale;
+        self.inverse_full_scale = V::one() / full_scale;
+    }
+
+    /// Get the value of the wrapped samples that corresponds to full scale.
+    pub fn full_scale(&self) -> V {
+        self.full_scale
+    }
+
+    /// Enable or disable clipping of written values to the range -1.0 .. +1.0.
+    /// Clipping is enabled by default.
+    /// Integer samples are always limited to the range of the integer type,
+    /// so disabling clipping only makes a difference for float samples.
+    pub fn set_clipping(&mut self, clipping: bool) {
+        self.clipping = clipping;
+    }
+
+    /// Check if clipping of written values is enabled.
+    pub fn clipping(&self) -> bool {
+        self.clipping
+    }
+
+    /// Set the rounding mode used when writing float values to integer samples.
+    /// The default is [RoundingMode::Truncate].
+    pub fn set_rounding(&mut self, rounding: RoundingMode) {
+        self.rounding = rounding;
+    }
+
+    /// Get the rounding mode used when writing float values to integer samples.
+    pub fn rounding(&self) -> RoundingMode {
+        self.rounding
+    }
+
+    /// Set a ceiling in dBFS for the values written to the buffer,
+    /// for example `-1.0` to keep the output 1 dB below full scale.
+    /// Written values above the ceiling are clamped to it,
+    /// and are counted as clipped.
+    /// Setting `None` removes the ceiling, which is the default.
+    pub fn set_ceiling_dbfs(&mut self, ceiling: Option<V>) {
+        self.ceiling = ceiling.map(Ceiling::from_dbfs);
+    }
+
+    /// Get the ceiling in dBFS for the values written to the buffer.
+    pub fn ceiling_dbfs(&self) -> Option<V> {
+        self.ceiling.map(|ceiling| ceiling.dbfs())
+    }
+
+    /// Enable or disable logging a warning when values are clipped while writing.
+    /// Only the first clipping is logged, and calling this method rearms the warning.
+    /// Logging is enabled by default.
+    #[cfg(feature = "log")]
+    pub fn set_log_clipping(&mut self, enabled: bool) {
+        self.clip_log.set_enabled(enabled);
+    }
+
+    /// Check if logging of clipped values is enabled.
+    #[cfg(feature = "log")]
+    pub fn log_clipping(&self) -> bool {
+        self.clip_log.is_enabled()
+    }
+
+    /// Enable recording of the positions of clipped samples when writing.
+    /// For each channel, the frame indices of the first `max_per_channel`
+    /// clipped samples are recorded, and can be read with [ConvertNumbers::clip_positions].
+    /// A value of zero disables the recording, which is the default.
+    /// This also clears any previously recorded positions.
+    #[cfg(feature = "std")]
+    pub fn set_record_clip_positions(&mut self, max_per_channel: usize) {
+        self.clip_log.set_max_positions(max_per_channel);
+    }
+
+    /// Get the recorded frame indices of the clipped samples of a channel,
+    /// in the order they were written.
+    #[cfg(feature = "std")]
+    pub fn clip_positions(&self, channel: usize) -> &[usize] {
+        self.clip_log.positions(channel)
+    }
+
+    /// Clear the recorded positions of clipped samples.
+    #[cfg(feature = "std")]
+    pub fn clear_clip_positions(&mut self) {
+        self.clip_log.clear_positions();
+    }
+}
+
 impl<'a, T, U> ConvertNumbers<&'a dyn Adapter<'a, U>, T>
 where
     T: Float + 'a,
@@ -170,7 +484,12 @@ where
     /// containing numerical samples.
     pub fn new(buf: &'a dyn Adapter<'a, U>) -> Self {
         Self {
-            _phantom: core::marker::PhantomData,
+            full_scale: T::one(),
+            inverse_full_scale: T::one(),
+            clipping: true,
+            rounding: RoundingMode::Truncate,
+            ceiling: None,
+            clip_log: ClipLog::new(),
             buf,
         }
     }
@@ -185,7 +504,12 @@ where
     /// containing numerical samples.
     pub fn new_mut(buf: &'a mut dyn AdapterMut<'a, U>) -> Self {
         Self {
-            _phantom: core::marker::PhantomData,
+            full_scale: T::one(),
+            inverse_full_scale: T::one(),
+            clipping: true,
+            rounding: RoundingMode::Truncate,
+            ceiling: None,
+            clip_log: ClipLog::new(),
             buf,
         }
     }
@@ -199,10 +523,12 @@ where
     unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
         self.buf
             .read_sample_unchecked(channel, frame)
-            .to_scaled_float()
+            .to_scaled_float::<T>()
+            * self.inverse_full_scale
     }
 
     implement_wrapped_size_getters!();
+    implement_wrapped_slice_reads!();
 }
 
 impl<'a, T, U> Adapter<'a, T> for ConvertNumbers<&'a mut dyn AdapterMut<'a, U>, T>
@@ -213,10 +539,12 @@ where
     unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
         self.buf
             .read_sample_unchecked(channel, frame)
-            .to_scaled_float()
+            .to_scaled_float::<T>()
+            * self.inverse_full_scale
     }
 
     implement_wrapped_size_getters!();
+    implement_wrapped_slice_reads!();
 }
 
 impl<'a, T, U> AdapterMut<'a, T> for ConvertNumbers<&'a mut dyn AdapterMut<'a, U>, T>
@@ -225,12 +553,158 @@ where
     U: RawSample + Clone + 'a,
 {
     unsafe fn write_sample_unchecked(&mut self, channel: usize, frame: usize, value: &T) -> bool {
-        let converted = U::from_scaled_float(*value);
+        // The ceiling applies also when clipping is disabled.
+        let (mut value, mut clipped) = limit_to_ceiling(*value, self.ceiling);
+        if self.clipping {
+            // Clip before scaling, so that the limits follow the full scale value.
+            clipped |= value.is_nan() || value.abs() > T::one();
+            value = value.max(-T::one()).min(T::one());
+        }
+        let scaled = U::round_scaled(value * self.full_scale, self.rounding);
+        let converted = U::from_scaled_float_unclipped(scaled);
         self.buf
             .write_sample_unchecked(channel, frame, &converted.value);
-        converted.clipped
+        let clipped = clipped || converted.clipped;
+        self.clip_log.report(clipped as usize, channel, frame);
+        if clipped {
+            self.clip_log.record(channel, frame);
+        }
+        clipped
+    }
+
+    implement_wrapped_slice_writes!();
+
+    fn copy_frames_within(&mut self, src: usize, dest: usize, count: usize) -> Option<usize> {
+        self.buf.copy_frames_within(src, dest, count)
+    }
+}
+
+/// A wrapper for an [Adapter] or [AdapterMut] buffer containing float samples,
+/// that enables reading and writing the samples as numerical values of type `T`.
+/// This is the reverse of [ConvertNumbers],
+/// and allows a buffer of floats to be passed to code that expects
+/// for example an `Adapter<i16>` or an `AdapterMut<i32>`.
+/// With a float type for `T`, it also converts between float types,
+/// for example to present a buffer of `f32` as a buffer of `f64`.
+///
+/// Float values in the range -1.0 to +1.0 map to the full range of the numerical type.
+/// Values outside this range are clipped to the nearest limit when read.
+/// Since reading cannot report clipping, the wrapper instead counts the clipped reads,
+/// see [ConvertToNumbers::clipped_reads].
+/// Written values are converted to float and passed on to the wrapped buffer,
+/// which then reports any clipping.
+///
+/// The wrapped buffer is accessed via a trait object without a [Send] or [Sync] bound,
+/// and therefore the wrapper is neither [Send] nor [Sync].
+pub struct ConvertToNumbers<U, T> {
+    rounding: RoundingMode,
+    clipped_reads: Cell<usize>,
+    buf: U,
+    _phantom: PhantomData<T>,
+}
+
+impl<U, T> ConvertToNumbers<U, T> {
+    /// Set the rounding mode used when reading float values as integer samples.
+    /// The default is [RoundingMode::Truncate].
+    pub fn set_rounding(&mut self, rounding: RoundingMode) {
+        self.rounding = rounding;
+    }
+
+    /// Get the rounding mode used when reading float values as integer samples.
+    pub fn rounding(&self) -> RoundingMode {
+        self.rounding
+    }
+
+    /// Get the number of values that were clipped when read,
+    /// since the wrapper was created or the count was last reset.
+    pub fn clipped_reads(&self) -> usize {
+        self.clipped_reads.get()
+    }
+
+    /// Reset the count of values that were clipped when read.
+    pub fn reset_clipped_reads(&mut self) {
+        self.clipped_reads.set(0);
+    }
+}
+
+impl<'a, T, V> ConvertToNumbers<&'a dyn Adapter<'a, V>, T>
+where
+    T: RawSample + 'a,
+    V: Float + 'a,
+{
+    /// Create a new wrapper for a buffer implementing the [Adapter] trait,
+    /// containing float samples.
+    pub fn new(buf: &'a dyn Adapter<'a, V>) -> Self {
+        Self {
+            rounding: RoundingMode::Truncate,
+            clipped_reads: Cell::new(0),
+            buf,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, V> ConvertToNumbers<&'a mut dyn AdapterMut<'a, V>, T>
+where
+    T: RawSample + 'a,
+    V: Float + 'a,
+{
+    /// Create a new wrapper for a mutable buffer implementing the [AdapterMut] trait,
+    /// containing float samples.
+    pub fn new_mut(buf: &'a mut dyn AdapterMut<'a, V>) -> Self {
+        Self {
+            rounding: RoundingMode::Truncate,
+            clipped_reads: Cell::new(0),
+            buf,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+macro_rules! implement_number_reads {
+    () => {
+        unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
+            let value = self.buf.read_sample_unchecked(channel, frame);
+            let converted = T::from_scaled_float(T::round_scaled(value, self.rounding));
+            if converted.clipped {
+                self.clipped_reads.set(self.clipped_reads.get() + 1);
+            }
+            converted.value
+        }
+
+        implement_wrapped_size_getters!();
+        implement_wrapped_slice_reads!();
+    };
+}
+
+impl<'a, T, V> Adapter<'a, T> for ConvertToNumbers<&'a dyn Adapter<'a, V>, T>
+where
+    T: RawSample + Clone + 'a,
+    V: Float + 'a,
+{
+    implement_number_reads!();
+}
+
+impl<'a, T, V> Adapter<'a, T> for ConvertToNumbers<&'a mut dyn AdapterMut<'a, V>, T>
+where
+    T: RawSample + Clone + 'a,
+    V: Float + 'a,
+{
+    implement_number_reads!();
+}
+
+impl<'a, T, V> AdapterMut<'a, T> for ConvertToNumbers<&'a mut dyn AdapterMut<'a, V>, T>
+where
+    T: RawSample + Clone + 'a,
+    V: Float + 'a,
+{
+    unsafe fn write_sample_unchecked(&mut self, channel: usize, frame: usize, value: &T) -> bool {
+        self.buf
+            .write_sample_unchecked(channel, frame, &value.to_scaled_float::<V>())
     }
 
+    implement_wrapped_slice_writes!();
+
     fn copy_frames_within(&mut self, src: usize, dest: usize, count: usize) -> Option<usize> {
         self.buf.copy_frames_within(src, dest, count)
     }
@@ -246,6 +720,7 @@ where
 mod tests {
     use super::*;
     use crate::direct::InterleavedSlice;
+    use crate::sample::{F64LE, I16LE, I24LE};
     use crate::Adapter;
 
     #[test]
@@ -262,6 +737,18 @@ mod tests {
         assert_eq!(converter.read_sample(1, 2).unwrap(), -0.25);
     }
 
+    #[test]
+    fn read_write_i24_bytes() {
+        let mut data: [[u8; 3]; 2] = [[0, 0, 64], [0, 0, 0]];
+        let mut buffer: InterleavedSlice<&mut [[u8; 3]]> =
+            InterleavedSlice::new_mut(&mut data, 1, 2).unwrap();
+        let mut converter: ConvertBytes<f32, I24LE<3>, _> =
+            ConvertBytes::new_mut(&mut buffer as &mut dyn AdapterMut<[u8; 3]>);
+        assert_eq!(converter.read_sample(0, 0).unwrap(), 0.5);
+        converter.write_sample(0, 1, &-0.5).unwrap();
+        assert_eq!(data[1], [0, 0, 192]);
+    }
+
     #[test]
     fn read_i16() {
         let data: [i16; 6] = [0, i16::MIN, 1 << 14, -(1 << 14), 1 << 13, -(1 << 13)];
@@ -276,6 +763,108 @@ mod tests {
         assert_eq!(converter.read_sample(1, 2).unwrap(), -0.25);
     }
 
+    #[test]
+    fn slice_transfers() {
+        let mut data: [i16; 6] = [0, 0, 1 << 14, 0, 0, 0];
+        let mut buffer = InterleavedSlice::new_mut(&mut data, 2, 3).unwrap();
+        let mut converter: ConvertNumbers<&mut dyn AdapterMut<i16>, f32> =
+            ConvertNumbers::new_mut(&mut buffer as &mut dyn AdapterMut<i16>);
+        let mut values = [0.0; 4];
+        assert_eq!(converter.write_from_channel_to_slice(0, 1, &mut values), 2);
+        assert_eq!(values[..2], [0.5, 0.0]);
+        assert_eq!(converter.write_from_channel_to_slice(2, 0, &mut values), 0);
+        assert_eq!(converter.write_from_frame_to_slice(1, 1, &mut values), 1);
+        assert_eq!(converter.write_from_frame_to_slice(3, 0, &mut values), 0);
+        assert_eq!(
+            converter.write_from_slice_to_channel(1, 1, &[0.25, 2.0, 0.5]),
+            (2, 1)
+        );
+        assert_eq!(converter.write_from_slice_to_frame(0, 0, &[-0.5]), (1, 0));
+        assert_eq!(converter.write_from_slice_to_frame(0, 2, &[-0.5]), (0, 0));
+        assert_eq!(data, [-(1 << 14), 0, 1 << 14, 1 << 13, 0, i16::MAX]);
+    }
+
+    #[test]
+    fn full_scale_floats() {
+        let mut data: [f32; 4] = [8388608.0, -4194304.0, 0.0, 0.0];
+        let mut buffer: InterleavedSlice<&mut [f32]> =
+            InterleavedSlice::new_mut(&mut data, 2, 2).unwrap();
+        let mut converter: ConvertNumbers<&mut dyn AdapterMut<f32>, f32> =
+            ConvertNumbers::new_mut(&mut buffer as &mut dyn AdapterMut<f32>);
+        assert_eq!(converter.full_scale(), 1.0);
+        assert_eq!(converter.read_sample(1, 0).unwrap(), -4194304.0);
+        converter.set_full_scale(8388608.0);
+        assert_eq!(converter.read_sample(0, 0).unwrap(), 1.0);
+        assert_eq!(converter.read_sample(1, 0).unwrap(), -0.5);
+        converter.write_sample(1, 1, &0.25).unwrap();
+        assert_eq!(data[3], 2097152.0);
+    }
+
+    #[test]
+    fn clip_floats() {
+        let mut data = [0.0_f32; 4];
+        let mut buffer: InterleavedSlice<&mut [f32]> =
+            InterleavedSlice::new_mut(&mut data, 2, 2).unwrap();
+        let mut converter: ConvertNumbers<&mut dyn AdapterMut<f32>, f64> =
+            ConvertNumbers::new_mut(&mut buffer as &mut dyn AdapterMut<f32>);
+        assert_eq!(converter.write_sample(0, 0, &0.5), Some(false));
+        assert_eq!(converter.write_sample(1, 0, &1.5), Some(true));
+        assert_eq!(converter.write_sample(0, 1, &-1.0e300), Some(true));
+        converter.set_clipping(false);
+        assert_eq!(converter.write_sample(1, 1, &1.0e300), Some(true));
+        assert_eq!(converter.write_sample(1, 0, &1.5), Some(false));
+        assert_eq!(data, [0.5, 1.5, -1.0, f32::MAX]);
+    }
+
+    #[test]
+    fn ceiling_floats() {
+        let mut data = [0.0_f32; 4];
+        let mut buffer: InterleavedSlice<&mut [f32]> =
+            InterleavedSlice::new_mut(&mut data, 2, 2).unwrap();
+        let mut converter: ConvertNumbers<&mut dyn AdapterMut<f32>, f64> =
+            ConvertNumbers::new_mut(&mut buffer as &mut dyn AdapterMut<f32>);
+        assert_eq!(converter.ceiling_dbfs(), None);
+        converter.set_ceiling_dbfs(Some(20.0 * 0.5_f64.log10()));
+        assert_eq!(converter.write_sample(0, 0, &0.25), Some(false));
+        assert_eq!(converter.write_sample(1, 0, &0.75), Some(true));
+        converter.set_clipping(false);
+        assert_eq!(converter.write_sample(0, 1, &-2.0), Some(true));
+        assert_eq!(converter.write_from_slice_to_frame(1, 1, &[0.5]), (1, 0));
+        assert_eq!(data[0], 0.25);
+        assert!((data[1] - 0.5).abs() < 1.0e-6);
+        assert!((data[2] + 0.5).abs() < 1.0e-6);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn record_clip_positions() {
+        let mut data = [0_i16; 6];
+        let mut buffer: InterleavedSlice<&mut [i16]> =
+            InterleavedSlice::new_mut(&mut data, 2, 3).unwrap();
+        let mut converter: ConvertNumbers<&mut dyn AdapterMut<i16>, f32> =
+            ConvertNumbers::new_mut(&mut buffer as &mut dyn AdapterMut<i16>);
+        converter.set_record_clip_positions(4);
+        converter.write_from_slice_to_channel(1, 0, &[1.5, 0.5, -1.5]);
+        converter.write_sample(0, 2, &2.0).unwrap();
+        assert_eq!(converter.clip_positions(0), &[2]);
+        assert_eq!(converter.clip_positions(1), &[0, 2]);
+        assert_eq!(converter.clip_positions(2), &[] as &[usize]);
+    }
+
+    #[test]
+    fn round_when_writing() {
+        let mut data = [0_i16; 2];
+        let mut buffer: InterleavedSlice<&mut [i16]> =
+            InterleavedSlice::new_mut(&mut data, 2, 1).unwrap();
+        let mut converter: ConvertNumbers<&mut dyn AdapterMut<i16>, f32> =
+            ConvertNumbers::new_mut(&mut buffer as &mut dyn AdapterMut<i16>);
+        assert_eq!(converter.rounding(), RoundingMode::Truncate);
+        converter.set_rounding(RoundingMode::NearestAway);
+        converter.write_sample(0, 0, &(0.6 / 32768.0)).unwrap();
+        converter.write_sample(1, 0, &(-1.5 / 32768.0)).unwrap();
+        assert_eq!(data, [1, -2]);
+    }
+
     #[test]
     fn write_i16_bytes() {
         let expected: [[u8; 2]; 6] = [[0, 0], [0, 128], [0, 64], [0, 192], [0, 32], [0, 224]];
@@ -293,6 +882,22 @@ mod tests {
         assert_eq!(data, expected);
     }
 
+    #[test]
+    fn f64_bytes_bit_exact() {
+        let value = 1.0 / 3.0_f64;
+        let mut data = [[0; 8]; 2];
+        let mut buffer: InterleavedSlice<&mut [[u8; 8]]> =
+            InterleavedSlice::new_mut(&mut data, 1, 2).unwrap();
+        let mut converter: ConvertBytes<f64, F64LE, _> =
+            ConvertBytes::new_mut(&mut buffer as &mut dyn AdapterMut<[u8; 8]>);
+        converter.write_sample(0, 1, &value).unwrap();
+        assert_eq!(
+            converter.read_sample(0, 1).unwrap().to_bits(),
+            value.to_bits()
+        );
+        assert_eq!(data[1], value.to_le_bytes());
+    }
+
     #[test]
     fn write_i16() {
         let expected: [i16; 6] = [0, i16::MIN, 1 << 14, -(1 << 14), 1 << 13, -(1 << 13)];
@@ -309,4 +914,39 @@ mod tests {
         converter.write_sample(1, 2, &-0.25).unwrap();
         assert_eq!(data, expected);
     }
+
+    #[test]
+    fn floats_as_numbers() {
+        let data = [0.5_f32, -1.0, 1.5, 0.25];
+        let buffer = InterleavedSlice::new(&data, 2, 2).unwrap();
+        let converter: ConvertToNumbers<&dyn Adapter<f32>, i16> =
+            ConvertToNumbers::new(&buffer as &dyn Adapter<f32>);
+        assert_eq!(converter.channels(), 2);
+        assert_eq!(converter.read_sample(0, 0), Some(1 << 14));
+        assert_eq!(converter.read_sample(1, 0), Some(i16::MIN));
+        assert_eq!(converter.clipped_reads(), 0);
+        assert_eq!(converter.read_sample(0, 1), Some(i16::MAX));
+        assert_eq!(converter.read_sample(1, 1), Some(1 << 13));
+        assert_eq!(converter.clipped_reads(), 1);
+
+        let converter: ConvertToNumbers<&dyn Adapter<f32>, f64> =
+            ConvertToNumbers::new(&buffer as &dyn Adapter<f32>);
+        assert_eq!(converter.read_sample(1, 1), Some(0.25));
+    }
+
+    #[test]
+    fn write_numbers_to_floats() {
+        let mut data = [0.0_f32; 4];
+        let mut buffer = InterleavedSlice::new_mut(&mut data, 2, 2).unwrap();
+        let mut converter: ConvertToNumbers<&mut dyn AdapterMut<f32>, i32> =
+            ConvertToNumbers::new_mut(&mut buffer as &mut dyn AdapterMut<f32>);
+        assert_eq!(converter.write_sample(0, 0, &(1 << 30)), Some(false));
+        assert_eq!(converter.write_sample(1, 1, &i32::MIN), Some(false));
+        assert_eq!(
+            converter.write_from_slice_to_channel(1, 0, &[-(1 << 29)]),
+            (1, 0)
+        );
+        assert_eq!(converter.read_sample(0, 0), Some(1 << 30));
+        assert_eq!(data, [0.5, -0.25, 0.0, -1.0]);
+    }
 }
@@ -0,0 +1,192 @@
+//! # [Arrow](https://crates.io/crates/arrow) compatibility
+//!
+//! This module provides a wrapper for reading audio data stored in Arrow arrays,
+//! with one `Float32Array` per channel, as well as functions for exporting
+//! the contents of any buffer to Arrow arrays.
+//!
+//! The wrapper reads the values directly from the Arrow buffers, without copying.
+//! Null values are not supported,
+//! and any values marked as null are read as whatever value is stored in the array.
+//!
+//! ## Example
+//! Export a buffer to a record batch and wrap it again.
+//! ```
+//! use audioadapter::arrow::{to_record_batch, ArrowChannels};
+//! use audioadapter::direct::InterleavedSlice;
+//! use audioadapter::Adapter;
+//!
+//! let data = [1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0];
+//! let buffer = InterleavedSlice::new(&data, 2, 3).unwrap();
+//!
+//! let batch = to_record_batch(&buffer).unwrap();
+//! assert_eq!(batch.num_columns(), 2);
+//! assert_eq!(batch.num_rows(), 3);
+//!
+//! let wrapped = ArrowChannels::new(&batch).unwrap();
+//! assert_eq!(wrapped.read_sample(1, 2), Some(6.0));
+//! ```
+
+use std::sync::Arc;
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::Float32Type;
+use arrow_array::{ArrayRef, Float32Array, RecordBatch};
+use arrow_schema::{ArrowError, DataType, Field, Schema};
+
+use crate::{Adapter, IterationOrder};
+
+/// A wrapper for Arrow arrays of `f32` values, with one array per channel.
+/// The samples of each channel are stored consecutively,
+/// which means that the wrapper behaves like a sequential buffer.
+pub struct ArrowChannels<'a> {
+    channels: Vec<&'a [f32]>,
+    frames: usize,
+}
+
+impl<'a> ArrowChannels<'a> {
+    /// Wrap a [RecordBatch] where each column is one channel.
+    /// All columns must have the data type `Float32`.
+    pub fn new(batch: &'a RecordBatch) -> Result<Self, ArrowError> {
+        let channels = batch
+            .columns()
+            .iter()
+            .enumerate()
+            .map(|(idx, column)| {
+                column
+                    .as_primitive_opt::<Float32Type>()
+                    .map(|array| array.values().as_ref())
+                    .ok_or_else(|| {
+                        ArrowError::InvalidArgumentError(format!(
+                            "Column {} has data type {}, expected Float32",
+                            idx,
+                            column.data_type()
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            channels,
+            frames: batch.num_rows(),
+        })
+    }
+
+    /// Wrap a slice of arrays where each array is one channel.
+    /// All arrays must have the same length.
+    pub fn from_arrays(arrays: &[&'a Float32Array]) -> Result<Self, ArrowError> {
+        let frames = arrays.first().map(|array| array.len()).unwrap_or_default();
+        if let Some((idx, array)) = arrays
+            .iter()
+            .enumerate()
+            .find(|(_, array)| array.len() != frames)
+        {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Array for channel {} has length {}, expected {}",
+                idx,
+                array.len(),
+                frames
+            )));
+        }
+        Ok(Self {
+            channels: arrays.iter().map(|array| array.values().as_ref()).collect(),
+            frames,
+        })
+    }
+}
+
+impl<'a> Adapter<'a, f32> for ArrowChannels<'a> {
+    unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> f32 {
+        *self.channels.get_unchecked(channel).get_unchecked(frame)
+    }
+
+    fn channels(&self) -> usize {
+        self.channels.len()
+    }
+
+    fn frames(&self) -> usize {
+        self.frames
+    }
+
+    fn preferred_iteration(&self) -> IterationOrder {
+        IterationOrder::ChannelsOuter
+    }
+
+    fn write_from_channel_to_slice(&self, channel: usize, skip: usize, slice: &mut [f32]) -> usize {
+        if channel >= self.channels.len() || skip >= self.frames {
+            return 0;
+        }
+        let frames_to_write = if (self.frames - skip) < slice.len() {
+            self.frames - skip
+        } else {
+            slice.len()
+        };
+        slice[..frames_to_write]
+            .copy_from_slice(&self.channels[channel][skip..skip + frames_to_write]);
+        frames_to_write
+    }
+}
+
+/// Copy each channel of a buffer into a new [Float32Array].
+pub fn to_arrays(buffer: &dyn Adapter<f32>) -> Vec<Float32Array> {
+    (0..buffer.channels())
+        .map(|channel| {
+            let mut values = vec![0.0; buffer.frames()];
+            buffer.write_from_channel_to_slice(channel, 0, &mut values);
+            Float32Array::from(values)
+        })
+        .collect()
+}
+
+/// Copy the contents of a buffer into a new [RecordBatch],
+/// with one `Float32` column per channel.
+/// The columns are named `channel_0`, `channel_1` etc.
+pub fn to_record_batch(buffer: &dyn Adapter<f32>) -> Result<RecordBatch, ArrowError> {
+    let fields: Vec<Field> = (0..buffer.channels())
+        .map(|channel| Field::new(format!("channel_{}", channel), DataType::Float32, false))
+        .collect();
+    let columns: Vec<ArrayRef> = to_arrays(buffer)
+        .into_iter()
+        .map(|array| Arc::new(array) as ArrayRef)
+        .collect();
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direct::SequentialSlice;
+    use arrow_array::Int32Array;
+
+    #[test]
+    fn roundtrip() {
+        let data = [1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let buffer = SequentialSlice::new(&data, 3, 2).unwrap();
+        let arrays = to_arrays(&buffer);
+        assert_eq!(arrays.len(), 3);
+        assert_eq!(arrays[1].values().as_ref(), &[3.0, 4.0]);
+
+        let array_refs: Vec<&Float32Array> = arrays.iter().collect();
+        let wrapped = ArrowChannels::from_arrays(&array_refs).unwrap();
+        assert_eq!(wrapped.channels(), 3);
+        assert_eq!(wrapped.frames(), 2);
+        let mut channel = [0.0; 3];
+        assert_eq!(wrapped.write_from_channel_to_slice(2, 0, &mut channel), 2);
+        assert_eq!(channel, [5.0, 6.0, 0.0]);
+    }
+
+    #[test]
+    fn invalid_arrays() {
+        let short = Float32Array::from(vec![1.0]);
+        let long = Float32Array::from(vec![1.0, 2.0]);
+        assert!(ArrowChannels::from_arrays(&[&short, &long]).is_err());
+
+        let ints: ArrayRef = Arc::new(Int32Array::from(vec![1, 2]));
+        let batch = RecordBatch::try_from_iter(vec![("ints", ints)]).unwrap();
+        assert!(ArrowChannels::new(&batch).is_err());
+    }
+}
@@ -2,85 +2,398 @@
 //!
 //! This module implements the `audioadapter` traits
 //! for `ExactSizeBuf` buffers from the [audio](https://crates.io/crates/audio) crate.
+//!
+//! Buffers that only implement `Buf`, without knowing their exact number of frames,
+//! can be used by wrapping them in an [AudioBuf] together with a frame count.
+//!
+//! Buffers implementing `ResizableBuf` can be grown to fit the data
+//! copied into them, using [copy_to_resizable].
+//!
+//! ## Example
+//! Copy a buffer into an empty `audio` buffer, growing it as needed.
+//! ```
+//! use audioadapter::audio::copy_to_resizable;
+//! use audioadapter::direct::InterleavedSlice;
+//! use audio_core::ExactSizeBuf;
+//!
+//! let data = [1_i32, 2, 3, 4, 5, 6];
+//! let src = InterleavedSlice::new(&data, 2, 3).unwrap();
+//! let mut dst = audio::buf::Sequential::<i32>::new();
+//!
+//! assert_eq!(copy_to_resizable(&src, &mut dst), 3);
+//! assert_eq!(dst.frames(), 3);
+//! ```
 
-use crate::{Adapter, AdapterMut};
+use crate::{Adapter, AdapterMut, SizeError};
 
-use audio_core::{Buf, BufMut, Channel, ChannelMut, ExactSizeBuf, Sample};
+use audio_core::{Buf, BufMut, Channel, ChannelMut, ExactSizeBuf, ResizableBuf, Sample};
 
-impl<'a, T, U> Adapter<'a, T> for U
+/// Copy samples from a channel of an `audio` buffer to a slice.
+fn read_channel_to_slice<B>(
+    buf: &B,
+    frames: usize,
+    channel: usize,
+    skip: usize,
+    slice: &mut [B::Sample],
+) -> usize
 where
-    T: Clone + Sample + 'a,
-    U: Buf<Sample = T> + ExactSizeBuf<Sample = T>,
+    B: Buf,
 {
-    fn channels(&self) -> usize {
-        self.channels()
+    if channel >= buf.channels() || skip >= frames {
+        return 0;
     }
+    let frames_to_write = (frames - skip).min(slice.len());
+    let chan = buf.get_channel(channel).unwrap();
+    chan.iter()
+        .skip(skip)
+        .take(frames_to_write)
+        .zip(slice.iter_mut())
+        .for_each(|(s, o)| *o = s);
+    frames_to_write
+}
 
-    fn frames(&self) -> usize {
-        self.frames()
+/// Copy samples from a frame of an `audio` buffer to a slice.
+fn read_frame_to_slice<B>(
+    buf: &B,
+    frames: usize,
+    frame: usize,
+    skip: usize,
+    slice: &mut [B::Sample],
+) -> usize
+where
+    B: Buf,
+{
+    if frame >= frames || skip >= buf.channels() {
+        return 0;
     }
+    let channels_to_write = (buf.channels() - skip).min(slice.len());
+    buf.iter_channels()
+        .skip(skip)
+        .take(channels_to_write)
+        .zip(slice.iter_mut())
+        .for_each(|(chan, o)| *o = chan.get(frame).unwrap());
+    channels_to_write
+}
 
-    unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
-        self.get_channel(channel).unwrap().get(frame).unwrap()
+/// Copy samples from a slice to a channel of an `audio` buffer.
+fn write_slice_to_channel<B>(
+    buf: &mut B,
+    frames: usize,
+    channel: usize,
+    skip: usize,
+    slice: &[B::Sample],
+) -> (usize, usize)
+where
+    B: BufMut,
+    B::Sample: Copy,
+{
+    if channel >= buf.channels() || skip >= frames {
+        return (0, 0);
     }
+    let frames_to_read = (frames - skip).min(slice.len());
+    let mut chan = buf.get_channel_mut(channel).unwrap();
+    chan.iter_mut()
+        .skip(skip)
+        .take(frames_to_read)
+        .zip(slice.iter())
+        .for_each(|(s, o)| *s = *o);
+    (frames_to_read, 0)
+}
 
-    fn write_from_channel_to_slice(&self, channel: usize, skip: usize, slice: &mut [T]) -> usize {
-        if channel >= self.channels() || skip >= self.frames() {
-            return 0;
-        }
-        let frames_to_write = if (self.frames() - skip) < slice.len() {
-            self.frames() - skip
+/// Copy samples from a slice to a frame of an `audio` buffer.
+fn write_slice_to_frame<B>(
+    buf: &mut B,
+    frames: usize,
+    frame: usize,
+    skip: usize,
+    slice: &[B::Sample],
+) -> (usize, usize)
+where
+    B: BufMut,
+    B::Sample: Copy,
+{
+    if frame >= frames || skip >= buf.channels() {
+        return (0, 0);
+    }
+    let channels_to_read = (buf.channels() - skip).min(slice.len());
+    buf.iter_channels_mut()
+        .skip(skip)
+        .take(channels_to_read)
+        .zip(slice.iter())
+        .for_each(|(mut chan, o)| *chan.get_mut(frame).unwrap() = *o);
+    (channels_to_read, 0)
+}
+
+/// Write a value to the frames `start..start+count` of each channel of an `audio` buffer.
+fn fill_frames<B>(buf: &mut B, start: usize, count: usize, value: B::Sample)
+where
+    B: BufMut,
+    B::Sample: Copy,
+{
+    for mut chan in buf.iter_channels_mut() {
+        chan.iter_mut()
+            .skip(start)
+            .take(count)
+            .for_each(|s| *s = value);
+    }
+}
+
+/// Copy `count` frames within each channel of an `audio` buffer,
+/// from `src..src+count` to `dest..dest+count`.
+fn copy_frames<B>(buf: &mut B, src: usize, dest: usize, count: usize)
+where
+    B: BufMut,
+    B::Sample: Copy,
+{
+    for mut chan in buf.iter_channels_mut() {
+        if let Some(linear) = chan.try_as_linear_mut() {
+            linear.copy_within(src..src + count, dest);
+        } else if dest < src {
+            for frame in 0..count {
+                let value = *chan.get_mut(frame + src).unwrap();
+                *chan.get_mut(frame + dest).unwrap() = value;
+            }
         } else {
-            slice.len()
-        };
-        let chan = self.get_channel(channel).unwrap();
-        chan.iter()
-            .skip(skip)
-            .take(frames_to_write)
-            .zip(slice.iter_mut())
-            .for_each(|(s, o)| *o = s);
-        frames_to_write
+            for frame in (0..count).rev() {
+                let value = *chan.get_mut(frame + src).unwrap();
+                *chan.get_mut(frame + dest).unwrap() = value;
+            }
+        }
     }
 }
 
+/// Implement the [Adapter] trait methods for an `audio` buffer,
+/// given by the expression `$buf`, with `$frames` frames.
+macro_rules! implement_audio_reads {
+    (|$s:ident| $buf:expr, $frames:expr) => {
+        fn channels(&self) -> usize {
+            let $s = self;
+            $buf.channels()
+        }
+
+        fn frames(&self) -> usize {
+            let $s = self;
+            $frames
+        }
+
+        unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
+            let $s = self;
+            $buf.get_channel(channel).unwrap().get(frame).unwrap()
+        }
+
+        fn write_from_channel_to_slice(
+            &self,
+            channel: usize,
+            skip: usize,
+            slice: &mut [T],
+        ) -> usize {
+            let $s = self;
+            read_channel_to_slice($buf, $frames, channel, skip, slice)
+        }
+
+        fn write_from_frame_to_slice(&self, frame: usize, skip: usize, slice: &mut [T]) -> usize {
+            let $s = self;
+            read_frame_to_slice($buf, $frames, frame, skip, slice)
+        }
+    };
+}
+
+/// Implement the [AdapterMut] trait methods for an `audio` buffer,
+/// given by the expression `$buf`.
+/// The methods reuse the channel handles of the buffer where possible,
+/// instead of looking up the channel for every sample.
+macro_rules! implement_audio_writes {
+    (|$s:ident| $buf:expr) => {
+        unsafe fn write_sample_unchecked(
+            &mut self,
+            channel: usize,
+            frame: usize,
+            value: &T,
+        ) -> bool {
+            let $s = self;
+            *$buf
+                .get_channel_mut(channel)
+                .unwrap()
+                .get_mut(frame)
+                .unwrap() = *value;
+            false
+        }
+
+        fn write_from_slice_to_channel(
+            &mut self,
+            channel: usize,
+            skip: usize,
+            slice: &[T],
+        ) -> (usize, usize) {
+            let frames = Adapter::frames(self);
+            let $s = self;
+            write_slice_to_channel($buf, frames, channel, skip, slice)
+        }
+
+        fn write_from_slice_to_frame(
+            &mut self,
+            frame: usize,
+            skip: usize,
+            slice: &[T],
+        ) -> (usize, usize) {
+            let frames = Adapter::frames(self);
+            let $s = self;
+            write_slice_to_frame($buf, frames, frame, skip, slice)
+        }
+
+        fn fill_channel_with(&mut self, channel: usize, value: &T) -> Option<()> {
+            let frames = Adapter::frames(self);
+            let $s = self;
+            let mut chan = $buf.get_channel_mut(channel)?;
+            chan.iter_mut().take(frames).for_each(|s| *s = *value);
+            Some(())
+        }
+
+        fn fill_frame_with(&mut self, frame: usize, value: &T) -> Option<()> {
+            if frame >= Adapter::frames(self) {
+                return None;
+            }
+            let $s = self;
+            fill_frames($buf, frame, 1, *value);
+            Some(())
+        }
+
+        fn fill_frames_with(&mut self, start: usize, count: usize, value: &T) -> Option<usize> {
+            if start + count > Adapter::frames(self) {
+                return None;
+            }
+            let $s = self;
+            fill_frames($buf, start, count, *value);
+            Some(count)
+        }
+
+        fn fill_with(&mut self, value: &T) {
+            let frames = Adapter::frames(self);
+            let $s = self;
+            fill_frames($buf, 0, frames, *value);
+        }
+
+        fn copy_frames_within(&mut self, src: usize, dest: usize, count: usize) -> Option<usize> {
+            let frames = Adapter::frames(self);
+            if src + count > frames || dest + count > frames {
+                return None;
+            }
+            if count == 0 || src == dest {
+                return Some(count);
+            }
+            let $s = self;
+            copy_frames($buf, src, dest, count);
+            Some(count)
+        }
+    };
+}
+
+impl<'a, T, U> Adapter<'a, T> for U
+where
+    T: Clone + Sample + 'a,
+    U: Buf<Sample = T> + ExactSizeBuf<Sample = T>,
+{
+    implement_audio_reads!(|s| s, ExactSizeBuf::frames(s));
+}
+
 impl<'a, T, U> AdapterMut<'a, T> for U
 where
     T: Clone + Sample + 'a,
     U: BufMut<Sample = T> + ExactSizeBuf<Sample = T>,
 {
-    unsafe fn write_sample_unchecked(&mut self, channel: usize, frame: usize, value: &T) -> bool {
-        *self
-            .get_channel_mut(channel)
-            .unwrap()
-            .get_mut(frame)
-            .unwrap() = *value;
-        false
-    }
-
-    fn write_from_slice_to_channel(
-        &mut self,
-        channel: usize,
-        skip: usize,
-        slice: &[T],
-    ) -> (usize, usize) {
-        if channel >= Adapter::channels(self) || skip >= Adapter::frames(self) {
-            return (0, 0);
-        }
-        let frames_to_read = if (Adapter::frames(self) - skip) < slice.len() {
-            Adapter::frames(self) - skip
-        } else {
-            slice.len()
-        };
-        let mut chan = self.get_channel_mut(channel).unwrap();
-        chan.iter_mut()
-            .skip(skip)
-            .take(frames_to_read)
-            .zip(slice.iter())
-            .for_each(|(s, o)| *s = *o);
-        (frames_to_read, 0)
+    implement_audio_writes!(|s| s);
+}
+
+/// A wrapper for an `audio` buffer implementing `Buf`,
+/// for buffers that do not implement `ExactSizeBuf`.
+/// The number of frames is given when creating the wrapper.
+pub struct AudioBuf<B> {
+    buf: B,
+    frames: usize,
+}
+
+impl<B> AudioBuf<B>
+where
+    B: Buf,
+{
+    /// Create a new wrapper for a buffer implementing `Buf`,
+    /// giving access to `frames` frames.
+    /// Every channel of the buffer must have at least `frames` frames.
+    pub fn new(buf: B, frames: usize) -> Result<Self, SizeError> {
+        for (index, channel) in buf.iter_channels().enumerate() {
+            if channel.len() < frames {
+                return Err(SizeError::Channel {
+                    index,
+                    actual: channel.len(),
+                    required: frames,
+                });
+            }
+        }
+        Ok(Self { buf, frames })
+    }
+
+    /// Get a reference to the wrapped buffer.
+    pub fn inner(&self) -> &B {
+        &self.buf
+    }
+
+    /// Take back the wrapped buffer, consuming the wrapper.
+    pub fn into_inner(self) -> B {
+        self.buf
+    }
+}
+
+impl<B> AudioBuf<B>
+where
+    B: Buf + ResizableBuf,
+{
+    /// Resize the wrapped buffer to the given number of frames,
+    /// and make all of them accessible via the wrapper.
+    pub fn resize_frames(&mut self, frames: usize) {
+        self.buf.resize_frames(frames);
+        self.frames = frames;
     }
 }
 
+impl<'a, T, B> Adapter<'a, T> for AudioBuf<B>
+where
+    T: Clone + Sample + 'a,
+    B: Buf<Sample = T>,
+{
+    implement_audio_reads!(|s| &s.buf, s.frames);
+}
+
+impl<'a, T, B> AdapterMut<'a, T> for AudioBuf<B>
+where
+    T: Clone + Sample + 'a,
+    B: BufMut<Sample = T>,
+{
+    implement_audio_writes!(|s| &mut s.buf);
+}
+
+/// Copy all samples of a buffer to a resizable `audio` buffer.
+/// The destination is first grown to have at least as many channels and frames
+/// as the source, it is never shrunk.
+/// Returns the number of frames copied.
+pub fn copy_to_resizable<'a, T, B>(src: &dyn Adapter<'a, T>, dst: &mut B) -> usize
+where
+    T: Clone + Sample + 'a,
+    B: BufMut<Sample = T> + ExactSizeBuf<Sample = T> + ResizableBuf,
+{
+    let channels = src.channels().max(Buf::channels(dst));
+    let frames = src.frames().max(ExactSizeBuf::frames(dst));
+    if channels > Buf::channels(dst) || frames > ExactSizeBuf::frames(dst) {
+        dst.resize_topology(channels, frames);
+    }
+    for channel in 0..src.channels() {
+        let mut chan = dst.get_channel_mut(channel).unwrap();
+        for (frame, sample) in chan.iter_mut().take(src.frames()).enumerate() {
+            *sample = unsafe { src.read_sample_unchecked(channel, frame) };
+        }
+    }
+    src.frames()
+}
+
 //   _____         _
 //  |_   _|__  ___| |_ ___
 //    | |/ _ \/ __| __/ __|
@@ -94,6 +407,7 @@ mod tests {
     use crate::byte_slice_as_type;
     use crate::sample::RawSample;
     use crate::sample::I16LE;
+    use crate::tests::check_copy_within;
     use audio::wrap;
 
     #[test]
@@ -165,6 +479,64 @@ mod tests {
         assert_eq!(buf.get_channel(1).unwrap().get(1).unwrap(), 4);
     }
 
+    #[test]
+    fn copy_within() {
+        let mut buf = audio::buf::Sequential::<u32>::with_topology(2, 10);
+        check_copy_within(&mut buf);
+        let mut buf = audio::buf::Interleaved::<u32>::with_topology(2, 10);
+        check_copy_within(&mut buf);
+    }
+
+    #[test]
+    fn frames_and_fill() {
+        let mut buf = audio::buf::Interleaved::<i32>::with_topology(3, 4);
+        assert_eq!(buf.write_from_slice_to_frame(1, 1, &[1, 2, 3]), (2, 0));
+        let mut values = [0; 3];
+        assert_eq!(buf.write_from_frame_to_slice(1, 0, &mut values), 3);
+        assert_eq!(values, [0, 1, 2]);
+        buf.fill_frame_with(3, &5).unwrap();
+        buf.fill_channel_with(0, &7).unwrap();
+        assert_eq!(buf.as_slice(), [7, 0, 0, 7, 1, 2, 7, 0, 0, 7, 5, 5]);
+        buf.fill_with(&0);
+        assert_eq!(buf.as_slice(), [0; 12]);
+    }
+
+    #[test]
+    fn wrap_dynamic() {
+        let data = vec![vec![1, 2, 3], vec![4, 5]];
+        assert!(matches!(
+            AudioBuf::new(wrap::dynamic(data.clone()), 3),
+            Err(SizeError::Channel {
+                index: 1,
+                actual: 2,
+                required: 3
+            })
+        ));
+        let mut buf = AudioBuf::new(wrap::dynamic(data), 2).unwrap();
+        assert_eq!(buf.frames(), 2);
+        assert_eq!(buf.read_sample(1, 1), Some(5));
+        assert_eq!(buf.read_sample(0, 2), None);
+        assert_eq!(buf.write_from_slice_to_channel(0, 1, &[7, 8]), (1, 0));
+        buf.resize_frames(4);
+        assert_eq!(buf.write_sample(1, 3, &9), Some(false));
+        assert_eq!(
+            buf.into_inner().into_inner(),
+            vec![vec![1, 7, 3, 0], vec![4, 5, 0, 9]]
+        );
+    }
+
+    #[test]
+    fn copy_to_growing_buffer() {
+        let data = [1, 2, 3, 4, 5, 6];
+        let src = crate::direct::InterleavedSlice::new(&data, 2, 3).unwrap();
+        let mut dst = audio::buf::Sequential::<i32>::with_topology(1, 2);
+        assert_eq!(copy_to_resizable(&src, &mut dst), 3);
+        assert_eq!(Buf::channels(&dst), 2);
+        assert_eq!(ExactSizeBuf::frames(&dst), 3);
+        assert_eq!(dst.get_channel(0).unwrap().get(2), Some(5));
+        assert_eq!(dst.get_channel(1).unwrap().get(0), Some(2));
+    }
+
     #[test]
     fn test_convert_i16() {
         let data: [i16; 6] = [0, i16::MIN, 1 << 14, -(1 << 14), 1 << 13, -(1 << 13)];
@@ -0,0 +1,217 @@
+//! # Packed bit masks
+//!
+//! This module provides a wrapper for slices of integers,
+//! where each integer holds one frame of on/off values packed as bits.
+//! Bit `n` of each integer is the value of channel `n`,
+//! where bit 0 is the least significant bit.
+//! This is a compact format for gate and trigger signals,
+//! that are often processed alongside audio,
+//! for example in modular synthesizer software.
+//!
+//! The wrapper implements [Adapter] and [AdapterMut] for `bool` values.
+//! To read the signals as floats, where `false` is 0.0 and `true` is 1.0,
+//! wrap it in a [ConvertNumbers](crate::adapter_to_float::ConvertNumbers).
+//!
+//! ## Example
+//! ```
+//! use audioadapter::bitmask::PackedBits;
+//! use audioadapter::Adapter;
+//!
+//! // Three frames of four gate channels.
+//! let data: [u8; 3] = [0b0001, 0b0011, 0b1010];
+//! let gates = PackedBits::new(&data, 4, 3).unwrap();
+//! assert_eq!(gates.read_sample(0, 1), Some(true));
+//! assert_eq!(gates.read_sample(0, 2), Some(false));
+//! assert_eq!(gates.read_sample(3, 2), Some(true));
+//! ```
+
+use num_traits::PrimInt;
+
+use crate::{check_slice_length, implement_size_getters};
+use crate::{Adapter, AdapterMut, IterationOrder, SizeError};
+
+/// Wrapper for a slice of integers of length `frames`,
+/// where each integer holds the values of all channels of one frame as bits.
+/// The number of channels can be at most the number of bits of the integer type.
+pub struct PackedBits<U> {
+    buf: U,
+    frames: usize,
+    channels: usize,
+}
+
+/// Check that the integer type has room for the given number of channels.
+fn check_bits<W>(channels: usize) -> Result<(), SizeError> {
+    let bits = 8 * core::mem::size_of::<W>();
+    if channels > bits {
+        return Err(SizeError::Frame {
+            index: 0,
+            actual: bits,
+            required: channels,
+        });
+    }
+    Ok(())
+}
+
+impl<'a, W> PackedBits<&'a [W]>
+where
+    W: PrimInt,
+{
+    /// Create a new `PackedBits` to wrap a slice of integers.
+    /// The slice length must be at least `frames`,
+    /// and `channels` must not be larger than the number of bits of the integer type.
+    pub fn new(buf: &'a [W], channels: usize, frames: usize) -> Result<Self, SizeError> {
+        check_bits::<W>(channels)?;
+        check_slice_length!(1, frames, buf.len());
+        Ok(Self {
+            buf,
+            frames,
+            channels,
+        })
+    }
+}
+
+impl<'a, W> PackedBits<&'a mut [W]>
+where
+    W: PrimInt,
+{
+    /// Create a new `PackedBits` to wrap a mutable slice of integers.
+    /// The slice length must be at least `frames`,
+    /// and `channels` must not be larger than the number of bits of the integer type.
+    pub fn new_mut(buf: &'a mut [W], channels: usize, frames: usize) -> Result<Self, SizeError> {
+        check_bits::<W>(channels)?;
+        check_slice_length!(1, frames, buf.len());
+        Ok(Self {
+            buf,
+            frames,
+            channels,
+        })
+    }
+}
+
+macro_rules! implement_bit_reads {
+    () => {
+        unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> bool {
+            (*self.buf.get_unchecked(frame) >> channel) & W::one() == W::one()
+        }
+
+        implement_size_getters!();
+
+        fn preferred_iteration(&self) -> IterationOrder {
+            IterationOrder::FramesOuter
+        }
+    };
+}
+
+impl<'a, W> Adapter<'a, bool> for PackedBits<&'a [W]>
+where
+    W: PrimInt,
+{
+    implement_bit_reads!();
+}
+
+impl<'a, W> Adapter<'a, bool> for PackedBits<&'a mut [W]>
+where
+    W: PrimInt,
+{
+    implement_bit_reads!();
+}
+
+impl<'a, W> AdapterMut<'a, bool> for PackedBits<&'a mut [W]>
+where
+    W: PrimInt,
+{
+    unsafe fn write_sample_unchecked(
+        &mut self,
+        channel: usize,
+        frame: usize,
+        value: &bool,
+    ) -> bool {
+        let word = self.buf.get_unchecked_mut(frame);
+        let bit = W::one() << channel;
+        if *value {
+            *word = *word | bit;
+        } else {
+            *word = *word & !bit;
+        }
+        false
+    }
+
+    fn fill_frame_with(&mut self, frame: usize, value: &bool) -> Option<()> {
+        if frame >= self.frames {
+            return None;
+        }
+        let mask = if self.channels == 8 * core::mem::size_of::<W>() {
+            !W::zero()
+        } else {
+            (W::one() << self.channels) - W::one()
+        };
+        let word = &mut self.buf[frame];
+        if *value {
+            *word = *word | mask;
+        } else {
+            *word = *word & !mask;
+        }
+        Some(())
+    }
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapter_to_float::ConvertNumbers;
+
+    // Check that a buffer is Send + Sync,
+    // meaning it can be sent between threads.
+    // This test is not designed to be run, only to compile.
+    #[allow(dead_code)]
+    fn test_adapter_send_and_sync() {
+        fn is_send<T: Send>() {}
+        fn is_sync<T: Sync>() {}
+        is_send::<PackedBits<&[u8]>>();
+        is_sync::<PackedBits<&[u8]>>();
+        is_send::<PackedBits<&mut [u32]>>();
+        is_sync::<PackedBits<&mut [u32]>>();
+    }
+
+    #[test]
+    fn read_and_write_bits() {
+        let mut data = [0b1000_0001_u8, 0b0100_0000];
+        let mut bits = PackedBits::new_mut(&mut data, 8, 2).unwrap();
+        assert_eq!(bits.read_sample(0, 0), Some(true));
+        assert_eq!(bits.read_sample(7, 0), Some(true));
+        assert_eq!(bits.read_sample(6, 1), Some(true));
+        assert_eq!(bits.read_sample(8, 1), None);
+        assert_eq!(bits.write_sample(0, 0, &false), Some(false));
+        assert_eq!(bits.write_sample(2, 1, &true), Some(false));
+        assert_eq!(bits.fill_frame_with(0, &true), Some(()));
+        assert_eq!(data, [0b1111_1111, 0b0100_0100]);
+
+        let mut data = [0xffff_u16];
+        let mut bits = PackedBits::new_mut(&mut data, 3, 1).unwrap();
+        bits.fill_frame_with(0, &false).unwrap();
+        assert_eq!(data, [0xfff8]);
+    }
+
+    #[test]
+    fn too_many_channels() {
+        let data = [0_u8; 2];
+        assert!(PackedBits::new(&data, 9, 2).is_err());
+        assert!(PackedBits::new(&data, 8, 3).is_err());
+    }
+
+    #[test]
+    fn read_as_float() {
+        let data = [0b10_u32, 0b01];
+        let bits = PackedBits::new(&data, 2, 2).unwrap();
+        let converter = ConvertNumbers::<_, f32>::new(&bits as &dyn Adapter<bool>);
+        assert_eq!(converter.read_sample(0, 0), Some(0.0));
+        assert_eq!(converter.read_sample(1, 0), Some(1.0));
+        assert_eq!(converter.read_sample(0, 1), Some(1.0));
+    }
+}
@@ -0,0 +1,392 @@
+//! # C API
+//!
+//! This module exposes a small set of `extern "C"` functions,
+//! for using the crate from other languages via FFI,
+//! for example from Python using `ctypes` or `cffi`.
+//!
+//! A buffer is created by wrapping a block of memory containing raw sample bytes,
+//! giving the number of channels and frames, the layout, and the sample format.
+//! This returns an opaque handle, that is then passed to the other functions.
+//! The memory is not owned by the handle,
+//! and must stay valid until the handle has been freed with [aa_buffer_free].
+//!
+//! The layout is given as [AA_LAYOUT_INTERLEAVED] or [AA_LAYOUT_SEQUENTIAL].
+//! The sample format is given as the index of the format in the
+//! [SampleFormat] enum, starting from zero for `I16LE`.
+//!
+//! To build a shared library, use for example:
+//! ```sh
+//! cargo rustc --release --features capi --crate-type cdylib
+//! ```
+//!
+//! ## Example
+//! Copy data between two buffers with different formats.
+//! ```
+//! use audioadapter::capi::*;
+//!
+//! let mut src_data = [0_u8, 64, 0, 192]; // I16LE: 0.5, -0.5
+//! let mut dst_data = [0_u8; 8];
+//! unsafe {
+//!     let src = aa_buffer_new(src_data.as_mut_ptr(), 4, 2, 1, AA_LAYOUT_INTERLEAVED, 0);
+//!     let dst = aa_buffer_new(dst_data.as_mut_ptr(), 8, 2, 1, AA_LAYOUT_INTERLEAVED, 20);
+//!     assert_eq!(aa_buffer_copy(src, dst), 1);
+//!     aa_buffer_free(src);
+//!     aa_buffer_free(dst);
+//! }
+//! assert_eq!(dst_data[4..], (-0.5_f32).to_le_bytes());
+//! ```
+
+use crate::number_to_float::{InterleavedNumbers, SequentialNumbers};
+use crate::sample::SampleFormat;
+use crate::{Adapter, AdapterMut};
+
+/// Samples are stored in _interleaved_ order.
+pub const AA_LAYOUT_INTERLEAVED: u32 = 0;
+/// Samples are stored in _sequential_ order.
+pub const AA_LAYOUT_SEQUENTIAL: u32 = 1;
+
+/// The return value of the functions returning a count, when an error occurred.
+pub const AA_ERROR: isize = -1;
+
+/// The sample formats, in the order given by their numerical codes.
+const FORMATS: [SampleFormat; 28] = [
+    SampleFormat::I16LE,
+    SampleFormat::I16BE,
+    SampleFormat::U16LE,
+    SampleFormat::U16BE,
+    SampleFormat::I24LE3,
+    SampleFormat::I24LE4,
+    SampleFormat::I24BE3,
+    SampleFormat::I24BE4,
+    SampleFormat::U24LE3,
+    SampleFormat::U24LE4,
+    SampleFormat::U24BE3,
+    SampleFormat::U24BE4,
+    SampleFormat::I32LE,
+    SampleFormat::I32BE,
+    SampleFormat::U32LE,
+    SampleFormat::U32BE,
+    SampleFormat::I64LE,
+    SampleFormat::I64BE,
+    SampleFormat::U64LE,
+    SampleFormat::U64BE,
+    SampleFormat::F32LE,
+    SampleFormat::F32BE,
+    SampleFormat::F64LE,
+    SampleFormat::F64BE,
+    SampleFormat::I24InHighLE,
+    SampleFormat::I24InHighBE,
+    SampleFormat::I24InLowLE,
+    SampleFormat::I24InLowBE,
+];
+
+/// The number of frames converted at a time when copying between buffers.
+const CHUNK_SIZE: usize = 1024;
+
+/// An opaque handle for a buffer of raw sample bytes.
+pub struct AaBuffer {
+    data: *mut u8,
+    len: usize,
+    channels: usize,
+    frames: usize,
+    interleaved: bool,
+    format: SampleFormat,
+}
+
+impl AaBuffer {
+    /// Call a function with a converting wrapper for the buffer.
+    unsafe fn with_adapter<R>(&self, f: impl FnOnce(&dyn Adapter<f64>) -> R) -> R {
+        let bytes = core::slice::from_raw_parts(self.data, self.len);
+        crate::with_format!(self.format, |S| {
+            if self.interleaved {
+                let buffer = InterleavedNumbers::<&[S], f64>::new_from_bytes(
+                    bytes,
+                    self.channels,
+                    self.frames,
+                )
+                .unwrap();
+                f(&buffer)
+            } else {
+                let buffer = SequentialNumbers::<&[S], f64>::new_from_bytes(
+                    bytes,
+                    self.channels,
+                    self.frames,
+                )
+                .unwrap();
+                f(&buffer)
+            }
+        })
+    }
+
+    /// Call a function with a mutable converting wrapper for the buffer.
+    unsafe fn with_adapter_mut<R>(&mut self, f: impl FnOnce(&mut dyn AdapterMut<f64>) -> R) -> R {
+        let bytes = core::slice::from_raw_parts_mut(self.data, self.len);
+        crate::with_format!(self.format, |S| {
+            if self.interleaved {
+                let mut buffer = InterleavedNumbers::<&mut [S], f64>::new_from_bytes_mut(
+                    bytes,
+                    self.channels,
+                    self.frames,
+                )
+                .unwrap();
+                f(&mut buffer)
+            } else {
+                let mut buffer = SequentialNumbers::<&mut [S], f64>::new_from_bytes_mut(
+                    bytes,
+                    self.channels,
+                    self.frames,
+                )
+                .unwrap();
+                f(&mut buffer)
+            }
+        })
+    }
+}
+
+/// Create a new buffer handle wrapping `len` bytes at `data`.
+/// Returns a null pointer if the layout or format is invalid,
+/// or if `len` is too small for the given number of channels and frames.
+///
+/// # Safety
+/// `data` must point to at least `len` bytes that are valid for reading and writing,
+/// and must stay valid until the handle is freed.
+/// The memory must not be accessed by other means while the handle is being used.
+#[no_mangle]
+pub unsafe extern "C" fn aa_buffer_new(
+    data: *mut u8,
+    len: usize,
+    channels: usize,
+    frames: usize,
+    layout: u32,
+    format: u32,
+) -> *mut AaBuffer {
+    let interleaved = match layout {
+        AA_LAYOUT_INTERLEAVED => true,
+        AA_LAYOUT_SEQUENTIAL => false,
+        _ => return core::ptr::null_mut(),
+    };
+    let format = match FORMATS.get(format as usize) {
+        Some(format) => *format,
+        None => return core::ptr::null_mut(),
+    };
+    let required = channels
+        .checked_mul(frames)
+        .and_then(|samples| samples.checked_mul(format.bytes_per_sample()));
+    if data.is_null() || required.map_or(true, |required| len < required) {
+        return core::ptr::null_mut();
+    }
+    Box::into_raw(Box::new(AaBuffer {
+        data,
+        len,
+        channels,
+        frames,
+        interleaved,
+        format,
+    }))
+}
+
+/// Free a buffer handle. The wrapped memory is not freed.
+/// Passing a null pointer does nothing.
+///
+/// # Safety
+/// `buffer` must be a handle returned by [aa_buffer_new] that has not already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn aa_buffer_free(buffer: *mut AaBuffer) {
+    if !buffer.is_null() {
+        drop(Box::from_raw(buffer));
+    }
+}
+
+/// Get the number of channels of a buffer.
+///
+/// # Safety
+/// `buffer` must be a valid handle returned by [aa_buffer_new].
+#[no_mangle]
+pub unsafe extern "C" fn aa_buffer_channels(buffer: *const AaBuffer) -> usize {
+    (*buffer).channels
+}
+
+/// Get the number of frames of a buffer.
+///
+/// # Safety
+/// `buffer` must be a valid handle returned by [aa_buffer_new].
+#[no_mangle]
+pub unsafe extern "C" fn aa_buffer_frames(buffer: *const AaBuffer) -> usize {
+    (*buffer).frames
+}
+
+/// Read up to `len` samples from a channel of the buffer,
+/// starting at frame `skip`, converted to `f32`.
+/// Returns the number of samples read, or [AA_ERROR] if a pointer is null.
+///
+/// # Safety
+/// `buffer` must be a valid handle returned by [aa_buffer_new],
+/// and `out` must point to at least `len` values that are valid for writing.
+#[no_mangle]
+pub unsafe extern "C" fn aa_read_channel_f32(
+    buffer: *const AaBuffer,
+    channel: usize,
+    skip: usize,
+    out: *mut f32,
+    len: usize,
+) -> isize {
+    if buffer.is_null() || out.is_null() {
+        return AA_ERROR;
+    }
+    let out = core::slice::from_raw_parts_mut(out, len);
+    (*buffer).with_adapter(|adapter| {
+        let frames = adapter.frames().saturating_sub(skip).min(len);
+        if channel >= adapter.channels() {
+            return 0;
+        }
+        for (n, value) in out.iter_mut().enumerate().take(frames) {
+            *value = adapter.read_sample_unchecked(channel, skip + n) as f32;
+        }
+        frames as isize
+    })
+}
+
+/// Write up to `len` samples to a channel of the buffer,
+/// starting at frame `skip`, converted from `f32`.
+/// Values outside the range of an integer format are clipped.
+/// Returns the number of samples written, or [AA_ERROR] if a pointer is null.
+///
+/// # Safety
+/// `buffer` must be a valid handle returned by [aa_buffer_new],
+/// and `values` must point to at least `len` values that are valid for reading.
+#[no_mangle]
+pub unsafe extern "C" fn aa_write_channel_f32(
+    buffer: *mut AaBuffer,
+    channel: usize,
+    skip: usize,
+    values: *const f32,
+    len: usize,
+) -> isize {
+    if buffer.is_null() || values.is_null() {
+        return AA_ERROR;
+    }
+    let values = core::slice::from_raw_parts(values, len);
+    (*buffer).with_adapter_mut(|adapter| {
+        let frames = adapter.frames().saturating_sub(skip).min(len);
+        if channel >= adapter.channels() {
+            return 0;
+        }
+        for (n, value) in values.iter().enumerate().take(frames) {
+            adapter.write_sample_unchecked(channel, skip + n, &(*value as f64));
+        }
+        frames as isize
+    })
+}
+
+/// Copy samples from one buffer to another, converting between the sample formats.
+/// The number of channels and frames copied are the smallest of the two buffers.
+/// Values outside the range of an integer destination format are clipped.
+/// Returns the number of frames copied, or [AA_ERROR] if a pointer is null.
+///
+/// # Safety
+/// `src` and `dst` must be valid handles returned by [aa_buffer_new],
+/// wrapping memory that does not overlap.
+#[no_mangle]
+pub unsafe extern "C" fn aa_buffer_copy(src: *const AaBuffer, dst: *mut AaBuffer) -> isize {
+    if src.is_null() || dst.is_null() {
+        return AA_ERROR;
+    }
+    let channels = (*src).channels.min((*dst).channels);
+    let frames = (*src).frames.min((*dst).frames);
+    let mut chunk = [0.0_f64; CHUNK_SIZE];
+    for channel in 0..channels {
+        let mut skip = 0;
+        while skip < frames {
+            let count = CHUNK_SIZE.min(frames - skip);
+            (*src).with_adapter(|adapter| {
+                adapter.write_from_channel_to_slice(channel, skip, &mut chunk[..count])
+            });
+            (*dst).with_adapter_mut(|adapter| {
+                adapter.write_from_slice_to_channel(channel, skip, &chunk[..count])
+            });
+            skip += count;
+        }
+    }
+    frames as isize
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_arguments() {
+        let mut data = [0_u8; 8];
+        unsafe {
+            assert!(aa_buffer_new(data.as_mut_ptr(), 8, 2, 2, 2, 0).is_null());
+            assert!(aa_buffer_new(data.as_mut_ptr(), 8, 2, 2, 0, FORMATS.len() as u32).is_null());
+            assert!(aa_buffer_new(data.as_mut_ptr(), 8, 2, 3, 0, 0).is_null());
+            assert!(aa_buffer_new(core::ptr::null_mut(), 8, 2, 2, 0, 0).is_null());
+            assert_eq!(
+                aa_buffer_copy(core::ptr::null(), core::ptr::null_mut()),
+                AA_ERROR
+            );
+            aa_buffer_free(core::ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn read_write_and_copy() {
+        // 2 channels, 3 frames, I16LE, sequential
+        let mut src_data = [0_u8; 12];
+        // 2 channels, 3 frames, F32LE, interleaved
+        let mut dst_data = [0_u8; 24];
+        unsafe {
+            let src = aa_buffer_new(src_data.as_mut_ptr(), 12, 2, 3, AA_LAYOUT_SEQUENTIAL, 0);
+            let dst = aa_buffer_new(dst_data.as_mut_ptr(), 24, 2, 3, AA_LAYOUT_INTERLEAVED, 20);
+            assert_eq!(aa_buffer_channels(src), 2);
+            assert_eq!(aa_buffer_frames(src), 3);
+
+            let values = [0.5_f32, -0.25, 2.0, 0.125];
+            assert_eq!(aa_write_channel_f32(src, 1, 0, values.as_ptr(), 4), 3);
+            assert_eq!(aa_write_channel_f32(src, 2, 0, values.as_ptr(), 4), 0);
+            assert_eq!(aa_buffer_copy(src, dst), 3);
+
+            let mut out = [0.0_f32; 4];
+            assert_eq!(aa_read_channel_f32(dst, 1, 1, out.as_mut_ptr(), 4), 2);
+            assert_eq!(out[0], -0.25);
+            assert!((out[1] - 1.0).abs() < 1.0e-4);
+            assert_eq!(aa_read_channel_f32(dst, 0, 0, out.as_mut_ptr(), 4), 3);
+            assert_eq!(out[..3], [0.0, 0.0, 0.0]);
+
+            aa_buffer_free(src);
+            aa_buffer_free(dst);
+        }
+        assert_eq!(dst_data[4..8], 0.5_f32.to_le_bytes());
+    }
+
+    #[test]
+    fn padded_24_bit_formats() {
+        // 2 channels, 1 frame, I24InHighLE and I24InLowLE
+        let mut high_data = [0_u8; 8];
+        let mut low_data = [0_u8; 8];
+        unsafe {
+            let high = aa_buffer_new(high_data.as_mut_ptr(), 8, 2, 1, AA_LAYOUT_INTERLEAVED, 24);
+            let low = aa_buffer_new(low_data.as_mut_ptr(), 8, 2, 1, AA_LAYOUT_INTERLEAVED, 26);
+            assert!(!high.is_null() && !low.is_null());
+            let values = [0.5_f32];
+            assert_eq!(aa_write_channel_f32(high, 1, 0, values.as_ptr(), 1), 1);
+            assert_eq!(aa_buffer_copy(high, low), 1);
+
+            let mut out = [0.0_f32; 1];
+            assert_eq!(aa_read_channel_f32(low, 1, 0, out.as_mut_ptr(), 1), 1);
+            assert_eq!(out[0], 0.5);
+
+            aa_buffer_free(high);
+            aa_buffer_free(low);
+        }
+        assert_eq!(high_data, [0, 0, 0, 0, 0, 0, 0, 0x40]);
+        assert_eq!(low_data, [0, 0, 0, 0, 0, 0, 0x40, 0]);
+    }
+}
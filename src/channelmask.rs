@@ -0,0 +1,186 @@
+//! # Speaker positions of channel masks
+//!
+//! Wav files and audio APIs using the `WAVEFORMATEXTENSIBLE` structure
+//! describe the speaker positions of the channels with a bit mask, `dwChannelMask`.
+//! Bit `n` of the mask is set when the stream has a channel for speaker position `n`,
+//! and the channels of the stream are the set bits in order, starting from the lowest.
+//! A 5.1 stream without the center channel, for example, has the mask `0x3B`,
+//! and its five channels are the speaker positions 0, 1, 3, 4 and 5.
+//!
+//! This module converts between such channel masks and the masks of active channels
+//! used by [SparseSequentialSliceOfVecs](crate::direct::SparseSequentialSliceOfVecs).
+//! A sparse buffer created with
+//! [new_from_channel_mask](crate::direct::SparseSequentialSliceOfVecs::new_from_channel_mask)
+//! has one channel for every speaker position up to the highest one in the mask,
+//! so that the channel numbers of the buffer are the speaker positions.
+//! The positions that are not in the mask are inactive channels.
+//! The [spread_to_positions](crate::channelmask::spread_to_positions) function
+//! places the channels of a stream at their speaker positions,
+//! ready to be wrapped.
+//!
+//! ## Example
+//! Wrap a captured 5.1 stream without the center channel,
+//! and read the low frequency channel by its speaker position.
+//! ```
+//! use audioadapter::channelmask::{spread_to_positions, LOW_FREQUENCY};
+//! use audioadapter::direct::SparseSequentialSliceOfVecs;
+//! use audioadapter::Adapter;
+//!
+//! let channel_mask = 0x3B;
+//! let captured = vec![vec![0.1_f32; 4], vec![0.2; 4], vec![0.3; 4], vec![0.4; 4], vec![0.5; 4]];
+//! let channels = spread_to_positions(captured, channel_mask).unwrap();
+//! let buffer = SparseSequentialSliceOfVecs::new_from_channel_mask(&channels, 4, channel_mask).unwrap();
+//!
+//! let position = LOW_FREQUENCY.trailing_zeros() as usize;
+//! assert_eq!(buffer.read_sample(position, 0), Some(0.3));
+//! assert!(!buffer.is_channel_active(2));
+//! assert_eq!(buffer.channel_mask(), Some(channel_mask));
+//! ```
+
+use crate::SizeError;
+
+/// The bit of the front left speaker position.
+pub const FRONT_LEFT: u32 = 0x1;
+/// The bit of the front right speaker position.
+pub const FRONT_RIGHT: u32 = 0x2;
+/// The bit of the front center speaker position.
+pub const FRONT_CENTER: u32 = 0x4;
+/// The bit of the low frequency speaker position.
+pub const LOW_FREQUENCY: u32 = 0x8;
+/// The bit of the back left speaker position.
+pub const BACK_LEFT: u32 = 0x10;
+/// The bit of the back right speaker position.
+pub const BACK_RIGHT: u32 = 0x20;
+/// The bit of the front left of center speaker position.
+pub const FRONT_LEFT_OF_CENTER: u32 = 0x40;
+/// The bit of the front right of center speaker position.
+pub const FRONT_RIGHT_OF_CENTER: u32 = 0x80;
+/// The bit of the back center speaker position.
+pub const BACK_CENTER: u32 = 0x100;
+/// The bit of the side left speaker position.
+pub const SIDE_LEFT: u32 = 0x200;
+/// The bit of the side right speaker position.
+pub const SIDE_RIGHT: u32 = 0x400;
+/// The bit of the top center speaker position.
+pub const TOP_CENTER: u32 = 0x800;
+/// The bit of the top front left speaker position.
+pub const TOP_FRONT_LEFT: u32 = 0x1000;
+/// The bit of the top front center speaker position.
+pub const TOP_FRONT_CENTER: u32 = 0x2000;
+/// The bit of the top front right speaker position.
+pub const TOP_FRONT_RIGHT: u32 = 0x4000;
+/// The bit of the top back left speaker position.
+pub const TOP_BACK_LEFT: u32 = 0x8000;
+/// The bit of the top back center speaker position.
+pub const TOP_BACK_CENTER: u32 = 0x10000;
+/// The bit of the top back right speaker position.
+pub const TOP_BACK_RIGHT: u32 = 0x20000;
+
+/// Get the mask of active channels for a channel mask.
+/// The returned mask has one entry for each speaker position
+/// up to the highest position in the channel mask,
+/// and is empty for a channel mask of zero.
+pub fn active_channels(channel_mask: u32) -> Vec<bool> {
+    let positions = 32 - channel_mask.leading_zeros() as usize;
+    (0..positions)
+        .map(|position| channel_mask & (1 << position) != 0)
+        .collect()
+}
+
+/// Get the channel mask for a mask of active channels,
+/// where the channel numbers are the speaker positions.
+/// Returns `None` if a channel above position 31 is active,
+/// since it cannot be represented in a channel mask.
+pub fn channel_mask(active_channels: &[bool]) -> Option<u32> {
+    let mut channel_mask = 0;
+    for (position, _) in active_channels
+        .iter()
+        .enumerate()
+        .filter(|(_, active)| **active)
+    {
+        if position >= 32 {
+            return None;
+        }
+        channel_mask |= 1 << position;
+    }
+    Some(channel_mask)
+}
+
+/// Place the channels of a stream at their speaker positions.
+/// The `channels` vector holds the channels in the order of the stream,
+/// one for each bit set in the channel mask.
+/// The returned vector has one entry for each speaker position
+/// up to the highest position in the channel mask,
+/// with empty vectors for the positions that are not in the mask.
+/// Returns a [SizeError] if the number of channels does not match the channel mask.
+pub fn spread_to_positions<T>(
+    channels: Vec<Vec<T>>,
+    channel_mask: u32,
+) -> Result<Vec<Vec<T>>, SizeError> {
+    let required = channel_mask.count_ones() as usize;
+    if channels.len() != required {
+        return Err(SizeError::Mask {
+            actual: channels.len(),
+            required,
+        });
+    }
+    let mut channels = channels.into_iter();
+    Ok(active_channels(channel_mask)
+        .into_iter()
+        .map(|active| {
+            if active {
+                channels.next().unwrap_or_default()
+            } else {
+                Vec::new()
+            }
+        })
+        .collect())
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direct::SparseSequentialSliceOfVecs;
+    use crate::{Adapter, AdapterMut};
+
+    #[test]
+    fn mask_round_trip() {
+        let surround = FRONT_LEFT | FRONT_RIGHT | LOW_FREQUENCY | SIDE_LEFT | SIDE_RIGHT;
+        let mask = active_channels(surround);
+        assert_eq!(mask.len(), 11);
+        assert_eq!(mask.iter().filter(|active| **active).count(), 5);
+        assert!(mask[3] && !mask[2]);
+        assert_eq!(channel_mask(&mask), Some(surround));
+        assert!(active_channels(0).is_empty());
+        assert_eq!(
+            channel_mask(&active_channels(0x8000_0000)),
+            Some(0x8000_0000)
+        );
+        let mut too_many = vec![false; 33];
+        too_many[32] = true;
+        assert_eq!(channel_mask(&too_many), None);
+    }
+
+    #[test]
+    fn sparse_from_channel_mask() {
+        let channel_mask = FRONT_CENTER | BACK_CENTER;
+        assert!(spread_to_positions(vec![vec![1, 2]], channel_mask).is_err());
+        let mut channels = spread_to_positions(vec![vec![1, 2], vec![3, 4]], channel_mask).unwrap();
+        assert_eq!(channels.len(), 9);
+        let mut buffer =
+            SparseSequentialSliceOfVecs::new_from_channel_mask_mut(&mut channels, 2, channel_mask)
+                .unwrap();
+        assert_eq!(buffer.channels(), 9);
+        assert_eq!(buffer.read_sample(2, 1), Some(2));
+        assert_eq!(buffer.read_sample(0, 1), Some(0));
+        buffer.write_sample(8, 0, &5).unwrap();
+        assert_eq!(buffer.channel_mask(), Some(channel_mask));
+        assert_eq!(channels[8], [5, 4]);
+    }
+}
@@ -0,0 +1,173 @@
+//! Logging of clipped samples in the converting wrappers.
+//!
+//! When the `log` feature is enabled, the converting wrappers emit a warning
+//! via the [log](https://crates.io/crates/log) crate the first time
+//! a value is clipped while writing to the wrapped buffer.
+//! Only one warning is emitted per wrapper,
+//! to avoid flooding the log when a signal is clipped continuously.
+//! Without the `log` feature, this compiles to nothing.
+//!
+//! With the `std` feature, the wrappers can also record the frame indices
+//! of the first clipped samples of each channel.
+//! This is disabled by default.
+
+/// Keeps track of whether clipping has been reported for a wrapper,
+/// and of the recorded positions of clipped samples.
+#[derive(Debug, Clone)]
+pub(crate) struct ClipLog {
+    #[cfg(feature = "log")]
+    enabled: bool,
+    #[cfg(feature = "log")]
+    reported: bool,
+    #[cfg(feature = "std")]
+    max_positions: usize,
+    #[cfg(feature = "std")]
+    positions: Vec<Vec<usize>>,
+}
+
+impl ClipLog {
+    /// Create a new `ClipLog`, with logging enabled.
+    pub(crate) fn new() -> Self {
+        Self {
+            #[cfg(feature = "log")]
+            enabled: true,
+            #[cfg(feature = "log")]
+            reported: false,
+            #[cfg(feature = "std")]
+            max_positions: 0,
+            #[cfg(feature = "std")]
+            positions: Vec::new(),
+        }
+    }
+
+    /// Enable or disable logging.
+    /// This also rearms the warning, so that the next clipped value is reported again.
+    #[cfg(feature = "log")]
+    pub(crate) fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        self.reported = false;
+    }
+
+    /// Check if logging is enabled.
+    #[cfg(feature = "log")]
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Report the number of values that were clipped by a write
+    /// starting at the given channel and frame.
+    /// A warning is logged if this is the first clipping since logging was enabled.
+    #[inline]
+    pub(crate) fn report(&mut self, nbr_clipped: usize, channel: usize, frame: usize) {
+        #[cfg(feature = "log")]
+        if nbr_clipped > 0 && self.enabled && !self.reported {
+            self.reported = true;
+            log::warn!(
+                "Clipped {} sample(s) when writing to channel {}, frame {}",
+                nbr_clipped,
+                channel,
+                frame
+            );
+        }
+        #[cfg(not(feature = "log"))]
+        let _ = (nbr_clipped, channel, frame);
+    }
+
+    /// Set the maximum number of clipped positions to record for each channel.
+    /// Zero disables the recording.
+    /// This also clears any previously recorded positions.
+    #[cfg(feature = "std")]
+    pub(crate) fn set_max_positions(&mut self, max_positions: usize) {
+        self.max_positions = max_positions;
+        self.positions.clear();
+    }
+
+    /// Check if the positions of clipped samples are being recorded.
+    #[inline]
+    pub(crate) fn is_recording(&self) -> bool {
+        #[cfg(feature = "std")]
+        return self.max_positions > 0;
+        #[cfg(not(feature = "std"))]
+        false
+    }
+
+    /// Record that the sample at the given channel and frame was clipped.
+    /// Nothing is recorded if recording is disabled,
+    /// or if the maximum number of positions for the channel has been reached.
+    #[inline]
+    pub(crate) fn record(&mut self, channel: usize, frame: usize) {
+        #[cfg(feature = "std")]
+        if self.max_positions > 0 {
+            if channel >= self.positions.len() {
+                self.positions.resize_with(channel + 1, Vec::new);
+            }
+            let positions = &mut self.positions[channel];
+            if positions.len() < self.max_positions {
+                positions.push(frame);
+            }
+        }
+        #[cfg(not(feature = "std"))]
+        let _ = (channel, frame);
+    }
+
+    /// Get the recorded frame indices of clipped samples for a channel.
+    #[cfg(feature = "std")]
+    pub(crate) fn positions(&self, channel: usize) -> &[usize] {
+        self.positions
+            .get(channel)
+            .map_or(&[], |positions| positions.as_slice())
+    }
+
+    /// Clear the recorded positions, keeping the recording enabled.
+    #[cfg(feature = "std")]
+    pub(crate) fn clear_positions(&mut self) {
+        self.positions.clear();
+    }
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    #[cfg(any(feature = "log", feature = "std"))]
+    use super::*;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn record_positions() {
+        let mut clip_log = ClipLog::new();
+        assert!(!clip_log.is_recording());
+        clip_log.record(0, 3);
+        assert_eq!(clip_log.positions(0), &[] as &[usize]);
+        clip_log.set_max_positions(2);
+        assert!(clip_log.is_recording());
+        for frame in 0..4 {
+            clip_log.record(1, frame);
+        }
+        assert_eq!(clip_log.positions(0), &[] as &[usize]);
+        assert_eq!(clip_log.positions(1), &[0, 1]);
+        clip_log.clear_positions();
+        assert_eq!(clip_log.positions(1), &[] as &[usize]);
+    }
+
+    #[cfg(feature = "log")]
+    #[test]
+    fn report_once() {
+        let mut clip_log = ClipLog::new();
+        clip_log.report(0, 0, 0);
+        assert!(!clip_log.reported);
+        clip_log.report(2, 1, 5);
+        assert!(clip_log.reported);
+        clip_log.set_enabled(false);
+        assert!(!clip_log.reported);
+        clip_log.report(1, 0, 0);
+        assert!(!clip_log.reported);
+        clip_log.set_enabled(true);
+        clip_log.report(1, 0, 0);
+        assert!(clip_log.reported);
+    }
+}
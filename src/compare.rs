@@ -0,0 +1,201 @@
+//! # Comparing the contents of buffers
+//!
+//! This module provides functions for checking that two buffers
+//! contain the same audio, within some tolerance.
+//! The buffers are accessed via the [Adapter] trait,
+//! which means that buffers with different layouts can be compared directly.
+//! Buffers with other sample formats can be compared by wrapping them
+//! in one of the converting wrappers first.
+//!
+//! Only the channels and frames that exist in both buffers are compared.
+//!
+//! For caching, the exact contents of a buffer can be summarized with [content_hash](crate::compare::content_hash),
+//! and two buffers can be checked for identical contents with [content_eq](crate::compare::content_eq).
+//! These take the dimensions of the buffers into account,
+//! and compare the bit patterns of the samples without any tolerance.
+//!
+//! ## Example
+//! Compare a sequential float buffer with an interleaved buffer of 16-bit integers.
+//! ```
+//! use audioadapter::compare::{max_abs_diff, snr};
+//! use audioadapter::direct::SequentialSlice;
+//! use audioadapter::number_to_float::InterleavedNumbers;
+//!
+//! let reference = [0.5_f32, -0.25, 0.25, -0.5];
+//! let converted = [16384_i16, 8192, -8192, -16384];
+//! let a = SequentialSlice::new(&reference, 2, 2).unwrap();
+//! let b = InterleavedNumbers::<_, f32>::new(&converted, 2, 2).unwrap();
+//!
+//! assert_eq!(max_abs_diff(&a, &b), 0.0);
+//! assert!(snr(&a, &b) > 120.0);
+//! ```
+
+use crate::Adapter;
+
+/// Call `compare` for each pair of channels in the two buffers,
+/// with the samples of the channel from `a` and from `b`.
+fn for_each_channel<'a>(
+    a: &dyn Adapter<'a, f32>,
+    b: &dyn Adapter<'a, f32>,
+    mut compare: impl FnMut(&[f32], &[f32]),
+) {
+    let channels = a.channels().min(b.channels());
+    let frames = a.frames().min(b.frames());
+    let mut values_a = vec![0.0; frames];
+    let mut values_b = vec![0.0; frames];
+    for channel in 0..channels {
+        a.write_from_channel_to_slice(channel, 0, &mut values_a);
+        b.write_from_channel_to_slice(channel, 0, &mut values_b);
+        compare(&values_a, &values_b);
+    }
+}
+
+/// Calculate the signal-to-noise ratio in dB of buffer `b`,
+/// using buffer `a` as the reference signal.
+/// The noise is the difference between the two buffers.
+///
+/// Returns positive infinity if the buffers are identical.
+pub fn snr<'a>(a: &dyn Adapter<'a, f32>, b: &dyn Adapter<'a, f32>) -> f64 {
+    let mut signal = 0.0;
+    let mut noise = 0.0;
+    for_each_channel(a, b, |values_a, values_b| {
+        for (value_a, value_b) in values_a.iter().zip(values_b.iter()) {
+            signal += (*value_a as f64).powi(2);
+            noise += (*value_a as f64 - *value_b as f64).powi(2);
+        }
+    });
+    if noise == 0.0 {
+        return f64::INFINITY;
+    }
+    10.0 * (signal / noise).log10()
+}
+
+/// Find the largest absolute difference between
+/// the samples of buffer `a` and buffer `b`.
+pub fn max_abs_diff<'a>(a: &dyn Adapter<'a, f32>, b: &dyn Adapter<'a, f32>) -> f32 {
+    let mut max_diff = 0.0_f32;
+    for_each_channel(a, b, |values_a, values_b| {
+        for (value_a, value_b) in values_a.iter().zip(values_b.iter()) {
+            max_diff = max_diff.max((value_a - value_b).abs());
+        }
+    });
+    max_diff
+}
+
+/// Check if two buffers have the same dimensions and identical contents.
+/// The samples are compared by their bit patterns,
+/// which means that NaN values with the same bits are equal,
+/// while `0.0` and `-0.0` are not.
+/// This matches [content_hash], so that equal buffers always have equal hashes.
+pub fn content_eq<'a>(a: &dyn Adapter<'a, f32>, b: &dyn Adapter<'a, f32>) -> bool {
+    if a.channels() != b.channels() || a.frames() != b.frames() {
+        return false;
+    }
+    let mut equal = true;
+    for_each_channel(a, b, |values_a, values_b| {
+        equal = equal
+            && values_a
+                .iter()
+                .zip(values_b.iter())
+                .all(|(value_a, value_b)| value_a.to_bits() == value_b.to_bits());
+    });
+    equal
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Feed the bytes of a value to a 64-bit FNV-1a hash.
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// Calculate a 64-bit hash of the dimensions and the samples of a buffer.
+/// The hash only depends on the sample values and not on the layout,
+/// so buffers with different layouts but the same contents get the same hash.
+/// The hash is calculated with the FNV-1a algorithm,
+/// and is stable between runs and platforms.
+/// It is intended for detecting changed buffers, and is not cryptographically secure.
+pub fn content_hash<'a>(buffer: &dyn Adapter<'a, f32>) -> u64 {
+    let mut hash = fnv1a(FNV_OFFSET, &(buffer.channels() as u64).to_le_bytes());
+    hash = fnv1a(hash, &(buffer.frames() as u64).to_le_bytes());
+    let mut values = vec![0.0; buffer.frames()];
+    for channel in 0..buffer.channels() {
+        buffer.write_from_channel_to_slice(channel, 0, &mut values);
+        for value in values.iter() {
+            hash = fnv1a(hash, &value.to_bits().to_le_bytes());
+        }
+    }
+    hash
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direct::{InterleavedSlice, SequentialSlice};
+
+    #[test]
+    fn identical_buffers() {
+        let data_a = [1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let data_b = [1.0_f32, 4.0, 2.0, 5.0, 3.0, 6.0];
+        let a = SequentialSlice::new(&data_a, 2, 3).unwrap();
+        let b = InterleavedSlice::new(&data_b, 2, 3).unwrap();
+        assert_eq!(max_abs_diff(&a, &b), 0.0);
+        assert_eq!(snr(&a, &b), f64::INFINITY);
+    }
+
+    #[test]
+    fn different_buffers() {
+        let data_a = [1.0_f32, -1.0, 1.0, -1.0];
+        let data_b = [0.9_f32, -1.0, 1.1, -1.0, 7.0];
+        let a = SequentialSlice::new(&data_a, 1, 4).unwrap();
+        let b = SequentialSlice::new(&data_b, 1, 5).unwrap();
+        assert!((max_abs_diff(&a, &b) - 0.1).abs() < 1.0e-6);
+        // Signal power 4, noise power 0.02.
+        assert!((snr(&a, &b) - 23.0103).abs() < 1.0e-3);
+    }
+
+    #[test]
+    fn equal_contents() {
+        let data_a = [1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let data_b = [1.0_f32, 4.0, 2.0, 5.0, 3.0, 6.0];
+        let a = SequentialSlice::new(&data_a, 2, 3).unwrap();
+        let b = InterleavedSlice::new(&data_b, 2, 3).unwrap();
+        assert!(content_eq(&a, &b));
+        assert_eq!(content_hash(&a), content_hash(&b));
+
+        // Same samples with other dimensions.
+        let c = SequentialSlice::new(&data_a, 3, 2).unwrap();
+        assert!(!content_eq(&a, &c));
+        assert_ne!(content_hash(&a), content_hash(&c));
+    }
+
+    #[test]
+    fn different_contents() {
+        let data_a = [0.0_f32, 1.0, f32::NAN];
+        let data_b = [-0.0_f32, 1.0, f32::NAN];
+        let a = SequentialSlice::new(&data_a, 1, 3).unwrap();
+        let b = SequentialSlice::new(&data_b, 1, 3).unwrap();
+        assert!(content_eq(&a, &a));
+        assert!(!content_eq(&a, &b));
+        assert_ne!(content_hash(&a), content_hash(&b));
+    }
+
+    #[cfg(not(feature = "reject-zero-dimensions"))]
+    #[test]
+    fn empty_contents() {
+        let data = [0.0_f32, 1.0, 2.0];
+        let a = SequentialSlice::new(&data, 1, 3).unwrap();
+        let empty = SequentialSlice::new(&data, 0, 0).unwrap();
+        assert!(content_eq(&empty, &empty));
+        assert_ne!(content_hash(&empty), content_hash(&a));
+    }
+}
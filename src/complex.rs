@@ -0,0 +1,231 @@
+//! # Complex samples
+//!
+//! This module adds support for buffers of complex samples,
+//! using the [Complex] type from the [num-complex](https://crates.io/crates/num-complex) crate.
+//! Such buffers are used for IQ data and analytic signals.
+//!
+//! The wrappers of the [direct](crate::direct) and [owned](crate::owned) modules
+//! are generic over the sample type, and can wrap complex samples directly.
+//! The [ComplexStats] trait provides statistics
+//! based on the magnitude of the samples,
+//! and the [ComplexView] wrapper gives a real valued view
+//! of one component of the samples, such as the real part or the magnitude.
+//! The view can be used with any code that takes a buffer of real samples,
+//! for example the [AdapterStats](crate::stats::AdapterStats) methods.
+//!
+//! This is enabled via the `complex` Cargo feature.
+//!
+//! ## Example
+//! Calculate the magnitude RMS of an IQ signal.
+//! ```
+//! use audioadapter::complex::{ComplexStats, ComplexView, Component};
+//! use audioadapter::direct::InterleavedSlice;
+//! use audioadapter::stats::AdapterStats;
+//! use audioadapter::Adapter;
+//! use num_complex::Complex;
+//!
+//! let data = [Complex::new(0.6_f32, 0.8), Complex::new(-0.6, -0.8)];
+//! let buffer = InterleavedSlice::new(&data, 1, 2).unwrap();
+//! assert!((buffer.channel_magnitude_rms(0) - 1.0).abs() < 1.0e-6);
+//!
+//! let real = ComplexView::new(&buffer as &dyn Adapter<Complex<f32>>, Component::Real);
+//! assert_eq!(real.channel_min_and_max(0), (-0.6, 0.6));
+//! ```
+
+use core::any::Any;
+
+use num_complex::Complex;
+use num_traits::{Float, ToPrimitive};
+
+use crate::stats::KahanSum;
+use crate::{Adapter, IterationOrder};
+
+/// Convert a complex sample to `f64` parts.
+fn to_f64<T: ToPrimitive>(value: &Complex<T>) -> Complex<f64> {
+    Complex::new(
+        value.re.to_f64().unwrap_or_default(),
+        value.im.to_f64().unwrap_or_default(),
+    )
+}
+
+/// A trait providing methods to calculate statistics of buffers with complex samples.
+/// The results are returned as `f64`.
+/// Invalid channel numbers give zero.
+pub trait ComplexStats<'a, T>: Adapter<'a, Complex<T>>
+where
+    T: Clone + ToPrimitive + 'a,
+{
+    /// Calculate the RMS value of the magnitude of the samples of the given channel.
+    /// This is the square root of the mean power.
+    fn channel_magnitude_rms(&self, channel: usize) -> f64 {
+        if channel >= self.channels() || self.frames() == 0 {
+            return 0.0;
+        }
+        let mut square_sum = KahanSum::default();
+        for frame in 0..self.frames() {
+            let value = unsafe { self.read_sample_unchecked(channel, frame) };
+            square_sum.add(to_f64(&value).norm_sqr());
+        }
+        (square_sum.value() / self.frames() as f64).sqrt()
+    }
+
+    /// Find the largest magnitude of the samples of the given channel.
+    fn channel_peak_magnitude(&self, channel: usize) -> f64 {
+        if channel >= self.channels() {
+            return 0.0;
+        }
+        (0..self.frames())
+            .map(|frame| unsafe { self.read_sample_unchecked(channel, frame) })
+            .map(|value| to_f64(&value).norm_sqr())
+            .fold(0.0, f64::max)
+            .sqrt()
+    }
+
+    /// Calculate the mean value of the samples of the given channel.
+    /// For IQ data, this is the DC offset of the I and Q components.
+    fn channel_mean(&self, channel: usize) -> Complex<f64> {
+        if channel >= self.channels() || self.frames() == 0 {
+            return Complex::new(0.0, 0.0);
+        }
+        let mut re_sum = KahanSum::default();
+        let mut im_sum = KahanSum::default();
+        for frame in 0..self.frames() {
+            let value = to_f64(&unsafe { self.read_sample_unchecked(channel, frame) });
+            re_sum.add(value.re);
+            im_sum.add(value.im);
+        }
+        let frames = self.frames() as f64;
+        Complex::new(re_sum.value() / frames, im_sum.value() / frames)
+    }
+}
+
+impl<'a, T, U> ComplexStats<'a, T> for U
+where
+    T: Clone + ToPrimitive + 'a,
+    U: Adapter<'a, Complex<T>> + ?Sized,
+{
+}
+
+/// A component of a complex sample.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Component {
+    /// The real part.
+    Real,
+    /// The imaginary part.
+    Imaginary,
+    /// The magnitude, or absolute value.
+    Magnitude,
+    /// The phase angle in radians, in the range -π to +π.
+    Phase,
+}
+
+/// A read-only wrapper for a buffer of complex samples,
+/// that gives a real valued view of one [Component] of the samples.
+pub struct ComplexView<U> {
+    buf: U,
+    component: Component,
+}
+
+impl<'a, 'b, T> ComplexView<&'b dyn Adapter<'a, Complex<T>>>
+where
+    T: Clone + 'a,
+{
+    /// Create a new view of a component of the samples of a buffer.
+    pub fn new(buf: &'b dyn Adapter<'a, Complex<T>>, component: Component) -> Self {
+        Self { buf, component }
+    }
+}
+
+impl<U> ComplexView<U> {
+    /// Get the component of the samples that the view gives.
+    pub fn component(&self) -> Component {
+        self.component
+    }
+
+    /// Change the component of the samples that the view gives.
+    pub fn set_component(&mut self, component: Component) {
+        self.component = component;
+    }
+}
+
+impl<'a, T> Adapter<'a, T> for ComplexView<&dyn Adapter<'a, Complex<T>>>
+where
+    T: Float + 'a,
+{
+    unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
+        let value = self.buf.read_sample_unchecked(channel, frame);
+        match self.component {
+            Component::Real => value.re,
+            Component::Imaginary => value.im,
+            Component::Magnitude => value.re.hypot(value.im),
+            Component::Phase => value.im.atan2(value.re),
+        }
+    }
+
+    fn channels(&self) -> usize {
+        self.buf.channels()
+    }
+
+    fn frames(&self) -> usize {
+        self.buf.frames()
+    }
+
+    fn is_channel_active(&self, channel: usize) -> bool {
+        self.buf.is_channel_active(channel)
+    }
+
+    fn preferred_iteration(&self) -> IterationOrder {
+        self.buf.preferred_iteration()
+    }
+
+    fn metadata(&self) -> Option<&dyn Any> {
+        self.buf.metadata()
+    }
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direct::SequentialSlice;
+    use crate::owned::InterleavedOwned;
+    use crate::AdapterMut;
+
+    #[test]
+    fn complex_stats() {
+        let data = [
+            Complex::new(3_i16, 4),
+            Complex::new(0, 0),
+            Complex::new(1, 1),
+            Complex::new(1, 3),
+        ];
+        let buffer = SequentialSlice::new(&data, 2, 2).unwrap();
+        assert!((buffer.channel_magnitude_rms(0) - 12.5_f64.sqrt()).abs() < 1.0e-9);
+        assert_eq!(buffer.channel_peak_magnitude(0), 5.0);
+        assert_eq!(buffer.channel_mean(1), Complex::new(1.0, 2.0));
+        assert_eq!(buffer.channel_magnitude_rms(2), 0.0);
+        assert_eq!(buffer.channel_mean(2), Complex::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn component_views() {
+        let mut buffer = InterleavedOwned::new(Complex::new(0.0_f64, 0.0), 2, 3);
+        buffer.write_sample(1, 2, &Complex::new(0.0, -0.5)).unwrap();
+        let dyn_buffer = &buffer as &dyn Adapter<Complex<f64>>;
+        let mut view = ComplexView::new(dyn_buffer, Component::Imaginary);
+        assert_eq!(view.channels(), 2);
+        assert_eq!(view.frames(), 3);
+        assert_eq!(view.read_sample(1, 2), Some(-0.5));
+        view.set_component(Component::Magnitude);
+        assert_eq!(view.read_sample(1, 2), Some(0.5));
+        view.set_component(Component::Phase);
+        assert_eq!(view.read_sample(1, 2), Some(-core::f64::consts::FRAC_PI_2));
+        assert_eq!(view.read_sample(2, 0), None);
+        assert_eq!(view.preferred_iteration(), IterationOrder::FramesOuter);
+    }
+}
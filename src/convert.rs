@@ -0,0 +1,185 @@
+//! # One-shot conversion functions
+//!
+//! This module provides functions for converting a complete block of samples
+//! between `f32` values and raw bytes in one call,
+//! without needing to set up wrappers for the source and destination.
+//! This covers the common case of moving data between the `f32` buffers
+//! of an audio application and the interleaved byte buffers of an audio device.
+//!
+//! The byte format is selected by the type parameter,
+//! that can be any of the raw byte sample types of the [sample](crate::sample) module.
+//! Values outside the range -1.0 .. +1.0 are clipped when converting to integer formats.
+//!
+//! For other combinations, or to access only parts of the buffers,
+//! use the wrappers of the [number_to_float](crate::number_to_float) module.
+//!
+//! ## Example
+//! Convert two channels of `f32` samples to interleaved 24-bit little endian bytes.
+//! ```
+//! use audioadapter::convert::planar_f32_to_interleaved_bytes;
+//! use audioadapter::sample::I24LE;
+//!
+//! let left = [0.0_f32, 0.5];
+//! let right = [-0.5_f32, 1.0];
+//! let mut bytes = [0_u8; 12];
+//! let frames = planar_f32_to_interleaved_bytes::<I24LE<3>>(&[&left, &right], &mut bytes);
+//! assert_eq!(frames, 2);
+//! assert_eq!(bytes[3..6], [0x00, 0x00, 0xc0]);
+//! assert_eq!(bytes[9..12], [0xff, 0xff, 0x7f]);
+//! ```
+
+use crate::sample::{BytesSample, RawSample};
+
+/// Get the number of frames that can be converted,
+/// given the number of frames available in the float buffers and in the byte buffer.
+fn nbr_frames<S: BytesSample>(float_frames: usize, channels: usize, bytes: usize) -> usize {
+    if channels == 0 {
+        return 0;
+    }
+    float_frames.min(bytes / (channels * S::BYTES_PER_SAMPLE))
+}
+
+/// Convert `f32` samples stored as one slice per channel,
+/// to raw bytes of the sample format `S` in _interleaved_ order.
+/// The number of frames converted is limited by the shortest channel slice,
+/// and by the number of complete frames that fit in the destination.
+///
+/// Returns the number of frames converted.
+pub fn planar_f32_to_interleaved_bytes<S>(src: &[&[f32]], dst: &mut [u8]) -> usize
+where
+    S: BytesSample + RawSample,
+{
+    let channels = src.len();
+    let shortest = src.iter().map(|chan| chan.len()).min().unwrap_or_default();
+    let frames = nbr_frames::<S>(shortest, channels, dst.len());
+    let mut samples = dst.chunks_exact_mut(S::BYTES_PER_SAMPLE);
+    for frame in 0..frames {
+        for (chan, bytes) in src.iter().zip(samples.by_ref()) {
+            bytes.copy_from_slice(S::from_scaled_float(chan[frame]).value.as_slice());
+        }
+    }
+    frames
+}
+
+/// Convert raw bytes of the sample format `S` in _interleaved_ order,
+/// to `f32` samples stored as one slice per channel.
+/// The number of frames converted is limited by the shortest channel slice,
+/// and by the number of complete frames in the source.
+///
+/// Returns the number of frames converted.
+pub fn interleaved_bytes_to_planar_f32<S>(src: &[u8], dst: &mut [&mut [f32]]) -> usize
+where
+    S: BytesSample + RawSample,
+{
+    let channels = dst.len();
+    let shortest = dst.iter().map(|chan| chan.len()).min().unwrap_or_default();
+    let frames = nbr_frames::<S>(shortest, channels, src.len());
+    let mut samples = src.chunks_exact(S::BYTES_PER_SAMPLE);
+    for frame in 0..frames {
+        for (chan, bytes) in dst.iter_mut().zip(samples.by_ref()) {
+            chan[frame] = S::from_slice(bytes).to_scaled_float();
+        }
+    }
+    frames
+}
+
+/// Convert interleaved `f32` samples to raw bytes of the sample format `S`,
+/// keeping the order of the samples.
+/// Since the order is kept, the number of channels does not need to be given.
+/// The number of samples converted is limited by the number of complete samples
+/// that fit in the destination.
+///
+/// Returns the number of samples converted.
+pub fn interleaved_f32_to_interleaved_bytes<S>(src: &[f32], dst: &mut [u8]) -> usize
+where
+    S: BytesSample + RawSample,
+{
+    let mut converted = 0;
+    for (value, bytes) in src.iter().zip(dst.chunks_exact_mut(S::BYTES_PER_SAMPLE)) {
+        bytes.copy_from_slice(S::from_scaled_float(*value).value.as_slice());
+        converted += 1;
+    }
+    converted
+}
+
+/// Convert raw bytes of the sample format `S` to interleaved `f32` samples,
+/// keeping the order of the samples.
+/// Since the order is kept, the number of channels does not need to be given.
+/// The number of samples converted is limited by the length of the destination,
+/// and by the number of complete samples in the source.
+///
+/// Returns the number of samples converted.
+pub fn interleaved_bytes_to_interleaved_f32<S>(src: &[u8], dst: &mut [f32]) -> usize
+where
+    S: BytesSample + RawSample,
+{
+    let mut converted = 0;
+    for (value, bytes) in dst.iter_mut().zip(src.chunks_exact(S::BYTES_PER_SAMPLE)) {
+        *value = S::from_slice(bytes).to_scaled_float();
+        converted += 1;
+    }
+    converted
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample::{I16LE, I24LE};
+
+    #[test]
+    fn planar_round_trip() {
+        let left = [0.0_f32, 0.5, -1.0];
+        let right = [0.25_f32, -0.5];
+        let mut bytes = [0_u8; 14];
+        // limited by the shortest channel
+        assert_eq!(
+            planar_f32_to_interleaved_bytes::<I16LE>(&[&left, &right], &mut bytes),
+            2
+        );
+        assert_eq!(bytes[..8], [0x00, 0x00, 0x00, 0x20, 0x00, 0x40, 0x00, 0xc0]);
+        // limited by the size of the destination
+        assert_eq!(
+            planar_f32_to_interleaved_bytes::<I16LE>(&[&left, &right], &mut bytes[..7]),
+            1
+        );
+
+        let mut left_out = [0.0_f32; 4];
+        let mut right_out = [0.0_f32; 4];
+        assert_eq!(
+            interleaved_bytes_to_planar_f32::<I16LE>(&bytes, &mut [&mut left_out, &mut right_out]),
+            3
+        );
+        assert_eq!(left_out[..2], left[..2]);
+        assert_eq!(right_out[..2], right);
+        assert_eq!(planar_f32_to_interleaved_bytes::<I16LE>(&[], &mut bytes), 0);
+    }
+
+    #[test]
+    fn interleaved_round_trip() {
+        let values = [0.5_f32, -0.25, 2.0];
+        let mut bytes = [0_u8; 8];
+        assert_eq!(
+            interleaved_f32_to_interleaved_bytes::<I24LE<3>>(&values, &mut bytes),
+            2
+        );
+        assert_eq!(bytes[..6], [0x00, 0x00, 0x40, 0x00, 0x00, 0xe0]);
+        let mut bytes = [0_u8; 9];
+        interleaved_f32_to_interleaved_bytes::<I24LE<3>>(&values, &mut bytes);
+        // the last value is clipped
+        assert_eq!(bytes[6..], [0xff, 0xff, 0x7f]);
+
+        let mut converted = [0.0_f32; 4];
+        assert_eq!(
+            interleaved_bytes_to_interleaved_f32::<I24LE<3>>(&bytes, &mut converted),
+            3
+        );
+        assert_eq!(converted[..2], values[..2]);
+        assert!(converted[2] < 1.0 && converted[2] > 0.999);
+    }
+}
@@ -0,0 +1,192 @@
+//! # Cursors for incremental access
+//!
+//! This module provides cursors that keep track of the current position
+//! in a buffer, for code that reads or writes a buffer incrementally,
+//! one frame at a time.
+//!
+//! ## Example
+//! Fill a buffer frame by frame.
+//! ```
+//! use audioadapter::direct::InterleavedSlice;
+//! use audioadapter::{Adapter, AdapterMut};
+//!
+//! let mut data = [0_i32; 4];
+//! let mut buffer = InterleavedSlice::new_mut(&mut data, 2, 2).unwrap();
+//! let mut writer = buffer.frame_writer();
+//! writer.push_frame(&[1, 2]).unwrap();
+//! writer.push_frame(&[3, 4]).unwrap();
+//! assert!(writer.push_frame(&[5, 6]).is_err());
+//! assert_eq!(data, [1, 2, 3, 4]);
+//! ```
+//!
+//! Read a buffer frame by frame.
+//! ```
+//! use audioadapter::direct::InterleavedSlice;
+//! use audioadapter::Adapter;
+//!
+//! let data = [1_i32, 2, 3, 4, 5, 6];
+//! let buffer = InterleavedSlice::new(&data, 2, 3).unwrap();
+//! let mut reader = buffer.frame_reader();
+//! let mut frame = [0; 2];
+//! reader.seek_frames(1);
+//! assert_eq!(reader.next_frame_into(&mut frame), 2);
+//! assert_eq!(frame, [3, 4]);
+//! ```
+
+use crate::{Adapter, AdapterMut};
+
+/// Error returned when trying to write past the end of a buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Full;
+
+#[cfg(feature = "std")]
+impl std::error::Error for Full {}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for Full {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "The buffer is full")
+    }
+}
+
+/// A cursor for writing frames to an [AdapterMut] one at a time.
+/// Each pushed frame is written at the current position,
+/// which is then advanced to the next frame.
+pub struct FrameWriter<'a, 'b, T> {
+    buf: &'b mut dyn AdapterMut<'a, T>,
+    frame: usize,
+}
+
+impl<'a, 'b, T> FrameWriter<'a, 'b, T>
+where
+    T: Clone + 'a,
+{
+    /// Create a new writer, starting at the first frame of the buffer.
+    pub fn new(buffer: &'b mut dyn AdapterMut<'a, T>) -> Self {
+        Self {
+            buf: buffer,
+            frame: 0,
+        }
+    }
+
+    /// Write a frame at the current position and advance to the next frame.
+    /// The slice should contain one value per channel.
+    /// If the slice is longer than the number of channels,
+    /// only the number of values that fit are written.
+    /// If it is shorter, the remaining channels of the frame are left unchanged.
+    /// Returns [Full] if the end of the buffer has already been reached.
+    pub fn push_frame(&mut self, frame: &[T]) -> Result<(), Full> {
+        if self.frame >= self.buf.frames() {
+            return Err(Full);
+        }
+        self.buf.write_from_slice_to_frame(self.frame, 0, frame);
+        self.frame += 1;
+        Ok(())
+    }
+
+    /// Get the current position, meaning the index of the next frame to be written.
+    pub fn position(&self) -> usize {
+        self.frame
+    }
+
+    /// Get the number of frames that can still be written before the buffer is full.
+    pub fn frames_remaining(&self) -> usize {
+        self.buf.frames().saturating_sub(self.frame)
+    }
+}
+
+/// A cursor for reading frames from an [Adapter] one at a time.
+/// Each frame is read from the current position,
+/// which is then advanced to the next frame.
+pub struct FrameReader<'a, 'b, T> {
+    buf: &'b dyn Adapter<'a, T>,
+    frame: usize,
+}
+
+impl<'a, 'b, T> FrameReader<'a, 'b, T>
+where
+    T: Clone + 'a,
+{
+    /// Create a new reader, starting at the first frame of the buffer.
+    pub fn new(buffer: &'b dyn Adapter<'a, T>) -> Self {
+        Self {
+            buf: buffer,
+            frame: 0,
+        }
+    }
+
+    /// Read the frame at the current position into a slice
+    /// and advance to the next frame.
+    /// If the slice is longer than the number of channels,
+    /// only the number of available values are written.
+    /// Returns the number of values written,
+    /// which is zero when the end of the buffer has been reached.
+    pub fn next_frame_into(&mut self, frame: &mut [T]) -> usize {
+        if self.frame >= self.buf.frames() {
+            return 0;
+        }
+        let written = self.buf.write_from_frame_to_slice(self.frame, 0, frame);
+        self.frame += 1;
+        written
+    }
+
+    /// Skip forward by `frames` frames.
+    /// The position is not moved past the end of the buffer.
+    /// Returns the number of frames that were skipped.
+    pub fn seek_frames(&mut self, frames: usize) -> usize {
+        let skipped = frames.min(self.frames_remaining());
+        self.frame += skipped;
+        skipped
+    }
+
+    /// Get the current position, meaning the index of the next frame to be read.
+    pub fn position(&self) -> usize {
+        self.frame
+    }
+
+    /// Get the number of frames that remain to be read.
+    pub fn frames_remaining(&self) -> usize {
+        self.buf.frames().saturating_sub(self.frame)
+    }
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direct::SequentialSlice;
+
+    #[test]
+    fn push_frames() {
+        let mut data = [0_i32; 6];
+        let mut buffer = SequentialSlice::new_mut(&mut data, 2, 3).unwrap();
+        let mut writer = buffer.frame_writer();
+        assert_eq!(writer.frames_remaining(), 3);
+        writer.push_frame(&[1, 4]).unwrap();
+        writer.push_frame(&[2, 5, 8]).unwrap();
+        assert_eq!(writer.position(), 2);
+        writer.push_frame(&[3]).unwrap();
+        assert_eq!(writer.frames_remaining(), 0);
+        assert_eq!(writer.push_frame(&[9, 9]), Err(Full));
+        assert_eq!(data, [1, 2, 3, 4, 5, 0]);
+    }
+
+    #[test]
+    fn read_frames() {
+        let data = [1_i32, 2, 3, 4, 5, 6];
+        let buffer = SequentialSlice::new(&data, 2, 3).unwrap();
+        let mut reader = buffer.frame_reader();
+        let mut frame = [0; 3];
+        assert_eq!(reader.next_frame_into(&mut frame), 2);
+        assert_eq!(frame, [1, 4, 0]);
+        assert_eq!(reader.seek_frames(5), 2);
+        assert_eq!(reader.position(), 3);
+        assert_eq!(reader.frames_remaining(), 0);
+        assert_eq!(reader.next_frame_into(&mut frame), 0);
+    }
+}
@@ -0,0 +1,222 @@
+/// Get the index of a sample in a buffer with the given layout.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __layout_index {
+    (interleaved, $channels:expr, $frames:expr, $channel:expr, $frame:expr) => {
+        $frame * $channels + $channel
+    };
+    (sequential, $channels:expr, $frames:expr, $channel:expr, $frame:expr) => {
+        $channel * $frames + $frame
+    };
+}
+
+/// A macro for implementing the [Adapter](crate::Adapter) trait
+/// for a struct that stores its samples in a single buffer field,
+/// with the number of channels and frames stored in two other fields.
+/// The buffer field can be any type that can be indexed like a slice,
+/// such as a `Vec<T>`, an array, or a slice.
+/// The `layout` is either `interleaved` or `sequential`.
+///
+/// Generic parameters of the struct are given in square brackets before the type.
+/// For structs without generic parameters the brackets can be left out.
+/// The generated implementation uses the lifetime `'a`,
+/// so this name can't be used for a lifetime parameter of the struct.
+///
+/// The struct is responsible for making sure that the buffer
+/// contains at least `channels * frames` samples.
+/// Reading outside the buffer panics.
+///
+/// ```
+/// use audioadapter::{impl_adapter, Adapter};
+///
+/// struct Recording<T> {
+///     samples: Vec<T>,
+///     nbr_channels: usize,
+///     nbr_frames: usize,
+/// }
+///
+/// impl_adapter!([T] Recording<T> {
+///     sample: T,
+///     buffer: samples,
+///     channels: nbr_channels,
+///     frames: nbr_frames,
+///     layout: interleaved,
+/// });
+///
+/// let recording = Recording {
+///     samples: vec![1, 2, 3, 4, 5, 6],
+///     nbr_channels: 2,
+///     nbr_frames: 3,
+/// };
+/// assert_eq!(recording.read_sample(1, 1), Some(4));
+/// ```
+#[macro_export]
+macro_rules! impl_adapter {
+    ([$($generics:tt)*] $type:ty {
+        sample: $sample:ty,
+        buffer: $buf:ident,
+        channels: $channels:ident,
+        frames: $frames:ident,
+        layout: $layout:ident $(,)?
+    }) => {
+        impl<'a, $($generics)*> $crate::Adapter<'a, $sample> for $type
+        where
+            $sample: Clone + 'a,
+        {
+            unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> $sample {
+                let index =
+                    $crate::__layout_index!($layout, self.$channels, self.$frames, channel, frame);
+                self.$buf[index].clone()
+            }
+
+            fn channels(&self) -> usize {
+                self.$channels
+            }
+
+            fn frames(&self) -> usize {
+                self.$frames
+            }
+        }
+    };
+    ($type:ty { $($fields:tt)* }) => {
+        $crate::impl_adapter!([] $type { $($fields)* });
+    };
+}
+
+/// A macro for implementing both the [Adapter](crate::Adapter)
+/// and [AdapterMut](crate::AdapterMut) traits
+/// for a struct that stores its samples in a single buffer field.
+/// It takes the same arguments as [impl_adapter],
+/// and the buffer field must also support mutable indexing.
+///
+/// ```
+/// use audioadapter::{impl_adapter_mut, Adapter, AdapterMut};
+///
+/// struct Block {
+///     data: [f32; 8],
+///     channels: usize,
+///     frames: usize,
+/// }
+///
+/// impl_adapter_mut!(Block {
+///     sample: f32,
+///     buffer: data,
+///     channels: channels,
+///     frames: frames,
+///     layout: sequential,
+/// });
+///
+/// let mut block = Block {
+///     data: [0.0; 8],
+///     channels: 2,
+///     frames: 4,
+/// };
+/// block.write_sample(1, 0, &0.5).unwrap();
+/// assert_eq!(block.data[4], 0.5);
+/// ```
+#[macro_export]
+macro_rules! impl_adapter_mut {
+    ([$($generics:tt)*] $type:ty {
+        sample: $sample:ty,
+        buffer: $buf:ident,
+        channels: $channels:ident,
+        frames: $frames:ident,
+        layout: $layout:ident $(,)?
+    }) => {
+        $crate::impl_adapter!([$($generics)*] $type {
+            sample: $sample,
+            buffer: $buf,
+            channels: $channels,
+            frames: $frames,
+            layout: $layout,
+        });
+
+        impl<'a, $($generics)*> $crate::AdapterMut<'a, $sample> for $type
+        where
+            $sample: Clone + 'a,
+        {
+            unsafe fn write_sample_unchecked(
+                &mut self,
+                channel: usize,
+                frame: usize,
+                value: &$sample,
+            ) -> bool {
+                let index =
+                    $crate::__layout_index!($layout, self.$channels, self.$frames, channel, frame);
+                self.$buf[index] = value.clone();
+                false
+            }
+        }
+    };
+    ($type:ty { $($fields:tt)* }) => {
+        $crate::impl_adapter_mut!([] $type { $($fields)* });
+    };
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::check_copy_within;
+    use crate::{Adapter, AdapterMut};
+
+    struct Interleaved<T> {
+        buf: Vec<T>,
+        channels: usize,
+        frames: usize,
+    }
+
+    impl_adapter_mut!([T] Interleaved<T> {
+        sample: T,
+        buffer: buf,
+        channels: channels,
+        frames: frames,
+        layout: interleaved,
+    });
+
+    struct Sequential<'b> {
+        buf: &'b mut [u32],
+        nbr_channels: usize,
+        nbr_frames: usize,
+    }
+
+    impl_adapter_mut!(['b] Sequential<'b> {
+        sample: u32,
+        buffer: buf,
+        channels: nbr_channels,
+        frames: nbr_frames,
+        layout: sequential,
+    });
+
+    #[test]
+    fn interleaved_struct() {
+        let mut buffer = Interleaved {
+            buf: vec![0_u32; 20],
+            channels: 2,
+            frames: 10,
+        };
+        check_copy_within(&mut buffer);
+        buffer.write_sample(1, 2, &7).unwrap();
+        assert_eq!(buffer.buf[5], 7);
+        assert_eq!(buffer.read_sample(1, 2), Some(7));
+        assert_eq!(buffer.read_sample(2, 2), None);
+    }
+
+    #[test]
+    fn sequential_struct() {
+        let mut data = [0_u32; 30];
+        let mut buffer = Sequential {
+            buf: &mut data,
+            nbr_channels: 3,
+            nbr_frames: 10,
+        };
+        check_copy_within(&mut buffer);
+        buffer.write_sample(1, 2, &7).unwrap();
+        assert_eq!(buffer.read_sample(1, 2), Some(7));
+        assert_eq!(data[12], 7);
+    }
+}
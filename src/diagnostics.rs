@@ -0,0 +1,280 @@
+//! # Diagnostics for raw sample data
+//!
+//! This module helps with wrapping raw dumps of audio data of unknown format.
+//! Given a slice of bytes and a set of candidate interpretations,
+//! each combination of number of channels and sample format,
+//! [guess_layout](crate::diagnostics::guess_layout) scores how plausible
+//! each interpretation is, and returns them with the most likely first.
+//!
+//! The scoring is a heuristic, based on the observation that real audio signals
+//! change slowly from one sample to the next, while data interpreted with
+//! the wrong format or number of channels looks like noise.
+//! Neighboring channels of real recordings also tend to be correlated.
+//! The result should be seen as a hint, and may be wrong for unusual signals,
+//! such as noise, silence, or channels that contain identical data.
+//! The data is assumed to be stored in _interleaved_ order.
+//!
+//! ## Example
+//! Find the format of a stereo 16-bit little endian dump.
+//! ```
+//! use audioadapter::diagnostics::{guess_layout, Candidate};
+//! use audioadapter::sample::SampleFormat;
+//!
+//! let mut data = Vec::new();
+//! for n in 0..1000 {
+//!     let left = (8000.0 * (n as f32 * 0.05).sin()) as i16;
+//!     let right = (8000.0 * (n as f32 * 0.03).cos()) as i16;
+//!     data.extend_from_slice(&left.to_le_bytes());
+//!     data.extend_from_slice(&right.to_le_bytes());
+//! }
+//!
+//! let mut candidates = Vec::new();
+//! for channels in 1..=4 {
+//!     for format in [SampleFormat::I16LE, SampleFormat::I16BE, SampleFormat::F32LE] {
+//!         candidates.push(Candidate { channels, format });
+//!     }
+//! }
+//! let scores = guess_layout(&data, &candidates);
+//! assert_eq!(scores[0].candidate, Candidate { channels: 2, format: SampleFormat::I16LE });
+//! ```
+
+use crate::direct::SequentialSlice;
+use crate::number_to_float::InterleavedNumbers;
+use crate::sample::SampleFormat;
+use crate::stats::AdapterStats;
+use crate::Adapter;
+
+/// The largest absolute value of a sample that is considered valid.
+/// Integer formats are always within -1.0 .. +1.0,
+/// while float data with the wrong interpretation often contains huge values.
+const MAX_VALID_VALUE: f64 = 4.0;
+
+/// The weight of the inter-channel correlation in the score.
+const CORRELATION_WEIGHT: f64 = 0.25;
+
+/// A candidate interpretation of raw sample data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Candidate {
+    /// The number of channels.
+    pub channels: usize,
+    /// The sample format.
+    pub format: SampleFormat,
+}
+
+/// The result of scoring a [Candidate].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayoutScore {
+    /// The scored candidate.
+    pub candidate: Candidate,
+    /// The number of complete frames in the data with this interpretation.
+    pub frames: usize,
+    /// The fraction of the samples that are finite and within a reasonable range.
+    pub valid: f64,
+    /// The energy of the differences between consecutive samples,
+    /// relative to the energy of the signal, and divided by two.
+    /// This is 1.0 for white noise, and close to zero for smooth signals.
+    pub discontinuity: f64,
+    /// The mean absolute correlation between neighboring channels.
+    /// This is zero for a single channel.
+    pub correlation: f64,
+    /// The combined score, where a higher value means a more likely interpretation.
+    /// Candidates that cannot be evaluated, for example because there are
+    /// fewer than two frames, get a score of negative infinity.
+    pub score: f64,
+}
+
+/// Score candidate interpretations of the raw sample data in `data`.
+/// Returns one [LayoutScore] for each candidate, sorted with the highest score first.
+///
+/// The score is the negative base 10 logarithm of the discontinuity,
+/// plus a bonus for correlation between neighboring channels,
+/// and is then scaled by the fraction of valid samples.
+/// Bytes at the end of the data that do not make up a complete frame are ignored.
+pub fn guess_layout(data: &[u8], candidates: &[Candidate]) -> Vec<LayoutScore> {
+    let mut scores: Vec<LayoutScore> = candidates
+        .iter()
+        .map(|candidate| score_candidate(data, *candidate))
+        .collect();
+    scores.sort_by(|a, b| b.score.total_cmp(&a.score));
+    scores
+}
+
+/// Score a single candidate interpretation.
+fn score_candidate(data: &[u8], candidate: Candidate) -> LayoutScore {
+    let channels = candidate.channels;
+    let frame_bytes = channels * candidate.format.bytes_per_sample();
+    let frames = data.len().checked_div(frame_bytes).unwrap_or(0);
+    let mut result = LayoutScore {
+        candidate,
+        frames,
+        valid: 0.0,
+        discontinuity: 1.0,
+        correlation: 0.0,
+        score: f64::NEG_INFINITY,
+    };
+    if frames < 2 {
+        return result;
+    }
+    let values = read_channels(data, candidate, frames);
+
+    let nbr_valid = values
+        .iter()
+        .flatten()
+        .filter(|value| value.is_finite() && value.abs() <= MAX_VALID_VALUE)
+        .count();
+    result.valid = nbr_valid as f64 / (channels * frames) as f64;
+
+    // Invalid values are replaced by zeros, to keep them from dominating the sums.
+    let values: Vec<f64> = values
+        .into_iter()
+        .flatten()
+        .map(|value| {
+            if value.is_finite() && value.abs() <= MAX_VALID_VALUE {
+                value
+            } else {
+                0.0
+            }
+        })
+        .collect();
+
+    let energy: f64 = values.iter().map(|value| value * value).sum();
+    let diff_energy: f64 = values
+        .chunks_exact(frames)
+        .flat_map(|chan| chan.windows(2).map(|pair| (pair[1] - pair[0]).powi(2)))
+        .sum();
+    if energy > 0.0 {
+        result.discontinuity = diff_energy / energy / 2.0;
+    }
+
+    if channels > 1 {
+        // Cannot fail since the vector holds all samples of all channels.
+        let buffer = SequentialSlice::new(&values, channels, frames).unwrap();
+        result.correlation = (1..channels)
+            .map(|channel| buffer.channel_correlation(channel - 1, channel).abs())
+            .sum::<f64>()
+            / (channels - 1) as f64;
+    }
+
+    let smoothness = -result.discontinuity.max(1.0e-9).log10();
+    result.score = result.valid * (smoothness + CORRELATION_WEIGHT * result.correlation);
+    result
+}
+
+/// Read the samples of all channels as float values.
+fn read_channels(data: &[u8], candidate: Candidate, frames: usize) -> Vec<Vec<f64>> {
+    let mut values = vec![vec![0.0; frames]; candidate.channels];
+    crate::with_format!(candidate.format, |S| {
+        // Cannot fail since the number of frames is calculated from the data length.
+        let buffer =
+            InterleavedNumbers::<&[S], f64>::new_from_bytes(data, candidate.channels, frames)
+                .unwrap();
+        for (channel, chan) in values.iter_mut().enumerate() {
+            buffer.write_from_channel_to_slice(channel, 0, chan);
+        }
+    });
+    values
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_candidates(formats: &[SampleFormat]) -> Vec<Candidate> {
+        (1..=4)
+            .flat_map(|channels| {
+                formats.iter().map(move |format| Candidate {
+                    channels,
+                    format: *format,
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn mono_24bit() {
+        let mut data = Vec::new();
+        for n in 0..2000 {
+            let value = (3_000_000.0 * (n as f64 * 0.02).sin()) as i32;
+            data.extend_from_slice(&value.to_le_bytes()[..3]);
+        }
+        let formats = [
+            SampleFormat::I16LE,
+            SampleFormat::I24LE3,
+            SampleFormat::I24BE3,
+            SampleFormat::U24LE3,
+        ];
+        let scores = guess_layout(&data, &all_candidates(&formats));
+        assert_eq!(
+            scores[0].candidate,
+            Candidate {
+                channels: 1,
+                format: SampleFormat::I24LE3
+            }
+        );
+        assert_eq!(scores[0].frames, 2000);
+        assert_eq!(scores[0].valid, 1.0);
+        assert!(scores[0].discontinuity < 1.0e-3);
+    }
+
+    #[test]
+    fn stereo_float() {
+        let mut data = Vec::new();
+        for n in 0..1000 {
+            let left = 0.5 * (n as f32 * 0.04).sin();
+            let right = 0.4 * (n as f32 * 0.04 + 0.3).sin();
+            data.extend_from_slice(&left.to_be_bytes());
+            data.extend_from_slice(&right.to_be_bytes());
+        }
+        let formats = [
+            SampleFormat::I16BE,
+            SampleFormat::I32BE,
+            SampleFormat::F32LE,
+            SampleFormat::F32BE,
+        ];
+        let scores = guess_layout(&data, &all_candidates(&formats));
+        assert_eq!(
+            scores[0].candidate,
+            Candidate {
+                channels: 2,
+                format: SampleFormat::F32BE
+            }
+        );
+        assert!(scores[0].correlation > 0.9);
+        let little_endian = scores
+            .iter()
+            .find(|score| score.candidate.format == SampleFormat::F32LE)
+            .unwrap();
+        assert!(little_endian.valid < 0.9);
+    }
+
+    #[test]
+    fn too_short() {
+        let data = [0_u8; 6];
+        let candidates = [
+            Candidate {
+                channels: 4,
+                format: SampleFormat::I16LE,
+            },
+            Candidate {
+                channels: 0,
+                format: SampleFormat::I16LE,
+            },
+            Candidate {
+                channels: 1,
+                format: SampleFormat::I16LE,
+            },
+        ];
+        let scores = guess_layout(&data, &candidates);
+        // Silence is evaluated, but gets the score of white noise.
+        assert_eq!(scores[0].candidate.channels, 1);
+        assert_eq!(scores[0].score, 0.0);
+        assert_eq!(scores[1].score, f64::NEG_INFINITY);
+        assert_eq!(scores[2].frames, 0);
+    }
+}
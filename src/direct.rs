@@ -12,6 +12,42 @@
 //!
 //! Each wrapper exist in an _interleaved_ and _sequential_ version.
 //!
+//! There is also `SequentialSliceWithOffsets`, for a slice
+//! where each channel starts at an arbitrary offset,
+//! `GroupedInterleavedSlice`, for a slice where groups of channels
+//! are stored one after another, each group in interleaved order,
+//! and `SequentialVecOfSlices`, for a vector of channel slices.
+//! The latter can also be created from a table of channel pointers,
+//! as used for non-interleaved buffers by C audio APIs such as PortAudio.
+//!
+//! ## Splitting buffers
+//! The mutable wrappers can be split into two buffers covering disjoint parts of the data,
+//! that can be processed independently, for example in separate threads.
+//! The plain slice and slice of vectors wrappers can be split by frames using `split_frames_at_mut`,
+//! and the sequential wrappers can also be split by channels using `split_channels_at_mut`.
+//!
+//! ## References to samples
+//! The wrappers can also be indexed with a `(channel, frame)` tuple,
+//! as `buffer[(channel, frame)]`, which gives a reference to the sample.
+//! Indexing panics if the channel or frame is out of bounds.
+//! The `sample_ref` and `sample_mut` methods instead return `None` for samples out of bounds.
+//! These are useful for modifying samples of types that are not cheap to clone,
+//! and for getting the address of a sample, for example to pass to a C library.
+//! This is not available for `SparseSequentialSliceOfVecs`,
+//! since unused channels have no samples to reference,
+//! nor for the converting wrappers, where the samples are converted when accessed.
+//!
+//! ## Tuples and arrays of slices
+//! The [ChannelSlicesAdapter](crate::direct::ChannelSlicesAdapter) wrapper implements the traits for tuples of two channel slices,
+//! `(&[T], &[T])` and `(&mut [T], &mut [T])`,
+//! as well as for arrays of channel slices, `[&[T]; N]` and `[&mut [T]; N]`.
+//! The number of frames is given by the shortest slice.
+//! Since the number of channels is fixed, the wrapper also implements
+//! the [KnownChannels](crate::fixed::KnownChannels) marker trait.
+//! The traits are not implemented for the tuples and arrays themselves,
+//! since that would conflict with the implementations for `audio` crate buffers.
+//! Wrap them with `ChannelSlicesAdapter::new` instead.
+//!
 //! ### Example
 //! Wrap a Vec of i32 as an interleaved buffer
 //! and print all the values.
@@ -39,15 +75,27 @@
 //! ```
 //!
 
+use crate::fixed::KnownChannels;
+use crate::sample::{NativeBytes, SaturatingSum};
 use crate::SizeError;
 
 use crate::slicetools::copy_within_slice;
-use crate::{check_slice_length, implement_size_getters};
-use crate::{Adapter, AdapterMut};
+use crate::{
+    check_slice_length, implement_active_frames, implement_sample_access, implement_size_getters,
+    implement_with_dims,
+};
+use crate::{Adapter, AdapterMut, IterationOrder};
+
+/// The two buffers with disjoint sets of channels returned by `split_channels_at_mut`.
+pub(crate) type SplitChannels<U> = (U, U);
+
+/// The two buffers with disjoint ranges of frames returned by `split_frames_at_mut`.
+pub(crate) type SplitFrames<U> = (U, U);
 
 #[cfg(feature = "std")]
 macro_rules! check_slice_and_vec_length {
     ($buf:expr, $channels:expr, $frames:expr, sequential) => {
+        $crate::check_dimensions!($channels, $frames);
         if $buf.len() < $channels {
             return Err(SizeError::Frame {
                 index: 0,
@@ -66,6 +114,7 @@ macro_rules! check_slice_and_vec_length {
         }
     };
     ($buf:expr, $channels:expr, $frames:expr, $mask:expr, sequential) => {
+        $crate::check_dimensions!($channels, $frames);
         if $mask.len() != $channels {
             return Err(SizeError::Mask {
                 actual: $mask.len(),
@@ -90,6 +139,7 @@ macro_rules! check_slice_and_vec_length {
         }
     };
     ($buf:expr, $channels:expr, $frames:expr, interleaved) => {
+        $crate::check_dimensions!($channels, $frames);
         if $buf.len() < $frames {
             return Err(SizeError::Channel {
                 index: 0,
@@ -108,6 +158,43 @@ macro_rules! check_slice_and_vec_length {
         }
     };
 }
+
+#[cfg(feature = "std")]
+macro_rules! check_for_swapped_dimensions {
+    ($buf:expr, $channels:expr, $frames:expr, sequential) => {
+        if $channels != $frames
+            && $buf.len() == $frames
+            && $buf.iter().all(|chan| chan.len() == $channels)
+        {
+            return Err(SizeError::SwappedDimensions {
+                channels: $channels,
+                frames: $frames,
+            });
+        }
+    };
+    ($buf:expr, $channels:expr, $frames:expr, interleaved) => {
+        if $channels != $frames
+            && $buf.len() == $channels
+            && $buf.iter().all(|frame| frame.len() == $frames)
+        {
+            return Err(SizeError::SwappedDimensions {
+                channels: $channels,
+                frames: $frames,
+            });
+        }
+    };
+}
+
+/// The length of the shortest of the given channel vectors,
+/// or `usize::MAX` if there are no channels.
+#[cfg(feature = "std")]
+fn shortest_channel<T>(buf: &[Vec<T>], channels: impl Iterator<Item = usize>) -> usize {
+    channels
+        .map(|idx| buf[idx].len())
+        .min()
+        .unwrap_or(usize::MAX)
+}
+
 //
 // =========================== SequentialSliceOfVecs ===========================
 //
@@ -137,6 +224,37 @@ impl<'a, T> SequentialSliceOfVecs<&'a [Vec<T>]> {
             channels,
         })
     }
+
+    implement_with_dims!(with_dims = new(buf: &'a [Vec<T>]) -> Result<Self, SizeError>);
+
+    /// Create a new `SequentialSliceOfVecs` to wrap a slice of vectors,
+    /// while also checking for swapped channels and frames.
+    /// This works like [SequentialSliceOfVecs::new],
+    /// but returns [SizeError::SwappedDimensions] if the slice contains
+    /// exactly `frames` vectors that are all exactly `channels` long,
+    /// which suggests that the data is stored with the other layout.
+    pub fn new_checked_layout(
+        buf: &'a [Vec<T>],
+        channels: usize,
+        frames: usize,
+    ) -> Result<Self, SizeError> {
+        check_for_swapped_dimensions!(buf, channels, frames, sequential);
+        Self::new(buf, channels, frames)
+    }
+
+    implement_active_frames!(|s| shortest_channel(s.buf, 0..s.channels));
+
+    /// Replace the wrapped slice of vectors with a new one,
+    /// keeping the number of channels and frames.
+    /// This allows a long-lived wrapper to be pointed at a new buffer,
+    /// for example in each call of an audio callback.
+    /// The new slice of vectors must fulfill the same length requirements as when creating the wrapper.
+    /// If it does not, the wrapper is left unchanged and an error is returned.
+    pub fn replace_buffer(&mut self, buf: &'a [Vec<T>]) -> Result<(), SizeError> {
+        check_slice_and_vec_length!(buf, self.channels, self.frames, sequential);
+        self.buf = buf;
+        Ok(())
+    }
 }
 
 #[cfg(feature = "std")]
@@ -159,8 +277,388 @@ impl<'a, T> SequentialSliceOfVecs<&'a mut [Vec<T>]> {
             channels,
         })
     }
+
+    implement_with_dims!(with_dims_mut = new_mut(buf: &'a mut [Vec<T>]) -> Result<Self, SizeError>);
+
+    /// Create a new `SequentialSliceOfVecs` to wrap a mutable slice of vectors,
+    /// while also checking for swapped channels and frames.
+    /// This works like [SequentialSliceOfVecs::new_mut],
+    /// but returns [SizeError::SwappedDimensions] if the slice contains
+    /// exactly `frames` vectors that are all exactly `channels` long,
+    /// which suggests that the data is stored with the other layout.
+    pub fn new_mut_checked_layout(
+        buf: &'a mut [Vec<T>],
+        channels: usize,
+        frames: usize,
+    ) -> Result<Self, SizeError> {
+        check_for_swapped_dimensions!(buf, channels, frames, sequential);
+        Self::new_mut(buf, channels, frames)
+    }
+
+    implement_active_frames!(|s| shortest_channel(s.buf, 0..s.channels));
+
+    /// Replace the wrapped slice of vectors with a new one,
+    /// keeping the number of channels and frames.
+    /// This allows a long-lived wrapper to be pointed at a new buffer,
+    /// for example in each call of an audio callback.
+    /// The new slice of vectors must fulfill the same length requirements as when creating the wrapper.
+    /// If it does not, the wrapper is left unchanged and an error is returned.
+    pub fn replace_buffer(&mut self, buf: &'a mut [Vec<T>]) -> Result<(), SizeError> {
+        check_slice_and_vec_length!(buf, self.channels, self.frames, sequential);
+        self.buf = buf;
+        Ok(())
+    }
+
+    /// Split the buffer into two buffers with disjoint sets of channels,
+    /// where the first contains the channels before `channel`,
+    /// and the second contains `channel` and the following channels.
+    /// Since the channels do not overlap in memory,
+    /// the two buffers can be processed in parallel, for example in separate threads.
+    /// Returns `None` if `channel` is larger than the number of channels.
+    pub fn split_channels_at_mut(
+        &mut self,
+        channel: usize,
+    ) -> Option<SplitChannels<SequentialSliceOfVecs<&mut [Vec<T>]>>> {
+        if channel > self.channels {
+            return None;
+        }
+        let (first, second) = self.buf.split_at_mut(channel);
+        Some((
+            SequentialSliceOfVecs {
+                buf: first,
+                frames: self.frames,
+                channels: channel,
+            },
+            SequentialSliceOfVecs {
+                buf: second,
+                frames: self.frames,
+                channels: self.channels - channel,
+            },
+        ))
+    }
+
+    /// Split the buffer into two buffers with disjoint ranges of frames,
+    /// where the first contains the frames before `frame`,
+    /// and the second contains `frame` and the following frames.
+    /// Each channel vector is split in two, and the halves are wrapped in
+    /// [SequentialVecOfSlices] buffers.
+    /// Returns `None` if `frame` is larger than the number of frames.
+    pub fn split_frames_at_mut(
+        &mut self,
+        frame: usize,
+    ) -> Option<SplitFrames<SequentialVecOfSlices<Vec<&mut [T]>>>> {
+        if frame > self.frames {
+            return None;
+        }
+        Some(split_channel_slices(
+            self.buf
+                .iter_mut()
+                .take(self.channels)
+                .map(|chan| &mut chan[..self.frames]),
+            frame,
+            self.frames,
+        ))
+    }
+}
+
+//
+// =========================== SequentialVecOfSlices ===========================
+//
+
+/// Wrapper for a vector of length `channels`, containing slices of length `frames`.
+/// Each slice contains the samples for all frames of one channel.
+/// This is returned when splitting sequential buffers with `split_frames_at_mut`,
+/// since the two halves of a sequential buffer are not contiguous in memory.
+/// It can also be created from the table of channel pointers
+/// that C audio APIs use for non-interleaved buffers.
+#[cfg(feature = "std")]
+pub struct SequentialVecOfSlices<U> {
+    buf: U,
+    frames: usize,
+    channels: usize,
+}
+
+/// Read the channel pointers from a table of `channels` pointers,
+/// and check that they are all non-null and aligned.
+///
+/// # Safety
+///
+/// The table must hold `channels` pointers.
+#[cfg(feature = "std")]
+unsafe fn channel_pointers<T>(
+    ptrs: *const *const T,
+    channels: usize,
+) -> impl Iterator<Item = *const T> {
+    if channels > 0 {
+        assert!(!ptrs.is_null(), "The table of channel pointers is null");
+        assert!(
+            ptrs as usize % core::mem::align_of::<*const T>() == 0,
+            "The table of channel pointers is not aligned"
+        );
+    }
+    (0..channels).map(move |channel| {
+        let ptr = *ptrs.add(channel);
+        let address = ptr as usize;
+        assert!(address != 0, "The pointer for channel {} is null", channel);
+        assert!(
+            address % core::mem::align_of::<T>() == 0,
+            "The pointer for channel {} is not aligned",
+            channel
+        );
+        ptr
+    })
+}
+
+/// Split each channel slice at `frame`,
+/// and collect the halves into two [SequentialVecOfSlices] buffers.
+#[cfg(feature = "std")]
+pub(crate) fn split_channel_slices<'a, T>(
+    channels: impl Iterator<Item = &'a mut [T]>,
+    frame: usize,
+    frames: usize,
+) -> SplitFrames<SequentialVecOfSlices<Vec<&'a mut [T]>>> {
+    let (first, second): (Vec<_>, Vec<_>) = channels.map(|chan| chan.split_at_mut(frame)).unzip();
+    let channels = first.len();
+    (
+        SequentialVecOfSlices {
+            buf: first,
+            frames: frame,
+            channels,
+        },
+        SequentialVecOfSlices {
+            buf: second,
+            frames: frames - frame,
+            channels,
+        },
+    )
+}
+
+#[cfg(feature = "std")]
+impl<'a, T> SequentialVecOfSlices<Vec<&'a [T]>> {
+    /// Create a new `SequentialVecOfSlices` to wrap a vector of immutable slices.
+    /// Each slice must be at least `frames` long.
+    /// The number of channels is given by the length of the vector.
+    pub fn new(buf: Vec<&'a [T]>, frames: usize) -> Result<Self, SizeError> {
+        crate::check_dimensions!(buf.len(), frames);
+        for (index, chan) in buf.iter().enumerate() {
+            if chan.len() < frames {
+                return Err(SizeError::Channel {
+                    index,
+                    actual: chan.len(),
+                    required: frames,
+                });
+            }
+        }
+        let channels = buf.len();
+        Ok(Self {
+            buf,
+            frames,
+            channels,
+        })
+    }
+
+    /// Create a new `SequentialVecOfSlices` from a table of channel pointers,
+    /// such as the `const float**` buffers given to the callbacks of
+    /// non-interleaved streams by PortAudio and other C audio APIs.
+    /// The table holds one pointer for each channel,
+    /// to the first of `frames` samples of that channel.
+    ///
+    /// Panics if the table or any of the channel pointers is null or not properly aligned.
+    ///
+    /// # Safety
+    ///
+    /// The table must hold `channels` pointers,
+    /// and each channel pointer must point to `frames` initialized samples.
+    /// The samples must stay valid and must not be modified for the lifetime `'a`.
+    pub unsafe fn from_raw_parts(
+        ptrs: *const *const T,
+        channels: usize,
+        frames: usize,
+    ) -> Result<Self, SizeError> {
+        let buf = channel_pointers(ptrs, channels)
+            .map(|ptr| core::slice::from_raw_parts(ptr, frames))
+            .collect();
+        Self::new(buf, frames)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, T> SequentialVecOfSlices<Vec<&'a mut [T]>> {
+    /// Create a new `SequentialVecOfSlices` to wrap a vector of mutable slices.
+    /// Each slice must be at least `frames` long.
+    /// The number of channels is given by the length of the vector.
+    pub fn new_mut(buf: Vec<&'a mut [T]>, frames: usize) -> Result<Self, SizeError> {
+        crate::check_dimensions!(buf.len(), frames);
+        for (index, chan) in buf.iter().enumerate() {
+            if chan.len() < frames {
+                return Err(SizeError::Channel {
+                    index,
+                    actual: chan.len(),
+                    required: frames,
+                });
+            }
+        }
+        let channels = buf.len();
+        Ok(Self {
+            buf,
+            frames,
+            channels,
+        })
+    }
+
+    /// Create a new `SequentialVecOfSlices` from a table of mutable channel pointers,
+    /// such as the `float**` output buffers given to the callbacks of
+    /// non-interleaved streams by PortAudio and other C audio APIs.
+    /// This works like [SequentialVecOfSlices::from_raw_parts].
+    ///
+    /// Panics if the table or any of the channel pointers is null or not properly aligned,
+    /// or if the channels overlap.
+    /// Returns [SizeError::Overflow] if the size of a channel in bytes does not fit in a `usize`.
+    ///
+    /// # Safety
+    ///
+    /// The table must hold `channels` pointers,
+    /// and each channel pointer must point to `frames` initialized samples.
+    /// The samples must stay valid and must not be accessed by other means for the lifetime `'a`.
+    pub unsafe fn from_raw_parts_mut(
+        ptrs: *const *mut T,
+        channels: usize,
+        frames: usize,
+    ) -> Result<Self, SizeError> {
+        let starts: Vec<*mut T> = channel_pointers(ptrs as *const *const T, channels)
+            .map(|ptr| ptr as *mut T)
+            .collect();
+        if core::mem::size_of::<T>() > 0 && frames > 0 {
+            let mut ranges: Vec<usize> = starts.iter().map(|ptr| *ptr as usize).collect();
+            ranges.sort_unstable();
+            let length = match frames.checked_mul(core::mem::size_of::<T>()) {
+                Some(length) => length,
+                None => return Err(SizeError::Overflow { channels, frames }),
+            };
+            assert!(
+                ranges.windows(2).all(|pair| pair[1] - pair[0] >= length),
+                "The channels overlap"
+            );
+        }
+        let buf = starts
+            .into_iter()
+            .map(|ptr| core::slice::from_raw_parts_mut(ptr, frames))
+            .collect();
+        Self::new_mut(buf, frames)
+    }
+
+    /// Split the buffer into two buffers with disjoint ranges of frames,
+    /// where the first contains the frames before `frame`,
+    /// and the second contains `frame` and the following frames.
+    /// Returns `None` if `frame` is larger than the number of frames.
+    pub fn split_frames_at_mut(
+        &mut self,
+        frame: usize,
+    ) -> Option<SplitFrames<SequentialVecOfSlices<Vec<&mut [T]>>>> {
+        if frame > self.frames {
+            return None;
+        }
+        Some(split_channel_slices(
+            self.buf.iter_mut().map(|chan| &mut chan[..self.frames]),
+            frame,
+            self.frames,
+        ))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, T> Adapter<'a, T> for SequentialVecOfSlices<Vec<&'a mut [T]>>
+where
+    T: Clone,
+{
+    unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
+        self.buf.get_unchecked(channel).get_unchecked(frame).clone()
+    }
+
+    implement_size_getters!();
+
+    fn preferred_iteration(&self) -> IterationOrder {
+        IterationOrder::ChannelsOuter
+    }
+
+    fn write_from_channel_to_slice(&self, channel: usize, skip: usize, slice: &mut [T]) -> usize {
+        if channel >= self.channels || skip >= self.frames {
+            return 0;
+        }
+        let frames_to_write = (self.frames - skip).min(slice.len());
+        slice[..frames_to_write].clone_from_slice(&self.buf[channel][skip..skip + frames_to_write]);
+        frames_to_write
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, T> AdapterMut<'a, T> for SequentialVecOfSlices<Vec<&'a mut [T]>>
+where
+    T: Clone,
+{
+    unsafe fn write_sample_unchecked(&mut self, channel: usize, frame: usize, value: &T) -> bool {
+        *self.buf.get_unchecked_mut(channel).get_unchecked_mut(frame) = value.clone();
+        false
+    }
+
+    fn write_from_slice_to_channel(
+        &mut self,
+        channel: usize,
+        skip: usize,
+        slice: &[T],
+    ) -> (usize, usize) {
+        if channel >= self.channels || skip >= self.frames {
+            return (0, 0);
+        }
+        let frames_to_read = (self.frames - skip).min(slice.len());
+        self.buf[channel][skip..skip + frames_to_read].clone_from_slice(&slice[..frames_to_read]);
+        (frames_to_read, 0)
+    }
+
+    fn copy_frames_within(&mut self, src: usize, dest: usize, count: usize) -> Option<usize> {
+        if src + count > self.frames || dest + count > self.frames {
+            return None;
+        }
+        for ch in self.buf.iter_mut() {
+            unsafe {
+                copy_within_slice(ch, src, dest, count);
+            }
+        }
+        Some(count)
+    }
+}
+
+#[cfg(feature = "std")]
+implement_sample_access!(['a, T] SequentialVecOfSlices<Vec<&'a mut [T]>>, |s, channel, frame| s.buf[channel][frame], mutable);
+
+#[cfg(feature = "std")]
+impl<'a, T> Adapter<'a, T> for SequentialVecOfSlices<Vec<&'a [T]>>
+where
+    T: Clone,
+{
+    unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
+        self.buf.get_unchecked(channel).get_unchecked(frame).clone()
+    }
+
+    implement_size_getters!();
+
+    fn preferred_iteration(&self) -> IterationOrder {
+        IterationOrder::ChannelsOuter
+    }
+
+    fn write_from_channel_to_slice(&self, channel: usize, skip: usize, slice: &mut [T]) -> usize {
+        if channel >= self.channels || skip >= self.frames {
+            return 0;
+        }
+        let frames_to_write = (self.frames - skip).min(slice.len());
+        slice[..frames_to_write].clone_from_slice(&self.buf[channel][skip..skip + frames_to_write]);
+        frames_to_write
+    }
 }
 
+#[cfg(feature = "std")]
+implement_sample_access!(['a, T] SequentialVecOfSlices<Vec<&'a [T]>>, |s, channel, frame| s.buf[channel][frame]);
+
 #[cfg(feature = "std")]
 impl<'a, T> Adapter<'a, T> for SequentialSliceOfVecs<&'a [Vec<T>]>
 where
@@ -172,6 +670,10 @@ where
 
     implement_size_getters!();
 
+    fn preferred_iteration(&self) -> IterationOrder {
+        IterationOrder::ChannelsOuter
+    }
+
     fn write_from_channel_to_slice(&self, channel: usize, skip: usize, slice: &mut [T]) -> usize {
         if channel >= self.channels || skip >= self.frames {
             return 0;
@@ -197,6 +699,10 @@ where
 
     implement_size_getters!();
 
+    fn preferred_iteration(&self) -> IterationOrder {
+        IterationOrder::ChannelsOuter
+    }
+
     fn write_from_channel_to_slice(&self, channel: usize, skip: usize, slice: &mut [T]) -> usize {
         if channel >= self.channels || skip >= self.frames {
             return 0;
@@ -252,6 +758,11 @@ where
     }
 }
 
+#[cfg(feature = "std")]
+implement_sample_access!(['a, T] SequentialSliceOfVecs<&'a [Vec<T>]>, |s, channel, frame| s.buf[channel][frame]);
+#[cfg(feature = "std")]
+implement_sample_access!(['a, T] SequentialSliceOfVecs<&'a mut [Vec<T>]>, |s, channel, frame| s.buf[channel][frame], mutable);
+
 //
 // =========================== SparseSequentialSliceOfVecs ===========================
 //
@@ -295,6 +806,39 @@ impl<'a, T> SparseSequentialSliceOfVecs<&'a [Vec<T>]> {
             mask,
         })
     }
+
+    /// Create a new `SparseSequentialSliceOfVecs` where the channel numbers
+    /// are the speaker positions of a `WAVEFORMATEXTENSIBLE` channel mask.
+    /// The buffer has one channel for each speaker position
+    /// up to the highest position in the mask,
+    /// and the positions that are not in the mask are inactive.
+    /// The slice must contain a vector for each of these channels,
+    /// as given by [spread_to_positions](crate::channelmask::spread_to_positions).
+    pub fn new_from_channel_mask(
+        buf: &'a [Vec<T>],
+        frames: usize,
+        channel_mask: u32,
+    ) -> Result<Self, SizeError> {
+        let mask = crate::channelmask::active_channels(channel_mask);
+        Self::new(buf, mask.len(), frames, &mask)
+    }
+
+    implement_active_frames!(|s| shortest_channel(
+        s.buf,
+        (0..s.channels).filter(|idx| s.mask[*idx])
+    ));
+
+    /// Replace the wrapped slice of vectors with a new one,
+    /// keeping the number of channels and frames.
+    /// This allows a long-lived wrapper to be pointed at a new buffer,
+    /// for example in each call of an audio callback.
+    /// The new slice of vectors must fulfill the same length requirements as when creating the wrapper.
+    /// If it does not, the wrapper is left unchanged and an error is returned.
+    pub fn replace_buffer(&mut self, buf: &'a [Vec<T>]) -> Result<(), SizeError> {
+        check_slice_and_vec_length!(buf, self.channels, self.frames, &self.mask, sequential);
+        self.buf = buf;
+        Ok(())
+    }
 }
 
 #[cfg(feature = "std")]
@@ -320,6 +864,46 @@ impl<'a, T> SparseSequentialSliceOfVecs<&'a mut [Vec<T>]> {
             mask,
         })
     }
+
+    /// Create a new `SparseSequentialSliceOfVecs` for a mutable slice of vectors,
+    /// where the channel numbers are the speaker positions of a `WAVEFORMATEXTENSIBLE` channel mask.
+    /// This works like [SparseSequentialSliceOfVecs::new_from_channel_mask].
+    pub fn new_from_channel_mask_mut(
+        buf: &'a mut [Vec<T>],
+        frames: usize,
+        channel_mask: u32,
+    ) -> Result<Self, SizeError> {
+        let mask = crate::channelmask::active_channels(channel_mask);
+        Self::new_mut(buf, mask.len(), frames, &mask)
+    }
+
+    implement_active_frames!(|s| shortest_channel(
+        s.buf,
+        (0..s.channels).filter(|idx| s.mask[*idx])
+    ));
+
+    /// Replace the wrapped slice of vectors with a new one,
+    /// keeping the number of channels and frames.
+    /// This allows a long-lived wrapper to be pointed at a new buffer,
+    /// for example in each call of an audio callback.
+    /// The new slice of vectors must fulfill the same length requirements as when creating the wrapper.
+    /// If it does not, the wrapper is left unchanged and an error is returned.
+    pub fn replace_buffer(&mut self, buf: &'a mut [Vec<T>]) -> Result<(), SizeError> {
+        check_slice_and_vec_length!(buf, self.channels, self.frames, &self.mask, sequential);
+        self.buf = buf;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<U> SparseSequentialSliceOfVecs<U> {
+    /// Get the `WAVEFORMATEXTENSIBLE` channel mask of the active channels,
+    /// where the channel numbers are the speaker positions.
+    /// Returns `None` if a channel above position 31 is active.
+    /// See the [channelmask](crate::channelmask) module.
+    pub fn channel_mask(&self) -> Option<u32> {
+        crate::channelmask::channel_mask(&self.mask)
+    }
 }
 
 #[cfg(feature = "std")]
@@ -336,6 +920,14 @@ where
 
     implement_size_getters!();
 
+    fn preferred_iteration(&self) -> IterationOrder {
+        IterationOrder::ChannelsOuter
+    }
+
+    fn is_channel_active(&self, channel: usize) -> bool {
+        channel < self.channels && self.mask[channel]
+    }
+
     fn write_from_channel_to_slice(&self, channel: usize, skip: usize, slice: &mut [T]) -> usize {
         if channel >= self.channels || !self.mask[channel] || skip >= self.frames {
             return 0;
@@ -348,6 +940,22 @@ where
         slice[..frames_to_write].clone_from_slice(&self.buf[channel][skip..skip + frames_to_write]);
         frames_to_write
     }
+
+    fn write_from_frame_to_slice(&self, frame: usize, skip: usize, slice: &mut [T]) -> usize {
+        if frame >= self.frames || skip >= self.channels {
+            return 0;
+        }
+        let channels_to_write = (self.channels - skip).min(slice.len());
+        for (n, item) in slice.iter_mut().enumerate().take(channels_to_write) {
+            let channel = skip + n;
+            *item = if self.mask[channel] {
+                self.buf[channel][frame].clone()
+            } else {
+                T::default()
+            };
+        }
+        channels_to_write
+    }
 }
 
 #[cfg(feature = "std")]
@@ -364,6 +972,14 @@ where
 
     implement_size_getters!();
 
+    fn preferred_iteration(&self) -> IterationOrder {
+        IterationOrder::ChannelsOuter
+    }
+
+    fn is_channel_active(&self, channel: usize) -> bool {
+        channel < self.channels && self.mask[channel]
+    }
+
     fn write_from_channel_to_slice(&self, channel: usize, skip: usize, slice: &mut [T]) -> usize {
         if channel >= self.channels || !self.mask[channel] || skip >= self.frames {
             return 0;
@@ -376,6 +992,22 @@ where
         slice[..frames_to_write].clone_from_slice(&self.buf[channel][skip..skip + frames_to_write]);
         frames_to_write
     }
+
+    fn write_from_frame_to_slice(&self, frame: usize, skip: usize, slice: &mut [T]) -> usize {
+        if frame >= self.frames || skip >= self.channels {
+            return 0;
+        }
+        let channels_to_write = (self.channels - skip).min(slice.len());
+        for (n, item) in slice.iter_mut().enumerate().take(channels_to_write) {
+            let channel = skip + n;
+            *item = if self.mask[channel] {
+                self.buf[channel][frame].clone()
+            } else {
+                T::default()
+            };
+        }
+        channels_to_write
+    }
 }
 
 #[cfg(feature = "std")]
@@ -408,24 +1040,153 @@ where
         (frames_to_read, 0)
     }
 
-    fn copy_frames_within(&mut self, src: usize, dest: usize, count: usize) -> Option<usize> {
-        if src + count > self.frames || dest + count > self.frames {
-            return None;
+    fn write_from_slice_to_frame(
+        &mut self,
+        frame: usize,
+        skip: usize,
+        slice: &[T],
+    ) -> (usize, usize) {
+        if frame >= self.frames || skip >= self.channels {
+            return (0, 0);
         }
-        for (ch, active) in self.buf.iter_mut().zip(self.mask.iter()) {
-            if *active {
-                unsafe {
-                    copy_within_slice(ch, src, dest, count);
-                }
+        let channels_to_read = (self.channels - skip).min(slice.len());
+        for (n, item) in slice.iter().enumerate().take(channels_to_read) {
+            let channel = skip + n;
+            if self.mask[channel] {
+                self.buf[channel][frame] = item.clone();
             }
         }
-        Some(count)
+        (channels_to_read, 0)
     }
-}
+
+    fn write_from_other_to_channel(
+        &mut self,
+        other: &dyn Adapter<'a, T>,
+        other_channel: usize,
+        self_channel: usize,
+        other_skip: usize,
+        self_skip: usize,
+        take: usize,
+    ) -> Option<usize> {
+        if self_channel >= self.channels
+            || take + self_skip > self.frames
+            || other_channel >= other.channels()
+            || take + other_skip > other.frames()
+        {
+            return None;
+        }
+        if self.mask[self_channel] {
+            other.write_from_channel_to_slice(
+                other_channel,
+                other_skip,
+                &mut self.buf[self_channel][self_skip..self_skip + take],
+            );
+        }
+        Some(0)
+    }
+
+    fn fill_channel_with(&mut self, channel: usize, value: &T) -> Option<()> {
+        if channel >= self.channels {
+            return None;
+        }
+        if self.mask[channel] {
+            self.buf[channel][..self.frames].fill(value.clone());
+        }
+        Some(())
+    }
+
+    fn fill_frame_with(&mut self, frame: usize, value: &T) -> Option<()> {
+        if frame >= self.frames {
+            return None;
+        }
+        for (ch, active) in self
+            .buf
+            .iter_mut()
+            .zip(self.mask.iter())
+            .take(self.channels)
+        {
+            if *active {
+                ch[frame] = value.clone();
+            }
+        }
+        Some(())
+    }
+
+    fn fill_frames_with(&mut self, start: usize, count: usize, value: &T) -> Option<usize> {
+        if start + count > self.frames {
+            return None;
+        }
+        for (ch, active) in self
+            .buf
+            .iter_mut()
+            .zip(self.mask.iter())
+            .take(self.channels)
+        {
+            if *active {
+                ch[start..start + count].fill(value.clone());
+            }
+        }
+        Some(count)
+    }
+
+    fn fill_range_with(
+        &mut self,
+        channels: core::ops::Range<usize>,
+        frames: core::ops::Range<usize>,
+        value: &T,
+    ) -> Option<usize> {
+        if channels.start > channels.end
+            || frames.start > frames.end
+            || channels.end > self.channels
+            || frames.end > self.frames
+        {
+            return None;
+        }
+        for (ch, active) in self
+            .buf
+            .iter_mut()
+            .zip(self.mask.iter())
+            .take(channels.end)
+            .skip(channels.start)
+        {
+            if *active {
+                ch[frames.clone()].fill(value.clone());
+            }
+        }
+        Some(frames.len())
+    }
+
+    fn fill_with(&mut self, value: &T) {
+        for (ch, active) in self
+            .buf
+            .iter_mut()
+            .zip(self.mask.iter())
+            .take(self.channels)
+        {
+            if *active {
+                ch[..self.frames].fill(value.clone());
+            }
+        }
+    }
+
+    fn copy_frames_within(&mut self, src: usize, dest: usize, count: usize) -> Option<usize> {
+        if src + count > self.frames || dest + count > self.frames {
+            return None;
+        }
+        for (ch, active) in self.buf.iter_mut().zip(self.mask.iter()) {
+            if *active {
+                unsafe {
+                    copy_within_slice(ch, src, dest, count);
+                }
+            }
+        }
+        Some(count)
+    }
+}
 
 //
-// =========================== InterleavedSliceOfVecs ===========================
-//
+// =========================== InterleavedSliceOfVecs ===========================
+//
 
 /// Wrapper for a slice of length `frames`, containing vectors of length `channels`.
 /// Each vector contains the samples for all channels of one frame.
@@ -452,6 +1213,41 @@ impl<'a, T> InterleavedSliceOfVecs<&'a [Vec<T>]> {
             channels,
         })
     }
+
+    implement_with_dims!(with_dims = new(buf: &'a [Vec<T>]) -> Result<Self, SizeError>);
+
+    /// Create a new `InterleavedSliceOfVecs` to wrap a slice of vectors,
+    /// while also checking for swapped channels and frames.
+    /// This works like [InterleavedSliceOfVecs::new],
+    /// but returns [SizeError::SwappedDimensions] if the slice contains
+    /// exactly `channels` vectors that are all exactly `frames` long,
+    /// which suggests that the data is stored with the other layout.
+    pub fn new_checked_layout(
+        buf: &'a [Vec<T>],
+        channels: usize,
+        frames: usize,
+    ) -> Result<Self, SizeError> {
+        check_for_swapped_dimensions!(buf, channels, frames, interleaved);
+        Self::new(buf, channels, frames)
+    }
+
+    implement_active_frames!(|s| s
+        .buf
+        .iter()
+        .take_while(|frame| frame.len() >= s.channels)
+        .count());
+
+    /// Replace the wrapped slice of vectors with a new one,
+    /// keeping the number of channels and frames.
+    /// This allows a long-lived wrapper to be pointed at a new buffer,
+    /// for example in each call of an audio callback.
+    /// The new slice of vectors must fulfill the same length requirements as when creating the wrapper.
+    /// If it does not, the wrapper is left unchanged and an error is returned.
+    pub fn replace_buffer(&mut self, buf: &'a [Vec<T>]) -> Result<(), SizeError> {
+        check_slice_and_vec_length!(buf, self.channels, self.frames, interleaved);
+        self.buf = buf;
+        Ok(())
+    }
 }
 
 #[cfg(feature = "std")]
@@ -474,6 +1270,85 @@ impl<'a, T> InterleavedSliceOfVecs<&'a mut [Vec<T>]> {
             channels,
         })
     }
+
+    implement_with_dims!(with_dims_mut = new_mut(buf: &'a mut [Vec<T>]) -> Result<Self, SizeError>);
+
+    /// Create a new `InterleavedSliceOfVecs` to wrap a mutable slice of vectors,
+    /// while also checking for swapped channels and frames.
+    /// This works like [InterleavedSliceOfVecs::new_mut],
+    /// but returns [SizeError::SwappedDimensions] if the slice contains
+    /// exactly `channels` vectors that are all exactly `frames` long,
+    /// which suggests that the data is stored with the other layout.
+    pub fn new_mut_checked_layout(
+        buf: &'a mut [Vec<T>],
+        channels: usize,
+        frames: usize,
+    ) -> Result<Self, SizeError> {
+        check_for_swapped_dimensions!(buf, channels, frames, interleaved);
+        Self::new_mut(buf, channels, frames)
+    }
+
+    implement_active_frames!(|s| s
+        .buf
+        .iter()
+        .take_while(|frame| frame.len() >= s.channels)
+        .count());
+
+    /// Replace the wrapped slice of vectors with a new one,
+    /// keeping the number of channels and frames.
+    /// This allows a long-lived wrapper to be pointed at a new buffer,
+    /// for example in each call of an audio callback.
+    /// The new slice of vectors must fulfill the same length requirements as when creating the wrapper.
+    /// If it does not, the wrapper is left unchanged and an error is returned.
+    pub fn replace_buffer(&mut self, buf: &'a mut [Vec<T>]) -> Result<(), SizeError> {
+        check_slice_and_vec_length!(buf, self.channels, self.frames, interleaved);
+        self.buf = buf;
+        Ok(())
+    }
+
+    /// Rotate the order of the frames by `shift` frames.
+    /// A positive shift moves each frame towards the end of the buffer,
+    /// and the frames that pass the end wrap around to the start.
+    /// A negative shift moves the frames towards the start.
+    /// Since each frame is a separate vector, this only moves the vectors,
+    /// without copying any samples.
+    pub fn rotate_frames(&mut self, shift: isize) {
+        if self.frames == 0 {
+            return;
+        }
+        let steps = shift.unsigned_abs() % self.frames;
+        if shift > 0 {
+            self.buf[..self.frames].rotate_right(steps);
+        } else {
+            self.buf[..self.frames].rotate_left(steps);
+        }
+    }
+
+    /// Split the buffer into two buffers with disjoint ranges of frames,
+    /// where the first contains the frames before `frame`,
+    /// and the second contains `frame` and the following frames.
+    /// Returns `None` if `frame` is larger than the number of frames.
+    pub fn split_frames_at_mut(
+        &mut self,
+        frame: usize,
+    ) -> Option<SplitFrames<InterleavedSliceOfVecs<&mut [Vec<T>]>>> {
+        if frame > self.frames {
+            return None;
+        }
+        let (first, second) = self.buf[..self.frames].split_at_mut(frame);
+        Some((
+            InterleavedSliceOfVecs {
+                buf: first,
+                frames: frame,
+                channels: self.channels,
+            },
+            InterleavedSliceOfVecs {
+                buf: second,
+                frames: self.frames - frame,
+                channels: self.channels,
+            },
+        ))
+    }
 }
 
 #[cfg(feature = "std")]
@@ -487,6 +1362,10 @@ where
 
     implement_size_getters!();
 
+    fn preferred_iteration(&self) -> IterationOrder {
+        IterationOrder::FramesOuter
+    }
+
     fn write_from_frame_to_slice(&self, frame: usize, skip: usize, slice: &mut [T]) -> usize {
         if frame >= self.frames || skip >= self.channels {
             return 0;
@@ -513,6 +1392,10 @@ where
 
     implement_size_getters!();
 
+    fn preferred_iteration(&self) -> IterationOrder {
+        IterationOrder::FramesOuter
+    }
+
     fn write_from_frame_to_slice(&self, frame: usize, skip: usize, slice: &mut [T]) -> usize {
         if frame >= self.frames || skip >= self.channels {
             return 0;
@@ -555,8 +1438,39 @@ where
         self.buf[frame][skip..skip + channels_to_read].clone_from_slice(&slice[..channels_to_read]);
         (channels_to_read, 0)
     }
+
+    fn copy_frames_within(&mut self, src: usize, dest: usize, count: usize) -> Option<usize> {
+        if src + count > self.frames || dest + count > self.frames {
+            return None;
+        }
+        let channels = self.channels;
+        let copy_frame = |buf: &mut [Vec<T>], from: usize, to: usize| {
+            if from < to {
+                let (first, second) = buf.split_at_mut(to);
+                second[0][..channels].clone_from_slice(&first[from][..channels]);
+            } else {
+                let (first, second) = buf.split_at_mut(from);
+                first[to][..channels].clone_from_slice(&second[0][..channels]);
+            }
+        };
+        if dest < src {
+            for n in 0..count {
+                copy_frame(self.buf, src + n, dest + n);
+            }
+        } else if dest > src {
+            for n in (0..count).rev() {
+                copy_frame(self.buf, src + n, dest + n);
+            }
+        }
+        Some(count)
+    }
 }
 
+#[cfg(feature = "std")]
+implement_sample_access!(['a, T] InterleavedSliceOfVecs<&'a [Vec<T>]>, |s, channel, frame| s.buf[frame][channel]);
+#[cfg(feature = "std")]
+implement_sample_access!(['a, T] InterleavedSliceOfVecs<&'a mut [Vec<T>]>, |s, channel, frame| s.buf[frame][channel], mutable);
+
 //
 // =========================== InterleavedSlice ===========================
 //
@@ -593,6 +1507,42 @@ impl<'a, T> InterleavedSlice<&'a [T]> {
             channels,
         })
     }
+
+    implement_with_dims!(with_dims = new(buf: &'a [T]) -> Result<Self, SizeError>);
+
+    /// Create a new `InterleavedSlice` to wrap a slice of numbers,
+    /// where each sample is accessed as an array of bytes in native byte order.
+    /// This allows for example a slice of [i16] to be used
+    /// where an `Adapter<[u8; 2]>` is expected,
+    /// such as by [ConvertBytes](crate::adapter_to_float::ConvertBytes),
+    /// without any unsafe casts in the calling code.
+    /// The slice length requirements are the same as for [InterleavedSlice::new].
+    pub fn new_from_numbers<U>(
+        buf: &'a [U],
+        channels: usize,
+        frames: usize,
+    ) -> Result<Self, SizeError>
+    where
+        U: NativeBytes<Bytes = T>,
+    {
+        // Safe since `NativeBytes` guarantees that `T` is a byte array of the same size as `U`.
+        let bytes = unsafe { core::slice::from_raw_parts(buf.as_ptr() as *const T, buf.len()) };
+        Self::new(bytes, channels, frames)
+    }
+
+    implement_active_frames!(|s| s.buf.len().checked_div(s.channels).unwrap_or(usize::MAX));
+
+    /// Replace the wrapped slice with a new one,
+    /// keeping the number of channels and frames.
+    /// This allows a long-lived wrapper to be pointed at a new buffer,
+    /// for example in each call of an audio callback.
+    /// The new slice must fulfill the same length requirements as when creating the wrapper.
+    /// If it does not, the wrapper is left unchanged and an error is returned.
+    pub fn replace_buffer(&mut self, buf: &'a [T]) -> Result<(), SizeError> {
+        check_slice_length!(self.channels, self.frames, buf.len());
+        self.buf = buf;
+        Ok(())
+    }
 }
 
 impl<'a, T> InterleavedSlice<&'a mut [T]> {
@@ -609,6 +1559,69 @@ impl<'a, T> InterleavedSlice<&'a mut [T]> {
             channels,
         })
     }
+
+    implement_with_dims!(with_dims_mut = new_mut(buf: &'a mut [T]) -> Result<Self, SizeError>);
+
+    /// Create a new `InterleavedSlice` to wrap a mutable slice of numbers,
+    /// where each sample is accessed as an array of bytes in native byte order.
+    /// This works like [InterleavedSlice::new_from_numbers], but for mutable slices.
+    pub fn new_from_numbers_mut<U>(
+        buf: &'a mut [U],
+        channels: usize,
+        frames: usize,
+    ) -> Result<Self, SizeError>
+    where
+        U: NativeBytes<Bytes = T>,
+    {
+        // Safe since `NativeBytes` guarantees that `T` is a byte array of the same size as `U`,
+        // and that any bytes written form a valid `U`.
+        let bytes =
+            unsafe { core::slice::from_raw_parts_mut(buf.as_mut_ptr() as *mut T, buf.len()) };
+        Self::new_mut(bytes, channels, frames)
+    }
+
+    implement_active_frames!(|s| s.buf.len().checked_div(s.channels).unwrap_or(usize::MAX));
+
+    /// Replace the wrapped slice with a new one,
+    /// keeping the number of channels and frames.
+    /// This allows a long-lived wrapper to be pointed at a new buffer,
+    /// for example in each call of an audio callback.
+    /// The new slice must fulfill the same length requirements as when creating the wrapper.
+    /// If it does not, the wrapper is left unchanged and an error is returned.
+    pub fn replace_buffer(&mut self, buf: &'a mut [T]) -> Result<(), SizeError> {
+        check_slice_length!(self.channels, self.frames, buf.len());
+        self.buf = buf;
+        Ok(())
+    }
+
+    /// Split the buffer into two buffers with disjoint ranges of frames,
+    /// where the first contains the frames before `frame`,
+    /// and the second contains `frame` and the following frames.
+    /// Since the samples of each frame are stored consecutively,
+    /// the buffer is simply split in two at the given frame.
+    /// Returns `None` if `frame` is larger than the number of frames.
+    pub fn split_frames_at_mut(
+        &mut self,
+        frame: usize,
+    ) -> Option<SplitFrames<InterleavedSlice<&mut [T]>>> {
+        if frame > self.frames {
+            return None;
+        }
+        let (first, second) =
+            self.buf[..self.frames * self.channels].split_at_mut(frame * self.channels);
+        Some((
+            InterleavedSlice {
+                buf: first,
+                frames: frame,
+                channels: self.channels,
+            },
+            InterleavedSlice {
+                buf: second,
+                frames: self.frames - frame,
+                channels: self.channels,
+            },
+        ))
+    }
 }
 
 impl<'a, T> Adapter<'a, T> for InterleavedSlice<&'a [T]>
@@ -622,6 +1635,10 @@ where
 
     implement_size_getters!();
 
+    fn preferred_iteration(&self) -> IterationOrder {
+        IterationOrder::FramesOuter
+    }
+
     fn write_from_frame_to_slice(&self, frame: usize, skip: usize, slice: &mut [T]) -> usize {
         if frame >= self.frames || skip >= self.channels {
             return 0;
@@ -636,6 +1653,14 @@ where
             .clone_from_slice(&self.buf[buffer_skip..buffer_skip + channels_to_write]);
         channels_to_write
     }
+
+    #[cfg(feature = "std")]
+    fn to_interleaved_vec(&self) -> Vec<T>
+    where
+        T: Clone + Default,
+    {
+        self.buf[..self.channels * self.frames].to_vec()
+    }
 }
 
 impl<'a, T> Adapter<'a, T> for InterleavedSlice<&'a mut [T]>
@@ -649,6 +1674,10 @@ where
 
     implement_size_getters!();
 
+    fn preferred_iteration(&self) -> IterationOrder {
+        IterationOrder::FramesOuter
+    }
+
     fn write_from_frame_to_slice(&self, frame: usize, skip: usize, slice: &mut [T]) -> usize {
         if frame >= self.frames || skip >= self.channels {
             return 0;
@@ -663,6 +1692,14 @@ where
             .clone_from_slice(&self.buf[buffer_skip..buffer_skip + channels_to_write]);
         channels_to_write
     }
+
+    #[cfg(feature = "std")]
+    fn to_interleaved_vec(&self) -> Vec<T>
+    where
+        T: Clone + Default,
+    {
+        self.buf[..self.channels * self.frames].to_vec()
+    }
 }
 
 impl<'a, T> AdapterMut<'a, T> for InterleavedSlice<&'a mut [T]>
@@ -709,8 +1746,18 @@ where
         }
         Some(count)
     }
+
+    #[cfg(feature = "std")]
+    fn copy_from_interleaved_slice(&mut self, slice: &[T]) -> usize {
+        let samples = (slice.len() / self.channels.max(1)).min(self.frames) * self.channels;
+        self.buf[..samples].clone_from_slice(&slice[..samples]);
+        0
+    }
 }
 
+implement_sample_access!(['a, T] InterleavedSlice<&'a [T]>);
+implement_sample_access!(['a, T] InterleavedSlice<&'a mut [T]>, mutable);
+
 //
 // =========================== SequentialSlice ===========================
 //
@@ -747,6 +1794,42 @@ impl<'a, T> SequentialSlice<&'a [T]> {
             channels,
         })
     }
+
+    implement_with_dims!(with_dims = new(buf: &'a [T]) -> Result<Self, SizeError>);
+
+    /// Create a new `SequentialSlice` to wrap a slice of numbers,
+    /// where each sample is accessed as an array of bytes in native byte order.
+    /// This allows for example a slice of [i16] to be used
+    /// where an `Adapter<[u8; 2]>` is expected,
+    /// such as by [ConvertBytes](crate::adapter_to_float::ConvertBytes),
+    /// without any unsafe casts in the calling code.
+    /// The slice length requirements are the same as for [SequentialSlice::new].
+    pub fn new_from_numbers<U>(
+        buf: &'a [U],
+        channels: usize,
+        frames: usize,
+    ) -> Result<Self, SizeError>
+    where
+        U: NativeBytes<Bytes = T>,
+    {
+        // Safe since `NativeBytes` guarantees that `T` is a byte array of the same size as `U`.
+        let bytes = unsafe { core::slice::from_raw_parts(buf.as_ptr() as *const T, buf.len()) };
+        Self::new(bytes, channels, frames)
+    }
+
+    implement_active_frames!(|s| s.buf.len().checked_div(s.channels).unwrap_or(usize::MAX));
+
+    /// Replace the wrapped slice with a new one,
+    /// keeping the number of channels and frames.
+    /// This allows a long-lived wrapper to be pointed at a new buffer,
+    /// for example in each call of an audio callback.
+    /// The new slice must fulfill the same length requirements as when creating the wrapper.
+    /// If it does not, the wrapper is left unchanged and an error is returned.
+    pub fn replace_buffer(&mut self, buf: &'a [T]) -> Result<(), SizeError> {
+        check_slice_length!(self.channels, self.frames, buf.len());
+        self.buf = buf;
+        Ok(())
+    }
 }
 
 impl<'a, T> SequentialSlice<&'a mut [T]> {
@@ -763,6 +1846,92 @@ impl<'a, T> SequentialSlice<&'a mut [T]> {
             channels,
         })
     }
+
+    implement_with_dims!(with_dims_mut = new_mut(buf: &'a mut [T]) -> Result<Self, SizeError>);
+
+    /// Create a new `SequentialSlice` to wrap a mutable slice of numbers,
+    /// where each sample is accessed as an array of bytes in native byte order.
+    /// This works like [SequentialSlice::new_from_numbers], but for mutable slices.
+    pub fn new_from_numbers_mut<U>(
+        buf: &'a mut [U],
+        channels: usize,
+        frames: usize,
+    ) -> Result<Self, SizeError>
+    where
+        U: NativeBytes<Bytes = T>,
+    {
+        // Safe since `NativeBytes` guarantees that `T` is a byte array of the same size as `U`,
+        // and that any bytes written form a valid `U`.
+        let bytes =
+            unsafe { core::slice::from_raw_parts_mut(buf.as_mut_ptr() as *mut T, buf.len()) };
+        Self::new_mut(bytes, channels, frames)
+    }
+
+    implement_active_frames!(|s| s.buf.len().checked_div(s.channels).unwrap_or(usize::MAX));
+
+    /// Replace the wrapped slice with a new one,
+    /// keeping the number of channels and frames.
+    /// This allows a long-lived wrapper to be pointed at a new buffer,
+    /// for example in each call of an audio callback.
+    /// The new slice must fulfill the same length requirements as when creating the wrapper.
+    /// If it does not, the wrapper is left unchanged and an error is returned.
+    pub fn replace_buffer(&mut self, buf: &'a mut [T]) -> Result<(), SizeError> {
+        check_slice_length!(self.channels, self.frames, buf.len());
+        self.buf = buf;
+        Ok(())
+    }
+
+    /// Split the buffer into two buffers with disjoint ranges of frames,
+    /// where the first contains the frames before `frame`,
+    /// and the second contains `frame` and the following frames.
+    /// Since the frames of each channel are stored consecutively, the two halves are not
+    /// contiguous in memory, and are returned as [SequentialVecOfSlices] buffers.
+    /// Returns `None` if `frame` is larger than the number of frames.
+    #[cfg(feature = "std")]
+    pub fn split_frames_at_mut(
+        &mut self,
+        frame: usize,
+    ) -> Option<SplitFrames<SequentialVecOfSlices<Vec<&mut [T]>>>> {
+        if frame > self.frames {
+            return None;
+        }
+        let frames = self.frames;
+        let mut rest = &mut self.buf[..self.channels * frames];
+        let channels = (0..self.channels).map(move |_| {
+            let (chan, tail) = core::mem::take(&mut rest).split_at_mut(frames);
+            rest = tail;
+            chan
+        });
+        Some(split_channel_slices(channels, frame, frames))
+    }
+
+    /// Split the buffer into two buffers with disjoint sets of channels,
+    /// where the first contains the channels before `channel`,
+    /// and the second contains `channel` and the following channels.
+    /// Since the channels do not overlap in memory,
+    /// the two buffers can be processed in parallel, for example in separate threads.
+    /// Returns `None` if `channel` is larger than the number of channels.
+    pub fn split_channels_at_mut(
+        &mut self,
+        channel: usize,
+    ) -> Option<SplitChannels<SequentialSlice<&mut [T]>>> {
+        if channel > self.channels {
+            return None;
+        }
+        let (first, second) = self.buf.split_at_mut(channel * self.frames);
+        Some((
+            SequentialSlice {
+                buf: first,
+                frames: self.frames,
+                channels: channel,
+            },
+            SequentialSlice {
+                buf: second,
+                frames: self.frames,
+                channels: self.channels - channel,
+            },
+        ))
+    }
 }
 
 impl<'a, T> Adapter<'a, T> for SequentialSlice<&'a [T]>
@@ -776,6 +1945,10 @@ where
 
     implement_size_getters!();
 
+    fn preferred_iteration(&self) -> IterationOrder {
+        IterationOrder::ChannelsOuter
+    }
+
     fn write_from_channel_to_slice(&self, channel: usize, skip: usize, slice: &mut [T]) -> usize {
         if channel >= self.channels || skip >= self.frames {
             return 0;
@@ -804,6 +1977,10 @@ where
 
     implement_size_getters!();
 
+    fn preferred_iteration(&self) -> IterationOrder {
+        IterationOrder::ChannelsOuter
+    }
+
     fn write_from_channel_to_slice(&self, channel: usize, skip: usize, slice: &mut [T]) -> usize {
         if channel >= self.channels || skip >= self.frames {
             return 0;
@@ -850,6 +2027,32 @@ where
         (frames_to_read, 0)
     }
 
+    fn add_from_slice_to_channel(
+        &mut self,
+        channel: usize,
+        skip: usize,
+        slice: &[T],
+    ) -> (usize, usize)
+    where
+        T: SaturatingSum,
+    {
+        if channel >= self.channels || skip >= self.frames {
+            return (0, 0);
+        }
+        let frames_to_read = (self.frames - skip).min(slice.len());
+        let buffer_skip = self.calc_index(channel, skip);
+        let mut nbr_saturated = 0;
+        for (sample, value) in self.buf[buffer_skip..buffer_skip + frames_to_read]
+            .iter_mut()
+            .zip(slice)
+        {
+            let (sum, saturated) = sample.saturating_sum(value);
+            *sample = sum;
+            nbr_saturated += saturated as usize;
+        }
+        (frames_to_read, nbr_saturated)
+    }
+
     fn copy_frames_within(&mut self, src: usize, dest: usize, count: usize) -> Option<usize> {
         if src + count > self.frames || dest + count > self.frames {
             return None;
@@ -864,81 +2067,766 @@ where
     }
 }
 
-//   _____         _
-//  |_   _|__  ___| |_ ___
-//    | |/ _ \/ __| __/ __|
-//    | |  __/\__ \ |_\__ \
-//    |_|\___||___/\__|___/
+implement_sample_access!(['a, T] SequentialSlice<&'a [T]>);
+implement_sample_access!(['a, T] SequentialSlice<&'a mut [T]>, mutable);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+//
+// =========================== SequentialSliceWithOffsets ===========================
+//
 
-    fn insert_data(buffer: &mut dyn AdapterMut<i32>) {
-        buffer.write_sample(0, 0, &1).unwrap();
-        buffer.write_sample(0, 1, &2).unwrap();
-        buffer.write_sample(0, 2, &3).unwrap();
-        buffer.write_sample(1, 0, &4).unwrap();
-        buffer.write_sample(1, 1, &5).unwrap();
-        buffer.write_sample(1, 2, &6).unwrap();
-    }
+/// Wrapper for a slice containing the samples of all channels,
+/// where each channel starts at an arbitrary offset.
+/// The samples for one channel are stored consecutively,
+/// starting at the offset given for that channel.
+/// This layout is used by some plugin APIs.
+/// For a stereo buffer containing four frames, with offsets `[2, 8]`, the order is
+/// `x, x, L1, L2, L3, L4, x, x, R1, R2, R3, R4`,
+/// where `x` are values that are not accessed.
+pub struct SequentialSliceWithOffsets<'o, U> {
+    buf: U,
+    offsets: &'o [usize],
+    frames: usize,
+    channels: usize,
+}
 
-    fn test_get(buffer: &mut dyn AdapterMut<i32>) {
-        insert_data(buffer);
-        assert_eq!(buffer.read_sample(0, 0).unwrap(), 1);
-        assert_eq!(buffer.read_sample(0, 1).unwrap(), 2);
-        assert_eq!(buffer.read_sample(0, 2).unwrap(), 3);
-        assert_eq!(buffer.read_sample(1, 0).unwrap(), 4);
-        assert_eq!(buffer.read_sample(1, 1).unwrap(), 5);
-        assert_eq!(buffer.read_sample(1, 2).unwrap(), 6);
+impl<U> SequentialSliceWithOffsets<'_, U> {
+    fn calc_index(&self, channel: usize, frame: usize) -> usize {
+        self.offsets[channel] + frame
     }
+}
 
-    fn test_slice_channel(buffer: &mut dyn AdapterMut<i32>) {
-        insert_data(buffer);
-        let mut other1 = [0; 2];
-        let mut other2 = [0; 4];
-        buffer.write_from_channel_to_slice(0, 1, &mut other1);
-        buffer.write_from_channel_to_slice(1, 0, &mut other2);
-        assert_eq!(other1[0], 2);
-        assert_eq!(other1[1], 3);
-        assert_eq!(other2[0], 4);
-        assert_eq!(other2[1], 5);
-        assert_eq!(other2[2], 6);
-        assert_eq!(other2[3], 0);
-    }
+macro_rules! check_offsets {
+    ($offsets:expr, $length:expr, $channels:expr, $frames:expr) => {
+        $crate::check_dimensions!($channels, $frames);
+        if $offsets.len() < $channels {
+            return Err(SizeError::Offsets {
+                actual: $offsets.len(),
+                required: $channels,
+            });
+        }
+        for (idx, offset) in $offsets.iter().take($channels).enumerate() {
+            if offset
+                .checked_add($frames)
+                .map_or(true, |end| end > $length)
+            {
+                return Err(SizeError::Channel {
+                    index: idx,
+                    actual: $length.saturating_sub(*offset),
+                    required: $frames,
+                });
+            }
+        }
+    };
+}
 
-    fn test_slice_frame(buffer: &mut dyn AdapterMut<i32>) {
-        insert_data(buffer);
-        let mut other1 = [0; 1];
-        let mut other2 = [0; 3];
-        buffer.write_from_frame_to_slice(0, 1, &mut other1);
-        buffer.write_from_frame_to_slice(1, 0, &mut other2);
-        assert_eq!(other1[0], 4);
-        assert_eq!(other2[0], 2);
-        assert_eq!(other2[1], 5);
-        assert_eq!(other2[2], 0);
+impl<'a, 'o, T> SequentialSliceWithOffsets<'o, &'a [T]> {
+    /// Create a new `SequentialSliceWithOffsets` to wrap a slice.
+    /// The `offsets` slice must contain at least `channels` values,
+    /// where each value is the index in `buf` of the first sample of a channel.
+    /// The slice must be long enough to contain `frames` samples
+    /// for every channel, starting at the offset for that channel.
+    pub fn new(
+        buf: &'a [T],
+        offsets: &'o [usize],
+        channels: usize,
+        frames: usize,
+    ) -> Result<Self, SizeError> {
+        check_offsets!(offsets, buf.len(), channels, frames);
+        Ok(Self {
+            buf,
+            offsets,
+            frames,
+            channels,
+        })
     }
 
-    fn test_mut_slice_channel(buffer: &mut dyn AdapterMut<i32>) {
-        insert_data(buffer);
-        let other1 = [8, 9];
-        let other2 = [10, 11, 12, 13];
-        buffer.write_from_slice_to_channel(0, 1, &other1);
-        buffer.write_from_slice_to_channel(1, 0, &other2);
-        assert_eq!(buffer.read_sample(0, 0).unwrap(), 1);
-        assert_eq!(buffer.read_sample(0, 1).unwrap(), 8);
-        assert_eq!(buffer.read_sample(0, 2).unwrap(), 9);
-        assert_eq!(buffer.read_sample(1, 0).unwrap(), 10);
-        assert_eq!(buffer.read_sample(1, 1).unwrap(), 11);
-        assert_eq!(buffer.read_sample(1, 2).unwrap(), 12);
+    implement_active_frames!(|s| s
+        .offsets
+        .iter()
+        .take(s.channels)
+        .map(|offset| s.buf.len().saturating_sub(*offset))
+        .min()
+        .unwrap_or(usize::MAX));
+
+    /// Replace the wrapped slice with a new one,
+    /// keeping the number of channels and frames.
+    /// This allows a long-lived wrapper to be pointed at a new buffer,
+    /// for example in each call of an audio callback.
+    /// The new slice must fulfill the same length requirements as when creating the wrapper.
+    /// If it does not, the wrapper is left unchanged and an error is returned.
+    /// The channel offsets are kept.
+    pub fn replace_buffer(&mut self, buf: &'a [T]) -> Result<(), SizeError> {
+        check_offsets!(self.offsets, buf.len(), self.channels, self.frames);
+        self.buf = buf;
+        Ok(())
     }
+}
 
-    fn test_mut_slice_frame(buffer: &mut dyn AdapterMut<i32>) {
-        insert_data(buffer);
-        let other1 = [8];
-        let other2 = [10, 11, 12];
-        buffer.write_from_slice_to_frame(0, 0, &other1);
-        buffer.write_from_slice_to_frame(1, 0, &other2);
+impl<'a, 'o, T> SequentialSliceWithOffsets<'o, &'a mut [T]> {
+    /// Create a new `SequentialSliceWithOffsets` to wrap a mutable slice.
+    /// The `offsets` slice must contain at least `channels` values,
+    /// where each value is the index in `buf` of the first sample of a channel.
+    /// The slice must be long enough to contain `frames` samples
+    /// for every channel, starting at the offset for that channel.
+    pub fn new_mut(
+        buf: &'a mut [T],
+        offsets: &'o [usize],
+        channels: usize,
+        frames: usize,
+    ) -> Result<Self, SizeError> {
+        check_offsets!(offsets, buf.len(), channels, frames);
+        Ok(Self {
+            buf,
+            offsets,
+            frames,
+            channels,
+        })
+    }
+
+    implement_active_frames!(|s| s
+        .offsets
+        .iter()
+        .take(s.channels)
+        .map(|offset| s.buf.len().saturating_sub(*offset))
+        .min()
+        .unwrap_or(usize::MAX));
+
+    /// Replace the wrapped slice with a new one,
+    /// keeping the number of channels and frames.
+    /// This allows a long-lived wrapper to be pointed at a new buffer,
+    /// for example in each call of an audio callback.
+    /// The new slice must fulfill the same length requirements as when creating the wrapper.
+    /// If it does not, the wrapper is left unchanged and an error is returned.
+    /// The channel offsets are kept.
+    pub fn replace_buffer(&mut self, buf: &'a mut [T]) -> Result<(), SizeError> {
+        check_offsets!(self.offsets, buf.len(), self.channels, self.frames);
+        self.buf = buf;
+        Ok(())
+    }
+}
+
+impl<'a, T> Adapter<'a, T> for SequentialSliceWithOffsets<'_, &'a [T]>
+where
+    T: Clone,
+{
+    unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
+        let index = self.calc_index(channel, frame);
+        self.buf.get_unchecked(index).clone()
+    }
+
+    implement_size_getters!();
+
+    fn preferred_iteration(&self) -> IterationOrder {
+        IterationOrder::ChannelsOuter
+    }
+
+    fn write_from_channel_to_slice(&self, channel: usize, skip: usize, slice: &mut [T]) -> usize {
+        if channel >= self.channels || skip >= self.frames {
+            return 0;
+        }
+        let frames_to_write = if (self.frames - skip) < slice.len() {
+            self.frames - skip
+        } else {
+            slice.len()
+        };
+        let buffer_skip = self.calc_index(channel, skip);
+        slice[..frames_to_write]
+            .clone_from_slice(&self.buf[buffer_skip..buffer_skip + frames_to_write]);
+        frames_to_write
+    }
+}
+
+impl<'a, T> Adapter<'a, T> for SequentialSliceWithOffsets<'_, &'a mut [T]>
+where
+    T: Clone,
+{
+    unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
+        let index = self.calc_index(channel, frame);
+        self.buf.get_unchecked(index).clone()
+    }
+
+    implement_size_getters!();
+
+    fn preferred_iteration(&self) -> IterationOrder {
+        IterationOrder::ChannelsOuter
+    }
+
+    fn write_from_channel_to_slice(&self, channel: usize, skip: usize, slice: &mut [T]) -> usize {
+        if channel >= self.channels || skip >= self.frames {
+            return 0;
+        }
+        let frames_to_write = if (self.frames - skip) < slice.len() {
+            self.frames - skip
+        } else {
+            slice.len()
+        };
+        let buffer_skip = self.calc_index(channel, skip);
+        slice[..frames_to_write]
+            .clone_from_slice(&self.buf[buffer_skip..buffer_skip + frames_to_write]);
+        frames_to_write
+    }
+}
+
+impl<'a, T> AdapterMut<'a, T> for SequentialSliceWithOffsets<'_, &'a mut [T]>
+where
+    T: Clone,
+{
+    unsafe fn write_sample_unchecked(&mut self, channel: usize, frame: usize, value: &T) -> bool {
+        let index = self.calc_index(channel, frame);
+        *self.buf.get_unchecked_mut(index) = value.clone();
+        false
+    }
+
+    fn write_from_slice_to_channel(
+        &mut self,
+        channel: usize,
+        skip: usize,
+        slice: &[T],
+    ) -> (usize, usize) {
+        if channel >= self.channels || skip >= self.frames {
+            return (0, 0);
+        }
+        let frames_to_read = if (self.frames - skip) < slice.len() {
+            self.frames - skip
+        } else {
+            slice.len()
+        };
+        let buffer_skip = self.calc_index(channel, skip);
+        self.buf[buffer_skip..buffer_skip + frames_to_read]
+            .clone_from_slice(&slice[..frames_to_read]);
+        (frames_to_read, 0)
+    }
+
+    fn copy_frames_within(&mut self, src: usize, dest: usize, count: usize) -> Option<usize> {
+        if src + count > self.frames || dest + count > self.frames {
+            return None;
+        }
+        for offset in self.offsets.iter().take(self.channels) {
+            unsafe {
+                copy_within_slice(self.buf, src + offset, dest + offset, count);
+            }
+        }
+        Some(count)
+    }
+}
+
+implement_sample_access!(['a, T] SequentialSliceWithOffsets<'_, &'a [T]>);
+implement_sample_access!(['a, T] SequentialSliceWithOffsets<'_, &'a mut [T]>, mutable);
+
+//
+// =========================== GroupedInterleavedSlice ===========================
+//
+
+/// Wrapper for a slice of length `frames * channels`,
+/// where the channels are divided into groups of `group_size` channels.
+/// Each group is stored in _interleaved_ order, and the groups are stored one after another.
+/// This layout is used by some multi-stream hardware,
+/// where each stream delivers frames of a few channels.
+/// For two stereo streams containing three frames, with a group size of 2, the order is
+/// `L1, R1, L2, R2, L3, R3, L1', R1', L2', R2', L3', R3'`,
+/// where `L'` and `R'` are the channels of the second stream.
+/// A group size equal to the number of channels gives the same layout as [InterleavedSlice],
+/// and a group size of one gives the same layout as [SequentialSlice].
+pub struct GroupedInterleavedSlice<U> {
+    buf: U,
+    frames: usize,
+    channels: usize,
+    group_size: usize,
+}
+
+impl<U> GroupedInterleavedSlice<U> {
+    fn calc_index(&self, channel: usize, frame: usize) -> usize {
+        let group = channel / self.group_size;
+        let group_channel = channel % self.group_size;
+        (group * self.frames + frame) * self.group_size + group_channel
+    }
+
+    /// Get the number of channels in each group.
+    pub fn group_size(&self) -> usize {
+        self.group_size
+    }
+}
+
+macro_rules! check_group_size {
+    ($group_size:expr, $channels:expr) => {
+        if $group_size == 0 || $channels % $group_size != 0 {
+            return Err(SizeError::Group {
+                channels: $channels,
+                group_size: $group_size,
+            });
+        }
+    };
+}
+
+impl<'a, T> GroupedInterleavedSlice<&'a [T]> {
+    /// Create a new `GroupedInterleavedSlice` to wrap a slice.
+    /// The number of channels must be a multiple of `group_size`.
+    /// The slice length must be at least `frames*channels`.
+    /// It is allowed to be longer than needed,
+    /// but these extra values cannot
+    /// be accessed via the trait methods.
+    pub fn new(
+        buf: &'a [T],
+        group_size: usize,
+        channels: usize,
+        frames: usize,
+    ) -> Result<Self, SizeError> {
+        check_group_size!(group_size, channels);
+        check_slice_length!(channels, frames, buf.len());
+        Ok(Self {
+            buf,
+            frames,
+            channels,
+            group_size,
+        })
+    }
+
+    implement_active_frames!(|s| s.buf.len().checked_div(s.channels).unwrap_or(usize::MAX));
+
+    /// Replace the wrapped slice with a new one,
+    /// keeping the number of channels, frames and the group size.
+    /// This allows a long-lived wrapper to be pointed at a new buffer,
+    /// for example in each call of an audio callback.
+    /// The new slice must fulfill the same length requirements as when creating the wrapper.
+    /// If it does not, the wrapper is left unchanged and an error is returned.
+    pub fn replace_buffer(&mut self, buf: &'a [T]) -> Result<(), SizeError> {
+        check_slice_length!(self.channels, self.frames, buf.len());
+        self.buf = buf;
+        Ok(())
+    }
+}
+
+impl<'a, T> GroupedInterleavedSlice<&'a mut [T]> {
+    /// Create a new `GroupedInterleavedSlice` to wrap a mutable slice.
+    /// The number of channels must be a multiple of `group_size`.
+    /// The slice length must be at least `frames*channels`.
+    /// It is allowed to be longer than needed,
+    /// but these extra values cannot
+    /// be accessed via the trait methods.
+    pub fn new_mut(
+        buf: &'a mut [T],
+        group_size: usize,
+        channels: usize,
+        frames: usize,
+    ) -> Result<Self, SizeError> {
+        check_group_size!(group_size, channels);
+        check_slice_length!(channels, frames, buf.len());
+        Ok(Self {
+            buf,
+            frames,
+            channels,
+            group_size,
+        })
+    }
+
+    implement_active_frames!(|s| s.buf.len().checked_div(s.channels).unwrap_or(usize::MAX));
+
+    /// Replace the wrapped slice with a new one,
+    /// keeping the number of channels, frames and the group size.
+    /// This allows a long-lived wrapper to be pointed at a new buffer,
+    /// for example in each call of an audio callback.
+    /// The new slice must fulfill the same length requirements as when creating the wrapper.
+    /// If it does not, the wrapper is left unchanged and an error is returned.
+    pub fn replace_buffer(&mut self, buf: &'a mut [T]) -> Result<(), SizeError> {
+        check_slice_length!(self.channels, self.frames, buf.len());
+        self.buf = buf;
+        Ok(())
+    }
+}
+
+macro_rules! implement_grouped_reads {
+    () => {
+        unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
+            let index = self.calc_index(channel, frame);
+            self.buf.get_unchecked(index).clone()
+        }
+
+        implement_size_getters!();
+
+        fn preferred_iteration(&self) -> IterationOrder {
+            if self.group_size == self.channels {
+                IterationOrder::FramesOuter
+            } else {
+                IterationOrder::ChannelsOuter
+            }
+        }
+
+        fn write_from_frame_to_slice(&self, frame: usize, skip: usize, slice: &mut [T]) -> usize {
+            if frame >= self.frames || skip >= self.channels {
+                return 0;
+            }
+            let channels_to_write = if (self.channels - skip) < slice.len() {
+                self.channels - skip
+            } else {
+                slice.len()
+            };
+            // The channels of each group are stored consecutively.
+            let mut channel = skip;
+            while channel < skip + channels_to_write {
+                let count = (self.group_size - channel % self.group_size)
+                    .min(skip + channels_to_write - channel);
+                let buffer_skip = self.calc_index(channel, frame);
+                slice[channel - skip..channel - skip + count]
+                    .clone_from_slice(&self.buf[buffer_skip..buffer_skip + count]);
+                channel += count;
+            }
+            channels_to_write
+        }
+    };
+}
+
+impl<'a, T> Adapter<'a, T> for GroupedInterleavedSlice<&'a [T]>
+where
+    T: Clone,
+{
+    implement_grouped_reads!();
+}
+
+impl<'a, T> Adapter<'a, T> for GroupedInterleavedSlice<&'a mut [T]>
+where
+    T: Clone,
+{
+    implement_grouped_reads!();
+}
+
+impl<'a, T> AdapterMut<'a, T> for GroupedInterleavedSlice<&'a mut [T]>
+where
+    T: Clone,
+{
+    unsafe fn write_sample_unchecked(&mut self, channel: usize, frame: usize, value: &T) -> bool {
+        let index = self.calc_index(channel, frame);
+        *self.buf.get_unchecked_mut(index) = value.clone();
+        false
+    }
+
+    fn copy_frames_within(&mut self, src: usize, dest: usize, count: usize) -> Option<usize> {
+        if src + count > self.frames || dest + count > self.frames {
+            return None;
+        }
+        for group in (0..self.channels).step_by(self.group_size) {
+            let start = self.calc_index(group, 0);
+            unsafe {
+                copy_within_slice(
+                    self.buf,
+                    start + src * self.group_size,
+                    start + dest * self.group_size,
+                    count * self.group_size,
+                );
+            }
+        }
+        Some(count)
+    }
+}
+
+implement_sample_access!(['a, T] GroupedInterleavedSlice<&'a [T]>);
+implement_sample_access!(['a, T] GroupedInterleavedSlice<&'a mut [T]>, mutable);
+
+//
+// =========================== Tuples and arrays of slices ===========================
+//
+
+/// A wrapper for a tuple of two channel slices, `(&[T], &[T])` or `(&mut [T], &mut [T])`,
+/// or an array of channel slices, `[&[T]; N]` or `[&mut [T]; N]`.
+/// Each slice holds the samples of one channel,
+/// and the number of frames is given by the shortest slice.
+///
+/// The traits can't be implemented directly for the tuples and arrays.
+/// With the `audio` feature enabled, [Adapter] and [AdapterMut] have blanket implementations
+/// for all types implementing the `Buf` and `ExactSizeBuf` traits of the `audio` crate.
+/// Since those traits are foreign, the coherence rules assume that they may
+/// be implemented for tuples and arrays in the future,
+/// and reject any direct implementation for them as overlapping.
+/// Code that used a tuple or array directly as an adapter
+/// only needs to wrap it, for example `ChannelSlicesAdapter::new((&left[..], &right[..]))`
+/// or `ChannelSlicesAdapter::new([&ch0[..], &ch1[..]])`.
+///
+/// ### Example
+/// ```
+/// use audioadapter::direct::ChannelSlicesAdapter;
+/// use audioadapter::Adapter;
+///
+/// let left = [1, 2, 3];
+/// let right = [4, 5, 6];
+/// let buffer = ChannelSlicesAdapter::new((&left[..], &right[..]));
+/// assert_eq!(buffer.channels(), 2);
+/// assert_eq!(buffer.read_sample(1, 2), Some(6));
+/// ```
+pub struct ChannelSlicesAdapter<U> {
+    slices: U,
+}
+
+impl<U> ChannelSlicesAdapter<U> {
+    /// Create a new wrapper for a tuple or array of channel slices.
+    pub fn new(slices: U) -> Self {
+        Self { slices }
+    }
+
+    /// Get a reference to the wrapped slices.
+    pub fn inner(&self) -> &U {
+        &self.slices
+    }
+
+    /// Take back the wrapped slices, consuming the wrapper.
+    pub fn into_inner(self) -> U {
+        self.slices
+    }
+}
+
+/// Access to the channel slices of a tuple or array of slices.
+trait ChannelSlices<T> {
+    const CHANNELS: usize;
+
+    fn channel_slice(&self, channel: usize) -> &[T];
+}
+
+/// Mutable access to the channel slices of a tuple or array of slices.
+trait ChannelSlicesMut<T>: ChannelSlices<T> {
+    fn channel_slice_mut(&mut self, channel: usize) -> &mut [T];
+}
+
+impl<T> ChannelSlices<T> for (&[T], &[T]) {
+    const CHANNELS: usize = 2;
+
+    fn channel_slice(&self, channel: usize) -> &[T] {
+        if channel == 0 {
+            self.0
+        } else {
+            self.1
+        }
+    }
+}
+
+impl<T> ChannelSlices<T> for (&mut [T], &mut [T]) {
+    const CHANNELS: usize = 2;
+
+    fn channel_slice(&self, channel: usize) -> &[T] {
+        if channel == 0 {
+            self.0
+        } else {
+            self.1
+        }
+    }
+}
+
+impl<T> ChannelSlicesMut<T> for (&mut [T], &mut [T]) {
+    fn channel_slice_mut(&mut self, channel: usize) -> &mut [T] {
+        if channel == 0 {
+            self.0
+        } else {
+            self.1
+        }
+    }
+}
+
+impl<T, const N: usize> ChannelSlices<T> for [&[T]; N] {
+    const CHANNELS: usize = N;
+
+    fn channel_slice(&self, channel: usize) -> &[T] {
+        self[channel]
+    }
+}
+
+impl<T, const N: usize> ChannelSlices<T> for [&mut [T]; N] {
+    const CHANNELS: usize = N;
+
+    fn channel_slice(&self, channel: usize) -> &[T] {
+        self[channel]
+    }
+}
+
+impl<T, const N: usize> ChannelSlicesMut<T> for [&mut [T]; N] {
+    fn channel_slice_mut(&mut self, channel: usize) -> &mut [T] {
+        self[channel]
+    }
+}
+
+macro_rules! impl_traits_for_channel_slices {
+    ([$($generics:tt)*], $type:ty) => {
+        impl<'a, T, $($generics)*> Adapter<'a, T> for ChannelSlicesAdapter<$type>
+        where
+            T: Clone,
+        {
+            unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
+                self.slices.channel_slice(channel).get_unchecked(frame).clone()
+            }
+
+            fn channels(&self) -> usize {
+                <$type as ChannelSlices<T>>::CHANNELS
+            }
+
+            fn preferred_iteration(&self) -> IterationOrder {
+                IterationOrder::ChannelsOuter
+            }
+
+            fn frames(&self) -> usize {
+                (0..<$type as ChannelSlices<T>>::CHANNELS)
+                    .map(|channel| self.slices.channel_slice(channel).len())
+                    .min()
+                    .unwrap_or_default()
+            }
+
+            fn write_from_channel_to_slice(
+                &self,
+                channel: usize,
+                skip: usize,
+                slice: &mut [T],
+            ) -> usize {
+                if channel >= self.channels() || skip >= self.frames() {
+                    return 0;
+                }
+                let frames_to_write = if (self.frames() - skip) < slice.len() {
+                    self.frames() - skip
+                } else {
+                    slice.len()
+                };
+                slice[..frames_to_write]
+                    .clone_from_slice(&self.slices.channel_slice(channel)[skip..skip + frames_to_write]);
+                frames_to_write
+            }
+        }
+    };
+    ([$($generics:tt)*], $type:ty, mutable) => {
+        impl_traits_for_channel_slices!([$($generics)*], $type);
+
+        impl<'a, T, $($generics)*> AdapterMut<'a, T> for ChannelSlicesAdapter<$type>
+        where
+            T: Clone,
+        {
+            unsafe fn write_sample_unchecked(
+                &mut self,
+                channel: usize,
+                frame: usize,
+                value: &T,
+            ) -> bool {
+                *self.slices.channel_slice_mut(channel).get_unchecked_mut(frame) = value.clone();
+                false
+            }
+
+            fn write_from_slice_to_channel(
+                &mut self,
+                channel: usize,
+                skip: usize,
+                slice: &[T],
+            ) -> (usize, usize) {
+                if channel >= self.channels() || skip >= self.frames() {
+                    return (0, 0);
+                }
+                let frames_to_read = if (self.frames() - skip) < slice.len() {
+                    self.frames() - skip
+                } else {
+                    slice.len()
+                };
+                self.slices.channel_slice_mut(channel)[skip..skip + frames_to_read]
+                    .clone_from_slice(&slice[..frames_to_read]);
+                (frames_to_read, 0)
+            }
+
+            fn copy_frames_within(
+                &mut self,
+                src: usize,
+                dest: usize,
+                count: usize,
+            ) -> Option<usize> {
+                if src + count > self.frames() || dest + count > self.frames() {
+                    return None;
+                }
+                for channel in 0..self.channels() {
+                    unsafe {
+                        copy_within_slice(self.slices.channel_slice_mut(channel), src, dest, count);
+                    }
+                }
+                Some(count)
+            }
+        }
+    };
+}
+
+impl_traits_for_channel_slices!([], (&'a [T], &'a [T]));
+impl_traits_for_channel_slices!([], (&'a mut [T], &'a mut [T]), mutable);
+impl_traits_for_channel_slices!([const N: usize], [&'a [T]; N]);
+impl_traits_for_channel_slices!([const N: usize], [&'a mut [T]; N], mutable);
+
+impl<T> KnownChannels<2> for ChannelSlicesAdapter<(&[T], &[T])> {}
+impl<T> KnownChannels<2> for ChannelSlicesAdapter<(&mut [T], &mut [T])> {}
+impl<T, const N: usize> KnownChannels<N> for ChannelSlicesAdapter<[&[T]; N]> {}
+impl<T, const N: usize> KnownChannels<N> for ChannelSlicesAdapter<[&mut [T]; N]> {}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AdapterRef;
+
+    fn insert_data(buffer: &mut dyn AdapterMut<i32>) {
+        buffer.write_sample(0, 0, &1).unwrap();
+        buffer.write_sample(0, 1, &2).unwrap();
+        buffer.write_sample(0, 2, &3).unwrap();
+        buffer.write_sample(1, 0, &4).unwrap();
+        buffer.write_sample(1, 1, &5).unwrap();
+        buffer.write_sample(1, 2, &6).unwrap();
+    }
+
+    fn test_get(buffer: &mut dyn AdapterMut<i32>) {
+        insert_data(buffer);
+        assert_eq!(buffer.read_sample(0, 0).unwrap(), 1);
+        assert_eq!(buffer.read_sample(0, 1).unwrap(), 2);
+        assert_eq!(buffer.read_sample(0, 2).unwrap(), 3);
+        assert_eq!(buffer.read_sample(1, 0).unwrap(), 4);
+        assert_eq!(buffer.read_sample(1, 1).unwrap(), 5);
+        assert_eq!(buffer.read_sample(1, 2).unwrap(), 6);
+    }
+
+    fn test_slice_channel(buffer: &mut dyn AdapterMut<i32>) {
+        insert_data(buffer);
+        let mut other1 = [0; 2];
+        let mut other2 = [0; 4];
+        buffer.write_from_channel_to_slice(0, 1, &mut other1);
+        buffer.write_from_channel_to_slice(1, 0, &mut other2);
+        assert_eq!(other1[0], 2);
+        assert_eq!(other1[1], 3);
+        assert_eq!(other2[0], 4);
+        assert_eq!(other2[1], 5);
+        assert_eq!(other2[2], 6);
+        assert_eq!(other2[3], 0);
+    }
+
+    fn test_slice_frame(buffer: &mut dyn AdapterMut<i32>) {
+        insert_data(buffer);
+        let mut other1 = [0; 1];
+        let mut other2 = [0; 3];
+        buffer.write_from_frame_to_slice(0, 1, &mut other1);
+        buffer.write_from_frame_to_slice(1, 0, &mut other2);
+        assert_eq!(other1[0], 4);
+        assert_eq!(other2[0], 2);
+        assert_eq!(other2[1], 5);
+        assert_eq!(other2[2], 0);
+    }
+
+    fn test_mut_slice_channel(buffer: &mut dyn AdapterMut<i32>) {
+        insert_data(buffer);
+        let other1 = [8, 9];
+        let other2 = [10, 11, 12, 13];
+        buffer.write_from_slice_to_channel(0, 1, &other1);
+        buffer.write_from_slice_to_channel(1, 0, &other2);
+        assert_eq!(buffer.read_sample(0, 0).unwrap(), 1);
+        assert_eq!(buffer.read_sample(0, 1).unwrap(), 8);
+        assert_eq!(buffer.read_sample(0, 2).unwrap(), 9);
+        assert_eq!(buffer.read_sample(1, 0).unwrap(), 10);
+        assert_eq!(buffer.read_sample(1, 1).unwrap(), 11);
+        assert_eq!(buffer.read_sample(1, 2).unwrap(), 12);
+    }
+
+    fn test_mut_slice_frame(buffer: &mut dyn AdapterMut<i32>) {
+        insert_data(buffer);
+        let other1 = [8];
+        let other2 = [10, 11, 12];
+        buffer.write_from_slice_to_frame(0, 0, &other1);
+        buffer.write_from_slice_to_frame(1, 0, &other2);
         assert_eq!(buffer.read_sample(0, 0).unwrap(), 8);
         assert_eq!(buffer.read_sample(1, 0).unwrap(), 4);
         assert_eq!(buffer.read_sample(0, 1).unwrap(), 10);
@@ -997,8 +2885,8 @@ mod tests {
     #[cfg(feature = "std")]
     #[test]
     fn boxed_buffer() {
-        let mut data = [1_i32, 2, 3, 4, 5, 6];
-        let boxed: Box<dyn Adapter<i32>> = Box::new(SequentialSlice::new(&mut data, 2, 3).unwrap());
+        let data = [1_i32, 2, 3, 4, 5, 6];
+        let boxed: Box<dyn Adapter<i32>> = Box::new(SequentialSlice::new(&data, 2, 3).unwrap());
         assert_eq!(boxed.read_sample(0, 0).unwrap(), 1);
     }
 
@@ -1006,15 +2894,30 @@ mod tests {
     // meaning it can be sent between threads.
     // This test is not designed to be run, only to compile.
     #[allow(dead_code)]
-    fn test_adapter_send_and_sync<T: Sync + Send + Clone>() {
+    fn test_adapter_send_and_sync() {
         fn is_send<T: Send>() {}
         fn is_sync<T: Sync>() {}
-        is_send::<InterleavedSlice<f32>>();
-        is_sync::<InterleavedSlice<f32>>();
+        is_send::<InterleavedSlice<&[f32]>>();
+        is_sync::<InterleavedSlice<&[f32]>>();
+        is_send::<InterleavedSlice<&mut [f32]>>();
+        is_sync::<InterleavedSlice<&mut [f32]>>();
+        is_send::<SequentialSlice<&[f32]>>();
+        is_sync::<SequentialSlice<&mut [f32]>>();
+        is_send::<SequentialSliceWithOffsets<&mut [f32]>>();
+        is_sync::<SequentialSliceWithOffsets<&[f32]>>();
         #[cfg(feature = "std")]
-        is_send::<InterleavedSliceOfVecs<f32>>();
-        #[cfg(feature = "std")]
-        is_sync::<InterleavedSliceOfVecs<f32>>();
+        {
+            is_send::<InterleavedSliceOfVecs<&[Vec<f32>]>>();
+            is_sync::<InterleavedSliceOfVecs<&mut [Vec<f32>]>>();
+            is_send::<SequentialSliceOfVecs<&mut [Vec<f32>]>>();
+            is_sync::<SequentialSliceOfVecs<&[Vec<f32>]>>();
+            is_send::<SparseSequentialSliceOfVecs<&mut [Vec<f32>]>>();
+            is_sync::<SparseSequentialSliceOfVecs<&[Vec<f32>]>>();
+            is_send::<SequentialVecOfSlices<Vec<&mut [f32]>>>();
+            is_sync::<SequentialVecOfSlices<Vec<&mut [f32]>>>();
+            is_send::<SequentialVecOfSlices<Vec<&[f32]>>>();
+            is_sync::<SequentialVecOfSlices<Vec<&[f32]>>>();
+        }
     }
 
     #[test]
@@ -1037,6 +2940,239 @@ mod tests {
         assert_eq!(buffer.read_sample(1, 2).unwrap(), 2.0);
     }
 
+    #[cfg(feature = "reject-zero-dimensions")]
+    #[test]
+    fn reject_zero_dimensions() {
+        let mut data = [0; 6];
+        assert!(matches!(
+            InterleavedSlice::new(&data, 0, 3),
+            Err(SizeError::ZeroDimension {
+                channels: 0,
+                frames: 3
+            })
+        ));
+        assert!(SequentialSlice::new_mut(&mut data, 2, 0).is_err());
+        let offsets = [0, 3];
+        assert!(SequentialSliceWithOffsets::new(&data, &offsets, 2, 0).is_err());
+        assert!(SequentialVecOfSlices::<Vec<&mut [i32]>>::new_mut(Vec::new(), 3).is_err());
+        let vecs = vec![vec![0; 3]; 2];
+        assert!(SequentialSliceOfVecs::new(&vecs, 0, 3).is_err());
+        assert!(InterleavedSliceOfVecs::new(&vecs, 3, 0).is_err());
+        assert!(InterleavedSlice::new(&data, 2, 3).is_ok());
+    }
+
+    #[test]
+    fn size_overflow() {
+        let data = [0; 6];
+        let frames = usize::MAX / 2 + 1;
+        assert!(matches!(
+            InterleavedSlice::new(&data, 2, frames),
+            Err(SizeError::Overflow { channels: 2, .. })
+        ));
+        let offsets = [usize::MAX];
+        assert!(matches!(
+            SequentialSliceWithOffsets::new(&data, &offsets, 1, 1),
+            Err(SizeError::Channel { actual: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn constructors_with_dims() {
+        let mut data = [1, 2, 3, 4, 5, 6];
+        let dims = crate::Dims {
+            channels: 3,
+            frames: 2,
+        };
+        let buffer = InterleavedSlice::with_dims(&data, dims).unwrap();
+        assert_eq!((buffer.channels(), buffer.frames()), (3, 2));
+        assert_eq!(buffer.read_sample(2, 0), Some(3));
+        let buffer = SequentialSlice::with_dims_mut(&mut data, dims).unwrap();
+        assert_eq!(buffer.read_sample(2, 0), Some(5));
+        assert!(SequentialSlice::with_dims(
+            &data,
+            crate::Dims {
+                channels: 3,
+                frames: 3
+            }
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn copy_frames_from_other() {
+        let data_other = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let other = SequentialSlice::new(&data_other, 3, 3).unwrap();
+        let mut data = [0; 8];
+        let mut buffer = InterleavedSlice::new_mut(&mut data, 2, 4).unwrap();
+        // Limited by the frames remaining in other.
+        assert_eq!(buffer.copy_frames_from_other(&other, 1, 0, 10), 2);
+        // Limited by the frames remaining in self.
+        assert_eq!(buffer.copy_frames_from_other(&other, 0, 3, 2), 1);
+        // Limited by max_frames.
+        assert_eq!(buffer.copy_frames_from_other(&other, 0, 2, 1), 1);
+        assert_eq!(buffer.copy_frames_from_other(&other, 3, 0, 1), 0);
+        assert_eq!(buffer.copy_frames_from_other(&other, 0, 5, 1), 0);
+        assert_eq!(data, [2, 5, 3, 6, 1, 4, 1, 4]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn vec_of_slices_from_raw_parts() {
+        let left = [1.0_f32, 2.0, 3.0];
+        let right = [4.0_f32, 5.0, 6.0];
+        let ptrs = [left.as_ptr(), right.as_ptr()];
+        let buffer = unsafe { SequentialVecOfSlices::from_raw_parts(ptrs.as_ptr(), 2, 3) }.unwrap();
+        assert_eq!(buffer.channels(), 2);
+        assert_eq!(buffer.read_sample(1, 2), Some(6.0));
+        assert_eq!(buffer[(0, 1)], 2.0);
+
+        let mut out = [0.0_f32; 8];
+        let (first, second) = out.split_at_mut(4);
+        let ptrs = [first.as_mut_ptr(), second.as_mut_ptr()];
+        let mut output =
+            unsafe { SequentialVecOfSlices::from_raw_parts_mut(ptrs.as_ptr(), 2, 3) }.unwrap();
+        assert_eq!(output.copy_frames_from_other(&buffer, 0, 1, 3), 2);
+        assert_eq!(out, [0.0, 1.0, 2.0, 0.0, 0.0, 4.0, 5.0, 0.0]);
+
+        let mut long = [0.0_f32; 1];
+        let ptrs = [long.as_mut_ptr()];
+        let too_long =
+            unsafe { SequentialVecOfSlices::from_raw_parts_mut(ptrs.as_ptr(), 1, usize::MAX / 2) };
+        assert!(matches!(too_long, Err(SizeError::Overflow { .. })));
+
+        let empty = unsafe {
+            SequentialVecOfSlices::<Vec<&[f32]>>::from_raw_parts(core::ptr::null(), 0, 3)
+        };
+        assert!(empty.is_ok() != cfg!(feature = "reject-zero-dimensions"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[should_panic(expected = "The pointer for channel 1 is null")]
+    fn vec_of_slices_from_raw_parts_null() {
+        let left = [1.0_f32, 2.0];
+        let ptrs = [left.as_ptr(), core::ptr::null()];
+        let _ = unsafe { SequentialVecOfSlices::from_raw_parts(ptrs.as_ptr(), 2, 2) };
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[should_panic(expected = "The channels overlap")]
+    fn vec_of_slices_from_raw_parts_overlap() {
+        let mut data = [0_i32; 6];
+        let ptr = data.as_mut_ptr();
+        let ptrs = [ptr, unsafe { ptr.add(2) }];
+        let _ = unsafe { SequentialVecOfSlices::from_raw_parts_mut(ptrs.as_ptr(), 2, 3) };
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn copy_and_rotate_interleaved_vecs() {
+        let mut data = vec![vec![1, 2], vec![3, 4], vec![5, 6], vec![7, 8, 9]];
+        let mut buffer = InterleavedSliceOfVecs::new_mut(&mut data, 2, 4).unwrap();
+        assert_eq!(buffer.copy_frames_within(0, 1, 3), Some(3));
+        assert_eq!(buffer.copy_frames_within(2, 0, 2), Some(2));
+        assert_eq!(buffer.copy_frames_within(2, 3, 2), None);
+        buffer.rotate_frames(1);
+        assert_eq!(buffer.read_sample(0, 1), Some(3));
+        buffer.rotate_frames(-6);
+        assert_eq!(
+            data,
+            vec![vec![5, 6], vec![3, 4], vec![5, 6, 9], vec![3, 4]]
+        );
+
+        let mut data = vec![vec![0; 2]; 10];
+        let mut buffer = InterleavedSliceOfVecs::new_mut(&mut data, 2, 10).unwrap();
+        check_copy_within(&mut buffer);
+    }
+
+    #[test]
+    fn shift_frames() {
+        let mut data = [1, 2, 3, 4, 5, 6, 7, 8];
+        let mut buffer = InterleavedSlice::new_mut(&mut data, 2, 4).unwrap();
+        buffer.shift_frames(1, &0);
+        assert_eq!(buffer.read_sample(1, 3), Some(6));
+        buffer.shift_frames(-2, &9);
+        assert_eq!(data, [3, 4, 5, 6, 9, 9, 9, 9]);
+
+        let mut data = [1, 2, 3, 4, 5, 6];
+        let mut buffer = SequentialSlice::new_mut(&mut data, 2, 3).unwrap();
+        buffer.shift_frames(0, &0);
+        buffer.shift_frames(-1, &0);
+        assert_eq!(data, [2, 3, 0, 5, 6, 0]);
+        let mut buffer = SequentialSlice::new_mut(&mut data, 2, 3).unwrap();
+        buffer.shift_frames(5, &7);
+        assert_eq!(data, [7; 6]);
+    }
+
+    #[test]
+    fn add_to_samples() {
+        let mut data = [1, 2, 3, 4, 5, 6];
+        let mut buffer = SequentialSlice::new_mut(&mut data, 2, 3).unwrap();
+        assert_eq!(buffer.add_sample(1, 0, &10), Some(false));
+        assert_eq!(buffer.add_sample(1, 3, &10), None);
+        assert_eq!(
+            buffer.add_from_slice_to_channel(0, 1, &[20, 30, 40]),
+            (2, 0)
+        );
+        assert_eq!(buffer.add_from_slice_to_channel(2, 0, &[20]), (0, 0));
+        let mut other = [1, 2, 3, 4, 5, 6];
+        let mut interleaved = InterleavedSlice::new_mut(&mut other, 2, 3).unwrap();
+        assert_eq!(
+            interleaved.add_from_slice_to_channel(1, 1, &[10, 10]),
+            (2, 0)
+        );
+        assert_eq!(data, [1, 22, 33, 14, 5, 6]);
+        assert_eq!(other, [1, 2, 3, 14, 5, 16]);
+    }
+
+    #[test]
+    fn add_to_samples_saturating() {
+        let mut data: [i16; 4] = [i16::MAX, -2, i16::MIN, 2];
+        let mut buffer = SequentialSlice::new_mut(&mut data, 2, 2).unwrap();
+        assert_eq!(buffer.add_sample(0, 0, &1), Some(true));
+        assert_eq!(buffer.add_sample(0, 1, &1), Some(false));
+        assert_eq!(
+            buffer.add_from_slice_to_channel(1, 0, &[-1, i16::MAX - 2]),
+            (2, 1)
+        );
+        assert_eq!(data, [i16::MAX, -1, i16::MIN, i16::MAX]);
+
+        let mut data: [u8; 2] = [250, 5];
+        let mut buffer = InterleavedSlice::new_mut(&mut data, 2, 1).unwrap();
+        assert_eq!(buffer.add_from_slice_to_channel(0, 0, &[10]), (1, 1));
+        assert_eq!(buffer.add_sample(1, 0, &10), Some(false));
+        assert_eq!(data, [u8::MAX, 15]);
+    }
+
+    #[test]
+    fn swap_frames_with_other() {
+        let mut data_other = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let mut other = SequentialSlice::new_mut(&mut data_other, 3, 3).unwrap();
+        let mut data = [10, 20, 30, 40, 50, 60, 70, 80];
+        let mut buffer = InterleavedSlice::new_mut(&mut data, 2, 4).unwrap();
+        assert_eq!(buffer.swap_frames_with(&mut other, 1, 0, 2), Some(2));
+        assert_eq!(buffer.swap_frames_with(&mut other, 3, 2, 2), None);
+        assert_eq!(buffer.swap_frames_with(&mut other, 0, 1, 4), None);
+        assert_eq!(data, [10, 20, 1, 4, 2, 5, 70, 80]);
+        assert_eq!(data_other, [30, 50, 3, 40, 60, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn copy_channel_from_other_with_gain() {
+        let data_other = [1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let other = SequentialSlice::new(&data_other, 2, 3).unwrap();
+        let mut data = [0.0; 6];
+        let mut buffer = SequentialSlice::new_mut(&mut data, 2, 3).unwrap();
+        let res = buffer.write_from_other_to_channel_with_gain(&other, 1, 0, 1, 0, 2, 0.5);
+        assert_eq!(res, Some(0));
+        assert_eq!(
+            buffer.write_from_other_to_channel_with_gain(&other, 0, 1, 2, 0, 2, 0.5),
+            None
+        );
+        assert_eq!(data, [2.5, 3.0, 0.0, 0.0, 0.0, 0.0]);
+    }
+
     #[test]
     fn fill_channel() {
         let mut data: [i32; 6] = [1; 6];
@@ -1055,6 +3191,95 @@ mod tests {
         assert_eq!(data, expected);
     }
 
+    #[test]
+    fn fill_frames_to_end() {
+        let mut data: [i32; 6] = [1; 6];
+        let mut buffer = InterleavedSlice::new_mut(&mut data, 2, 3).unwrap();
+        assert_eq!(buffer.fill_frames_with(1, 2, &2), Some(2));
+        assert_eq!(buffer.fill_frames_with(2, 2, &3), None);
+        let expected: [i32; 6] = [1, 1, 2, 2, 2, 2];
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn fill_range() {
+        let mut data: [i32; 9] = [1; 9];
+        let mut buffer = SequentialSlice::new_mut(&mut data, 3, 3).unwrap();
+        assert_eq!(buffer.fill_range_with(1..3, 1..3, &2), Some(2));
+        assert_eq!(buffer.fill_range_with(0..1, 3..3, &3), Some(0));
+        assert_eq!(buffer.fill_range_with(0..4, 0..1, &3), None);
+        assert_eq!(buffer.fill_range_with(0..1, 0..4, &3), None);
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 2..1;
+        assert_eq!(buffer.fill_range_with(0..1, reversed, &3), None);
+        let expected: [i32; 9] = [1, 1, 1, 1, 2, 2, 1, 2, 2];
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn split_channels() {
+        let mut data = [0_i32; 8];
+        let mut buffer = SequentialSlice::new_mut(&mut data, 4, 2).unwrap();
+        let (mut first, mut second) = buffer.split_channels_at_mut(1).unwrap();
+        assert_eq!((first.channels(), second.channels()), (1, 3));
+        std::thread::scope(|scope| {
+            scope.spawn(|| first.fill_with(&1));
+            scope.spawn(|| second.fill_channel_with(2, &2));
+        });
+        assert!(buffer.split_channels_at_mut(5).is_none());
+        let (empty, all) = buffer.split_channels_at_mut(0).unwrap();
+        assert_eq!((empty.channels(), all.channels()), (0, 4));
+        assert_eq!(data, [1, 1, 0, 0, 0, 0, 2, 2]);
+
+        let mut data = vec![vec![1, 2], vec![3, 4], vec![5, 6]];
+        let mut buffer = SequentialSliceOfVecs::new_mut(&mut data, 3, 2).unwrap();
+        let (first, mut second) = buffer.split_channels_at_mut(2).unwrap();
+        assert_eq!(first.read_sample(1, 1), Some(4));
+        second.write_sample(0, 0, &7).unwrap();
+        assert_eq!(data[2], vec![7, 6]);
+    }
+
+    #[test]
+    fn split_frames() {
+        let mut data = [0_i32; 8];
+        let mut buffer = InterleavedSlice::new_mut(&mut data, 2, 4).unwrap();
+        let (mut first, mut second) = buffer.split_frames_at_mut(1).unwrap();
+        assert_eq!((first.frames(), second.frames()), (1, 3));
+        std::thread::scope(|scope| {
+            scope.spawn(|| first.fill_with(&1));
+            scope.spawn(|| second.fill_channel_with(1, &2));
+        });
+        assert!(buffer.split_frames_at_mut(5).is_none());
+        assert_eq!(data, [1, 1, 0, 2, 0, 2, 0, 2]);
+
+        let mut data = [0_i32; 8];
+        let mut buffer = SequentialSlice::new_mut(&mut data, 2, 4).unwrap();
+        let (mut first, mut second) = buffer.split_frames_at_mut(3).unwrap();
+        assert_eq!((first.channels(), first.frames()), (2, 3));
+        assert_eq!((second.channels(), second.frames()), (2, 1));
+        first.fill_frame_with(2, &1);
+        second.fill_with(&2);
+        // the halves can be split further
+        let (_, mut last) = first.split_frames_at_mut(2).unwrap();
+        last.write_sample(0, 0, &3).unwrap();
+        assert!(second.split_frames_at_mut(2).is_none());
+        assert_eq!(data, [0, 0, 3, 2, 0, 0, 1, 2]);
+
+        let mut data = vec![vec![1, 2], vec![3, 4], vec![5, 6]];
+        let mut buffer = InterleavedSliceOfVecs::new_mut(&mut data, 2, 3).unwrap();
+        let (first, mut second) = buffer.split_frames_at_mut(2).unwrap();
+        assert_eq!(first.read_sample(1, 1), Some(4));
+        second.write_sample(0, 0, &7).unwrap();
+        assert_eq!(data[2], vec![7, 6]);
+
+        let mut data = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        let mut buffer = SequentialSliceOfVecs::new_mut(&mut data, 2, 3).unwrap();
+        let (first, mut second) = buffer.split_frames_at_mut(1).unwrap();
+        assert_eq!(first.read_sample(1, 0), Some(4));
+        second.write_sample(1, 0, &7).unwrap();
+        assert_eq!(data[1], vec![4, 7, 6]);
+    }
+
     #[test]
     fn fill_buffer() {
         let mut data: [i32; 6] = [1; 6];
@@ -1091,6 +3316,131 @@ mod tests {
         assert_eq!(buffer.channel_rms(1), 0.0);
     }
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn to_and_from_vecs() {
+        let data = [1, 2, 3, 4, 5, 6];
+        let buffer = InterleavedSlice::new(&data, 2, 3).unwrap();
+        let vecs = buffer.to_vecs();
+        assert_eq!(vecs, vec![vec![1, 3, 5], vec![2, 4, 6]]);
+
+        let mut out = [0; 8];
+        let mut buffer = SequentialSlice::new_mut(&mut out, 2, 4).unwrap();
+        assert_eq!(buffer.copy_from_vecs(&vecs), 0);
+        assert_eq!(
+            buffer.copy_from_vecs(&[vec![7, 8, 9, 10, 11], vec![], vec![12]]),
+            0
+        );
+        assert_eq!(out, [7, 8, 9, 10, 2, 4, 6, 0]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn to_and_from_interleaved_vec() {
+        let data = [1, 2, 3, 4, 5, 6];
+        let sequential = SequentialSlice::new(&data, 2, 3).unwrap();
+        let interleaved = sequential.to_interleaved_vec();
+        assert_eq!(interleaved, vec![1, 4, 2, 5, 3, 6]);
+        let buffer = InterleavedSlice::new(&interleaved, 2, 3).unwrap();
+        assert_eq!(buffer.to_interleaved_vec(), interleaved);
+
+        let mut out = [0; 6];
+        let mut buffer = SequentialSlice::new_mut(&mut out, 2, 3).unwrap();
+        assert_eq!(buffer.copy_from_interleaved_slice(&[1, 2, 3, 4, 5]), 0);
+        assert_eq!(out, [1, 3, 0, 2, 4, 0]);
+        let mut out = [0; 6];
+        let mut buffer = InterleavedSlice::new_mut(&mut out, 2, 3).unwrap();
+        assert_eq!(
+            buffer.copy_from_interleaved_slice(&[1, 2, 3, 4, 5, 6, 7, 8]),
+            0
+        );
+        assert_eq!(out, [1, 2, 3, 4, 5, 6]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn sparse_sequential_frames() {
+        let mut data = vec![vec![1, 2, 3], Vec::new(), vec![4, 5, 6]];
+        let mask = vec![true, false, true];
+        let mut buffer = SparseSequentialSliceOfVecs::new_mut(&mut data, 3, 3, &mask).unwrap();
+        let mut values = [9; 3];
+        assert_eq!(buffer.write_from_frame_to_slice(1, 0, &mut values), 3);
+        assert_eq!(values, [2, 0, 5]);
+        assert_eq!(buffer.write_from_frame_to_slice(2, 1, &mut values), 2);
+        assert_eq!(values[..2], [0, 6]);
+
+        assert_eq!(buffer.write_from_slice_to_frame(0, 0, &[7, 8, 9]), (3, 0));
+        assert_eq!(buffer.fill_frame_with(2, &10), Some(()));
+        assert_eq!(buffer.fill_frame_with(3, &10), None);
+        assert_eq!(buffer.read_sample(1, 0), Some(0));
+        assert!(buffer.is_channel_active(0));
+        assert!(!buffer.is_channel_active(1));
+        assert!(!buffer.is_channel_active(3));
+        assert_eq!(data, vec![vec![7, 2, 10], Vec::new(), vec![9, 5, 10]]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn sparse_sequential_fill_and_copy() {
+        let mut data = vec![Vec::new(), vec![1, 2, 3]];
+        let mask = vec![false, true];
+        let mut buffer = SparseSequentialSliceOfVecs::new_mut(&mut data, 2, 3, &mask).unwrap();
+        buffer.fill_with(&4);
+        assert_eq!(buffer.fill_channel_with(0, &5), Some(()));
+        assert_eq!(buffer.fill_channel_with(2, &5), None);
+        assert_eq!(buffer.fill_frames_with(0, 2, &6), Some(2));
+        assert_eq!(buffer.fill_range_with(0..2, 2..3, &7), Some(1));
+        assert_eq!(buffer.fill_range_with(1..3, 0..1, &7), None);
+        assert_eq!(buffer.read_sample(1, 1), Some(6));
+        assert_eq!(buffer.read_sample(1, 2), Some(7));
+
+        let other_data = [1, 2, 3, 4, 5, 6];
+        let other = SequentialSlice::new(&other_data, 2, 3).unwrap();
+        assert_eq!(
+            buffer.write_from_other_to_channel(&other, 1, 0, 0, 0, 3),
+            Some(0)
+        );
+        assert_eq!(
+            buffer.write_from_other_to_channel(&other, 0, 1, 1, 2, 1),
+            Some(0)
+        );
+        assert_eq!(
+            buffer.write_from_other_to_channel(&other, 0, 1, 1, 2, 2),
+            None
+        );
+        assert_eq!(data, vec![Vec::new(), vec![6, 6, 2]]);
+    }
+
+    #[test]
+    fn numbers_as_bytes() {
+        let data = [1_i16, -2, 3, -4];
+        let buffer = InterleavedSlice::new_from_numbers(&data, 2, 2).unwrap();
+        assert_eq!(buffer.read_sample(1, 0), Some((-2_i16).to_ne_bytes()));
+        let buffer = SequentialSlice::new_from_numbers(&data, 2, 2).unwrap();
+        assert_eq!(buffer.read_sample(1, 0), Some(3_i16.to_ne_bytes()));
+        assert!(SequentialSlice::new_from_numbers(&data, 2, 3).is_err());
+
+        let mut data = [0_i16; 4];
+        let mut buffer = SequentialSlice::new_from_numbers_mut(&mut data, 2, 2).unwrap();
+        buffer.write_sample(1, 1, &1000_i16.to_ne_bytes()).unwrap();
+        let mut buffer = InterleavedSlice::new_from_numbers_mut(&mut data, 2, 2).unwrap();
+        buffer.write_sample(1, 0, &(-5_i16).to_ne_bytes()).unwrap();
+        assert_eq!(data, [0, -5, 0, 1000]);
+    }
+
+    #[cfg(target_endian = "little")]
+    #[test]
+    fn numbers_as_bytes_converted() {
+        use crate::adapter_to_float::ConvertBytes;
+        use crate::sample::I16LE;
+
+        let data = [16384_i16, -8192];
+        let buffer = InterleavedSlice::new_from_numbers(&data, 2, 1).unwrap();
+        let converter = ConvertBytes::<f32, I16LE, _>::new(&buffer as &dyn Adapter<[u8; 2]>);
+        assert_eq!(converter.read_sample(0, 0), Some(0.5));
+        assert_eq!(converter.read_sample(1, 0), Some(-0.25));
+    }
+
     use crate::tests::check_copy_within;
 
     #[test]
@@ -1114,10 +3464,323 @@ mod tests {
         check_copy_within(&mut adapter);
     }
 
+    #[test]
+    fn copy_within_sequential_with_offsets() {
+        let mut data = vec![0; 25];
+        let offsets = [3, 15];
+        let mut adapter = SequentialSliceWithOffsets::new_mut(&mut data, &offsets, 2, 10).unwrap();
+        check_copy_within(&mut adapter);
+    }
+
+    #[test]
+    fn sequential_with_offsets() {
+        let mut data = [0_i32; 9];
+        let offsets = [1, 5];
+        let mut buffer = SequentialSliceWithOffsets::new_mut(&mut data, &offsets, 2, 3).unwrap();
+        test_get(&mut buffer);
+        test_slice_channel(&mut buffer);
+        test_slice_frame(&mut buffer);
+        test_mut_slice_channel(&mut buffer);
+        test_mut_slice_frame(&mut buffer);
+        assert_eq!(data, [0, 8, 10, 3, 0, 4, 11, 6, 0]);
+    }
+
+    #[test]
+    fn grouped_interleaved() {
+        let mut data = [0_i32; 6];
+        let mut buffer = GroupedInterleavedSlice::new_mut(&mut data, 1, 2, 3).unwrap();
+        test_get(&mut buffer);
+        test_slice_channel(&mut buffer);
+        test_slice_frame(&mut buffer);
+        test_mut_slice_channel(&mut buffer);
+        test_mut_slice_frame(&mut buffer);
+        assert_eq!(data, [8, 10, 3, 4, 11, 6]);
+
+        // Two stereo streams with three frames each.
+        let data = [1_i32, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+        let buffer = GroupedInterleavedSlice::new(&data, 2, 4, 3).unwrap();
+        assert_eq!(buffer.group_size(), 2);
+        assert_eq!(buffer.read_sample(1, 1), Some(4));
+        assert_eq!(buffer.read_sample(2, 0), Some(7));
+        assert_eq!(buffer.read_sample(3, 2), Some(12));
+        let mut values = [0; 4];
+        assert_eq!(buffer.write_from_frame_to_slice(1, 0, &mut values), 4);
+        assert_eq!(values, [3, 4, 9, 10]);
+        let mut values = [0; 2];
+        assert_eq!(buffer.write_from_frame_to_slice(2, 1, &mut values), 2);
+        assert_eq!(values, [6, 11]);
+        assert_eq!(buffer[(2, 1)], 9);
+        assert_eq!(buffer.preferred_iteration(), IterationOrder::ChannelsOuter);
+    }
+
+    #[test]
+    fn copy_within_grouped_interleaved() {
+        let mut data = vec![0; 20];
+        let mut adapter = GroupedInterleavedSlice::new_mut(&mut data, 1, 2, 10).unwrap();
+        check_copy_within(&mut adapter);
+        let mut adapter = GroupedInterleavedSlice::new_mut(&mut data, 2, 2, 10).unwrap();
+        check_copy_within(&mut adapter);
+    }
+
+    #[test]
+    fn grouped_interleaved_size_check() {
+        let data = [0_i32; 12];
+        assert!(GroupedInterleavedSlice::new(&data, 2, 4, 3).is_ok());
+        assert!(matches!(
+            GroupedInterleavedSlice::new(&data, 3, 4, 3),
+            Err(SizeError::Group {
+                channels: 4,
+                group_size: 3
+            })
+        ));
+        assert!(matches!(
+            GroupedInterleavedSlice::new(&data, 0, 4, 3),
+            Err(SizeError::Group { .. })
+        ));
+        assert!(matches!(
+            GroupedInterleavedSlice::new(&data, 2, 4, 4),
+            Err(SizeError::Total { .. })
+        ));
+    }
+
+    #[test]
+    fn sequential_with_offsets_size_check() {
+        let data = [0_i32; 8];
+        assert!(SequentialSliceWithOffsets::new(&data, &[0, 4], 2, 4).is_ok());
+        assert!(matches!(
+            SequentialSliceWithOffsets::new(&data, &[0, 5], 2, 4),
+            Err(SizeError::Channel {
+                index: 1,
+                actual: 3,
+                required: 4
+            })
+        ));
+        assert!(matches!(
+            SequentialSliceWithOffsets::new(&data, &[0], 2, 4),
+            Err(SizeError::Offsets {
+                actual: 1,
+                required: 2
+            })
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn active_frames() {
+        let mut data = [1_i32, 2, 3, 4, 5, 6, 7, 8];
+        let mut buffer = SequentialSlice::new_mut(&mut data, 2, 2).unwrap();
+        assert_eq!(buffer.capacity_frames(), 4);
+        buffer.set_active_frames(4).unwrap();
+        assert_eq!(buffer.frames(), 4);
+        assert_eq!(buffer.read_sample(1, 0), Some(5));
+        assert!(buffer.set_active_frames(5).is_err());
+        assert_eq!(buffer.frames(), 4);
+        buffer.set_active_frames(3).unwrap();
+        assert_eq!(buffer.read_sample(1, 0), Some(4));
+
+        let data = vec![vec![1_i32, 2], vec![3, 4]];
+        let mut buffer = InterleavedSliceOfVecs::new(&data, 2, 1).unwrap();
+        assert_eq!(buffer.capacity_frames(), 2);
+        buffer.set_active_frames(2).unwrap();
+        assert_eq!(buffer.read_sample(1, 1), Some(4));
+
+        let data = vec![vec![1_i32, 2, 3], vec![4, 5]];
+        let buffer = SparseSequentialSliceOfVecs::new(&data, 2, 2, &[true, false]).unwrap();
+        assert_eq!(buffer.capacity_frames(), 3);
+
+        let data = [0_i32; 10];
+        let buffer = SequentialSliceWithOffsets::new(&data, &[2, 5], 2, 3).unwrap();
+        assert_eq!(buffer.capacity_frames(), 5);
+    }
+
+    #[test]
+    fn replace_buffer() {
+        let mut first = [1_i32, 2, 3, 4];
+        let mut second = [5_i32, 6, 7, 8, 9];
+        let mut too_short = [0_i32; 3];
+        {
+            let mut buffer = InterleavedSlice::new_mut(&mut first, 2, 2).unwrap();
+            buffer.write_sample(0, 0, &10).unwrap();
+            buffer.replace_buffer(&mut second).unwrap();
+            assert_eq!(buffer.read_sample(1, 1), Some(8));
+            buffer.write_sample(0, 0, &11).unwrap();
+            assert!(buffer.replace_buffer(&mut too_short).is_err());
+            assert_eq!(buffer.read_sample(0, 0), Some(11));
+        }
+        assert_eq!(first[0], 10);
+        assert_eq!(second[0], 11);
+
+        let first = vec![vec![1_i32, 2], vec![3, 4]];
+        let second = vec![vec![5_i32, 6], vec![7, 8]];
+        let mut buffer = SequentialSliceOfVecs::new(&first, 2, 2).unwrap();
+        buffer.replace_buffer(&second).unwrap();
+        assert_eq!(buffer.read_sample(1, 0), Some(7));
+        let too_short = vec![vec![1_i32, 2]];
+        assert!(buffer.replace_buffer(&too_short).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn swapped_dimensions() {
+        // 480 frames of stereo, stored as a vector per frame
+        let data = vec![vec![0.0_f32; 2]; 480];
+        assert!(matches!(
+            SequentialSliceOfVecs::new_checked_layout(&data, 2, 480),
+            Err(SizeError::SwappedDimensions {
+                channels: 2,
+                frames: 480
+            })
+        ));
+        assert!(InterleavedSliceOfVecs::new_checked_layout(&data, 2, 480).is_ok());
+
+        let mut data = vec![vec![0.0_f32; 480]; 2];
+        assert!(matches!(
+            InterleavedSliceOfVecs::new_mut_checked_layout(&mut data, 2, 480),
+            Err(SizeError::SwappedDimensions {
+                channels: 2,
+                frames: 480
+            })
+        ));
+        assert!(SequentialSliceOfVecs::new_mut_checked_layout(&mut data, 2, 480).is_ok());
+        // square buffers can't be checked
+        let data = vec![vec![0.0_f32; 2]; 2];
+        assert!(SequentialSliceOfVecs::new_checked_layout(&data, 2, 2).is_ok());
+    }
+
+    #[test]
+    fn tuple_of_slices() {
+        let mut left = [0_i32; 3];
+        let mut right = [0_i32; 4];
+        let mut buffer = ChannelSlicesAdapter::new((&mut left[..], &mut right[..]));
+        assert_eq!(buffer.channels(), 2);
+        assert_eq!(buffer.frames(), 3);
+        test_get(&mut buffer);
+        test_slice_channel(&mut buffer);
+        test_slice_frame(&mut buffer);
+        test_mut_slice_channel(&mut buffer);
+        test_mut_slice_frame(&mut buffer);
+        assert_eq!(left, [8, 10, 3]);
+        assert_eq!(right, [4, 11, 6, 0]);
+
+        let buffer = ChannelSlicesAdapter::new((&left[..], &right[..]));
+        assert_eq!(buffer.read_sample(1, 1), Some(11));
+        assert_eq!(buffer.read_sample(1, 3), None);
+    }
+
+    #[test]
+    fn array_of_slices() {
+        let mut ch0 = [0_u32; 10];
+        let mut ch1 = [0_u32; 10];
+        let mut ch2 = [0_u32; 10];
+        let mut buffer = ChannelSlicesAdapter::new([&mut ch0[..], &mut ch1[..], &mut ch2[..]]);
+        assert_eq!(buffer.channels(), 3);
+        check_copy_within(&mut buffer);
+        assert_eq!(buffer.into_inner()[1][9], 109);
+
+        let buffer = ChannelSlicesAdapter::new([&ch0[..], &ch1[..], &ch2[..]]);
+        assert_eq!(buffer.read_sample(2, 9), Some(209));
+    }
+
     #[test]
     fn copy_within_sequential_vecs() {
         let mut data = vec![vec![0; 10]; 2];
         let mut adapter = SequentialSliceOfVecs::new_mut(&mut data, 2, 10).unwrap();
         check_copy_within(&mut adapter);
     }
+
+    #[test]
+    fn index_slices() {
+        let mut data = [1, 2, 3, 4, 5, 6];
+        let mut buffer = InterleavedSlice::new_mut(&mut data, 2, 3).unwrap();
+        assert_eq!(buffer[(1, 0)], 2);
+        buffer[(0, 2)] += 10;
+        assert_eq!(data, [1, 2, 3, 4, 15, 6]);
+
+        let mut buffer = SequentialSlice::new_mut(&mut data, 2, 3).unwrap();
+        assert_eq!(buffer[(1, 0)], 4);
+        buffer[(0, 2)] = 0;
+        assert_eq!(data, [1, 2, 0, 4, 15, 6]);
+
+        let offsets = [3, 0];
+        let buffer = SequentialSliceWithOffsets::new(&data, &offsets, 2, 3).unwrap();
+        assert_eq!(buffer[(0, 1)], 15);
+        assert_eq!(buffer[(1, 2)], 0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn index_vecs() {
+        let mut data = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        let mut buffer = SequentialSliceOfVecs::new_mut(&mut data, 2, 3).unwrap();
+        assert_eq!(buffer[(1, 0)], 4);
+        buffer[(0, 2)] = 7;
+        let mut buffer = InterleavedSliceOfVecs::new_mut(&mut data, 3, 2).unwrap();
+        assert_eq!(buffer[(2, 0)], 7);
+        buffer[(0, 1)] = 8;
+        assert_eq!(data, [vec![1, 2, 7], vec![8, 5, 6]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds() {
+        let data = [1, 2, 3, 4, 5, 6];
+        let buffer = InterleavedSlice::new(&data, 2, 2).unwrap();
+        // The sample exists in the slice, but not in the buffer.
+        let _ = buffer[(2, 0)];
+    }
+
+    #[test]
+    fn sample_references() {
+        let mut data = [1, 2, 3, 4, 5, 6];
+        let mut buffer = SequentialSlice::new_mut(&mut data, 2, 2).unwrap();
+        assert_eq!(buffer.sample_ref(1, 1), Some(&4));
+        assert_eq!(buffer.sample_ref(2, 0), None);
+        assert_eq!(buffer.sample_ref(0, 2), None);
+        *buffer.sample_mut(1, 0).unwrap() = 10;
+        assert!(buffer.sample_mut(1, 2).is_none());
+        let first: *const i32 = buffer.sample_ref(0, 0).unwrap();
+        assert_eq!(first, data.as_ptr());
+        assert_eq!(data, [1, 2, 10, 4, 5, 6]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn sample_references_non_copy() {
+        let mut data = vec![vec![String::new(); 2]; 2];
+        let mut buffer = InterleavedSliceOfVecs::new_mut(&mut data, 2, 2).unwrap();
+        buffer.sample_mut(1, 0).unwrap().push_str("abc");
+        assert_eq!(buffer.sample_ref(1, 0).unwrap(), "abc");
+        assert!(buffer.sample_ref(0, 2).is_none());
+    }
+
+    fn sum_of_bytes<'a>(buffer: &dyn AdapterRef<'a, [u8; 3]>, channel: usize) -> u32 {
+        (0..buffer.frames())
+            .filter_map(|frame| buffer.read_sample_ref(channel, frame))
+            .map(|sample| sample.iter().map(|byte| *byte as u32).sum::<u32>())
+            .sum()
+    }
+
+    #[test]
+    fn borrowing_reads() {
+        let data = [[1, 2, 3], [4, 5, 6], [7, 8, 9], [10, 11, 12]];
+        let buffer = InterleavedSlice::new(&data, 2, 2).unwrap();
+        assert_eq!(buffer.read_sample_ref(1, 0), Some(&[4, 5, 6]));
+        assert_eq!(buffer.read_sample_ref(2, 0), None);
+        let first: *const [u8; 3] = buffer.read_sample_ref(0, 0).unwrap();
+        assert_eq!(first, data.as_ptr());
+        assert_eq!(sum_of_bytes(&buffer, 0), 6 + 24);
+
+        let buffer = SequentialSlice::new(&data, 2, 2).unwrap();
+        assert_eq!(sum_of_bytes(&buffer, 1), 24 + 33);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn borrowing_reads_mutable() {
+        let mut data = vec![vec![[0_u8; 3]; 2]; 2];
+        let mut buffer = SequentialSliceOfVecs::new_mut(&mut data, 2, 2).unwrap();
+        buffer.sample_mut(1, 1).unwrap()[2] = 5;
+        assert_eq!(buffer.read_sample_ref(1, 1), Some(&[0, 0, 5]));
+        assert_eq!(sum_of_bytes(&buffer, 1), 5);
+    }
 }
@@ -0,0 +1,326 @@
+//! # Dummy buffers for testing
+//!
+//! This module provides wrappers that are useful when testing code
+//! that uses buffers implementing [Adapter] or [AdapterMut].
+//!
+//! The `FailingAdapter` wrapper makes reads and writes fail in a controlled way,
+//! either after a given number of successful sample accesses,
+//! or for given ranges of frames.
+//! This makes it possible to deterministically exercise the code paths
+//! that handle partial reads and writes.
+//!
+//! ## Example
+//! Let writes fail after three samples.
+//! ```
+//! use audioadapter::direct::InterleavedSlice;
+//! use audioadapter::dummy::FailingAdapter;
+//! use audioadapter::AdapterMut;
+//!
+//! let mut data = vec![0_i32; 8];
+//! let mut buffer = InterleavedSlice::new_mut(&mut data, 2, 4).unwrap();
+//! let mut failing = FailingAdapter::new_mut(&mut buffer as &mut dyn AdapterMut<i32>);
+//! failing.set_fail_after(Some(3));
+//!
+//! assert_eq!(failing.write_from_slice_to_channel(0, 0, &[1, 2, 3, 4]), (3, 0));
+//! assert_eq!(failing.write_sample(1, 0, &5), None);
+//! ```
+
+use core::any::Any;
+use core::cell::Cell;
+use core::ops::Range;
+
+use crate::{Adapter, AdapterMut, IterationOrder};
+
+/// A wrapper for an [Adapter] or [AdapterMut] buffer,
+/// where reading and writing samples can be made to fail.
+///
+/// Each sample that is read or written successfully counts as one access.
+/// When a limit is set using [FailingAdapter::set_fail_after],
+/// all accesses fail once the limit has been reached.
+/// Accesses to frames in the ranges added with [FailingAdapter::add_failing_frames]
+/// always fail.
+///
+/// Reading or writing a failing sample returns `None`.
+/// Reading or writing slices stops at the first failing sample,
+/// and returns the number of samples processed until then.
+/// The `read_sample_unchecked` and `write_sample_unchecked` methods never fail,
+/// and are not counted.
+pub struct FailingAdapter<U> {
+    buf: U,
+    fail_after: Option<usize>,
+    failing_frames: Vec<Range<usize>>,
+    accesses: Cell<usize>,
+}
+
+impl<U> FailingAdapter<U> {
+    /// Set the number of successful sample accesses after which all accesses fail.
+    /// Use `None` to remove the limit.
+    pub fn set_fail_after(&mut self, count: Option<usize>) {
+        self.fail_after = count;
+    }
+
+    /// Make all accesses to the frames in the given range fail.
+    pub fn add_failing_frames(&mut self, range: Range<usize>) {
+        self.failing_frames.push(range);
+    }
+
+    /// Remove all failing frame ranges.
+    pub fn clear_failing_frames(&mut self) {
+        self.failing_frames.clear();
+    }
+
+    /// Get the number of successful sample accesses so far.
+    pub fn accesses(&self) -> usize {
+        self.accesses.get()
+    }
+
+    /// Reset the number of successful sample accesses to zero.
+    pub fn reset_accesses(&mut self) {
+        self.accesses.set(0);
+    }
+
+    /// Check if an access to the given frame should succeed,
+    /// and count it if it does.
+    fn try_access(&self, frame: usize) -> bool {
+        let accesses = self.accesses.get();
+        if self.fail_after.is_some_and(|limit| accesses >= limit)
+            || self
+                .failing_frames
+                .iter()
+                .any(|range| range.contains(&frame))
+        {
+            return false;
+        }
+        self.accesses.set(accesses + 1);
+        true
+    }
+}
+
+impl<'a, T> FailingAdapter<&'a dyn Adapter<'a, T>>
+where
+    T: Clone + 'a,
+{
+    /// Create a new wrapper for a buffer implementing the [Adapter] trait.
+    /// Initially no accesses fail.
+    pub fn new(buf: &'a dyn Adapter<'a, T>) -> Self {
+        Self {
+            buf,
+            fail_after: None,
+            failing_frames: Vec::new(),
+            accesses: Cell::new(0),
+        }
+    }
+}
+
+impl<'a, T> FailingAdapter<&'a mut dyn AdapterMut<'a, T>>
+where
+    T: Clone + 'a,
+{
+    /// Create a new wrapper for a mutable buffer implementing the [AdapterMut] trait.
+    /// Initially no accesses fail.
+    pub fn new_mut(buf: &'a mut dyn AdapterMut<'a, T>) -> Self {
+        Self {
+            buf,
+            fail_after: None,
+            failing_frames: Vec::new(),
+            accesses: Cell::new(0),
+        }
+    }
+}
+
+macro_rules! implement_failing_reads {
+    () => {
+        unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
+            self.buf.read_sample_unchecked(channel, frame)
+        }
+
+        fn read_sample(&self, channel: usize, frame: usize) -> Option<T> {
+            if channel >= self.buf.channels() || frame >= self.buf.frames() {
+                return None;
+            }
+            if !self.try_access(frame) {
+                return None;
+            }
+            Some(unsafe { self.buf.read_sample_unchecked(channel, frame) })
+        }
+
+        fn channels(&self) -> usize {
+            self.buf.channels()
+        }
+
+        fn frames(&self) -> usize {
+            self.buf.frames()
+        }
+
+        fn is_channel_active(&self, channel: usize) -> bool {
+            self.buf.is_channel_active(channel)
+        }
+
+        fn preferred_iteration(&self) -> IterationOrder {
+            self.buf.preferred_iteration()
+        }
+
+        fn metadata(&self) -> Option<&dyn Any> {
+            self.buf.metadata()
+        }
+
+        fn write_from_channel_to_slice(
+            &self,
+            channel: usize,
+            skip: usize,
+            slice: &mut [T],
+        ) -> usize {
+            if channel >= self.buf.channels() || skip >= self.buf.frames() {
+                return 0;
+            }
+            let frames_to_write = (self.buf.frames() - skip).min(slice.len());
+            for (n, item) in slice.iter_mut().enumerate().take(frames_to_write) {
+                if !self.try_access(skip + n) {
+                    return n;
+                }
+                *item = unsafe { self.buf.read_sample_unchecked(channel, skip + n) };
+            }
+            frames_to_write
+        }
+
+        fn write_from_frame_to_slice(&self, frame: usize, skip: usize, slice: &mut [T]) -> usize {
+            if frame >= self.buf.frames() || skip >= self.buf.channels() {
+                return 0;
+            }
+            let channels_to_write = (self.buf.channels() - skip).min(slice.len());
+            for (n, item) in slice.iter_mut().enumerate().take(channels_to_write) {
+                if !self.try_access(frame) {
+                    return n;
+                }
+                *item = unsafe { self.buf.read_sample_unchecked(skip + n, frame) };
+            }
+            channels_to_write
+        }
+    };
+}
+
+impl<'a, T> Adapter<'a, T> for FailingAdapter<&'a dyn Adapter<'a, T>>
+where
+    T: Clone + 'a,
+{
+    implement_failing_reads!();
+}
+
+impl<'a, T> Adapter<'a, T> for FailingAdapter<&'a mut dyn AdapterMut<'a, T>>
+where
+    T: Clone + 'a,
+{
+    implement_failing_reads!();
+}
+
+impl<'a, T> AdapterMut<'a, T> for FailingAdapter<&'a mut dyn AdapterMut<'a, T>>
+where
+    T: Clone + 'a,
+{
+    unsafe fn write_sample_unchecked(&mut self, channel: usize, frame: usize, value: &T) -> bool {
+        self.buf.write_sample_unchecked(channel, frame, value)
+    }
+
+    fn write_sample(&mut self, channel: usize, frame: usize, value: &T) -> Option<bool> {
+        if channel >= self.buf.channels() || frame >= self.buf.frames() {
+            return None;
+        }
+        if !self.try_access(frame) {
+            return None;
+        }
+        Some(unsafe { self.buf.write_sample_unchecked(channel, frame, value) })
+    }
+
+    fn write_from_slice_to_channel(
+        &mut self,
+        channel: usize,
+        skip: usize,
+        slice: &[T],
+    ) -> (usize, usize) {
+        if channel >= self.buf.channels() || skip >= self.buf.frames() {
+            return (0, 0);
+        }
+        let frames_to_read = (self.buf.frames() - skip).min(slice.len());
+        let mut nbr_clipped = 0;
+        for (n, item) in slice.iter().enumerate().take(frames_to_read) {
+            if !self.try_access(skip + n) {
+                return (n, nbr_clipped);
+            }
+            nbr_clipped +=
+                unsafe { self.buf.write_sample_unchecked(channel, skip + n, item) } as usize;
+        }
+        (frames_to_read, nbr_clipped)
+    }
+
+    fn write_from_slice_to_frame(
+        &mut self,
+        frame: usize,
+        skip: usize,
+        slice: &[T],
+    ) -> (usize, usize) {
+        if frame >= self.buf.frames() || skip >= self.buf.channels() {
+            return (0, 0);
+        }
+        let channels_to_read = (self.buf.channels() - skip).min(slice.len());
+        let mut nbr_clipped = 0;
+        for (n, item) in slice.iter().enumerate().take(channels_to_read) {
+            if !self.try_access(frame) {
+                return (n, nbr_clipped);
+            }
+            nbr_clipped +=
+                unsafe { self.buf.write_sample_unchecked(skip + n, frame, item) } as usize;
+        }
+        (channels_to_read, nbr_clipped)
+    }
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direct::SequentialSlice;
+
+    #[test]
+    fn fail_after_count() {
+        let data = [1_u32, 2, 3, 4, 5, 6];
+        let buffer = SequentialSlice::new(&data, 2, 3).unwrap();
+        let mut failing = FailingAdapter::new(&buffer as &dyn Adapter<u32>);
+        failing.set_fail_after(Some(4));
+        let mut values = [0_u32; 3];
+        assert_eq!(failing.write_from_channel_to_slice(0, 0, &mut values), 3);
+        assert_eq!(failing.read_sample(1, 0), Some(4));
+        assert_eq!(failing.read_sample(1, 1), None);
+        assert_eq!(failing.accesses(), 4);
+
+        failing.reset_accesses();
+        assert_eq!(failing.read_sample(1, 1), Some(5));
+        failing.set_fail_after(None);
+        assert_eq!(failing.write_from_frame_to_slice(2, 0, &mut values), 2);
+        assert_eq!(values[..2], [3, 6]);
+    }
+
+    #[test]
+    fn failing_frames() {
+        let mut data = [0_u32; 10];
+        let mut buffer = SequentialSlice::new_mut(&mut data, 2, 5).unwrap();
+        let mut failing = FailingAdapter::new_mut(&mut buffer as &mut dyn AdapterMut<u32>);
+        failing.add_failing_frames(2..4);
+        assert_eq!(
+            failing.write_from_slice_to_channel(0, 0, &[1, 2, 3, 4, 5]),
+            (2, 0)
+        );
+        assert_eq!(failing.write_from_slice_to_channel(1, 4, &[6]), (1, 0));
+        assert_eq!(failing.write_from_slice_to_frame(3, 0, &[7, 8]), (0, 0));
+        assert_eq!(failing.write_sample(0, 3, &9), None);
+        assert_eq!(failing.read_sample(0, 1), Some(2));
+
+        failing.clear_failing_frames();
+        assert_eq!(failing.write_sample(0, 3, &9), Some(false));
+        assert_eq!(data, [1, 2, 0, 9, 0, 0, 0, 0, 0, 6]);
+    }
+}
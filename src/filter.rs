@@ -0,0 +1,389 @@
+//! # Filtering of buffers
+//!
+//! This module provides a simple biquad filter,
+//! that filters the samples of a buffer implementing [AdapterMut] in place.
+//! The filter keeps separate state for each channel,
+//! so that a stream can be processed one buffer at a time.
+//!
+//! The coefficients are calculated according to the
+//! [Audio EQ Cookbook](https://www.w3.org/TR/audio-eq-cookbook/).
+//!
+//! ## Example
+//! Apply a lowpass filter to a stereo buffer.
+//! ```
+//! use audioadapter::direct::InterleavedSlice;
+//! use audioadapter::filter::{Biquad, BiquadCoefficients};
+//! use audioadapter::AdapterMut;
+//!
+//! let mut data = vec![0.0_f32; 2 * 1024];
+//! let mut buffer = InterleavedSlice::new_mut(&mut data, 2, 1024).unwrap();
+//!
+//! let coefficients = BiquadCoefficients::lowpass(1000.0, 0.707, 48000.0);
+//! let mut filter = Biquad::new(coefficients, 2);
+//! filter.process(&mut buffer as &mut dyn AdapterMut<f32>);
+//! ```
+
+use crate::AdapterMut;
+
+/// The number of samples processed at a time by [Biquad::process].
+const CHUNK_SIZE: usize = 256;
+
+/// Normalized coefficients of a biquad filter,
+/// where the `a0` coefficient has been divided out.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BiquadCoefficients {
+    pub b0: f64,
+    pub b1: f64,
+    pub b2: f64,
+    pub a1: f64,
+    pub a2: f64,
+}
+
+impl BiquadCoefficients {
+    /// Create a new set of coefficients, normalizing them by `a0`.
+    pub fn new(b0: f64, b1: f64, b2: f64, a0: f64, a1: f64, a2: f64) -> Self {
+        Self {
+            b0: b0 / a0,
+            b1: b1 / a0,
+            b2: b2 / a0,
+            a1: a1 / a0,
+            a2: a2 / a0,
+        }
+    }
+
+    /// Second order lowpass filter with cutoff frequency `freq` in Hz and quality factor `q`.
+    /// A `q` of `1/sqrt(2)` gives a Butterworth response.
+    pub fn lowpass(freq: f64, q: f64, sample_rate: f64) -> Self {
+        let (cos, alpha) = Self::omega_params(freq, q, sample_rate);
+        Self::new(
+            (1.0 - cos) / 2.0,
+            1.0 - cos,
+            (1.0 - cos) / 2.0,
+            1.0 + alpha,
+            -2.0 * cos,
+            1.0 - alpha,
+        )
+    }
+
+    /// Second order highpass filter with cutoff frequency `freq` in Hz and quality factor `q`.
+    /// A `q` of `1/sqrt(2)` gives a Butterworth response.
+    pub fn highpass(freq: f64, q: f64, sample_rate: f64) -> Self {
+        let (cos, alpha) = Self::omega_params(freq, q, sample_rate);
+        Self::new(
+            (1.0 + cos) / 2.0,
+            -(1.0 + cos),
+            (1.0 + cos) / 2.0,
+            1.0 + alpha,
+            -2.0 * cos,
+            1.0 - alpha,
+        )
+    }
+
+    /// Low shelf filter with center frequency `freq` in Hz,
+    /// gain `gain` in dB and quality factor `q`.
+    pub fn lowshelf(freq: f64, gain: f64, q: f64, sample_rate: f64) -> Self {
+        let (cos, alpha) = Self::omega_params(freq, q, sample_rate);
+        let a = 10.0_f64.powf(gain / 40.0);
+        let beta = 2.0 * a.sqrt() * alpha;
+        Self::new(
+            a * ((a + 1.0) - (a - 1.0) * cos + beta),
+            2.0 * a * ((a - 1.0) - (a + 1.0) * cos),
+            a * ((a + 1.0) - (a - 1.0) * cos - beta),
+            (a + 1.0) + (a - 1.0) * cos + beta,
+            -2.0 * ((a - 1.0) + (a + 1.0) * cos),
+            (a + 1.0) + (a - 1.0) * cos - beta,
+        )
+    }
+
+    /// High shelf filter with center frequency `freq` in Hz,
+    /// gain `gain` in dB and quality factor `q`.
+    pub fn highshelf(freq: f64, gain: f64, q: f64, sample_rate: f64) -> Self {
+        let (cos, alpha) = Self::omega_params(freq, q, sample_rate);
+        let a = 10.0_f64.powf(gain / 40.0);
+        let beta = 2.0 * a.sqrt() * alpha;
+        Self::new(
+            a * ((a + 1.0) + (a - 1.0) * cos + beta),
+            -2.0 * a * ((a - 1.0) + (a + 1.0) * cos),
+            a * ((a + 1.0) + (a - 1.0) * cos - beta),
+            (a + 1.0) - (a - 1.0) * cos + beta,
+            2.0 * ((a - 1.0) - (a + 1.0) * cos),
+            (a + 1.0) - (a - 1.0) * cos - beta,
+        )
+    }
+
+    /// Peaking filter with center frequency `freq` in Hz,
+    /// gain `gain` in dB and quality factor `q`.
+    pub fn peak(freq: f64, gain: f64, q: f64, sample_rate: f64) -> Self {
+        let (cos, alpha) = Self::omega_params(freq, q, sample_rate);
+        let a = 10.0_f64.powf(gain / 40.0);
+        Self::new(
+            1.0 + alpha * a,
+            -2.0 * cos,
+            1.0 - alpha * a,
+            1.0 + alpha / a,
+            -2.0 * cos,
+            1.0 - alpha / a,
+        )
+    }
+
+    /// First order highpass filter with cutoff frequency `freq` in Hz.
+    pub fn highpass_first_order(freq: f64, sample_rate: f64) -> Self {
+        let k = (core::f64::consts::PI * freq / sample_rate).tan();
+        Self::new(1.0, -1.0, 0.0, 1.0 + k, k - 1.0, 0.0)
+    }
+
+    /// Calculate the magnitude of the frequency response at the frequency `freq` in Hz.
+    pub fn magnitude_at(&self, freq: f64, sample_rate: f64) -> f64 {
+        let omega = 2.0 * core::f64::consts::PI * freq / sample_rate;
+        let (sin1, cos1) = omega.sin_cos();
+        let (sin2, cos2) = (2.0 * omega).sin_cos();
+        let num_re = self.b0 + self.b1 * cos1 + self.b2 * cos2;
+        let num_im = -self.b1 * sin1 - self.b2 * sin2;
+        let den_re = 1.0 + self.a1 * cos1 + self.a2 * cos2;
+        let den_im = -self.a1 * sin1 - self.a2 * sin2;
+        ((num_re * num_re + num_im * num_im) / (den_re * den_re + den_im * den_im)).sqrt()
+    }
+
+    fn omega_params(freq: f64, q: f64, sample_rate: f64) -> (f64, f64) {
+        let omega = 2.0 * core::f64::consts::PI * freq / sample_rate;
+        (omega.cos(), omega.sin() / (2.0 * q))
+    }
+
+    /// Filter a single sample, using and updating the given state.
+    /// The filter is implemented in transposed direct form II.
+    pub(crate) fn process_sample(&self, state: &mut [f64; 2], input: f64) -> f64 {
+        let output = self.b0 * input + state[0];
+        state[0] = self.b1 * input - self.a1 * output + state[1];
+        state[1] = self.b2 * input - self.a2 * output;
+        output
+    }
+}
+
+/// Frequency weighting curves for level measurements, as defined in IEC 61672-1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weighting {
+    /// A-weighting, approximating the sensitivity of the ear at low levels.
+    A,
+    /// C-weighting, approximating the sensitivity of the ear at high levels.
+    C,
+}
+
+impl Weighting {
+    /// Calculate the coefficients of a cascade of biquad sections implementing the weighting curve.
+    /// The gain is normalized to 0 dB at 1 kHz.
+    ///
+    /// The sections are obtained with the bilinear transform of the analog curve.
+    /// This gives a close match at low and mid frequencies,
+    /// while the response falls off faster than the analog curve towards the Nyquist frequency.
+    /// At 44.1 kHz and above, the deviation stays within the class 1 tolerances of IEC 61672-1.
+    pub fn sections(&self, sample_rate: f64) -> Vec<BiquadCoefficients> {
+        // The filter constructors prewarp the frequencies to place the poles exactly.
+        // Here the plain bilinear transform gives a better overall match,
+        // so the prewarping is undone by adjusting the pole frequencies.
+        let pole = |freq: f64| {
+            sample_rate / core::f64::consts::PI
+                * (core::f64::consts::PI * freq / sample_rate).atan()
+        };
+        let mut sections = vec![
+            BiquadCoefficients::highpass(pole(20.598997), 0.5, sample_rate),
+            BiquadCoefficients::lowpass(pole(12194.217), 0.5, sample_rate),
+        ];
+        if *self == Weighting::A {
+            sections.push(BiquadCoefficients::highpass_first_order(
+                pole(107.65265),
+                sample_rate,
+            ));
+            sections.push(BiquadCoefficients::highpass_first_order(
+                pole(737.86223),
+                sample_rate,
+            ));
+        }
+        let gain: f64 = sections
+            .iter()
+            .map(|section| section.magnitude_at(1000.0, sample_rate))
+            .product();
+        let first = &mut sections[0];
+        first.b0 /= gain;
+        first.b1 /= gain;
+        first.b2 /= gain;
+        sections
+    }
+}
+
+/// A biquad filter for processing buffers, with separate state for each channel.
+pub struct Biquad {
+    coefficients: BiquadCoefficients,
+    state: Vec<[f64; 2]>,
+}
+
+impl Biquad {
+    /// Create a new filter for the given number of channels.
+    pub fn new(coefficients: BiquadCoefficients, channels: usize) -> Self {
+        Self {
+            coefficients,
+            state: vec![[0.0; 2]; channels],
+        }
+    }
+
+    /// Replace the coefficients, keeping the state of the filter.
+    pub fn set_coefficients(&mut self, coefficients: BiquadCoefficients) {
+        self.coefficients = coefficients;
+    }
+
+    /// Get the current coefficients.
+    pub fn coefficients(&self) -> BiquadCoefficients {
+        self.coefficients
+    }
+
+    /// Reset the state of all channels.
+    pub fn reset(&mut self) {
+        for state in self.state.iter_mut() {
+            *state = [0.0; 2];
+        }
+    }
+
+    /// Filter all frames of every channel of the buffer in place.
+    /// If the buffer has more channels than the filter,
+    /// the extra channels are left unchanged.
+    /// Inactive channels are skipped, and their filter state is left as it is.
+    pub fn process(&mut self, buffer: &mut dyn AdapterMut<f32>) {
+        let channels = buffer.channels().min(self.state.len());
+        let frames = buffer.frames();
+        let mut chunk = [0.0_f32; CHUNK_SIZE];
+        for (channel, state) in self.state.iter_mut().enumerate().take(channels) {
+            if !buffer.is_channel_active(channel) {
+                continue;
+            }
+            let mut skip = 0;
+            while skip < frames {
+                let count = buffer.write_from_channel_to_slice(channel, skip, &mut chunk);
+                if count == 0 {
+                    break;
+                }
+                for value in chunk[..count].iter_mut() {
+                    *value = self.coefficients.process_sample(state, *value as f64) as f32;
+                }
+                buffer.write_from_slice_to_channel(channel, skip, &chunk[..count]);
+                skip += count;
+            }
+        }
+    }
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direct::SequentialSlice;
+    use crate::Adapter;
+
+    // Steady state gain of a filter for a sine at the given frequency.
+    fn gain_at(coefficients: BiquadCoefficients, freq: f64, sample_rate: f64) -> f64 {
+        let frames = 4800;
+        let mut data: Vec<f32> = (0..frames)
+            .map(|n| (2.0 * core::f64::consts::PI * freq * n as f64 / sample_rate).sin() as f32)
+            .collect();
+        let mut buffer = SequentialSlice::new_mut(&mut data, 1, frames).unwrap();
+        let mut filter = Biquad::new(coefficients, 1);
+        filter.process(&mut buffer);
+        let peak = (frames / 2..frames)
+            .map(|frame| buffer.read_sample(0, frame).unwrap().abs())
+            .fold(0.0, f32::max);
+        peak as f64
+    }
+
+    fn db(gain: f64) -> f64 {
+        20.0 * gain.log10()
+    }
+
+    #[test]
+    fn lowpass_and_highpass() {
+        let fs = 48000.0;
+        let lp = BiquadCoefficients::lowpass(1000.0, core::f64::consts::FRAC_1_SQRT_2, fs);
+        assert!((db(gain_at(lp, 100.0, fs))).abs() < 0.1);
+        assert!((db(gain_at(lp, 1000.0, fs)) + 3.0).abs() < 0.1);
+        assert!(db(gain_at(lp, 10000.0, fs)) < -35.0);
+        let hp = BiquadCoefficients::highpass(1000.0, core::f64::consts::FRAC_1_SQRT_2, fs);
+        assert!(db(gain_at(hp, 100.0, fs)) < -35.0);
+        assert!((db(gain_at(hp, 10000.0, fs))).abs() < 0.1);
+    }
+
+    #[test]
+    fn shelves_and_peak() {
+        let fs = 48000.0;
+        let ls = BiquadCoefficients::lowshelf(1000.0, 6.0, 0.707, fs);
+        assert!((db(gain_at(ls, 20.0, fs)) - 6.0).abs() < 0.1);
+        assert!((db(gain_at(ls, 15000.0, fs))).abs() < 0.1);
+        let hs = BiquadCoefficients::highshelf(1000.0, -6.0, 0.707, fs);
+        assert!((db(gain_at(hs, 50.0, fs))).abs() < 0.1);
+        assert!((db(gain_at(hs, 15000.0, fs)) + 6.0).abs() < 0.1);
+        let pk = BiquadCoefficients::peak(1000.0, 10.0, 1.0, fs);
+        assert!((db(gain_at(pk, 1000.0, fs)) - 10.0).abs() < 0.1);
+        assert!((db(gain_at(pk, 50.0, fs))).abs() < 0.1);
+    }
+
+    #[test]
+    fn weighting_curves() {
+        let fs = 48000.0;
+        let response = |weighting: Weighting, freq: f64| {
+            db(weighting
+                .sections(fs)
+                .iter()
+                .map(|section| section.magnitude_at(freq, fs))
+                .product())
+        };
+        // Reference values from IEC 61672-1.
+        // The tolerance grows at high frequencies where the bilinear transform deviates.
+        for (freq, a, c, tolerance) in [
+            (31.5, -39.4, -3.0, 0.2),
+            (100.0, -19.1, -0.3, 0.2),
+            (1000.0, 0.0, 0.0, 0.2),
+            (4000.0, 1.0, -0.8, 0.2),
+            (8000.0, -1.1, -3.0, 0.7),
+        ] {
+            assert!((response(Weighting::A, freq) - a).abs() < tolerance);
+            assert!((response(Weighting::C, freq) - c).abs() < tolerance);
+        }
+        let hp = BiquadCoefficients::highpass_first_order(1000.0, fs);
+        assert!((db(hp.magnitude_at(1000.0, fs)) + 3.01).abs() < 0.01);
+    }
+
+    #[test]
+    fn state_is_kept_between_buffers() {
+        let coefficients = BiquadCoefficients::lowpass(1000.0, 0.707, 48000.0);
+        let mut data = vec![1.0_f32; 2 * 600];
+        let mut buffer = SequentialSlice::new_mut(&mut data, 2, 600).unwrap();
+        let mut filter = Biquad::new(coefficients, 2);
+        filter.process(&mut buffer);
+
+        let mut first = vec![1.0_f32; 2 * 300];
+        let mut second = vec![1.0_f32; 2 * 300];
+        let mut filter = Biquad::new(coefficients, 2);
+        filter.process(&mut SequentialSlice::new_mut(&mut first, 2, 300).unwrap());
+        filter.process(&mut SequentialSlice::new_mut(&mut second, 2, 300).unwrap());
+        assert_eq!(data[..300], first[..300]);
+        assert_eq!(data[300..600], second[..300]);
+        assert_eq!(data[600..900], first[300..]);
+        assert_eq!(data[900..], second[300..]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn inactive_channels_are_skipped() {
+        use crate::direct::SparseSequentialSliceOfVecs;
+
+        let coefficients = BiquadCoefficients::lowpass(1000.0, 0.707, 48000.0);
+        let mut data = vec![vec![1.0_f32; 10], Vec::new()];
+        let mask = [true, false];
+        let mut buffer = SparseSequentialSliceOfVecs::new_mut(&mut data, 2, 10, &mask).unwrap();
+        let mut filter = Biquad::new(coefficients, 2);
+        filter.process(&mut buffer);
+        assert_ne!(buffer.read_sample(0, 9), Some(1.0));
+        assert_eq!(buffer.read_sample(1, 9), Some(0.0));
+    }
+}
@@ -0,0 +1,246 @@
+//! # Buffers with a fixed number of channels
+//!
+//! This module provides the [KnownChannels](crate::fixed::KnownChannels) marker trait,
+//! for buffers where the number of channels is known at compile time.
+//! Generic processing code can require this trait in addition to [Adapter],
+//! to select an implementation for a given number of channels,
+//! for example separate algorithms for mono and stereo,
+//! where the loops over channels can be unrolled by the compiler.
+//!
+//! The trait is implemented by the [ChannelSlicesAdapter](crate::direct::ChannelSlicesAdapter)
+//! wrapper for tuples and arrays of channel slices.
+//! Buffers where the number of channels is only known at runtime
+//! can be wrapped in a [FixedChannels](crate::fixed::FixedChannels),
+//! that checks the number of channels when it is created.
+//! This allows code that receives a `&dyn Adapter` to use the specialized
+//! implementation when possible, and fall back to a generic implementation otherwise.
+//!
+//! ## Example
+//! Use a specialized implementation for stereo buffers.
+//! ```
+//! use audioadapter::direct::InterleavedSlice;
+//! use audioadapter::fixed::{FixedChannels, KnownChannels};
+//! use audioadapter::Adapter;
+//!
+//! // The difference between the right and left channels, for stereo buffers only.
+//! fn balance_stereo<'a, A>(buffer: &A) -> f32
+//! where
+//!     A: Adapter<'a, f32> + KnownChannels<2>,
+//! {
+//!     (0..buffer.frames())
+//!         .map(|frame| unsafe {
+//!             buffer.read_sample_unchecked(1, frame) - buffer.read_sample_unchecked(0, frame)
+//!         })
+//!         .sum()
+//! }
+//!
+//! fn balance(buffer: &dyn Adapter<f32>) -> Option<f32> {
+//!     match FixedChannels::<_, 2>::new(buffer) {
+//!         Some(stereo) => Some(balance_stereo(&stereo)),
+//!         // Not a stereo buffer.
+//!         None => None,
+//!     }
+//! }
+//!
+//! let data = [0.0, 1.0, 0.5, 1.0];
+//! let stereo = InterleavedSlice::new(&data, 2, 2).unwrap();
+//! assert_eq!(balance(&stereo), Some(1.5));
+//! let mono = InterleavedSlice::new(&data, 1, 4).unwrap();
+//! assert_eq!(balance(&mono), None);
+//! ```
+
+use core::any::Any;
+
+use crate::{Adapter, AdapterMut, IterationOrder};
+
+/// A marker trait for buffers that always have `N` channels.
+/// Implementations must return `N` from [Adapter::channels].
+pub trait KnownChannels<const N: usize> {
+    /// The number of channels of the buffer.
+    const CHANNELS: usize = N;
+}
+
+/// A wrapper for an [Adapter] or [AdapterMut] buffer that has `N` channels,
+/// that implements [KnownChannels] for that number of channels.
+/// All calls are passed straight through to the wrapped buffer.
+pub struct FixedChannels<U, const N: usize> {
+    buf: U,
+}
+
+impl<'a, 'b, T, const N: usize> FixedChannels<&'b dyn Adapter<'a, T>, N>
+where
+    T: Clone + 'a,
+{
+    /// Create a new wrapper for a buffer implementing the [Adapter] trait.
+    /// Returns `None` if the buffer does not have `N` channels.
+    pub fn new(buf: &'b dyn Adapter<'a, T>) -> Option<Self> {
+        if buf.channels() != N {
+            return None;
+        }
+        Some(Self { buf })
+    }
+}
+
+impl<'a, 'b, T, const N: usize> FixedChannels<&'b mut dyn AdapterMut<'a, T>, N>
+where
+    T: Clone + 'a,
+{
+    /// Create a new wrapper for a mutable buffer implementing the [AdapterMut] trait.
+    /// Returns `None` if the buffer does not have `N` channels.
+    pub fn new_mut(buf: &'b mut dyn AdapterMut<'a, T>) -> Option<Self> {
+        if buf.channels() != N {
+            return None;
+        }
+        Some(Self { buf })
+    }
+}
+
+impl<U, const N: usize> KnownChannels<N> for FixedChannels<U, N> {}
+
+macro_rules! implement_fixed_reads {
+    () => {
+        unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
+            self.buf.read_sample_unchecked(channel, frame)
+        }
+
+        fn read_sample(&self, channel: usize, frame: usize) -> Option<T> {
+            self.buf.read_sample(channel, frame)
+        }
+
+        fn channels(&self) -> usize {
+            N
+        }
+
+        fn frames(&self) -> usize {
+            self.buf.frames()
+        }
+
+        fn is_channel_active(&self, channel: usize) -> bool {
+            self.buf.is_channel_active(channel)
+        }
+
+        fn preferred_iteration(&self) -> IterationOrder {
+            self.buf.preferred_iteration()
+        }
+
+        fn metadata(&self) -> Option<&dyn Any> {
+            self.buf.metadata()
+        }
+
+        fn write_from_channel_to_slice(
+            &self,
+            channel: usize,
+            skip: usize,
+            slice: &mut [T],
+        ) -> usize {
+            self.buf.write_from_channel_to_slice(channel, skip, slice)
+        }
+
+        fn write_from_frame_to_slice(&self, frame: usize, skip: usize, slice: &mut [T]) -> usize {
+            self.buf.write_from_frame_to_slice(frame, skip, slice)
+        }
+    };
+}
+
+impl<'a, T, const N: usize> Adapter<'a, T> for FixedChannels<&dyn Adapter<'a, T>, N>
+where
+    T: Clone + 'a,
+{
+    implement_fixed_reads!();
+}
+
+impl<'a, T, const N: usize> Adapter<'a, T> for FixedChannels<&mut dyn AdapterMut<'a, T>, N>
+where
+    T: Clone + 'a,
+{
+    implement_fixed_reads!();
+}
+
+impl<'a, T, const N: usize> AdapterMut<'a, T> for FixedChannels<&mut dyn AdapterMut<'a, T>, N>
+where
+    T: Clone + 'a,
+{
+    unsafe fn write_sample_unchecked(&mut self, channel: usize, frame: usize, value: &T) -> bool {
+        self.buf.write_sample_unchecked(channel, frame, value)
+    }
+
+    fn write_sample(&mut self, channel: usize, frame: usize, value: &T) -> Option<bool> {
+        self.buf.write_sample(channel, frame, value)
+    }
+
+    fn write_from_slice_to_channel(
+        &mut self,
+        channel: usize,
+        skip: usize,
+        slice: &[T],
+    ) -> (usize, usize) {
+        self.buf.write_from_slice_to_channel(channel, skip, slice)
+    }
+
+    fn write_from_slice_to_frame(
+        &mut self,
+        frame: usize,
+        skip: usize,
+        slice: &[T],
+    ) -> (usize, usize) {
+        self.buf.write_from_slice_to_frame(frame, skip, slice)
+    }
+
+    fn copy_frames_within(&mut self, src: usize, dest: usize, count: usize) -> Option<usize> {
+        self.buf.copy_frames_within(src, dest, count)
+    }
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direct::{ChannelSlicesAdapter, SequentialSlice};
+    use crate::tests::check_copy_within;
+
+    fn channels_of<const N: usize, A: KnownChannels<N>>(_buffer: &A) -> usize {
+        A::CHANNELS
+    }
+
+    #[test]
+    fn wrap_dynamic() {
+        let mut data = [0_u32; 20];
+        let mut buffer = SequentialSlice::new_mut(&mut data, 2, 10).unwrap();
+        assert!(FixedChannels::<_, 1>::new_mut(&mut buffer as &mut dyn AdapterMut<u32>).is_none());
+        let mut fixed =
+            FixedChannels::<_, 2>::new_mut(&mut buffer as &mut dyn AdapterMut<u32>).unwrap();
+        assert_eq!(channels_of(&fixed), 2);
+        check_copy_within(&mut fixed);
+        assert_eq!(fixed.write_sample(1, 9, &5), Some(false));
+        assert_eq!(fixed.read_sample(1, 9), Some(5));
+        assert_eq!(data[19], 5);
+
+        let buffer = SequentialSlice::new(&data, 4, 5).unwrap();
+        let fixed = FixedChannels::<_, 4>::new(&buffer as &dyn Adapter<u32>).unwrap();
+        assert_eq!(fixed.channels(), 4);
+        assert_eq!(fixed.read_sample(3, 4), Some(5));
+    }
+
+    #[test]
+    fn channel_slices() {
+        let left = [0.0_f32; 4];
+        let right = [0.0_f32; 4];
+        assert_eq!(
+            channels_of(&ChannelSlicesAdapter::new((&left[..], &right[..]))),
+            2
+        );
+        assert_eq!(
+            channels_of(&ChannelSlicesAdapter::new([
+                &left[..],
+                &right[..],
+                &left[..]
+            ])),
+            3
+        );
+    }
+}
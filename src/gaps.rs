@@ -0,0 +1,293 @@
+//! # Gap tracking wrapper
+//!
+//! This module provides a wrapper for buffers implementing [AdapterMut],
+//! that keeps track of which frames contain valid data and which are missing.
+//! This is useful for example in network audio receivers,
+//! where packets may be lost or arrive out of order,
+//! and the missing parts need to be concealed before playback.
+//! Keeping the bookkeeping together with the buffer
+//! avoids that the two get out of sync.
+//!
+//! ## Example
+//! Wrap a buffer, write two packets and find the missing frames.
+//! ```
+//! use audioadapter::direct::InterleavedSlice;
+//! use audioadapter::gaps::GappyAdapter;
+//! use audioadapter::{Adapter, AdapterMut};
+//!
+//! let mut data = vec![0.0_f32; 12];
+//! let mut buffer = InterleavedSlice::new_mut(&mut data, 2, 6).unwrap();
+//! let mut gappy = GappyAdapter::new(&mut buffer as &mut dyn AdapterMut<f32>);
+//!
+//! gappy.write_from_slice_to_frame(0, 0, &[0.1, 0.2]);
+//! gappy.write_from_slice_to_frame(1, 0, &[0.3, 0.4]);
+//! gappy.mark_valid(0..2);
+//! gappy.write_from_slice_to_frame(4, 0, &[0.5, 0.6]);
+//! gappy.mark_valid(4..5);
+//!
+//! assert_eq!(gappy.read_sample(1, 1), Some(0.4));
+//! assert_eq!(gappy.read_sample(1, 2), None);
+//! assert_eq!(gappy.missing_ranges(), vec![2..4, 5..6]);
+//! ```
+
+use core::any::Any;
+use core::ops::Range;
+
+use crate::{Adapter, AdapterMut, IterationOrder};
+
+/// A wrapper for an [AdapterMut] buffer that tracks
+/// which frame ranges contain valid data.
+/// All frames start out as missing, and are marked as valid
+/// by calling [GappyAdapter::mark_valid].
+/// Writing samples does not change the state of the frames.
+///
+/// Reading a sample in a missing frame returns `None`.
+/// Reading a channel into a slice stops at the first missing frame,
+/// and reading a missing frame into a slice reads nothing.
+/// The `read_sample_unchecked` method does not check the state of the frame,
+/// and returns the value stored in the wrapped buffer.
+pub struct GappyAdapter<U> {
+    buf: U,
+    valid: Vec<Range<usize>>,
+}
+
+impl<'a, T> GappyAdapter<&'a mut dyn AdapterMut<'a, T>>
+where
+    T: Clone + 'a,
+{
+    /// Create a new wrapper for a mutable buffer implementing the [AdapterMut] trait.
+    /// All frames are initially marked as missing.
+    pub fn new(buf: &'a mut dyn AdapterMut<'a, T>) -> Self {
+        Self {
+            buf,
+            valid: Vec::new(),
+        }
+    }
+
+    /// Mark a range of frames as valid.
+    /// The range is limited to the frames of the buffer.
+    pub fn mark_valid(&mut self, range: Range<usize>) {
+        let range = self.clamp(range);
+        if range.is_empty() {
+            return;
+        }
+        // Merge with all existing ranges that overlap or touch the new one.
+        let first = self.valid.partition_point(|r| r.end < range.start);
+        let last = self.valid.partition_point(|r| r.start <= range.end);
+        let mut merged = range;
+        if first < last {
+            merged.start = merged.start.min(self.valid[first].start);
+            merged.end = merged.end.max(self.valid[last - 1].end);
+        }
+        self.valid.splice(first..last, core::iter::once(merged));
+    }
+
+    /// Mark a range of frames as missing.
+    /// The range is limited to the frames of the buffer.
+    pub fn mark_missing(&mut self, range: Range<usize>) {
+        let range = self.clamp(range);
+        if range.is_empty() {
+            return;
+        }
+        let first = self.valid.partition_point(|r| r.end <= range.start);
+        let last = self.valid.partition_point(|r| r.start < range.end);
+        let mut remaining = Vec::with_capacity(2);
+        if first < last {
+            if self.valid[first].start < range.start {
+                remaining.push(self.valid[first].start..range.start);
+            }
+            if self.valid[last - 1].end > range.end {
+                remaining.push(range.end..self.valid[last - 1].end);
+            }
+        }
+        self.valid.splice(first..last, remaining);
+    }
+
+    /// Mark all frames as missing.
+    pub fn clear(&mut self) {
+        self.valid.clear();
+    }
+
+    /// Check if a frame contains valid data.
+    /// Returns `false` for frames outside the buffer.
+    pub fn is_valid(&self, frame: usize) -> bool {
+        let idx = self.valid.partition_point(|r| r.end <= frame);
+        self.valid
+            .get(idx)
+            .map(|r| r.contains(&frame))
+            .unwrap_or(false)
+    }
+
+    /// Get the ranges of frames that contain valid data, in increasing order.
+    pub fn valid_ranges(&self) -> Vec<Range<usize>> {
+        self.valid.clone()
+    }
+
+    /// Get the ranges of frames that are missing, in increasing order.
+    pub fn missing_ranges(&self) -> Vec<Range<usize>> {
+        let mut missing = Vec::new();
+        let mut start = 0;
+        for range in self.valid.iter() {
+            if range.start > start {
+                missing.push(start..range.start);
+            }
+            start = range.end;
+        }
+        if start < self.buf.frames() {
+            missing.push(start..self.buf.frames());
+        }
+        missing
+    }
+
+    /// Get the number of consecutive valid frames, starting at `frame`.
+    fn valid_frames_from(&self, frame: usize) -> usize {
+        let idx = self.valid.partition_point(|r| r.end <= frame);
+        match self.valid.get(idx) {
+            Some(r) if r.contains(&frame) => r.end - frame,
+            _ => 0,
+        }
+    }
+
+    fn clamp(&self, range: Range<usize>) -> Range<usize> {
+        let frames = self.buf.frames();
+        range.start.min(frames)..range.end.min(frames)
+    }
+}
+
+impl<'a, T> Adapter<'a, T> for GappyAdapter<&'a mut dyn AdapterMut<'a, T>>
+where
+    T: Clone + 'a,
+{
+    unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
+        self.buf.read_sample_unchecked(channel, frame)
+    }
+
+    fn read_sample(&self, channel: usize, frame: usize) -> Option<T> {
+        if !self.is_valid(frame) {
+            return None;
+        }
+        self.buf.read_sample(channel, frame)
+    }
+
+    fn channels(&self) -> usize {
+        self.buf.channels()
+    }
+
+    fn frames(&self) -> usize {
+        self.buf.frames()
+    }
+
+    fn is_channel_active(&self, channel: usize) -> bool {
+        self.buf.is_channel_active(channel)
+    }
+
+    fn preferred_iteration(&self) -> IterationOrder {
+        self.buf.preferred_iteration()
+    }
+
+    fn metadata(&self) -> Option<&dyn Any> {
+        self.buf.metadata()
+    }
+
+    fn write_from_channel_to_slice(&self, channel: usize, skip: usize, slice: &mut [T]) -> usize {
+        let valid_frames = self.valid_frames_from(skip);
+        let len = slice.len().min(valid_frames);
+        self.buf
+            .write_from_channel_to_slice(channel, skip, &mut slice[..len])
+    }
+
+    fn write_from_frame_to_slice(&self, frame: usize, skip: usize, slice: &mut [T]) -> usize {
+        if !self.is_valid(frame) {
+            return 0;
+        }
+        self.buf.write_from_frame_to_slice(frame, skip, slice)
+    }
+}
+
+impl<'a, T> AdapterMut<'a, T> for GappyAdapter<&'a mut dyn AdapterMut<'a, T>>
+where
+    T: Clone + 'a,
+{
+    unsafe fn write_sample_unchecked(&mut self, channel: usize, frame: usize, value: &T) -> bool {
+        self.buf.write_sample_unchecked(channel, frame, value)
+    }
+
+    fn write_from_slice_to_channel(
+        &mut self,
+        channel: usize,
+        skip: usize,
+        slice: &[T],
+    ) -> (usize, usize) {
+        self.buf.write_from_slice_to_channel(channel, skip, slice)
+    }
+
+    fn write_from_slice_to_frame(
+        &mut self,
+        frame: usize,
+        skip: usize,
+        slice: &[T],
+    ) -> (usize, usize) {
+        self.buf.write_from_slice_to_frame(frame, skip, slice)
+    }
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direct::SequentialSlice;
+
+    #[test]
+    fn mark_ranges() {
+        let mut data = [0_i32; 10];
+        let mut buffer = SequentialSlice::new_mut(&mut data, 1, 10).unwrap();
+        let mut gappy = GappyAdapter::new(&mut buffer as &mut dyn AdapterMut<i32>);
+        assert_eq!(gappy.missing_ranges(), vec![0..10]);
+        gappy.mark_valid(2..4);
+        gappy.mark_valid(6..8);
+        assert_eq!(gappy.valid_ranges(), vec![2..4, 6..8]);
+        // touching ranges are merged
+        gappy.mark_valid(4..5);
+        assert_eq!(gappy.valid_ranges(), vec![2..5, 6..8]);
+        // overlapping ranges are merged
+        gappy.mark_valid(3..7);
+        assert_eq!(gappy.valid_ranges(), vec![2..8]);
+        // ranges are clamped to the buffer
+        gappy.mark_valid(9..20);
+        assert_eq!(gappy.missing_ranges(), vec![0..2, 8..9]);
+        gappy.mark_missing(4..6);
+        assert_eq!(gappy.valid_ranges(), vec![2..4, 6..8, 9..10]);
+        gappy.mark_missing(0..7);
+        assert_eq!(gappy.valid_ranges(), vec![7..8, 9..10]);
+        assert!(gappy.is_valid(7));
+        assert!(!gappy.is_valid(8));
+        assert!(!gappy.is_valid(10));
+        gappy.clear();
+        assert_eq!(gappy.missing_ranges(), vec![0..10]);
+    }
+
+    #[test]
+    fn read_with_gaps() {
+        let mut data = [1_i32, 2, 3, 4, 5, 6, 7, 8];
+        let mut buffer = SequentialSlice::new_mut(&mut data, 2, 4).unwrap();
+        let mut gappy = GappyAdapter::new(&mut buffer as &mut dyn AdapterMut<i32>);
+        gappy.mark_valid(0..2);
+        gappy.mark_valid(3..4);
+        assert_eq!(gappy.read_sample(1, 1), Some(6));
+        assert_eq!(gappy.read_sample(1, 2), None);
+        assert_eq!(gappy.read_sample(1, 3), Some(8));
+
+        let mut slice = [0; 4];
+        assert_eq!(gappy.write_from_channel_to_slice(0, 0, &mut slice), 2);
+        assert_eq!(slice, [1, 2, 0, 0]);
+        assert_eq!(gappy.write_from_channel_to_slice(0, 2, &mut slice), 0);
+        assert_eq!(gappy.write_from_frame_to_slice(2, 0, &mut slice), 0);
+        assert_eq!(gappy.write_from_frame_to_slice(3, 0, &mut slice), 2);
+        assert_eq!(slice[..2], [4, 8]);
+    }
+}
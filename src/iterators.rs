@@ -1,3 +1,5 @@
+use core::iter::Enumerate;
+
 use crate::Adapter;
 
 // -------------------- Iterators returning immutable samples --------------------
@@ -8,6 +10,13 @@ pub trait AdapterIterators<'a, T: 'a> {
     /// Get an iterator that yields the sample value of the specified channel.
     fn iter_channel(&self, channel: usize) -> Option<ChannelSamples<'a, '_, T>>;
 
+    /// Get an iterator that yields `(frame, value)` pairs
+    /// for the samples of the specified channel.
+    fn iter_channel_enumerated(
+        &self,
+        channel: usize,
+    ) -> Option<Enumerate<ChannelSamples<'a, '_, T>>>;
+
     /// Get an iterator that yields iterators for the channels.
     fn iter_channels(&self) -> Channels<'a, '_, T>;
 
@@ -16,6 +25,10 @@ pub trait AdapterIterators<'a, T: 'a> {
 
     /// Get an iterator that yields iterators for the frames.
     fn iter_frames(&self) -> Frames<'a, '_, T>;
+
+    /// Get an iterator that yields `(frame, samples)` pairs,
+    /// where `samples` is an iterator for the samples of the frame.
+    fn iter_frames_enumerated(&self) -> Enumerate<Frames<'a, '_, T>>;
 }
 
 impl<'a, T, U> AdapterIterators<'a, T> for U
@@ -27,6 +40,13 @@ where
         ChannelSamples::new(self, channel)
     }
 
+    fn iter_channel_enumerated(
+        &self,
+        channel: usize,
+    ) -> Option<Enumerate<ChannelSamples<'a, '_, T>>> {
+        self.iter_channel(channel).map(Iterator::enumerate)
+    }
+
     fn iter_channels(&self) -> Channels<'a, '_, T> {
         Channels::new(self)
     }
@@ -38,6 +58,10 @@ where
     fn iter_frames(&self) -> Frames<'a, '_, T> {
         Frames::new(self)
     }
+
+    fn iter_frames_enumerated(&self) -> Enumerate<Frames<'a, '_, T>> {
+        self.iter_frames().enumerate()
+    }
 }
 
 /// An iterator that yields the sample values of a channel.
@@ -239,4 +263,18 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn enumerated() {
+        let data = [1_i32, 4, 2, 5, 3, 6];
+        let buffer = InterleavedSlice::new(&data, 2, 3).unwrap();
+        let pairs: Vec<(usize, i32)> = buffer.iter_channel_enumerated(1).unwrap().collect();
+        assert_eq!(pairs, [(0, 4), (1, 5), (2, 6)]);
+        assert!(buffer.iter_channel_enumerated(2).is_none());
+        for (frame, samples) in buffer.iter_frames_enumerated() {
+            let values: Vec<i32> = samples.collect();
+            assert_eq!(values, [frame as i32 + 1, frame as i32 + 4]);
+        }
+        assert_eq!(buffer.iter_frames_enumerated().count(), 3);
+    }
 }
@@ -0,0 +1,331 @@
+//! Shared indexing and bounds logic for the converting wrappers.
+//!
+//! The wrappers in `number_to_float` describe their slices with a [Layout],
+//! while the wrappers of other buffers in `adapter_to_float`
+//! use [transfer_length] for the bounds of their slice transfers.
+//! The functions here do not depend on the sample type,
+//! and are therefore only compiled once regardless of how many
+//! sample formats are used.
+//! Only the per-sample conversion in the wrappers remains generic.
+//!
+//! By default the functions are marked `#[inline]`,
+//! giving the compiler the option to inline them for best performance.
+//! When the `min-size` feature is enabled they are instead marked `#[inline(never)]`,
+//! to minimize the size of the generated code.
+
+use crate::IterationOrder;
+
+/// Apply the inlining strategy selected by the `min-size` feature.
+macro_rules! core_fn {
+    ($(#[$meta:meta])* $vis:vis fn $($rest:tt)*) => {
+        $(#[$meta])*
+        #[cfg_attr(feature = "min-size", inline(never))]
+        #[cfg_attr(not(feature = "min-size"), inline)]
+        $vis fn $($rest)*
+    };
+}
+
+core_fn! {
+    /// The number of values to transfer between a slice of length `len`
+    /// and a channel or frame of a buffer, starting at position `skip`.
+    /// For a channel, `index` is the channel, `count` the number of channels,
+    /// and `length` the number of frames, and the other way around for a frame.
+    /// Returns `None` if `index` or `skip` is out of bounds.
+    /// This is used by the converting wrappers of other buffers,
+    /// which do not have a [Layout] of their own.
+    pub(crate) fn transfer_length(
+        index: usize,
+        count: usize,
+        skip: usize,
+        length: usize,
+        len: usize,
+    ) -> Option<usize> {
+        if index >= count || skip >= length {
+            return None;
+        }
+        Some(len.min(length - skip))
+    }
+}
+
+/// The dimensions and sample order of a buffer stored in a single slice.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Layout {
+    pub(crate) channels: usize,
+    pub(crate) frames: usize,
+    channel_stride: usize,
+    pub(crate) frame_stride: usize,
+}
+
+/// A range of samples in a slice, given as the index of the first sample,
+/// the distance between consecutive samples, and the number of samples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Span {
+    pub(crate) start: usize,
+    pub(crate) stride: usize,
+    pub(crate) count: usize,
+}
+
+impl Span {
+    /// The slice index of the n:th sample in the span.
+    #[inline]
+    pub(crate) fn index(&self, n: usize) -> usize {
+        self.start + n * self.stride
+    }
+}
+
+impl Layout {
+    core_fn! {
+        /// Samples stored in _interleaved_ order.
+        pub(crate) fn interleaved(channels: usize, frames: usize) -> Self {
+            Self::interleaved_with_stride(channels, frames, channels)
+        }
+    }
+
+    core_fn! {
+        /// Samples stored in _interleaved_ order,
+        /// with frames starting `frame_stride` samples apart.
+        /// Any samples after the last channel of each frame are padding.
+        pub(crate) fn interleaved_with_stride(channels: usize, frames: usize, frame_stride: usize) -> Self {
+            Self {
+                channels,
+                frames,
+                channel_stride: 1,
+                frame_stride,
+            }
+        }
+    }
+
+    core_fn! {
+        /// Samples stored in _sequential_ order.
+        pub(crate) fn sequential(channels: usize, frames: usize) -> Self {
+            Self::sequential_with_stride(channels, frames, frames)
+        }
+    }
+
+    core_fn! {
+        /// Samples stored in _sequential_ order,
+        /// with channels starting `channel_stride` samples apart.
+        /// Any samples after the last frame of each channel are padding.
+        pub(crate) fn sequential_with_stride(channels: usize, frames: usize, channel_stride: usize) -> Self {
+            Self {
+                channels,
+                frames,
+                channel_stride,
+                frame_stride: 1,
+            }
+        }
+    }
+
+    core_fn! {
+        /// The iteration order that accesses the samples sequentially.
+        pub(crate) fn preferred_iteration(&self) -> IterationOrder {
+            IterationOrder::from_strides(self.channel_stride, self.frame_stride)
+        }
+    }
+
+    core_fn! {
+        /// The slice index of the sample at the given channel and frame.
+        pub(crate) fn index(&self, channel: usize, frame: usize) -> usize {
+            channel * self.channel_stride + frame * self.frame_stride
+        }
+    }
+
+    core_fn! {
+        /// The span of samples in a channel to transfer to or from a slice of length `len`,
+        /// starting at frame `skip`.
+        /// Returns `None` if the channel or `skip` is out of bounds.
+        pub(crate) fn channel_span(&self, channel: usize, skip: usize, len: usize) -> Option<Span> {
+            if channel >= self.channels || skip >= self.frames {
+                return None;
+            }
+            Some(Span {
+                start: self.index(channel, skip),
+                stride: self.frame_stride,
+                count: len.min(self.frames - skip),
+            })
+        }
+    }
+
+    core_fn! {
+        /// The span of samples in a frame to transfer to or from a slice of length `len`,
+        /// starting at channel `skip`.
+        /// Returns `None` if the frame or `skip` is out of bounds.
+        pub(crate) fn frame_span(&self, frame: usize, skip: usize, len: usize) -> Option<Span> {
+            if frame >= self.frames || skip >= self.channels {
+                return None;
+            }
+            Some(Span {
+                start: self.index(skip, frame),
+                stride: self.channel_stride,
+                count: len.min(self.channels - skip),
+            })
+        }
+    }
+
+    core_fn! {
+        /// Check that the source and destination ranges of a copy
+        /// of `count` frames are within the buffer.
+        pub(crate) fn copy_is_valid(&self, src: usize, dest: usize, count: usize) -> bool {
+            src + count <= self.frames && dest + count <= self.frames
+        }
+    }
+
+    core_fn! {
+        /// The contiguous blocks of samples to move when copying `count` frames
+        /// from `src` to `dest`.
+        /// The blocks are returned as tuples of `(src, dest, count)`,
+        /// given as indices into the underlying slice.
+        /// Padding samples between the frames of an interleaved layout are not included,
+        /// and the blocks are ordered so that copying them one by one
+        /// gives the correct result also when the ranges overlap.
+        pub(crate) fn copy_blocks(&self, src: usize, dest: usize, count: usize) -> CopyBlocks {
+            if self.frame_stride == 1 {
+                // Sequential, one block per channel.
+                CopyBlocks::new(
+                    self.index(0, src),
+                    self.index(0, dest),
+                    count,
+                    self.channel_stride,
+                    self.channels,
+                    false,
+                )
+            } else if self.frame_stride == self.channels {
+                // Interleaved without padding, all frames form one block.
+                CopyBlocks::new(
+                    self.index(0, src),
+                    self.index(0, dest),
+                    count * self.channels,
+                    0,
+                    1,
+                    false,
+                )
+            } else {
+                // Interleaved with padding, one block per frame.
+                // Copy the last frame first when moving frames forward,
+                // so that no frame is overwritten before it has been copied.
+                CopyBlocks::new(
+                    self.index(0, src),
+                    self.index(0, dest),
+                    self.channels,
+                    self.frame_stride,
+                    count,
+                    dest > src,
+                )
+            }
+        }
+    }
+}
+
+/// Iterator over the blocks of a copy within a buffer.
+pub(crate) struct CopyBlocks {
+    src: usize,
+    dest: usize,
+    count: usize,
+    step: usize,
+    blocks: usize,
+    next: usize,
+    backwards: bool,
+}
+
+impl CopyBlocks {
+    fn new(
+        src: usize,
+        dest: usize,
+        count: usize,
+        step: usize,
+        blocks: usize,
+        backwards: bool,
+    ) -> Self {
+        Self {
+            src,
+            dest,
+            count,
+            step,
+            blocks,
+            next: 0,
+            backwards,
+        }
+    }
+}
+
+impl Iterator for CopyBlocks {
+    type Item = (usize, usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next == self.blocks {
+            return None;
+        }
+        let block = if self.backwards {
+            self.blocks - 1 - self.next
+        } else {
+            self.next
+        };
+        self.next += 1;
+        let offset = block * self.step;
+        Some((self.src + offset, self.dest + offset, self.count))
+    }
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spans() {
+        let interleaved = Layout::interleaved(2, 3);
+        assert_eq!(interleaved.index(1, 2), 5);
+        assert_eq!(
+            interleaved.channel_span(1, 1, 10),
+            Some(Span {
+                start: 3,
+                stride: 2,
+                count: 2
+            })
+        );
+        assert_eq!(interleaved.frame_span(3, 0, 2), None);
+
+        let sequential = Layout::sequential(2, 3);
+        assert_eq!(sequential.index(1, 2), 5);
+        assert_eq!(
+            sequential.frame_span(1, 0, 1),
+            Some(Span {
+                start: 1,
+                stride: 3,
+                count: 1
+            })
+        );
+        assert_eq!(sequential.channel_span(2, 0, 2), None);
+    }
+
+    #[test]
+    fn transfer_lengths() {
+        assert_eq!(transfer_length(1, 2, 1, 3, 10), Some(2));
+        assert_eq!(transfer_length(0, 2, 0, 3, 1), Some(1));
+        assert_eq!(transfer_length(2, 2, 0, 3, 1), None);
+        assert_eq!(transfer_length(0, 2, 3, 3, 1), None);
+    }
+
+    #[test]
+    fn copy_blocks() {
+        let interleaved = Layout::interleaved(2, 10);
+        let blocks: Vec<_> = interleaved.copy_blocks(1, 5, 3).collect();
+        assert_eq!(blocks, vec![(2, 10, 6)]);
+
+        let padded = Layout::interleaved_with_stride(2, 10, 3);
+        let blocks: Vec<_> = padded.copy_blocks(1, 5, 2).collect();
+        assert_eq!(blocks, vec![(6, 18, 2), (3, 15, 2)]);
+        let blocks: Vec<_> = padded.copy_blocks(5, 1, 2).collect();
+        assert_eq!(blocks, vec![(15, 3, 2), (18, 6, 2)]);
+
+        let sequential = Layout::sequential(2, 10);
+        let blocks: Vec<_> = sequential.copy_blocks(1, 5, 3).collect();
+        assert_eq!(blocks, vec![(1, 5, 3), (11, 15, 3)]);
+        assert!(!sequential.copy_is_valid(8, 0, 3));
+    }
+}
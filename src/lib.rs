@@ -18,47 +18,298 @@ pub mod sample;
 /// Calculate statistics for adapters with numerical sample types
 pub mod stats;
 
+/// One-shot conversion between float samples and raw bytes.
+pub mod convert;
+
+/// Comparison of the contents of adapters.
+#[cfg(feature = "std")]
+pub mod compare;
+
+/// Diagnostics for finding the format of raw sample data.
+#[cfg(feature = "std")]
+pub mod diagnostics;
+
+/// Wrapper for measuring levels of samples written to adapters.
+#[cfg(feature = "std")]
+pub mod metering;
+
+/// Wrapper for tracking missing frames in adapters.
+#[cfg(feature = "std")]
+pub mod gaps;
+
+/// Wrapper for attaching timestamps to adapters.
+pub mod timestamps;
+
+/// Wrapper for attaching metadata to adapters.
+pub mod tagged;
+
+/// Wrapper for catching out-of-bounds accesses in debug builds.
+pub mod strict;
+
+/// Read-only view of mutable adapters.
+pub mod readonly;
+
+/// Marker trait and wrapper for buffers with a fixed number of channels.
+pub mod fixed;
+
+/// Verification of the conversions of sample formats.
+pub mod roundtrip;
+
+/// Pool of reusable owned buffers.
+#[cfg(feature = "std")]
+pub mod pool;
+
+/// Wrappers for testing code that uses adapters.
+#[cfg(feature = "std")]
+pub mod dummy;
+
+/// Precomputed copy plans between adapters.
+#[cfg(feature = "std")]
+pub mod plan;
+
+/// Common interface for processors operating on adapters.
+#[cfg(feature = "std")]
+pub mod process;
+
+/// Signal processing operations on adapters.
+#[cfg(feature = "std")]
+pub mod ops;
+
+/// Filtering of adapters.
+#[cfg(feature = "std")]
+pub mod filter;
+
+/// Wrappers for on/off signals packed as bits.
+pub mod bitmask;
+
 /// Read-only iterators
 mod iterators;
 
+/// Range based copying between adapters and slices.
+mod ranges;
+
+/// Cursors for reading and writing buffers incrementally.
+pub mod cursors;
+
 mod slicetools;
 
+mod layout;
+
+/// Logging of clipped samples in the converting wrappers.
+mod cliplog;
+
+/// Macros for implementing the traits for custom structs.
+mod derive;
+
 #[cfg(feature = "std")]
 use std::error::Error;
 #[cfg(feature = "std")]
 use std::fmt;
 
-pub use traits::{Adapter, AdapterMut};
+pub use traits::{Adapter, AdapterMut, AdapterRef, IterationOrder};
+#[cfg(feature = "std")]
+pub use traits::{AdapterAny, AdapterMutAny};
 
 pub use iterators::AdapterIterators;
 
+pub use ranges::{AdapterRanges, AdapterRangesMut};
+
 #[cfg(feature = "audio")]
 pub mod audio;
 
+#[cfg(feature = "nalgebra")]
+pub mod matrix;
+
+#[cfg(feature = "complex")]
+pub mod complex;
+
+#[cfg(feature = "arrow")]
+pub mod arrow;
+
+#[cfg(feature = "capi")]
+pub mod capi;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+/// Conversion of buffers using several threads.
+#[cfg(feature = "parallel")]
+pub mod parallel;
+
 pub mod adapter_to_float;
 
+/// Exact widening of integer samples to wider integer types.
+pub mod widen;
+
+/// Packing of frames to payloads for network audio.
+pub mod net;
+
+/// Conversion between speaker position channel masks and masks of active channels.
+#[cfg(feature = "std")]
+pub mod channelmask;
+
+/// The dimensions of a buffer.
+/// The constructors taking a `Dims` can be used instead of the ones
+/// taking the number of channels and frames as two positional arguments,
+/// which are easy to swap by mistake.
+///
+/// ## Example
+/// ```
+/// use audioadapter::direct::InterleavedSlice;
+/// use audioadapter::{Adapter, Dims};
+///
+/// let data = [0_i16; 6];
+/// let dims = Dims { channels: 2, frames: 3 };
+/// let buffer = InterleavedSlice::with_dims(&data, dims).unwrap();
+/// assert_eq!(buffer.frames(), 3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Dims {
+    /// The number of channels.
+    pub channels: usize,
+    /// The number of frames.
+    pub frames: usize,
+}
+
+/// A summary of the clipping that occurred while writing values to a buffer,
+/// returned by the `_report` variants of the writing methods of [AdapterMut].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClipReport {
+    /// The number of values written.
+    pub written: usize,
+    /// The number of clipped values, for each channel of the buffer.
+    pub per_channel: Vec<usize>,
+    /// The total number of clipped values.
+    pub total: usize,
+    /// The largest amount by which the magnitude of a clipped value exceeded 1.0.
+    /// Values clipped by a ceiling below full scale do not contribute.
+    pub max_overshoot: f32,
+}
+
+#[cfg(feature = "std")]
+impl ClipReport {
+    /// Create an empty report for a buffer with the given number of channels.
+    pub fn new(channels: usize) -> Self {
+        Self {
+            per_channel: vec![0; channels],
+            ..Default::default()
+        }
+    }
+
+    /// Returns `true` if any value was clipped.
+    pub fn is_clipped(&self) -> bool {
+        self.total > 0
+    }
+
+    /// Record that a value was written to a channel.
+    pub(crate) fn add<T: num_traits::ToPrimitive>(
+        &mut self,
+        channel: usize,
+        value: &T,
+        clipped: bool,
+    ) {
+        self.written += 1;
+        if clipped {
+            self.per_channel[channel] += 1;
+            self.total += 1;
+            let overshoot = value.to_f32().map_or(0.0, |value| value.abs() - 1.0);
+            self.max_overshoot = self.max_overshoot.max(overshoot);
+        }
+    }
+}
+
 /// Error returned when the wrapped data structure has the wrong dimensions,
 /// typically that it is too short.
+/// More variants may be added in the future,
+/// so matching on this enum requires a wildcard arm.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum SizeError {
+    /// The buffer of a channel is too short.
     Channel {
+        /// The index of the channel.
         index: usize,
+        /// The number of samples in the channel buffer.
         actual: usize,
+        /// The number of samples needed for the given number of frames.
         required: usize,
     },
+    /// The buffer of a frame is too short.
     Frame {
+        /// The index of the frame.
         index: usize,
+        /// The number of samples in the frame buffer.
         actual: usize,
+        /// The number of samples needed for the given number of channels.
         required: usize,
     },
+    /// The buffer is too short to hold all samples.
     Total {
+        /// The length of the buffer.
         actual: usize,
+        /// The length needed for the given number of channels and frames.
         required: usize,
     },
+    /// The channel mask does not have one value per channel.
     Mask {
+        /// The length of the mask.
+        actual: usize,
+        /// The number of channels.
+        required: usize,
+    },
+    /// There are fewer channel offsets than channels.
+    Offsets {
+        /// The number of offsets.
+        actual: usize,
+        /// The number of channels.
+        required: usize,
+    },
+    /// The buffer has the given number of frames as channels and vice versa,
+    /// indicating that the two arguments were swapped.
+    SwappedDimensions {
+        /// The given number of channels.
+        channels: usize,
+        /// The given number of frames.
+        frames: usize,
+    },
+    /// The requested number of active frames exceeds the capacity of the buffer.
+    Capacity {
+        /// The capacity of the buffer, in frames.
+        actual: usize,
+        /// The requested number of frames.
+        required: usize,
+    },
+    /// The stride is too short, or is not a multiple of the sample size.
+    Stride {
+        /// The given stride, in bytes.
         actual: usize,
+        /// The smallest allowed stride, in bytes.
         required: usize,
     },
+    /// The number of channels or frames is zero,
+    /// which is rejected when the `reject-zero-dimensions` feature is enabled.
+    ZeroDimension {
+        /// The given number of channels.
+        channels: usize,
+        /// The given number of frames.
+        frames: usize,
+    },
+    /// The size of the buffer in samples or bytes does not fit in a `usize`.
+    Overflow {
+        /// The given number of channels.
+        channels: usize,
+        /// The given number of frames.
+        frames: usize,
+    },
+    /// The number of channels is not a multiple of the group size,
+    /// or the group size is zero.
+    Group {
+        /// The given number of channels.
+        channels: usize,
+        /// The given group size.
+        group_size: usize,
+    },
 }
 
 #[cfg(feature = "std")]
@@ -92,6 +343,37 @@ impl fmt::Display for SizeError {
                 "Mask is wrong length, got: {}, required: {}",
                 actual, required
             ),
+            SizeError::Offsets { actual, required } => format!(
+                "Too few channel offsets, got: {}, required: {}",
+                actual, required
+            ),
+            SizeError::SwappedDimensions { channels, frames } => format!(
+                "Channels and frames appear to be swapped, got: {} channels and {} frames",
+                channels, frames
+            ),
+            SizeError::Capacity { actual, required } => format!(
+                "Buffer capacity is too small, got: {} frames, required: {}",
+                actual, required
+            ),
+            SizeError::Stride { actual, required } => format!(
+                "Stride must be at least {} bytes and a multiple of the sample size, got: {}",
+                required, actual
+            ),
+            SizeError::ZeroDimension { channels, frames } => format!(
+                "Buffer dimensions must not be zero, got: {} channels and {} frames",
+                channels, frames
+            ),
+            SizeError::Overflow { channels, frames } => format!(
+                "Buffer size overflows, got: {} channels and {} frames",
+                channels, frames
+            ),
+            SizeError::Group {
+                channels,
+                group_size,
+            } => format!(
+                "Number of channels must be a multiple of the group size, got: {} channels and group size {}",
+                channels, group_size
+            ),
         };
         write!(f, "{}", &desc)
     }
@@ -110,21 +392,175 @@ macro_rules! implement_size_getters {
 }
 pub(crate) use implement_size_getters;
 
-macro_rules! check_slice_length {
-    ($channels:expr , $frames:expr, $length:expr ) => {
-        if $length < $frames * $channels {
-            return Err(SizeError::Total {
-                actual: $length,
-                required: $frames * $channels,
+macro_rules! implement_active_frames {
+    (|$s:ident| $capacity:expr) => {
+        implement_active_frames!(|$s| $capacity, |this, frames| this.frames = frames);
+    };
+    (|$s:ident| $capacity:expr, |$s_set:ident, $frames:ident| $set_frames:expr) => {
+        /// Get the largest number of frames that fit in the wrapped storage,
+        /// for the current number of channels.
+        pub fn capacity_frames(&self) -> usize {
+            let $s = self;
+            $capacity
+        }
+
+        /// Set the number of frames that can be accessed via the trait methods.
+        /// This can be any value up to the capacity given by `capacity_frames()`,
+        /// which makes it possible to reuse a wrapper for buffers of varying size.
+        /// Returns a [SizeError] if the number of frames exceeds the capacity.
+        pub fn set_active_frames(&mut self, frames: usize) -> Result<(), SizeError> {
+            let capacity = self.capacity_frames();
+            if frames > capacity {
+                return Err(SizeError::Capacity {
+                    actual: capacity,
+                    required: frames,
+                });
+            }
+            let $s_set = self;
+            let $frames = frames;
+            $set_frames;
+            Ok(())
+        }
+    };
+}
+pub(crate) use implement_active_frames;
+
+macro_rules! implement_sample_access {
+    (@impl [$($generics:tt)*] $wrapper:ty, |$s:ident, $channel:ident, $frame:ident| $place:expr $(; $var:ident = $value:expr)?) => {
+        impl<$($generics)*> $wrapper {
+            /// Get a reference to the sample at `(channel, frame)`.
+            /// Returns `None` if the channel or frame is out of bounds.
+            pub fn sample_ref(&self, $channel: usize, $frame: usize) -> Option<&T> {
+                if $channel >= self.channels || $frame >= self.frames {
+                    return None;
+                }
+                let $s = self;
+                $(let $var = $value;)?
+                Some(&$place)
+            }
+        }
+
+        impl<$($generics)*> core::ops::Index<(usize, usize)> for $wrapper {
+            type Output = T;
+
+            /// Get a reference to the sample at `(channel, frame)`.
+            /// Panics if the channel or frame is out of bounds.
+            fn index(&self, (channel, frame): (usize, usize)) -> &T {
+                match self.sample_ref(channel, frame) {
+                    Some(value) => value,
+                    None => $crate::index_out_of_bounds(channel, frame, self.channels, self.frames),
+                }
+            }
+        }
+
+        impl<'r, $($generics)*> $crate::AdapterRef<'r, T> for $wrapper
+        where
+            T: Clone + 'r,
+            Self: $crate::Adapter<'r, T>,
+        {
+            fn read_sample_ref(&self, channel: usize, frame: usize) -> Option<&T> {
+                self.sample_ref(channel, frame)
+            }
+        }
+    };
+    (@impl [$($generics:tt)*] $wrapper:ty, |$s:ident, $channel:ident, $frame:ident| $place:expr $(; $var:ident = $value:expr)?, mutable) => {
+        $crate::implement_sample_access!(@impl [$($generics)*] $wrapper, |$s, $channel, $frame| $place $(; $var = $value)?);
+
+        impl<$($generics)*> $wrapper {
+            /// Get a mutable reference to the sample at `(channel, frame)`.
+            /// Returns `None` if the channel or frame is out of bounds.
+            pub fn sample_mut(&mut self, $channel: usize, $frame: usize) -> Option<&mut T> {
+                if $channel >= self.channels || $frame >= self.frames {
+                    return None;
+                }
+                let $s = self;
+                $(let $var = $value;)?
+                Some(&mut $place)
+            }
+        }
+
+        impl<$($generics)*> core::ops::IndexMut<(usize, usize)> for $wrapper {
+            /// Get a mutable reference to the sample at `(channel, frame)`.
+            /// Panics if the channel or frame is out of bounds.
+            fn index_mut(&mut self, (channel, frame): (usize, usize)) -> &mut T {
+                let (channels, frames) = (self.channels, self.frames);
+                match self.sample_mut(channel, frame) {
+                    Some(value) => value,
+                    None => $crate::index_out_of_bounds(channel, frame, channels, frames),
+                }
+            }
+        }
+    };
+    // Wrappers of a single slice or vector, where the index is given by `calc_index`.
+    ([$($generics:tt)*] $wrapper:ty $(, $mutable:ident)?) => {
+        $crate::implement_sample_access!(
+            @impl [$($generics)*] $wrapper,
+            |s, channel, frame| s.buf[index]; index = s.calc_index(channel, frame)
+            $(, $mutable)?
+        );
+    };
+    // Wrappers where the sample is found by indexing the buffer as given by `$place`.
+    ([$($generics:tt)*] $wrapper:ty, |$s:ident, $channel:ident, $frame:ident| $place:expr $(, $mutable:ident)?) => {
+        $crate::implement_sample_access!(@impl [$($generics)*] $wrapper, |$s, $channel, $frame| $place $(, $mutable)?);
+    };
+}
+pub(crate) use implement_sample_access;
+
+#[cold]
+#[track_caller]
+fn index_out_of_bounds(channel: usize, frame: usize, channels: usize, frames: usize) -> ! {
+    panic!(
+        "index ({}, {}) out of bounds for buffer with {} channels and {} frames",
+        channel, frame, channels, frames
+    );
+}
+
+macro_rules! implement_with_dims {
+    ($with_dims:ident = $new:ident($arg:ident: $arg_type:ty) -> $ret:ty) => {
+        #[doc = concat!("Create a new wrapper in the same way as [Self::", stringify!($new), "],")]
+        /// with the number of channels and frames given as a [Dims](crate::Dims).
+        pub fn $with_dims($arg: $arg_type, dims: $crate::Dims) -> $ret {
+            Self::$new($arg, dims.channels, dims.frames)
+        }
+    };
+}
+pub(crate) use implement_with_dims;
+
+macro_rules! check_dimensions {
+    ($channels:expr, $frames:expr) => {
+        if cfg!(feature = "reject-zero-dimensions") && ($channels == 0 || $frames == 0) {
+            return Err(SizeError::ZeroDimension {
+                channels: $channels,
+                frames: $frames,
             });
         }
     };
+}
+pub(crate) use check_dimensions;
+
+macro_rules! check_slice_length {
+    ($channels:expr , $frames:expr, $length:expr ) => {
+        $crate::check_slice_length!($channels, $frames, $length, 1);
+    };
     ($channels:expr , $frames:expr, $length:expr, $elements_per_sample:expr) => {
-        if $length < $frames * $channels * $elements_per_sample {
-            return Err(SizeError::Total {
-                actual: $length,
-                required: $frames * $channels * $elements_per_sample,
-            });
+        $crate::check_dimensions!($channels, $frames);
+        // The required length may not fit in a usize, in particular on 32-bit targets.
+        match usize::checked_mul($frames, $channels)
+            .and_then(|samples| samples.checked_mul($elements_per_sample))
+        {
+            Some(required) if $length < required => {
+                return Err(SizeError::Total {
+                    actual: $length,
+                    required,
+                });
+            }
+            Some(_) => {}
+            None => {
+                return Err(SizeError::Overflow {
+                    channels: $channels,
+                    frames: $frames,
+                });
+            }
         }
     };
 }
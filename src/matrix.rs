@@ -0,0 +1,213 @@
+//! # [nalgebra](https://crates.io/crates/nalgebra) matrix compatibility
+//!
+//! This module provides wrappers that implement the `audioadapter` traits
+//! for matrices from the [nalgebra](https://crates.io/crates/nalgebra) crate.
+//! Any matrix type is supported, including `DMatrix`, statically sized matrices,
+//! and matrix views.
+//!
+//! The traits can't be implemented directly for the matrix types,
+//! since this would conflict with the implementation for buffers
+//! from the [audio](https://crates.io/crates/audio) crate.
+//!
+//! By default each row of the matrix is one channel,
+//! and each column is one frame.
+//! Use the `_transposed` constructors for matrices where the columns are the channels.
+//!
+//! ## Example
+//! Wrap a matrix and read a sample.
+//! ```
+//! use audioadapter::matrix::MatrixAdapter;
+//! use audioadapter::Adapter;
+//! use nalgebra::DMatrix;
+//!
+//! // 2 channels, 3 frames
+//! let matrix = DMatrix::from_row_slice(2, 3, &[1, 2, 3, 4, 5, 6]);
+//! let buffer = MatrixAdapter::new(&matrix);
+//! assert_eq!(buffer.channels(), 2);
+//! assert_eq!(buffer.read_sample(1, 0), Some(4));
+//!
+//! let transposed = MatrixAdapter::new_transposed(&matrix);
+//! assert_eq!(transposed.channels(), 3);
+//! assert_eq!(transposed.read_sample(1, 0), Some(2));
+//! ```
+
+use nalgebra::{Dim, Matrix, RawStorage, RawStorageMut, Scalar};
+
+use crate::{Adapter, AdapterMut, IterationOrder};
+
+/// A wrapper for a nalgebra [Matrix],
+/// where either the rows or the columns are the channels.
+pub struct MatrixAdapter<U> {
+    matrix: U,
+    transposed: bool,
+}
+
+impl<U> MatrixAdapter<U> {
+    /// Get the (row, column) position of a sample.
+    fn position(&self, channel: usize, frame: usize) -> (usize, usize) {
+        if self.transposed {
+            (frame, channel)
+        } else {
+            (channel, frame)
+        }
+    }
+}
+
+macro_rules! matrix_constructors {
+    ($new:ident, $new_transposed:ident, $($mut:tt)?) => {
+        /// Wrap a matrix where each row is a channel and each column is a frame.
+        pub fn $new(matrix: &'a $($mut)? Matrix<T, R, C, S>) -> Self {
+            Self {
+                matrix,
+                transposed: false,
+            }
+        }
+
+        /// Wrap a matrix where each column is a channel and each row is a frame.
+        pub fn $new_transposed(matrix: &'a $($mut)? Matrix<T, R, C, S>) -> Self {
+            Self {
+                matrix,
+                transposed: true,
+            }
+        }
+    };
+}
+
+impl<'a, T, R, C, S> MatrixAdapter<&'a Matrix<T, R, C, S>>
+where
+    R: Dim,
+    C: Dim,
+    S: RawStorage<T, R, C>,
+{
+    matrix_constructors!(new, new_transposed,);
+}
+
+impl<'a, T, R, C, S> MatrixAdapter<&'a mut Matrix<T, R, C, S>>
+where
+    R: Dim,
+    C: Dim,
+    S: RawStorageMut<T, R, C>,
+{
+    matrix_constructors!(new_mut, new_mut_transposed, mut);
+}
+
+macro_rules! implement_matrix_reads {
+    () => {
+        unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
+            self.matrix
+                .get_unchecked(self.position(channel, frame))
+                .clone()
+        }
+
+        fn channels(&self) -> usize {
+            if self.transposed {
+                self.matrix.ncols()
+            } else {
+                self.matrix.nrows()
+            }
+        }
+
+        fn frames(&self) -> usize {
+            if self.transposed {
+                self.matrix.nrows()
+            } else {
+                self.matrix.ncols()
+            }
+        }
+
+        fn preferred_iteration(&self) -> IterationOrder {
+            let (row_stride, col_stride) = self.matrix.strides();
+            if self.transposed {
+                IterationOrder::from_strides(col_stride, row_stride)
+            } else {
+                IterationOrder::from_strides(row_stride, col_stride)
+            }
+        }
+    };
+}
+
+impl<'a, T, R, C, S> Adapter<'a, T> for MatrixAdapter<&'a Matrix<T, R, C, S>>
+where
+    T: Scalar,
+    R: Dim,
+    C: Dim,
+    S: RawStorage<T, R, C>,
+{
+    implement_matrix_reads!();
+}
+
+impl<'a, T, R, C, S> Adapter<'a, T> for MatrixAdapter<&'a mut Matrix<T, R, C, S>>
+where
+    T: Scalar,
+    R: Dim,
+    C: Dim,
+    S: RawStorageMut<T, R, C>,
+{
+    implement_matrix_reads!();
+}
+
+impl<'a, T, R, C, S> AdapterMut<'a, T> for MatrixAdapter<&'a mut Matrix<T, R, C, S>>
+where
+    T: Scalar,
+    R: Dim,
+    C: Dim,
+    S: RawStorageMut<T, R, C>,
+{
+    unsafe fn write_sample_unchecked(&mut self, channel: usize, frame: usize, value: &T) -> bool {
+        let position = self.position(channel, frame);
+        *self.matrix.get_unchecked_mut(position) = value.clone();
+        false
+    }
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::check_copy_within;
+    use nalgebra::{DMatrix, SMatrix};
+
+    #[test]
+    fn read_and_write() {
+        let mut matrix = DMatrix::from_element(2, 3, 0_i32);
+        let mut buffer = MatrixAdapter::new_mut(&mut matrix);
+        assert_eq!(buffer.channels(), 2);
+        assert_eq!(buffer.frames(), 3);
+        buffer.write_sample(1, 2, &5).unwrap();
+        assert_eq!(buffer.read_sample(1, 2), Some(5));
+        assert_eq!(buffer.read_sample(2, 0), None);
+        assert_eq!(matrix[(1, 2)], 5);
+
+        let mut buffer = MatrixAdapter::new_mut_transposed(&mut matrix);
+        assert_eq!(buffer.channels(), 3);
+        assert_eq!(buffer.frames(), 2);
+        buffer.write_sample(0, 1, &7).unwrap();
+        assert_eq!(matrix[(1, 0)], 7);
+    }
+
+    #[test]
+    fn copy_within() {
+        let mut matrix = DMatrix::from_element(3, 10, 0_u32);
+        check_copy_within(&mut MatrixAdapter::new_mut(&mut matrix));
+        let mut matrix = SMatrix::<u32, 10, 2>::zeros();
+        check_copy_within(&mut MatrixAdapter::new_mut_transposed(&mut matrix));
+    }
+
+    #[test]
+    fn matrix_view() {
+        let matrix = DMatrix::from_row_slice(3, 3, &[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let view = matrix.view((1, 1), (2, 2));
+        let buffer = MatrixAdapter::new(&view);
+        assert_eq!(buffer.channels(), 2);
+        assert_eq!(buffer.read_sample(0, 0), Some(5));
+        assert_eq!(buffer.read_sample(1, 1), Some(9));
+        let mut frame = [0; 2];
+        assert_eq!(buffer.write_from_frame_to_slice(1, 0, &mut frame), 2);
+        assert_eq!(frame, [6, 9]);
+    }
+}
@@ -0,0 +1,220 @@
+//! # Metering wrapper
+//!
+//! This module provides a wrapper for buffers implementing [AdapterMut],
+//! that keeps track of the signal levels of the samples written through it.
+//! This makes it possible to get level metering in for example a playback path,
+//! without needing a second pass over the data.
+//!
+//! ## Example
+//! Wrap a buffer, write some samples and read the levels.
+//! ```
+//! use audioadapter::direct::InterleavedSlice;
+//! use audioadapter::metering::MeteringAdapter;
+//! use audioadapter::{Adapter, AdapterMut};
+//!
+//! let mut data = vec![0.0_f32; 6];
+//! let mut buffer = InterleavedSlice::new_mut(&mut data, 2, 3).unwrap();
+//! let mut meter = MeteringAdapter::new(&mut buffer as &mut dyn AdapterMut<f32>);
+//!
+//! meter.write_from_slice_to_channel(0, 0, &[0.5, -0.25, 0.0]);
+//! meter.write_from_slice_to_channel(1, 0, &[0.1, 0.1, 0.1]);
+//!
+//! let levels = meter.levels();
+//! assert_eq!(levels[0].peak, 0.5);
+//! assert!((levels[1].rms - 0.1).abs() < 1.0e-6);
+//! ```
+
+use core::any::Any;
+
+use num_traits::ToPrimitive;
+
+use crate::{Adapter, AdapterMut, IterationOrder};
+
+/// The levels measured for a channel.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChannelLevels {
+    /// The largest absolute sample value written since the last reset.
+    pub peak: f64,
+    /// The RMS value of all samples written since the last reset.
+    pub rms: f64,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct ChannelMeter {
+    peak: f64,
+    square_sum: f64,
+    nbr_samples: usize,
+}
+
+impl ChannelMeter {
+    fn update(&mut self, value: f64) {
+        let abs = value.abs();
+        if abs > self.peak {
+            self.peak = abs;
+        }
+        self.square_sum += value * value;
+        self.nbr_samples += 1;
+    }
+
+    fn levels(&self) -> ChannelLevels {
+        let rms = if self.nbr_samples > 0 {
+            (self.square_sum / self.nbr_samples as f64).sqrt()
+        } else {
+            0.0
+        };
+        ChannelLevels {
+            peak: self.peak,
+            rms,
+        }
+    }
+}
+
+/// A wrapper for an [AdapterMut] buffer that measures
+/// the peak and RMS levels of each channel.
+/// The levels are updated every time a sample is written through the wrapper,
+/// and hold their values until reset by [MeteringAdapter::reset_levels].
+/// Reading samples passes straight through to the wrapped buffer
+/// and does not affect the levels.
+pub struct MeteringAdapter<U> {
+    buf: U,
+    meters: Vec<ChannelMeter>,
+}
+
+impl<'a, T> MeteringAdapter<&'a mut dyn AdapterMut<'a, T>>
+where
+    T: Clone + ToPrimitive + 'a,
+{
+    /// Create a new wrapper for a mutable buffer implementing the [AdapterMut] trait.
+    pub fn new(buf: &'a mut dyn AdapterMut<'a, T>) -> Self {
+        let meters = vec![ChannelMeter::default(); buf.channels()];
+        Self { buf, meters }
+    }
+
+    /// Get the levels measured for each channel since the last reset.
+    pub fn levels(&self) -> Vec<ChannelLevels> {
+        self.meters.iter().map(|m| m.levels()).collect()
+    }
+
+    /// Get the levels measured for a single channel since the last reset.
+    /// Returns `None` if called with an invalid channel number.
+    pub fn channel_levels(&self, channel: usize) -> Option<ChannelLevels> {
+        self.meters.get(channel).map(|m| m.levels())
+    }
+
+    /// Reset the levels of all channels.
+    pub fn reset_levels(&mut self) {
+        for meter in self.meters.iter_mut() {
+            *meter = ChannelMeter::default();
+        }
+    }
+}
+
+impl<'a, T> Adapter<'a, T> for MeteringAdapter<&'a mut dyn AdapterMut<'a, T>>
+where
+    T: Clone + ToPrimitive + 'a,
+{
+    unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
+        self.buf.read_sample_unchecked(channel, frame)
+    }
+
+    fn channels(&self) -> usize {
+        self.buf.channels()
+    }
+
+    fn frames(&self) -> usize {
+        self.buf.frames()
+    }
+
+    fn is_channel_active(&self, channel: usize) -> bool {
+        self.buf.is_channel_active(channel)
+    }
+
+    fn preferred_iteration(&self) -> IterationOrder {
+        self.buf.preferred_iteration()
+    }
+
+    fn metadata(&self) -> Option<&dyn Any> {
+        self.buf.metadata()
+    }
+
+    fn write_from_channel_to_slice(&self, channel: usize, skip: usize, slice: &mut [T]) -> usize {
+        self.buf.write_from_channel_to_slice(channel, skip, slice)
+    }
+
+    fn write_from_frame_to_slice(&self, frame: usize, skip: usize, slice: &mut [T]) -> usize {
+        self.buf.write_from_frame_to_slice(frame, skip, slice)
+    }
+}
+
+impl<'a, T> AdapterMut<'a, T> for MeteringAdapter<&'a mut dyn AdapterMut<'a, T>>
+where
+    T: Clone + ToPrimitive + 'a,
+{
+    unsafe fn write_sample_unchecked(&mut self, channel: usize, frame: usize, value: &T) -> bool {
+        self.meters
+            .get_unchecked_mut(channel)
+            .update(value.to_f64().unwrap_or_default());
+        self.buf.write_sample_unchecked(channel, frame, value)
+    }
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direct::SequentialSlice;
+
+    #[test]
+    fn peak_and_rms() {
+        let mut data = [0_i32; 8];
+        let mut buffer = SequentialSlice::new_mut(&mut data, 2, 4).unwrap();
+        let mut meter = MeteringAdapter::new(&mut buffer as &mut dyn AdapterMut<i32>);
+        meter.write_from_slice_to_channel(0, 0, &[1, -1, 1, -1]);
+        meter.write_sample(1, 2, &-5).unwrap();
+        assert_eq!(
+            meter.channel_levels(0),
+            Some(ChannelLevels {
+                peak: 1.0,
+                rms: 1.0
+            })
+        );
+        assert_eq!(
+            meter.channel_levels(1),
+            Some(ChannelLevels {
+                peak: 5.0,
+                rms: 5.0
+            })
+        );
+        assert_eq!(meter.channel_levels(2), None);
+        // reading does not affect the levels
+        assert_eq!(meter.read_sample(1, 2), Some(-5));
+        assert_eq!(meter.levels()[1].peak, 5.0);
+        drop(meter);
+        assert_eq!(data, [1, -1, 1, -1, 0, 0, -5, 0]);
+    }
+
+    #[test]
+    fn peak_hold_and_reset() {
+        let mut data = [0.0_f32; 4];
+        let mut buffer = SequentialSlice::new_mut(&mut data, 1, 4).unwrap();
+        let mut meter = MeteringAdapter::new(&mut buffer as &mut dyn AdapterMut<f32>);
+        meter.write_sample(0, 0, &-0.75).unwrap();
+        meter.write_sample(0, 1, &0.25).unwrap();
+        assert_eq!(meter.levels()[0].peak, 0.75);
+        meter.reset_levels();
+        assert_eq!(
+            meter.levels()[0],
+            ChannelLevels {
+                peak: 0.0,
+                rms: 0.0
+            }
+        );
+        meter.write_sample(0, 2, &0.5).unwrap();
+        assert_eq!(meter.levels()[0].peak, 0.5);
+    }
+}
@@ -0,0 +1,348 @@
+//! # Payloads for network audio
+//!
+//! Network audio protocols such as RTP and AES67 send linear PCM samples
+//! in big-endian byte order, with the frames interleaved.
+//! The formats are called L16 for 16-bit samples and L24 for packed 24-bit samples.
+//!
+//! This module provides [PayloadFormat](crate::net::PayloadFormat), that describes the encoding and the number of channels
+//! of a stream, and packs frames of a buffer to payloads and unpacks them back.
+//! A payload always holds a whole number of frames.
+//! Packing converts the samples of any [Adapter](crate::Adapter) to the payload format.
+//! When the buffer already stores its samples as [I16BE](crate::sample::I16BE) or [I24BE<3>](crate::sample::I24BE)
+//! bytes in an [InterleavedNumbers](crate::number_to_float::InterleavedNumbers) wrapper,
+//! [pack_raw](crate::net::PayloadFormat::pack_raw) instead copies the raw frames without any conversion.
+//!
+//! ## Example
+//! Pack a stereo buffer to L24 payloads of 2 frames, and unpack them again.
+//! ```
+//! use audioadapter::direct::InterleavedSlice;
+//! use audioadapter::net::{Encoding, PayloadFormat};
+//! use audioadapter::{Adapter, AdapterMut};
+//!
+//! let data = [0.5_f32, -0.5, 0.25, -0.25, 0.0, 0.0];
+//! let buffer = InterleavedSlice::new(&data, 2, 3).unwrap();
+//! let format = PayloadFormat::new(Encoding::L24, 2);
+//! let mut payload = [0_u8; 12];
+//!
+//! let frames = format.pack(&buffer, 0, &mut payload).unwrap();
+//! assert_eq!(frames, 2);
+//! assert_eq!(payload[..3], [0x40, 0, 0]);
+//!
+//! let mut received = [0.0_f32; 4];
+//! let mut output = InterleavedSlice::new_mut(&mut received, 2, 2).unwrap();
+//! assert_eq!(format.unpack(&payload, &mut output, 0), Ok(2));
+//! assert_eq!(received, [0.5, -0.5, 0.25, -0.25]);
+//! ```
+
+use core::fmt;
+use num_traits::Float;
+
+use crate::sample::{BytesSample, RawSample, I16BE, I24BE};
+use crate::{Adapter, AdapterMut};
+
+/// The sample encoding of a payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// 16-bit signed integer samples in big-endian byte order.
+    L16,
+    /// 24-bit signed integer samples in big-endian byte order, stored as 3 bytes.
+    L24,
+}
+
+impl Encoding {
+    /// Get the number of bytes used for each sample.
+    pub fn bytes_per_sample(&self) -> usize {
+        match self {
+            Encoding::L16 => I16BE::BYTES_PER_SAMPLE,
+            Encoding::L24 => I24BE::<3>::BYTES_PER_SAMPLE,
+        }
+    }
+}
+
+/// Error returned when a buffer or payload does not match the payload format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadError {
+    /// The buffer does not have the number of channels of the format.
+    Channels { expected: usize, actual: usize },
+    /// The length of a payload or a raw frame in bytes is not a whole number of frames.
+    Length { bytes: usize, frame_bytes: usize },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PayloadError {}
+
+impl fmt::Display for PayloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PayloadError::Channels { expected, actual } => write!(
+                f,
+                "Wrong number of channels, expected: {}, actual: {}",
+                expected, actual
+            ),
+            PayloadError::Length { bytes, frame_bytes } => write!(
+                f,
+                "Length of {} bytes is not a whole number of frames of {} bytes",
+                bytes, frame_bytes
+            ),
+        }
+    }
+}
+
+/// The format of the payloads of a stream,
+/// given by the sample encoding and the number of channels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PayloadFormat {
+    encoding: Encoding,
+    channels: usize,
+}
+
+impl PayloadFormat {
+    /// Create a new payload format.
+    pub fn new(encoding: Encoding, channels: usize) -> Self {
+        Self { encoding, channels }
+    }
+
+    /// Get the sample encoding.
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// Get the number of channels.
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Get the number of bytes of one frame.
+    pub fn frame_bytes(&self) -> usize {
+        self.channels * self.encoding.bytes_per_sample()
+    }
+
+    /// Get the number of bytes of a payload holding the given number of frames.
+    pub fn payload_bytes(&self, frames: usize) -> usize {
+        frames * self.frame_bytes()
+    }
+
+    /// Get the number of frames in a payload.
+    /// Returns an error if the payload is not a whole number of frames.
+    pub fn frames_in(&self, payload: &[u8]) -> Result<usize, PayloadError> {
+        let frame_bytes = self.frame_bytes();
+        match payload.len().checked_rem(frame_bytes) {
+            Some(0) => Ok(payload.len() / frame_bytes),
+            None if payload.is_empty() => Ok(0),
+            _ => Err(PayloadError::Length {
+                bytes: payload.len(),
+                frame_bytes,
+            }),
+        }
+    }
+
+    /// Pack frames of a buffer to a payload, starting at frame `first_frame` of the buffer.
+    /// As many whole frames as fit in the payload are packed,
+    /// limited by the number of frames remaining in the buffer.
+    /// Values outside the range -1.0 to +1.0 are clipped.
+    /// Returns the number of packed frames,
+    /// or an error if the buffer does not have the number of channels of the format.
+    pub fn pack<'a, T>(
+        &self,
+        buf: &dyn Adapter<'a, T>,
+        first_frame: usize,
+        payload: &mut [u8],
+    ) -> Result<usize, PayloadError>
+    where
+        T: Float + 'a,
+    {
+        self.check_channels(buf.channels())?;
+        let frames = payload
+            .len()
+            .checked_div(self.frame_bytes())
+            .unwrap_or(0)
+            .min(buf.frames().saturating_sub(first_frame));
+        match self.encoding {
+            Encoding::L16 => pack_samples::<T, I16BE>(buf, first_frame, frames, payload),
+            Encoding::L24 => pack_samples::<T, I24BE<3>>(buf, first_frame, frames, payload),
+        }
+        Ok(frames)
+    }
+
+    /// Pack raw frames to a payload, without any conversion.
+    /// The frames are typically given by the `iter_raw_frames` method
+    /// of an [InterleavedNumbers](crate::number_to_float::InterleavedNumbers) wrapper,
+    /// storing its samples as the sample type of the encoding.
+    /// As many frames as fit in the payload are copied.
+    /// Returns the number of packed frames,
+    /// or an error if a frame does not have the length of a frame of the format.
+    pub fn pack_raw<'b>(
+        &self,
+        frames: impl IntoIterator<Item = &'b [u8]>,
+        payload: &mut [u8],
+    ) -> Result<usize, PayloadError> {
+        let frame_bytes = self.frame_bytes();
+        if frame_bytes == 0 {
+            return Ok(0);
+        }
+        let mut packed = 0;
+        for (frame, chunk) in frames
+            .into_iter()
+            .zip(payload.chunks_exact_mut(frame_bytes))
+        {
+            if frame.len() != frame_bytes {
+                return Err(PayloadError::Length {
+                    bytes: frame.len(),
+                    frame_bytes,
+                });
+            }
+            chunk.copy_from_slice(frame);
+            packed += 1;
+        }
+        Ok(packed)
+    }
+
+    /// Unpack the frames of a payload to a buffer, starting at frame `first_frame` of the buffer.
+    /// Frames that do not fit in the buffer are skipped.
+    /// Returns the number of unpacked frames, or an error if the buffer
+    /// does not have the number of channels of the format,
+    /// or if the payload is not a whole number of frames.
+    pub fn unpack<'a, T>(
+        &self,
+        payload: &[u8],
+        buf: &mut dyn AdapterMut<'a, T>,
+        first_frame: usize,
+    ) -> Result<usize, PayloadError>
+    where
+        T: Float + 'a,
+    {
+        self.check_channels(buf.channels())?;
+        let frames = self
+            .frames_in(payload)?
+            .min(buf.frames().saturating_sub(first_frame));
+        match self.encoding {
+            Encoding::L16 => unpack_samples::<T, I16BE>(payload, buf, first_frame, frames),
+            Encoding::L24 => unpack_samples::<T, I24BE<3>>(payload, buf, first_frame, frames),
+        }
+        Ok(frames)
+    }
+
+    fn check_channels(&self, channels: usize) -> Result<(), PayloadError> {
+        if channels != self.channels {
+            return Err(PayloadError::Channels {
+                expected: self.channels,
+                actual: channels,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Convert frames of a buffer to samples of type `U`, and store their bytes in the payload.
+fn pack_samples<'a, T, U>(
+    buf: &dyn Adapter<'a, T>,
+    first_frame: usize,
+    frames: usize,
+    payload: &mut [u8],
+) where
+    T: Float + 'a,
+    U: BytesSample + RawSample,
+{
+    let channels = buf.channels();
+    let mut chunks = payload.chunks_exact_mut(U::BYTES_PER_SAMPLE);
+    for frame in first_frame..first_frame + frames {
+        for (channel, chunk) in (0..channels).zip(&mut chunks) {
+            let value = unsafe { buf.read_sample_unchecked(channel, frame) };
+            chunk.copy_from_slice(U::from_scaled_float(value).value.as_slice());
+        }
+    }
+}
+
+/// Read the bytes of samples of type `U` from a payload, and write them to frames of a buffer.
+fn unpack_samples<'a, T, U>(
+    payload: &[u8],
+    buf: &mut dyn AdapterMut<'a, T>,
+    first_frame: usize,
+    frames: usize,
+) where
+    T: Float + 'a,
+    U: BytesSample + RawSample,
+{
+    let channels = buf.channels();
+    let mut chunks = payload.chunks_exact(U::BYTES_PER_SAMPLE);
+    for frame in first_frame..first_frame + frames {
+        for (channel, chunk) in (0..channels).zip(&mut chunks) {
+            let value = U::from_slice(chunk).to_scaled_float();
+            unsafe { buf.write_sample_unchecked(channel, frame, &value) };
+        }
+    }
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direct::SequentialSlice;
+    use crate::number_to_float::InterleavedNumbers;
+
+    #[test]
+    fn pack_and_unpack_l16() {
+        let data = [0.5_f32, 0.25, -1.0, -0.5, 0.0, 2.0];
+        let buffer = SequentialSlice::new(&data, 2, 3).unwrap();
+        let format = PayloadFormat::new(Encoding::L16, 2);
+        assert_eq!(format.frame_bytes(), 4);
+        let mut payload = [0_u8; 8];
+        assert_eq!(format.pack(&buffer, 1, &mut payload), Ok(2));
+        assert_eq!(payload, [0x20, 0, 0, 0, 0x80, 0, 0x7f, 0xff]);
+        assert_eq!(format.pack(&buffer, 3, &mut payload), Ok(0));
+
+        let mut received = [0.0_f32; 6];
+        let mut output = SequentialSlice::new_mut(&mut received, 2, 3).unwrap();
+        assert_eq!(format.unpack(&payload, &mut output, 2), Ok(1));
+        assert_eq!(received, [0.0, 0.0, 0.25, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn pack_raw_frames() {
+        let data: [u8; 12] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+        let buffer = InterleavedNumbers::<&[I24BE<3>], f32>::new_from_bytes(&data, 2, 2).unwrap();
+        let format = PayloadFormat::new(Encoding::L24, 2);
+        let mut payload = [0_u8; 8];
+        assert_eq!(
+            format.pack_raw(buffer.iter_raw_frames(), &mut payload),
+            Ok(1)
+        );
+        assert_eq!(payload[..6], data[..6]);
+
+        let mono = PayloadFormat::new(Encoding::L24, 1);
+        assert_eq!(
+            mono.pack_raw(buffer.iter_raw_frames(), &mut payload),
+            Err(PayloadError::Length {
+                bytes: 6,
+                frame_bytes: 3
+            })
+        );
+    }
+
+    #[test]
+    fn payload_errors() {
+        let format = PayloadFormat::new(Encoding::L24, 2);
+        assert_eq!(format.frames_in(&[0; 12]), Ok(2));
+        assert_eq!(
+            format.frames_in(&[0; 10]),
+            Err(PayloadError::Length {
+                bytes: 10,
+                frame_bytes: 6
+            })
+        );
+        let mut data = [0.0_f32; 3];
+        let mut buffer = SequentialSlice::new_mut(&mut data, 1, 3).unwrap();
+        assert_eq!(
+            format.unpack(&[0; 12], &mut buffer, 0),
+            Err(PayloadError::Channels {
+                expected: 2,
+                actual: 1
+            })
+        );
+    }
+}
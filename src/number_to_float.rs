@@ -8,6 +8,15 @@
 //! There are two wrappers availabe for each sample format,
 //! one for interleaved and one for sequential data.
 //!
+//! ## Float precision
+//! The float type of the wrappers is a type parameter, and can be `f32` or `f64`.
+//! The samples are converted directly between the stored format and this type,
+//! without passing through `f32` on the way.
+//! Wrapping `f64` or [F64LE](crate::sample::F64LE) samples with `f64` as the float type
+//! therefore gives bit-exact access to the stored values.
+//! Values outside the range -1.0 .. +1.0 are clipped when writing,
+//! also for float formats.
+//!
 //! ## Example
 //! Wrap a Vec of 16-bit integer samples as an interleaved buffer
 //! and print all the values.
@@ -63,11 +72,13 @@ use core::mem::size_of;
 
 use num_traits::Float;
 
-use crate::sample::RawSample;
+use crate::cliplog::ClipLog;
+use crate::layout::{Layout, Span};
+use crate::sample::{limit_to_ceiling, Ceiling, ConversionResult, RawSample, RoundingMode};
 use crate::slicetools::copy_within_slice;
 use crate::SizeError;
-use crate::{check_slice_length, implement_size_getters};
-use crate::{Adapter, AdapterMut};
+use crate::{check_slice_length, implement_active_frames, implement_with_dims};
+use crate::{Adapter, AdapterMut, IterationOrder};
 
 /// A macro for creating a view of an immutable slice of bytes
 /// as a different type.
@@ -96,31 +107,31 @@ macro_rules! byte_slice_as_type_mut {
 }
 
 /// A wrapper for a slice containing interleaved numerical samples.
+///
+/// The wrapper is [Send] and [Sync] when the wrapped slice is.
 pub struct InterleavedNumbers<U, V> {
-    _phantom: core::marker::PhantomData<V>,
+    // A function pointer, so that the float type does not affect the auto traits.
+    _phantom: core::marker::PhantomData<fn() -> V>,
     buf: U,
-    frames: usize,
-    channels: usize,
+    layout: Layout,
+    rounding: RoundingMode,
+    ceiling: Option<Ceiling<V>>,
+    clip_log: ClipLog,
 }
 
-/// A wrapper for a slice containing interleaved numerical samples.
+/// A wrapper for a slice containing sequential numerical samples.
+///
+/// The wrapper is [Send] and [Sync] when the wrapped slice is.
 pub struct SequentialNumbers<U, V> {
-    _phantom: core::marker::PhantomData<V>,
+    // A function pointer, so that the float type does not affect the auto traits.
+    _phantom: core::marker::PhantomData<fn() -> V>,
     buf: U,
-    frames: usize,
-    channels: usize,
-}
-
-impl<U, V> InterleavedNumbers<U, V> {
-    fn calc_index(&self, channel: usize, frame: usize) -> usize {
-        frame * self.channels + channel
-    }
-}
-
-impl<U, V> SequentialNumbers<U, V> {
-    fn calc_index(&self, channel: usize, frame: usize) -> usize {
-        frame + channel * self.frames
-    }
+    layout: Layout,
+    // Distance between channels in samples, when given explicitly at creation.
+    channel_stride: Option<usize>,
+    rounding: RoundingMode,
+    ceiling: Option<Ceiling<V>>,
+    clip_log: ClipLog,
 }
 
 impl<'a, U, T> InterleavedNumbers<&'a [U], T>
@@ -139,11 +150,15 @@ where
         Ok(Self {
             _phantom: core::marker::PhantomData,
             buf,
-            frames,
-            channels,
+            layout: Layout::interleaved(channels, frames),
+            rounding: RoundingMode::Truncate,
+            ceiling: None,
+            clip_log: ClipLog::new(),
         })
     }
 
+    implement_with_dims!(with_dims = new(buf: &'a [U]) -> Result<Self, SizeError>);
+
     /// Create a new wrapper for a mutable slice
     /// of numerical samples implementing [RawSample],
     /// stored as raw bytes in _interleaved_ order.
@@ -161,10 +176,75 @@ where
         Ok(Self {
             _phantom: core::marker::PhantomData,
             buf: buf_view,
-            frames,
-            channels,
+            layout: Layout::interleaved(channels, frames),
+            rounding: RoundingMode::Truncate,
+            ceiling: None,
+            clip_log: ClipLog::new(),
         })
     }
+
+    /// Create a new wrapper for a immutable slice
+    /// of numerical samples implementing [RawSample],
+    /// stored as raw bytes in _interleaved_ order,
+    /// where each frame is followed by padding.
+    /// Consecutive frames start `frame_stride_bytes` bytes apart,
+    /// and the bytes following the last channel of each frame are skipped.
+    /// The stride must be a multiple of `core::mem::size_of::<U>()`,
+    /// and at least `core::mem::size_of::<U>() * channels`.
+    /// The slice length must be at least `frame_stride_bytes * frames`.
+    /// It is allowed to be longer than needed,
+    /// but these extra values cannot
+    /// be accessed via the `Adapter` trait methods.
+    pub fn new_from_bytes_with_stride(
+        buf: &'a [u8],
+        channels: usize,
+        frames: usize,
+        frame_stride_bytes: usize,
+    ) -> Result<Self, SizeError> {
+        let frame_stride = stride_in_samples::<U>(channels, frame_stride_bytes)?;
+        check_slice_length!(frame_stride, frames, buf.len(), size_of::<U>());
+        let buf_view = byte_slice_as_type!(buf, U);
+        Ok(Self {
+            _phantom: core::marker::PhantomData,
+            buf: buf_view,
+            layout: Layout::interleaved_with_stride(channels, frames, frame_stride),
+            rounding: RoundingMode::Truncate,
+            ceiling: None,
+            clip_log: ClipLog::new(),
+        })
+    }
+
+    implement_active_frames!(
+        |s| s
+            .buf
+            .len()
+            .checked_div(s.layout.frame_stride)
+            .unwrap_or(usize::MAX),
+        |s, frames| s.layout =
+            Layout::interleaved_with_stride(s.layout.channels, frames, s.layout.frame_stride)
+    );
+
+    /// Replace the wrapped slice with a new one,
+    /// keeping the number of channels and frames.
+    /// This allows a long-lived wrapper to be pointed at a new buffer,
+    /// for example in each call of an audio callback.
+    /// The new slice must fulfill the same length requirements as when creating the wrapper.
+    /// If it does not, the wrapper is left unchanged and an error is returned.
+    pub fn replace_buffer(&mut self, buf: &'a [U]) -> Result<(), SizeError> {
+        check_slice_length!(self.layout.frame_stride, self.layout.frames, buf.len());
+        self.buf = buf;
+        Ok(())
+    }
+
+    /// Iterate over the frames of the wrapped slice as raw bytes.
+    /// Each item holds the samples of one frame,
+    /// `channels * core::mem::size_of::<U>()` bytes, exactly as they are stored.
+    /// Any padding after the last channel of each frame is not included.
+    /// This gives access to the stored data without copying,
+    /// for example for packetizing the frames for network transmission.
+    pub fn iter_raw_frames(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        raw_frames(self.buf, &self.layout)
+    }
 }
 
 impl<'a, U, T> InterleavedNumbers<&'a mut [U], T>
@@ -184,11 +264,15 @@ where
         Ok(Self {
             _phantom: core::marker::PhantomData,
             buf,
-            frames,
-            channels,
+            layout: Layout::interleaved(channels, frames),
+            rounding: RoundingMode::Truncate,
+            ceiling: None,
+            clip_log: ClipLog::new(),
         })
     }
 
+    implement_with_dims!(with_dims_mut = new_mut(buf: &'a mut [U]) -> Result<Self, SizeError>);
+
     /// Create a new wrapper for a mutable slice
     /// of numerical samples implementing [RawSample],
     /// stored as raw bytes in _interleaved_ order.
@@ -206,24 +290,74 @@ where
         Ok(Self {
             _phantom: core::marker::PhantomData,
             buf: buf_view,
-            frames,
-            channels,
+            layout: Layout::interleaved(channels, frames),
+            rounding: RoundingMode::Truncate,
+            ceiling: None,
+            clip_log: ClipLog::new(),
         })
     }
 
-    fn copy_frames_within_impl(&mut self, src: usize, dest: usize, count: usize) -> Option<usize> {
-        if src + count > self.frames || dest + count > self.frames {
-            return None;
-        }
-        unsafe {
-            copy_within_slice(
-                self.buf,
-                src * self.channels,
-                dest * self.channels,
-                count * self.channels,
-            );
-        }
-        Some(count)
+    /// Create a new wrapper for a mutable slice
+    /// of numerical samples implementing [RawSample],
+    /// stored as raw bytes in _interleaved_ order,
+    /// where each frame is followed by padding.
+    /// Consecutive frames start `frame_stride_bytes` bytes apart,
+    /// and the bytes following the last channel of each frame are skipped.
+    /// The stride must be a multiple of `core::mem::size_of::<U>()`,
+    /// and at least `core::mem::size_of::<U>() * channels`.
+    /// The slice length must be at least `frame_stride_bytes * frames`.
+    /// It is allowed to be longer than needed,
+    /// but these extra values cannot
+    /// be accessed via the `Adapter` or `AdapterMut` trait methods.
+    pub fn new_from_bytes_with_stride_mut(
+        buf: &'a mut [u8],
+        channels: usize,
+        frames: usize,
+        frame_stride_bytes: usize,
+    ) -> Result<Self, SizeError> {
+        let frame_stride = stride_in_samples::<U>(channels, frame_stride_bytes)?;
+        check_slice_length!(frame_stride, frames, buf.len(), size_of::<U>());
+        let buf_view = byte_slice_as_type_mut!(buf, U);
+        Ok(Self {
+            _phantom: core::marker::PhantomData,
+            buf: buf_view,
+            layout: Layout::interleaved_with_stride(channels, frames, frame_stride),
+            rounding: RoundingMode::Truncate,
+            ceiling: None,
+            clip_log: ClipLog::new(),
+        })
+    }
+
+    implement_active_frames!(
+        |s| s
+            .buf
+            .len()
+            .checked_div(s.layout.frame_stride)
+            .unwrap_or(usize::MAX),
+        |s, frames| s.layout =
+            Layout::interleaved_with_stride(s.layout.channels, frames, s.layout.frame_stride)
+    );
+
+    /// Replace the wrapped slice with a new one,
+    /// keeping the number of channels and frames.
+    /// This allows a long-lived wrapper to be pointed at a new buffer,
+    /// for example in each call of an audio callback.
+    /// The new slice must fulfill the same length requirements as when creating the wrapper.
+    /// If it does not, the wrapper is left unchanged and an error is returned.
+    pub fn replace_buffer(&mut self, buf: &'a mut [U]) -> Result<(), SizeError> {
+        check_slice_length!(self.layout.frame_stride, self.layout.frames, buf.len());
+        self.buf = buf;
+        Ok(())
+    }
+
+    /// Iterate over the frames of the wrapped slice as raw bytes.
+    /// Each item holds the samples of one frame,
+    /// `channels * core::mem::size_of::<U>()` bytes, exactly as they are stored.
+    /// Any padding after the last channel of each frame is not included.
+    /// This gives access to the stored data without copying,
+    /// for example for packetizing the frames for network transmission.
+    pub fn iter_raw_frames(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        raw_frames(&*self.buf, &self.layout)
     }
 }
 
@@ -243,11 +377,16 @@ where
         Ok(Self {
             _phantom: core::marker::PhantomData,
             buf,
-            frames,
-            channels,
+            layout: Layout::sequential(channels, frames),
+            channel_stride: None,
+            rounding: RoundingMode::Truncate,
+            ceiling: None,
+            clip_log: ClipLog::new(),
         })
     }
 
+    implement_with_dims!(with_dims = new(buf: &'a [U]) -> Result<Self, SizeError>);
+
     /// Create a new wrapper for a mutable slice
     /// of numerical samples implementing [RawSample],
     /// stored as raw bytes in _sequential_ order.
@@ -265,10 +404,75 @@ where
         Ok(Self {
             _phantom: core::marker::PhantomData,
             buf: buf_view,
-            frames,
-            channels,
+            layout: Layout::sequential(channels, frames),
+            channel_stride: None,
+            rounding: RoundingMode::Truncate,
+            ceiling: None,
+            clip_log: ClipLog::new(),
+        })
+    }
+
+    /// Create a new wrapper for a immutable slice
+    /// of numerical samples implementing [RawSample],
+    /// stored as raw bytes in _sequential_ order,
+    /// where each channel is followed by padding.
+    /// Consecutive channels start `channel_stride_bytes` bytes apart,
+    /// and the bytes following the last frame of each channel are skipped.
+    /// The stride must be a multiple of `core::mem::size_of::<U>()`,
+    /// and at least `core::mem::size_of::<U>() * frames`.
+    /// The slice length must be at least `channel_stride_bytes * channels`.
+    /// It is allowed to be longer than needed,
+    /// but these extra values cannot
+    /// be accessed via the `Adapter` trait methods.
+    pub fn new_from_bytes_with_stride(
+        buf: &'a [u8],
+        channels: usize,
+        frames: usize,
+        channel_stride_bytes: usize,
+    ) -> Result<Self, SizeError> {
+        let channel_stride = stride_in_samples::<U>(frames, channel_stride_bytes)?;
+        check_slice_length!(channels, channel_stride, buf.len(), size_of::<U>());
+        let buf_view = byte_slice_as_type!(buf, U);
+        Ok(Self {
+            _phantom: core::marker::PhantomData,
+            buf: buf_view,
+            layout: Layout::sequential_with_stride(channels, frames, channel_stride),
+            channel_stride: Some(channel_stride),
+            rounding: RoundingMode::Truncate,
+            ceiling: None,
+            clip_log: ClipLog::new(),
         })
     }
+
+    implement_active_frames!(
+        |s| s.channel_stride.unwrap_or_else(|| {
+            s.buf
+                .len()
+                .checked_div(s.layout.channels)
+                .unwrap_or(usize::MAX)
+        }),
+        |s, frames| s.layout = Layout::sequential_with_stride(
+            s.layout.channels,
+            frames,
+            s.channel_stride.unwrap_or(frames)
+        )
+    );
+
+    /// Replace the wrapped slice with a new one,
+    /// keeping the number of channels and frames.
+    /// This allows a long-lived wrapper to be pointed at a new buffer,
+    /// for example in each call of an audio callback.
+    /// The new slice must fulfill the same length requirements as when creating the wrapper.
+    /// If it does not, the wrapper is left unchanged and an error is returned.
+    pub fn replace_buffer(&mut self, buf: &'a [U]) -> Result<(), SizeError> {
+        check_slice_length!(
+            self.layout.channels,
+            self.channel_stride.unwrap_or(self.layout.frames),
+            buf.len()
+        );
+        self.buf = buf;
+        Ok(())
+    }
 }
 
 impl<'a, U, T> SequentialNumbers<&'a mut [U], T>
@@ -288,11 +492,16 @@ where
         Ok(Self {
             _phantom: core::marker::PhantomData,
             buf,
-            frames,
-            channels,
+            layout: Layout::sequential(channels, frames),
+            channel_stride: None,
+            rounding: RoundingMode::Truncate,
+            ceiling: None,
+            clip_log: ClipLog::new(),
         })
     }
 
+    implement_with_dims!(with_dims_mut = new_mut(buf: &'a mut [U]) -> Result<Self, SizeError>);
+
     /// Create a new wrapper for a mutable slice
     /// of numerical samples implementing [RawSample],
     /// stored as raw bytes in _sequential_ order.
@@ -310,23 +519,105 @@ where
         Ok(Self {
             _phantom: core::marker::PhantomData,
             buf: buf_view,
-            frames,
-            channels,
+            layout: Layout::sequential(channels, frames),
+            channel_stride: None,
+            rounding: RoundingMode::Truncate,
+            ceiling: None,
+            clip_log: ClipLog::new(),
         })
     }
 
-    fn copy_frames_within_impl(&mut self, src: usize, dest: usize, count: usize) -> Option<usize> {
-        if src + count > self.frames || dest + count > self.frames {
-            return None;
-        }
-        for ch in 0..self.channels {
-            let offset = ch * self.frames;
-            unsafe {
-                copy_within_slice(self.buf, src + offset, dest + offset, count);
-            }
-        }
-        Some(count)
+    /// Create a new wrapper for a mutable slice
+    /// of numerical samples implementing [RawSample],
+    /// stored as raw bytes in _sequential_ order,
+    /// where each channel is followed by padding.
+    /// Consecutive channels start `channel_stride_bytes` bytes apart,
+    /// and the bytes following the last frame of each channel are skipped.
+    /// The stride must be a multiple of `core::mem::size_of::<U>()`,
+    /// and at least `core::mem::size_of::<U>() * frames`.
+    /// The slice length must be at least `channel_stride_bytes * channels`.
+    /// It is allowed to be longer than needed,
+    /// but these extra values cannot
+    /// be accessed via the `Adapter` or `AdapterMut` trait methods.
+    pub fn new_from_bytes_with_stride_mut(
+        buf: &'a mut [u8],
+        channels: usize,
+        frames: usize,
+        channel_stride_bytes: usize,
+    ) -> Result<Self, SizeError> {
+        let channel_stride = stride_in_samples::<U>(frames, channel_stride_bytes)?;
+        check_slice_length!(channels, channel_stride, buf.len(), size_of::<U>());
+        let buf_view = byte_slice_as_type_mut!(buf, U);
+        Ok(Self {
+            _phantom: core::marker::PhantomData,
+            buf: buf_view,
+            layout: Layout::sequential_with_stride(channels, frames, channel_stride),
+            channel_stride: Some(channel_stride),
+            rounding: RoundingMode::Truncate,
+            ceiling: None,
+            clip_log: ClipLog::new(),
+        })
+    }
+
+    implement_active_frames!(
+        |s| s.channel_stride.unwrap_or_else(|| {
+            s.buf
+                .len()
+                .checked_div(s.layout.channels)
+                .unwrap_or(usize::MAX)
+        }),
+        |s, frames| s.layout = Layout::sequential_with_stride(
+            s.layout.channels,
+            frames,
+            s.channel_stride.unwrap_or(frames)
+        )
+    );
+
+    /// Replace the wrapped slice with a new one,
+    /// keeping the number of channels and frames.
+    /// This allows a long-lived wrapper to be pointed at a new buffer,
+    /// for example in each call of an audio callback.
+    /// The new slice must fulfill the same length requirements as when creating the wrapper.
+    /// If it does not, the wrapper is left unchanged and an error is returned.
+    pub fn replace_buffer(&mut self, buf: &'a mut [U]) -> Result<(), SizeError> {
+        check_slice_length!(
+            self.layout.channels,
+            self.channel_stride.unwrap_or(self.layout.frames),
+            buf.len()
+        );
+        self.buf = buf;
+        Ok(())
+    }
+}
+
+/// Convert a stride in bytes to a number of samples of type `U`.
+/// Returns a [SizeError] if the stride is not a whole number of samples,
+/// or if it is too short to hold `samples` samples.
+fn stride_in_samples<U>(samples: usize, stride_bytes: usize) -> Result<usize, SizeError> {
+    // A required stride that overflows can never be reached.
+    let required = samples.saturating_mul(size_of::<U>());
+    if stride_bytes < required || stride_bytes % size_of::<U>() != 0 {
+        return Err(SizeError::Stride {
+            actual: stride_bytes,
+            required,
+        });
     }
+    Ok(stride_bytes / size_of::<U>())
+}
+
+/// Iterate over the frames of an interleaved slice as raw bytes.
+fn raw_frames<'b, U>(buf: &'b [U], layout: &Layout) -> impl Iterator<Item = &'b [u8]> {
+    let sample_bytes = size_of::<U>();
+    let frame_bytes = layout.channels * sample_bytes;
+    // The wrappers are created from slices of bytes viewed as samples,
+    // and the samples are therefore also valid to view as bytes.
+    let bytes = unsafe {
+        core::slice::from_raw_parts(buf.as_ptr() as *const u8, core::mem::size_of_val(buf))
+    };
+    bytes
+        .chunks((layout.frame_stride * sample_bytes).max(1))
+        .take(layout.frames)
+        .map(move |frame| &frame[..frame_bytes])
 }
 
 macro_rules! impl_traits_newtype {
@@ -337,11 +628,12 @@ macro_rules! impl_traits_newtype {
             U: RawSample,
         {
             unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
-                let index = self.calc_index(channel, frame);
+                let index = self.layout.index(channel, frame);
                 self.buf[index].to_scaled_float()
             }
 
-            implement_size_getters!();
+            implement_layout_getters!();
+            implement_converting_reads!();
         }
 
         impl<'a, T, U> Adapter<'a, T> for $structname<&'a mut [U], T>
@@ -350,11 +642,12 @@ macro_rules! impl_traits_newtype {
             U: RawSample,
         {
             unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
-                let index = self.calc_index(channel, frame);
+                let index = self.layout.index(channel, frame);
                 self.buf[index].to_scaled_float()
             }
 
-            implement_size_getters!();
+            implement_layout_getters!();
+            implement_converting_reads!();
         }
 
         impl<'a, T, U> AdapterMut<'a, T> for $structname<&'a mut [U], T>
@@ -368,10 +661,54 @@ macro_rules! impl_traits_newtype {
                 frame: usize,
                 value: &T,
             ) -> bool {
-                let index = self.calc_index(channel, frame);
-                let converted = U::from_scaled_float(*value);
+                let index = self.layout.index(channel, frame);
+                let (value, limited) = limit_to_ceiling(*value, self.ceiling);
+                let converted = convert_rounded::<T, U>(value, self.rounding);
                 self.buf[index] = converted.value;
-                converted.clipped
+                let clipped = limited || converted.clipped;
+                self.clip_log.report(clipped as usize, channel, frame);
+                if clipped {
+                    self.clip_log.record(channel, frame);
+                }
+                clipped
+            }
+
+            fn write_from_slice_to_channel(
+                &mut self,
+                channel: usize,
+                skip: usize,
+                slice: &[T],
+            ) -> (usize, usize) {
+                match self.layout.channel_span(channel, skip, slice.len()) {
+                    Some(span) => {
+                        let nbr_clipped = self.write_span(span, slice);
+                        self.clip_log.report(nbr_clipped, channel, skip);
+                        if nbr_clipped > 0 && self.clip_log.is_recording() {
+                            self.record_clipped(&slice[..span.count], |n| (channel, skip + n));
+                        }
+                        (span.count, nbr_clipped)
+                    }
+                    None => (0, 0),
+                }
+            }
+
+            fn write_from_slice_to_frame(
+                &mut self,
+                frame: usize,
+                skip: usize,
+                slice: &[T],
+            ) -> (usize, usize) {
+                match self.layout.frame_span(frame, skip, slice.len()) {
+                    Some(span) => {
+                        let nbr_clipped = self.write_span(span, slice);
+                        self.clip_log.report(nbr_clipped, skip, frame);
+                        if nbr_clipped > 0 && self.clip_log.is_recording() {
+                            self.record_clipped(&slice[..span.count], |n| (skip + n, frame));
+                        }
+                        (span.count, nbr_clipped)
+                    }
+                    None => (0, 0),
+                }
             }
 
             fn copy_frames_within(
@@ -380,7 +717,280 @@ macro_rules! impl_traits_newtype {
                 dest: usize,
                 count: usize,
             ) -> Option<usize> {
-                self.copy_frames_within_impl(src, dest, count)
+                if !self.layout.copy_is_valid(src, dest, count) {
+                    return None;
+                }
+                for (block_src, block_dest, block_count) in
+                    self.layout.copy_blocks(src, dest, count)
+                {
+                    unsafe {
+                        copy_within_slice(self.buf, block_src, block_dest, block_count);
+                    }
+                }
+                Some(count)
+            }
+        }
+
+        impl<U, T> $structname<U, T> {
+            /// Set the rounding mode used when writing float values to integer samples.
+            /// The default is [RoundingMode::Truncate].
+            pub fn set_rounding(&mut self, rounding: RoundingMode) {
+                self.rounding = rounding;
+            }
+
+            /// Get the rounding mode used when writing float values to integer samples.
+            pub fn rounding(&self) -> RoundingMode {
+                self.rounding
+            }
+
+            /// Enable or disable logging a warning when values are clipped while writing.
+            /// Only the first clipping is logged, and calling this method rearms the warning.
+            /// Logging is enabled by default.
+            #[cfg(feature = "log")]
+            pub fn set_log_clipping(&mut self, enabled: bool) {
+                self.clip_log.set_enabled(enabled);
+            }
+
+            /// Check if logging of clipped values is enabled.
+            #[cfg(feature = "log")]
+            pub fn log_clipping(&self) -> bool {
+                self.clip_log.is_enabled()
+            }
+
+            /// Enable recording of the positions of clipped samples when writing.
+            /// For each channel, the frame indices of the first `max_per_channel`
+            /// clipped samples are recorded, and can be read with `clip_positions`.
+            /// A value of zero disables the recording, which is the default.
+            /// This also clears any previously recorded positions.
+            #[cfg(feature = "std")]
+            pub fn set_record_clip_positions(&mut self, max_per_channel: usize) {
+                self.clip_log.set_max_positions(max_per_channel);
+            }
+
+            /// Get the recorded frame indices of the clipped samples of a channel,
+            /// in the order they were written.
+            #[cfg(feature = "std")]
+            pub fn clip_positions(&self, channel: usize) -> &[usize] {
+                self.clip_log.positions(channel)
+            }
+
+            /// Clear the recorded positions of clipped samples.
+            #[cfg(feature = "std")]
+            pub fn clear_clip_positions(&mut self) {
+                self.clip_log.clear_positions();
+            }
+        }
+
+        impl<U, T> $structname<U, T>
+        where
+            T: Float,
+        {
+            /// Set a ceiling in dBFS for the values written to the buffer,
+            /// for example `-1.0` to keep the output 1 dB below full scale.
+            /// Written values above the ceiling are clamped to it,
+            /// and are counted as clipped.
+            /// Setting `None` removes the ceiling, which is the default.
+            pub fn set_ceiling_dbfs(&mut self, ceiling: Option<T>) {
+                self.ceiling = ceiling.map(Ceiling::from_dbfs);
+            }
+
+            /// Get the ceiling in dBFS for the values written to the buffer.
+            pub fn ceiling_dbfs(&self) -> Option<T> {
+                self.ceiling.map(|ceiling| ceiling.dbfs())
+            }
+        }
+
+        impl<'a, T, U> $structname<&'a mut [U], T>
+        where
+            T: Float + 'a,
+            U: RawSample + Clone,
+        {
+            /// Record the positions of the values of a slice that are clipped when converted.
+            /// The `position` closure gives the channel and frame for each index in the slice.
+            fn record_clipped(&mut self, values: &[T], position: impl Fn(usize) -> (usize, usize)) {
+                for (n, value) in values.iter().enumerate() {
+                    let (value, limited) = limit_to_ceiling(*value, self.ceiling);
+                    if limited
+                        || U::from_scaled_float(U::round_scaled(value, self.rounding)).clipped
+                    {
+                        let (channel, frame) = position(n);
+                        self.clip_log.record(channel, frame);
+                    }
+                }
+            }
+
+            /// Convert and write the values of a slice to a span of samples.
+            /// The samples are accessed via iterators over the buffer,
+            /// to avoid a bounds check for every sample.
+            fn write_span(&mut self, span: Span, slice: &[T]) -> usize {
+                if span.count == 0 {
+                    return 0;
+                }
+                let values = &slice[..span.count];
+                let end = span.index(span.count - 1) + 1;
+                let samples = &mut self.buf[span.start..end];
+                match span.stride {
+                    1 => convert_and_write(samples.iter_mut(), values, self.rounding, self.ceiling),
+                    // A channel of interleaved stereo, the most common case.
+                    2 if self.ceiling.is_none() => {
+                        convert_and_write_stereo(samples, values, self.rounding)
+                    }
+                    _ => convert_and_write(
+                        samples.iter_mut().step_by(span.stride),
+                        values,
+                        self.rounding,
+                        self.ceiling,
+                    ),
+                }
+            }
+        }
+    };
+}
+
+/// Round and convert a float value to a sample value, before it is written to a buffer.
+/// With the `verify-roundtrip` feature, this panics if the sample does not round-trip.
+#[inline]
+fn convert_rounded<T, U>(value: T, rounding: RoundingMode) -> ConversionResult<U>
+where
+    T: Float,
+    U: RawSample,
+{
+    let converted = U::from_scaled_float(U::round_scaled(value, rounding));
+    #[cfg(feature = "verify-roundtrip")]
+    crate::roundtrip::verify_stored::<T, U>(&converted.value);
+    converted
+}
+
+/// Convert float values and write them to the samples of an iterator.
+/// Returns the number of values that were clipped.
+#[inline]
+fn convert_and_write<'b, T, U>(
+    samples: impl Iterator<Item = &'b mut U>,
+    values: &[T],
+    rounding: RoundingMode,
+    ceiling: Option<Ceiling<T>>,
+) -> usize
+where
+    T: Float,
+    U: RawSample + 'b,
+{
+    let mut nbr_clipped = 0;
+    if let Some(ceiling) = ceiling {
+        for (sample, value) in samples.zip(values) {
+            let (value, limited) = ceiling.limit(*value);
+            let converted = convert_rounded::<T, U>(value, rounding);
+            *sample = converted.value;
+            nbr_clipped += (limited || converted.clipped) as usize;
+        }
+        return nbr_clipped;
+    }
+    for (sample, value) in samples.zip(values) {
+        let converted = convert_rounded::<T, U>(*value, rounding);
+        *sample = converted.value;
+        nbr_clipped += converted.clipped as usize;
+    }
+    nbr_clipped
+}
+
+/// Convert float values and write them to every other sample of a slice,
+/// as when writing to a channel of an interleaved stereo buffer.
+/// The loop is unrolled to handle two values per iteration.
+/// Returns the number of values that were clipped.
+#[inline]
+fn convert_and_write_stereo<T, U>(samples: &mut [U], values: &[T], rounding: RoundingMode) -> usize
+where
+    T: Float,
+    U: RawSample,
+{
+    let mut nbr_clipped = 0;
+    let pairs = values.chunks_exact(2);
+    let remainder = pairs.remainder();
+    for (chunk, pair) in samples.chunks_mut(4).zip(pairs) {
+        let first = convert_rounded::<T, U>(pair[0], rounding);
+        let second = convert_rounded::<T, U>(pair[1], rounding);
+        chunk[0] = first.value;
+        chunk[2] = second.value;
+        nbr_clipped += first.clipped as usize + second.clipped as usize;
+    }
+    if let Some(value) = remainder.first() {
+        let converted = convert_rounded::<T, U>(*value, rounding);
+        samples[2 * values.len() - 2] = converted.value;
+        nbr_clipped += converted.clipped as usize;
+    }
+    nbr_clipped
+}
+
+/// Read and convert every other sample of a slice to float values,
+/// as when reading from a channel of an interleaved stereo buffer.
+/// The loop is unrolled to handle two values per iteration.
+#[inline]
+fn read_and_convert_stereo<T, U>(samples: &[U], values: &mut [T])
+where
+    T: Float,
+    U: RawSample,
+{
+    for (chunk, pair) in samples.chunks(4).zip(values.chunks_exact_mut(2)) {
+        pair[0] = chunk[0].to_scaled_float();
+        pair[1] = chunk[2].to_scaled_float();
+    }
+    let count = values.len();
+    if count % 2 == 1 {
+        values[count - 1] = samples[2 * count - 2].to_scaled_float();
+    }
+}
+
+/// Size getters for wrappers storing their dimensions in a [Layout].
+macro_rules! implement_layout_getters {
+    () => {
+        fn channels(&self) -> usize {
+            self.layout.channels
+        }
+
+        fn frames(&self) -> usize {
+            self.layout.frames
+        }
+
+        fn preferred_iteration(&self) -> IterationOrder {
+            self.layout.preferred_iteration()
+        }
+    };
+}
+
+/// Reads to slices for wrappers storing their dimensions in a [Layout].
+macro_rules! implement_converting_reads {
+    () => {
+        fn write_from_channel_to_slice(
+            &self,
+            channel: usize,
+            skip: usize,
+            slice: &mut [T],
+        ) -> usize {
+            match self.layout.channel_span(channel, skip, slice.len()) {
+                Some(span) if span.stride == 2 && span.count > 0 => {
+                    // A channel of interleaved stereo, the most common case.
+                    let end = span.index(span.count - 1) + 1;
+                    read_and_convert_stereo(&self.buf[span.start..end], &mut slice[..span.count]);
+                    span.count
+                }
+                Some(span) => {
+                    for (n, item) in slice.iter_mut().take(span.count).enumerate() {
+                        *item = self.buf[span.index(n)].to_scaled_float();
+                    }
+                    span.count
+                }
+                None => 0,
+            }
+        }
+
+        fn write_from_frame_to_slice(&self, frame: usize, skip: usize, slice: &mut [T]) -> usize {
+            match self.layout.frame_span(frame, skip, slice.len()) {
+                Some(span) => {
+                    for (n, item) in slice.iter_mut().take(span.count).enumerate() {
+                        *item = self.buf[span.index(n)].to_scaled_float();
+                    }
+                    span.count
+                }
+                None => 0,
             }
         }
     };
@@ -398,7 +1008,10 @@ impl_traits_newtype!(SequentialNumbers);
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::sample::{I16LE, I24LE};
+    use crate::sample::{
+        I24BEPacked, I24BEPadded, I24InHighBE, I24InHighLE, I24InLowBE, I24InLowLE, I24LEPacked,
+        I24LEPadded, U24BEPacked, U24BEPadded, U24LEPacked, U24LEPadded, F64LE, I16LE, I24LE,
+    };
 
     #[test]
     fn read_i32() {
@@ -513,15 +1126,190 @@ mod tests {
         assert_eq!(values_right, expected_right);
     }
 
+    #[test]
+    fn slice_channel_and_frame_sequential() {
+        let data: [i16; 6] = [0, 1 << 14, 1 << 13, i16::MIN, -(1 << 14), -(1 << 13)];
+        let buffer = SequentialNumbers::<_, f32>::new(&data, 2, 3).unwrap();
+        let mut values = [0.0; 4];
+        assert_eq!(buffer.write_from_channel_to_slice(1, 1, &mut values), 2);
+        assert_eq!(values, [-0.5, -0.25, 0.0, 0.0]);
+        assert_eq!(buffer.write_from_frame_to_slice(1, 0, &mut values), 2);
+        assert_eq!(values, [0.5, -0.5, 0.0, 0.0]);
+        assert_eq!(buffer.write_from_frame_to_slice(3, 0, &mut values), 0);
+    }
+
+    #[test]
+    fn to_frame_with_clipping() {
+        let mut data = [0_i16; 6];
+        let mut buffer = InterleavedNumbers::<_, f32>::new_mut(&mut data, 2, 3).unwrap();
+        assert_eq!(
+            buffer.write_from_slice_to_frame(1, 0, &[0.5, 2.0, 1.0]),
+            (2, 1)
+        );
+        assert_eq!(data, [0, 0, 1 << 14, i16::MAX, 0, 0]);
+    }
+
+    #[test]
+    fn stereo_channels() {
+        for frames in [0, 1, 2, 3, 4, 7] {
+            if frames == 0 && cfg!(feature = "reject-zero-dimensions") {
+                continue;
+            }
+            let values: Vec<f32> = (0..frames).map(|n| n as f32 / 8.0 - 0.25).collect();
+            let mut data = vec![0_i16; 2 * frames + 2];
+            let mut buffer = InterleavedNumbers::<_, f32>::new_mut(&mut data, 2, frames).unwrap();
+            assert_eq!(
+                buffer.write_from_slice_to_channel(1, 0, &values),
+                (frames, 0)
+            );
+            let mut read_back = vec![0.0; frames];
+            assert_eq!(
+                buffer.write_from_channel_to_slice(1, 0, &mut read_back),
+                frames
+            );
+            assert_eq!(read_back, values);
+            assert!(data[..2 * frames]
+                .iter()
+                .step_by(2)
+                .all(|value| *value == 0));
+            assert_eq!(data[2 * frames..], [0, 0]);
+        }
+    }
+
+    #[test]
+    fn stereo_channel_with_clipping() {
+        let mut data = [0_i16; 6];
+        let mut buffer = InterleavedNumbers::<_, f32>::new_mut(&mut data, 2, 3).unwrap();
+        assert_eq!(
+            buffer.write_from_slice_to_channel(0, 1, &[2.0, -2.0, 1.0]),
+            (2, 2)
+        );
+        assert_eq!(data, [0, 0, i16::MAX, 0, i16::MIN, 0]);
+    }
+
+    #[test]
+    fn write_with_ceiling() {
+        let mut data = [0_i16; 6];
+        let mut buffer = InterleavedNumbers::<_, f32>::new_mut(&mut data, 2, 3).unwrap();
+        buffer.set_ceiling_dbfs(Some(20.0 * 0.5_f32.log10()));
+        assert!(buffer.ceiling_dbfs().is_some());
+        assert_eq!(
+            buffer.write_from_slice_to_channel(0, 0, &[0.25, 0.75, -1.0]),
+            (3, 2)
+        );
+        assert_eq!(buffer.write_sample(1, 0, &2.0), Some(true));
+        assert_eq!(buffer.write_sample(1, 1, &-0.25), Some(false));
+        buffer.set_ceiling_dbfs(None);
+        assert_eq!(buffer.write_sample(1, 2, &0.75), Some(false));
+        assert_eq!(data[0], 1 << 13);
+        assert!((data[2] - (1 << 14)).abs() <= 1);
+        assert!((data[4] + (1 << 14)).abs() <= 1);
+        assert!((data[1] - (1 << 14)).abs() <= 1);
+        assert_eq!(data[3], -(1 << 13));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn record_clip_positions() {
+        let mut data = [0_i16; 8];
+        let mut buffer = SequentialNumbers::<_, f32>::new_mut(&mut data, 2, 4).unwrap();
+        buffer.write_from_slice_to_channel(0, 0, &[2.0, 0.0, -2.0, 2.0]);
+        assert_eq!(buffer.clip_positions(0), &[] as &[usize]);
+        buffer.set_record_clip_positions(2);
+        buffer.write_from_slice_to_channel(0, 1, &[2.0, 0.0, -2.0]);
+        buffer.write_from_slice_to_frame(0, 0, &[0.0, 1.5]);
+        buffer.write_sample(0, 0, &-3.0).unwrap();
+        assert_eq!(buffer.clip_positions(0), &[1, 3]);
+        assert_eq!(buffer.clip_positions(1), &[0]);
+        buffer.clear_clip_positions();
+        assert_eq!(buffer.clip_positions(0), &[] as &[usize]);
+    }
+
+    #[test]
+    fn active_frames() {
+        let data: [i16; 6] = [0, 1, 2, 3, 4, 5];
+        let mut buffer: SequentialNumbers<&[i16], f32> =
+            SequentialNumbers::new(&data, 2, 2).unwrap();
+        assert_eq!(buffer.capacity_frames(), 3);
+        buffer.set_active_frames(3).unwrap();
+        assert_eq!(buffer.frames(), 3);
+        assert_eq!(buffer.read_sample(1, 0), Some(3.0 / 32768.0));
+        assert!(buffer.set_active_frames(4).is_err());
+
+        let mut data: [i16; 6] = [0, 1, 2, 3, 4, 5];
+        let mut buffer: InterleavedNumbers<&mut [i16], f32> =
+            InterleavedNumbers::new_mut(&mut data, 3, 1).unwrap();
+        assert_eq!(buffer.capacity_frames(), 2);
+        buffer.set_active_frames(2).unwrap();
+        assert_eq!(buffer.read_sample(0, 1), Some(3.0 / 32768.0));
+    }
+
+    #[test]
+    fn replace_buffer() {
+        let first: [i16; 4] = [0, 1, 2, 3];
+        let second: [i16; 4] = [4, 5, 6, 7];
+        let mut buffer: InterleavedNumbers<&[i16], f32> =
+            InterleavedNumbers::new(&first, 2, 2).unwrap();
+        buffer.replace_buffer(&second).unwrap();
+        assert_eq!(buffer.read_sample(1, 0), Some(5.0 / 32768.0));
+        assert!(buffer.replace_buffer(&first[..3]).is_err());
+        assert_eq!(buffer.read_sample(1, 0), Some(5.0 / 32768.0));
+    }
+
+    #[test]
+    fn copy_within_interleaved() {
+        let mut data = [0_u32; 20];
+        let mut buffer = InterleavedNumbers::<_, f32>::new_mut(&mut data, 2, 10).unwrap();
+        check_copy_within_converted(&mut buffer);
+    }
+
+    #[test]
+    fn copy_within_sequential() {
+        let mut data = [0_u32; 20];
+        let mut buffer = SequentialNumbers::<_, f32>::new_mut(&mut data, 2, 10).unwrap();
+        check_copy_within_converted(&mut buffer);
+    }
+
+    fn check_copy_within_converted(buffer: &mut dyn AdapterMut<'_, f32>) {
+        for channel in 0..buffer.channels() {
+            for frame in 0..buffer.frames() {
+                let value = (channel * 10 + frame) as f32 / 100.0;
+                buffer.write_sample(channel, frame, &value).unwrap();
+            }
+        }
+        assert_eq!(buffer.copy_frames_within(1, 5, 3), Some(3));
+        for channel in 0..buffer.channels() {
+            for frame in 0..buffer.frames() {
+                let source = if (5..8).contains(&frame) {
+                    frame - 4
+                } else {
+                    frame
+                };
+                let expected = (channel * 10 + source) as f32 / 100.0;
+                let value = buffer.read_sample(channel, frame).unwrap();
+                assert!((value - expected).abs() < 1.0e-6);
+            }
+        }
+        assert_eq!(buffer.copy_frames_within(8, 0, 3), None);
+    }
+
     // Check that a buffer is Send + Sync,
     // meaning it can be sent between threads.
     // This test is not designed to be run, only to compile.
     #[allow(dead_code)]
-    fn test_adapter_send_and_sync<T: Sync + Send + Clone>() {
+    fn test_adapter_send_and_sync() {
         fn is_send<T: Send>() {}
         fn is_sync<T: Sync>() {}
         is_send::<InterleavedNumbers<&[i32], f32>>();
         is_sync::<InterleavedNumbers<&[i32], f32>>();
+        is_send::<InterleavedNumbers<&mut [i16], f64>>();
+        is_sync::<InterleavedNumbers<&mut [i16], f64>>();
+        is_send::<InterleavedNumbers<&[I24LE<3>], f32>>();
+        is_sync::<InterleavedNumbers<&mut [I16LE], f32>>();
+        is_send::<SequentialNumbers<&[i32], f32>>();
+        is_sync::<SequentialNumbers<&[i32], f32>>();
+        is_send::<SequentialNumbers<&mut [I24InHighLE], f64>>();
+        is_sync::<SequentialNumbers<&mut [I24InHighLE], f64>>();
     }
 
     #[test]
@@ -551,6 +1339,169 @@ mod tests {
         assert_eq!(data, expected);
     }
 
+    #[test]
+    fn read_write_i16_bytes_with_padding() {
+        // 2 channels in frames of 6 bytes, the last 2 bytes of each frame are padding.
+        let mut data: [u8; 18] = [0, 128, 0, 64, 1, 1, 0, 192, 0, 32, 2, 2, 0, 0, 0, 0, 3, 3];
+        let mut buffer = InterleavedNumbers::<&mut [I16LE], f32>::new_from_bytes_with_stride_mut(
+            &mut data, 2, 3, 6,
+        )
+        .unwrap();
+        assert_eq!(buffer.read_sample(1, 0).unwrap(), 0.5);
+        assert_eq!(buffer.read_sample(0, 1).unwrap(), -0.5);
+        let mut values = [0.0; 3];
+        assert_eq!(buffer.write_from_channel_to_slice(1, 0, &mut values), 3);
+        assert_eq!(values, [0.5, 0.25, 0.0]);
+        assert_eq!(
+            buffer.write_from_slice_to_channel(0, 1, &[0.5, -1.0]),
+            (2, 0)
+        );
+        buffer.copy_frames_within(1, 0, 1).unwrap();
+        assert_eq!(buffer.capacity_frames(), 3);
+        assert_eq!(
+            data,
+            [0, 64, 0, 32, 1, 1, 0, 64, 0, 32, 2, 2, 0, 128, 0, 0, 3, 3]
+        );
+        // The padding stays in place also when overlapping frames are moved forward.
+        let mut buffer = InterleavedNumbers::<&mut [I16LE], f32>::new_from_bytes_with_stride_mut(
+            &mut data, 2, 3, 6,
+        )
+        .unwrap();
+        buffer.write_sample(0, 0, &-1.0).unwrap();
+        buffer.copy_frames_within(0, 1, 2).unwrap();
+        assert_eq!(
+            data,
+            [0, 128, 0, 32, 1, 1, 0, 128, 0, 32, 2, 2, 0, 64, 0, 32, 3, 3]
+        );
+    }
+
+    #[test]
+    fn invalid_frame_stride() {
+        let data = [0_u8; 24];
+        assert!(matches!(
+            InterleavedNumbers::<&[I16LE], f32>::new_from_bytes_with_stride(&data, 2, 4, 3),
+            Err(SizeError::Stride {
+                actual: 3,
+                required: 4
+            })
+        ));
+        assert!(matches!(
+            InterleavedNumbers::<&[I16LE], f32>::new_from_bytes_with_stride(&data, 2, 4, 7),
+            Err(SizeError::Stride { .. })
+        ));
+        assert!(matches!(
+            InterleavedNumbers::<&[I16LE], f32>::new_from_bytes_with_stride(&data, 2, 4, 8),
+            Err(SizeError::Total {
+                actual: 24,
+                required: 32
+            })
+        ));
+    }
+
+    #[test]
+    fn size_overflow() {
+        let data = [0_u8; 24];
+        let channels = usize::MAX / 4 + 1;
+        assert!(matches!(
+            InterleavedNumbers::<&[I16LE], f32>::new_from_bytes(&data, channels, 2),
+            Err(SizeError::Overflow { frames: 2, .. })
+        ));
+        assert!(matches!(
+            InterleavedNumbers::<&[I16LE], f32>::new_from_bytes_with_stride(&data, channels, 1, 8),
+            Err(SizeError::Stride { actual: 8, .. })
+        ));
+        assert!(matches!(
+            SequentialNumbers::<&[I16LE], f32>::new_from_bytes_with_stride(&data, 2, usize::MAX, 8),
+            Err(SizeError::Stride { .. })
+        ));
+    }
+
+    #[test]
+    fn read_write_i16_bytes_with_channel_padding() {
+        // 2 channels of 2 frames, each channel is padded to 6 bytes.
+        let mut data: [u8; 12] = [0, 128, 0, 64, 1, 1, 0, 192, 0, 32, 1, 1];
+        let mut buffer = SequentialNumbers::<&mut [I16LE], f32>::new_from_bytes_with_stride_mut(
+            &mut data, 2, 2, 6,
+        )
+        .unwrap();
+        assert_eq!(buffer.read_sample(0, 1).unwrap(), 0.5);
+        assert_eq!(buffer.read_sample(1, 0).unwrap(), -0.5);
+        let mut values = [0.0; 2];
+        assert_eq!(buffer.write_from_frame_to_slice(1, 0, &mut values), 2);
+        assert_eq!(values, [0.5, 0.25]);
+        assert_eq!(buffer.capacity_frames(), 3);
+        buffer.set_active_frames(3).unwrap();
+        assert_eq!(
+            buffer.write_from_slice_to_channel(1, 0, &[0.0, 0.0, 0.5]),
+            (3, 0)
+        );
+        assert_eq!(data, [0, 128, 0, 64, 1, 1, 0, 0, 0, 0, 0, 64]);
+        assert!(matches!(
+            SequentialNumbers::<&[I16LE], f32>::new_from_bytes_with_stride(&data, 2, 4, 6),
+            Err(SizeError::Stride {
+                actual: 6,
+                required: 8
+            })
+        ));
+    }
+
+    #[test]
+    fn write_i16_rounded() {
+        let mut data = [0_i16; 4];
+        let mut buffer = SequentialNumbers::<_, f32>::new_mut(&mut data, 2, 2).unwrap();
+        buffer.set_rounding(RoundingMode::NearestEven);
+        let values = [0.5 / 32768.0, 1.5 / 32768.0];
+        assert_eq!(buffer.write_from_slice_to_channel(0, 0, &values), (2, 0));
+        buffer.write_sample(1, 0, &(-2.7 / 32768.0)).unwrap();
+        assert_eq!(data, [0, 2, -3, 0]);
+    }
+
+    // Read two samples with the values 0.5 and -0.25 from a buffer of bytes,
+    // then write them back and check that the bytes are the same.
+    fn check_24bit_bytes<U>(data: &[u8])
+    where
+        U: RawSample + Clone,
+    {
+        let buffer = InterleavedNumbers::<&[U], f32>::new_from_bytes(data, 2, 1).unwrap();
+        assert_eq!(buffer.read_sample(0, 0).unwrap(), 0.5);
+        assert_eq!(buffer.read_sample(1, 0).unwrap(), -0.25);
+        let mut written = vec![0_u8; data.len()];
+        let mut buffer =
+            InterleavedNumbers::<&mut [U], f32>::new_from_bytes_mut(&mut written, 2, 1).unwrap();
+        assert_eq!(
+            buffer.write_from_slice_to_frame(0, 0, &[0.5, -0.25]),
+            (2, 0)
+        );
+        assert_eq!(written, data);
+    }
+
+    #[test]
+    fn preferred_iteration() {
+        let data = [0_i16; 8];
+        let buffer = SequentialNumbers::<&[i16], f32>::new(&data, 2, 4).unwrap();
+        assert_eq!(buffer.preferred_iteration(), IterationOrder::ChannelsOuter);
+        let buffer = InterleavedNumbers::<&[i16], f32>::new(&data, 2, 4).unwrap();
+        assert_eq!(buffer.preferred_iteration(), IterationOrder::FramesOuter);
+        let buffer = InterleavedNumbers::<&[i16], f32>::new(&data, 1, 8).unwrap();
+        assert_eq!(buffer.preferred_iteration(), IterationOrder::DontCare);
+    }
+
+    #[test]
+    fn all_24bit_formats() {
+        check_24bit_bytes::<I24LEPacked>(&[0, 0, 0x40, 0, 0, 0xe0]);
+        check_24bit_bytes::<I24LEPadded>(&[0, 0, 0x40, 0, 0, 0, 0xe0, 0]);
+        check_24bit_bytes::<I24BEPacked>(&[0x40, 0, 0, 0xe0, 0, 0]);
+        check_24bit_bytes::<I24BEPadded>(&[0, 0x40, 0, 0, 0, 0xe0, 0, 0]);
+        check_24bit_bytes::<U24LEPacked>(&[0, 0, 0xc0, 0, 0, 0x60]);
+        check_24bit_bytes::<U24LEPadded>(&[0, 0, 0xc0, 0, 0, 0, 0x60, 0]);
+        check_24bit_bytes::<U24BEPacked>(&[0xc0, 0, 0, 0x60, 0, 0]);
+        check_24bit_bytes::<U24BEPadded>(&[0, 0xc0, 0, 0, 0, 0x60, 0, 0]);
+        check_24bit_bytes::<I24InHighLE>(&[0, 0, 0, 0x40, 0, 0, 0, 0xe0]);
+        check_24bit_bytes::<I24InHighBE>(&[0x40, 0, 0, 0, 0xe0, 0, 0, 0]);
+        check_24bit_bytes::<I24InLowLE>(&[0, 0, 0x40, 0, 0, 0, 0xe0, 0xff]);
+        check_24bit_bytes::<I24InLowBE>(&[0, 0x40, 0, 0, 0xff, 0xe0, 0, 0]);
+    }
+
     #[test]
     fn read_i24_bytes_interleaved() {
         let data: [u8; 18] = [0, 0, 0, 0, 0, 128, 0, 0, 64, 0, 0, 192, 0, 0, 32, 0, 0, 224];
@@ -562,4 +1513,81 @@ mod tests {
         assert_eq!(buffer.read_sample(0, 2).unwrap(), 0.25);
         assert_eq!(buffer.read_sample(1, 2).unwrap(), -0.25);
     }
+
+    #[test]
+    fn iter_raw_frames() {
+        let data: [u8; 12] = [1, 2, 3, 4, 0, 0, 5, 6, 7, 8, 0, 0];
+        let buffer =
+            InterleavedNumbers::<&[I16LE], f32>::new_from_bytes_with_stride(&data, 2, 2, 6)
+                .unwrap();
+        let frames: Vec<&[u8]> = buffer.iter_raw_frames().collect();
+        assert_eq!(frames, [[1, 2, 3, 4], [5, 6, 7, 8]]);
+
+        let mut data: [u8; 6] = [1, 2, 3, 4, 5, 6];
+        let mut buffer =
+            InterleavedNumbers::<&mut [I16LE], f32>::new_from_bytes_mut(&mut data, 1, 3).unwrap();
+        buffer.write_sample(0, 1, &0.5).unwrap();
+        let frames: Vec<&[u8]> = buffer.iter_raw_frames().collect();
+        assert_eq!(frames, [[1, 2], [0, 64], [5, 6]]);
+    }
+
+    #[test]
+    fn clip_reports() {
+        let mut data = [0_i16; 6];
+        let mut buffer = InterleavedNumbers::<_, f32>::new_mut(&mut data, 2, 3).unwrap();
+        let report = buffer.write_from_slice_to_channel_report(1, 1, &[1.5, 0.5, -2.0]);
+        assert_eq!(report.written, 2);
+        assert_eq!(report.per_channel, [0, 1]);
+        assert_eq!(report.total, 1);
+        assert_eq!(report.max_overshoot, 0.5);
+
+        let report = buffer.copy_from_interleaved_slice_report(&[0.0, 1.25, -3.0, 0.0, 2.0]);
+        assert_eq!(report.written, 4);
+        assert_eq!(report.per_channel, [1, 1]);
+        assert_eq!(report.max_overshoot, 2.0);
+        assert!(report.is_clipped());
+
+        let vecs = vec![vec![0.5, 0.25], vec![-0.5]];
+        let report = buffer.copy_from_vecs_report(&vecs);
+        assert_eq!(report.written, 3);
+        assert!(!report.is_clipped());
+    }
+
+    #[test]
+    fn f64_bytes_bit_exact() {
+        let values = [
+            1.0 / 3.0,
+            -f64::MIN_POSITIVE / 2.0,
+            f64::EPSILON,
+            -1.0,
+            1.0 - f64::EPSILON,
+            0.1,
+        ];
+        let mut data = [0_u8; 48];
+        let mut buffer =
+            InterleavedNumbers::<&mut [F64LE], f64>::new_from_bytes_mut(&mut data, 2, 3).unwrap();
+        assert_eq!(buffer.copy_from_interleaved_slice(&values), 0);
+        for (n, chunk) in data.chunks_exact(8).enumerate() {
+            assert_eq!(chunk, values[n].to_le_bytes());
+        }
+        let buffer = SequentialNumbers::<&[F64LE], f64>::new_from_bytes(&data, 3, 2).unwrap();
+        for (n, value) in values.iter().enumerate() {
+            let read = buffer.read_sample(n / 2, n % 2).unwrap();
+            assert_eq!(read.to_bits(), value.to_bits());
+        }
+    }
+
+    #[test]
+    fn add_samples() {
+        let mut data: [i16; 4] = [1 << 14, 0, -(1 << 14), 0];
+        let mut buffer = SequentialNumbers::<_, f32>::new_mut(&mut data, 2, 2).unwrap();
+        assert_eq!(buffer.add_sample(0, 0, &0.25), Some(false));
+        assert_eq!(buffer.add_sample(0, 0, &0.5), Some(true));
+        assert_eq!(buffer.add_sample(2, 0, &0.5), None);
+        assert_eq!(
+            buffer.add_from_slice_to_channel(1, 0, &[0.5, -1.25, 0.5]),
+            (2, 1)
+        );
+        assert_eq!(data, [i16::MAX, 0, 0, i16::MIN]);
+    }
 }
@@ -0,0 +1,496 @@
+//! # Operations on buffers
+//!
+//! This module provides signal processing operations
+//! that read their input from buffers implementing [Adapter].
+//!
+//! ## Examples
+//! Split a stereo buffer into three frequency bands.
+//! ```
+//! use audioadapter::direct::InterleavedSlice;
+//! use audioadapter::ops::split_bands;
+//! use audioadapter::Adapter;
+//!
+//! let data = vec![0.0_f32; 2 * 1024];
+//! let buffer = InterleavedSlice::new(&data, 2, 1024).unwrap();
+//!
+//! let bands = split_bands(&buffer as &dyn Adapter<f32>, &[200.0, 2000.0], 48000.0);
+//! assert_eq!(bands.len(), 3);
+//! assert_eq!(bands[0].channels(), 2);
+//! assert_eq!(bands[0].frames(), 1024);
+//! ```
+//!
+//! Apply a FIR filter to a stream, one buffer at a time.
+//! ```
+//! use audioadapter::direct::InterleavedSlice;
+//! use audioadapter::ops::{convolve, FirState};
+//! use audioadapter::{Adapter, AdapterMut};
+//!
+//! let taps = [0.5_f32, 0.5];
+//! let mut state = FirState::new(1, taps.len());
+//! let mut output = vec![0.0_f32; 3];
+//!
+//! for input in [[1.0_f32, 1.0, 1.0], [3.0, 3.0, 3.0]] {
+//!     let src = InterleavedSlice::new(&input, 1, 3).unwrap();
+//!     let mut dst = InterleavedSlice::new_mut(&mut output, 1, 3).unwrap();
+//!     convolve(&src, &taps, &mut dst, &mut state);
+//! }
+//! assert_eq!(output, [2.0, 3.0, 3.0]);
+//! ```
+
+use crate::filter::BiquadCoefficients;
+use crate::owned::SequentialOwned;
+use crate::{Adapter, AdapterMut, IterationOrder};
+
+/// A 4th order Linkwitz-Riley filter, made up of two cascaded Butterworth filters.
+struct LinkwitzRiley {
+    coefficients: BiquadCoefficients,
+    state: [[f64; 2]; 2],
+}
+
+impl LinkwitzRiley {
+    fn lowpass(freq: f64, sample_rate: f64) -> Self {
+        Self {
+            coefficients: BiquadCoefficients::lowpass(
+                freq,
+                core::f64::consts::FRAC_1_SQRT_2,
+                sample_rate,
+            ),
+            state: [[0.0; 2]; 2],
+        }
+    }
+
+    fn highpass(freq: f64, sample_rate: f64) -> Self {
+        Self {
+            coefficients: BiquadCoefficients::highpass(
+                freq,
+                core::f64::consts::FRAC_1_SQRT_2,
+                sample_rate,
+            ),
+            state: [[0.0; 2]; 2],
+        }
+    }
+
+    fn process_slice(&mut self, values: &mut [f64]) {
+        let [first, second] = &mut self.state;
+        for value in values.iter_mut() {
+            let stage1 = self.coefficients.process_sample(first, *value);
+            *value = self.coefficients.process_sample(second, stage1);
+        }
+    }
+}
+
+/// Split the signal in a buffer into frequency bands,
+/// using 4th order Linkwitz-Riley crossover filters.
+/// The `crossovers` slice gives the crossover frequencies in Hz,
+/// in increasing order.
+/// Returns one owned buffer per band, starting with the lowest band,
+/// meaning that `crossovers.len() + 1` buffers are returned.
+/// Each buffer has the same number of channels and frames as the input.
+/// Frames that can't be read, for example those of an inactive channel,
+/// are treated as zeros.
+///
+/// The bands are split one crossover at a time,
+/// by dividing the remaining upper part of the spectrum into a lower and an upper band.
+/// The phase of the lower bands is not compensated for the later crossovers,
+/// so the sum of more than two bands does not give a flat magnitude response.
+/// This is fine for uses such as multiband metering.
+///
+/// Panics if the crossover frequencies are not in increasing order.
+pub fn split_bands(
+    buffer: &dyn Adapter<f32>,
+    crossovers: &[f32],
+    sample_rate: f32,
+) -> Vec<SequentialOwned<f32>> {
+    assert!(
+        crossovers.windows(2).all(|pair| pair[0] < pair[1]),
+        "Crossover frequencies must be in increasing order"
+    );
+    let channels = buffer.channels();
+    let frames = buffer.frames();
+    let mut bands: Vec<SequentialOwned<f32>> = (0..=crossovers.len())
+        .map(|_| SequentialOwned::new(0.0, channels, frames))
+        .collect();
+    let mut input = vec![0.0_f32; frames];
+    let mut remaining = vec![0.0_f64; frames];
+    let mut band = vec![0.0_f64; frames];
+    let mut output = vec![0.0_f32; frames];
+    for channel in 0..channels {
+        let count = buffer.write_from_channel_to_slice(channel, 0, &mut input);
+        input[count..].fill(0.0);
+        for (rem, value) in remaining.iter_mut().zip(input.iter()) {
+            *rem = *value as f64;
+        }
+        for (crossover, band_buffer) in crossovers.iter().zip(bands.iter_mut()) {
+            band.copy_from_slice(&remaining);
+            LinkwitzRiley::lowpass(*crossover as f64, sample_rate as f64).process_slice(&mut band);
+            LinkwitzRiley::highpass(*crossover as f64, sample_rate as f64)
+                .process_slice(&mut remaining);
+            write_channel(band_buffer, channel, &band, &mut output);
+        }
+        if let Some(top_band) = bands.last_mut() {
+            write_channel(top_band, channel, &remaining, &mut output);
+        }
+    }
+    bands
+}
+
+/// The state of a FIR filter, holding the most recent input samples of each channel.
+/// This enables processing a stream block by block using [convolve].
+pub struct FirState {
+    history: Vec<Vec<f32>>,
+}
+
+impl FirState {
+    /// Create a new state for a filter with `nbr_taps` taps,
+    /// for the given number of channels.
+    /// The history is initially filled with zeros.
+    pub fn new(channels: usize, nbr_taps: usize) -> Self {
+        Self {
+            history: vec![vec![0.0; nbr_taps.saturating_sub(1)]; channels],
+        }
+    }
+
+    /// Reset the history of all channels to zeros.
+    pub fn reset(&mut self) {
+        for channel in self.history.iter_mut() {
+            channel.fill(0.0);
+        }
+    }
+}
+
+/// Convolve the signal in `src` with the FIR filter given by `taps`,
+/// and write the result to `dst`.
+/// The input samples from previous calls are taken from `state`,
+/// and the state is then updated with the new input samples.
+/// This means that a stream can be processed in blocks of any size,
+/// giving the same result as when processing it all at once.
+///
+/// The number of channels processed is the smallest of the channels
+/// of `src`, `dst` and `state`,
+/// and the number of frames is the smallest of the frames of `src` and `dst`.
+/// Frames that can't be read from `src`, for example those of an inactive channel,
+/// are treated as zeros.
+/// Returns the number of frames processed.
+///
+/// Panics if the state was created for a different number of taps.
+pub fn convolve(
+    src: &dyn Adapter<f32>,
+    taps: &[f32],
+    dst: &mut dyn AdapterMut<f32>,
+    state: &mut FirState,
+) -> usize {
+    let history_len = taps.len().saturating_sub(1);
+    let channels = src.channels().min(dst.channels()).min(state.history.len());
+    let frames = src.frames().min(dst.frames());
+    let mut input = vec![0.0_f32; history_len + frames];
+    let mut output = vec![0.0_f32; frames];
+    for (channel, history) in state.history.iter_mut().enumerate().take(channels) {
+        assert_eq!(
+            history.len(),
+            history_len,
+            "The FIR state was created for a different number of taps"
+        );
+        input[..history_len].copy_from_slice(history);
+        let count = src.write_from_channel_to_slice(channel, 0, &mut input[history_len..]);
+        input[history_len + count..].fill(0.0);
+        for (n, out) in output.iter_mut().enumerate() {
+            // The newest sample is multiplied with the first tap.
+            *out = taps
+                .iter()
+                .zip(input[n..n + taps.len()].iter().rev())
+                .map(|(tap, value)| tap * value)
+                .sum();
+        }
+        dst.write_from_slice_to_channel(channel, 0, &output);
+        history.copy_from_slice(&input[frames..]);
+    }
+    frames
+}
+
+/// Copy the samples of `src` to `dst`, while multiplying them by `gain`.
+/// The gain is applied in the same pass as the copy,
+/// avoiding a separate pass for scaling the copied samples.
+///
+/// The number of channels and frames copied are the smallest of the two buffers.
+/// If both buffers prefer iterating over frames in the outer loop,
+/// the samples are copied frame by frame, otherwise channel by channel.
+/// Returns the number of samples that were clipped when writing to `dst`.
+pub fn copy_with_gain<'a>(
+    src: &dyn Adapter<'a, f32>,
+    dst: &mut dyn AdapterMut<'a, f32>,
+    gain: f32,
+) -> usize {
+    let channels = src.channels().min(dst.channels());
+    let frames = src.frames().min(dst.frames());
+    if src.preferred_iteration() == IterationOrder::FramesOuter
+        && dst.preferred_iteration() == IterationOrder::FramesOuter
+    {
+        let mut nbr_clipped = 0;
+        for frame in 0..frames {
+            for channel in 0..channels {
+                unsafe {
+                    let value = src.read_sample_unchecked(channel, frame) * gain;
+                    nbr_clipped += dst.write_sample_unchecked(channel, frame, &value) as usize;
+                }
+            }
+        }
+        return nbr_clipped;
+    }
+    (0..channels)
+        .map(|channel| {
+            dst.write_from_other_to_channel_with_gain(src, channel, channel, 0, 0, frames, gain)
+                .unwrap_or_default()
+        })
+        .sum()
+}
+
+/// Reduce each channel of a buffer to `buckets` pairs of minimum and maximum values,
+/// as needed for drawing a waveform.
+/// The frames are divided into `buckets` consecutive ranges of nearly equal length,
+/// and the smallest and largest sample value within each range is found.
+/// If there are more buckets than frames, each bucket contains the nearest frame,
+/// meaning that some frames are repeated.
+///
+/// Returns one vector of `(min, max)` pairs per channel.
+/// The pairs of an empty buffer are `(0.0, 0.0)`.
+/// Frames that can't be read, for example those of an inactive channel,
+/// are treated as zeros.
+pub fn decimate_min_max(src: &dyn Adapter<f32>, buckets: usize) -> Vec<Vec<(f32, f32)>> {
+    let frames = src.frames();
+    let mut values = vec![0.0_f32; frames];
+    (0..src.channels())
+        .map(|channel| {
+            if frames == 0 {
+                return vec![(0.0, 0.0); buckets];
+            }
+            // Frames that could not be read, such as those of an inactive channel, count as zeros.
+            let count = src.write_from_channel_to_slice(channel, 0, &mut values);
+            values[count..].fill(0.0);
+            (0..buckets)
+                .map(|bucket| {
+                    let start = (bucket * frames / buckets).min(frames - 1);
+                    let end = ((bucket + 1) * frames / buckets).max(start + 1);
+                    values[start..end]
+                        .iter()
+                        .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), value| {
+                            (min.min(*value), max.max(*value))
+                        })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn write_channel(
+    buffer: &mut SequentialOwned<f32>,
+    channel: usize,
+    values: &[f64],
+    scratch: &mut [f32],
+) {
+    for (out, value) in scratch.iter_mut().zip(values.iter()) {
+        *out = *value as f32;
+    }
+    buffer.write_from_slice_to_channel(channel, 0, scratch);
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direct::{InterleavedSlice, SequentialSlice};
+
+    fn sine(freq: f32, sample_rate: f32, frames: usize) -> Vec<f32> {
+        (0..frames)
+            .map(|n| (2.0 * core::f32::consts::PI * freq * n as f32 / sample_rate).sin())
+            .collect()
+    }
+
+    fn rms_of_tail(buffer: &SequentialOwned<f32>, channel: usize, skip: usize) -> f32 {
+        let values: Vec<f32> = (skip..buffer.frames())
+            .map(|frame| buffer.read_sample(channel, frame).unwrap())
+            .collect();
+        (values.iter().map(|v| v * v).sum::<f32>() / values.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn split_two_sines() {
+        let sample_rate = 48000.0;
+        let frames = 4800;
+        let mut data = sine(100.0, sample_rate, frames);
+        data.extend(sine(10000.0, sample_rate, frames));
+        let buffer = SequentialSlice::new(&data, 2, frames).unwrap();
+        let bands = split_bands(&buffer as &dyn Adapter<f32>, &[1000.0], sample_rate);
+        assert_eq!(bands.len(), 2);
+        let sine_rms = core::f32::consts::FRAC_1_SQRT_2;
+        // low frequency sine in channel 0 ends up in the low band
+        assert!((rms_of_tail(&bands[0], 0, 1000) - sine_rms).abs() < 0.01);
+        assert!(rms_of_tail(&bands[1], 0, 1000) < 0.01);
+        // high frequency sine in channel 1 ends up in the high band
+        assert!(rms_of_tail(&bands[0], 1, 1000) < 0.01);
+        assert!((rms_of_tail(&bands[1], 1, 1000) - sine_rms).abs() < 0.01);
+    }
+
+    #[test]
+    fn split_three_bands() {
+        let sample_rate = 48000.0;
+        let frames = 4800;
+        let data = sine(1000.0, sample_rate, frames);
+        let buffer = SequentialSlice::new(&data, 1, frames).unwrap();
+        let bands = split_bands(&buffer as &dyn Adapter<f32>, &[100.0, 10000.0], sample_rate);
+        assert_eq!(bands.len(), 3);
+        assert!(rms_of_tail(&bands[0], 0, 1000) < 0.01);
+        assert!(rms_of_tail(&bands[1], 0, 1000) > 0.69);
+        assert!(rms_of_tail(&bands[2], 0, 1000) < 0.01);
+    }
+
+    #[test]
+    fn convolve_in_blocks() {
+        let taps = [1.0_f32, 0.5, 0.25];
+        let data: Vec<f32> = (0..20).map(|n| n as f32).collect();
+        let input = SequentialSlice::new(&data, 2, 10).unwrap();
+        let mut result = vec![0.0_f32; 20];
+        let mut output = SequentialSlice::new_mut(&mut result, 2, 10).unwrap();
+        let mut state = FirState::new(2, taps.len());
+        assert_eq!(convolve(&input, &taps, &mut output, &mut state), 10);
+        assert_eq!(result[..4], [0.0, 1.0, 2.5, 4.25]);
+        assert_eq!(result[10..12], [10.0, 16.0]);
+
+        // process the same data in blocks of 4 frames
+        let mut state = FirState::new(2, taps.len());
+        let mut block_result = vec![0.0_f32; 20];
+        for start in (0..10).step_by(4) {
+            let len = 4.min(10 - start);
+            let block: Vec<f32> = data[start..start + len]
+                .iter()
+                .chain(data[10 + start..10 + start + len].iter())
+                .cloned()
+                .collect();
+            let input = SequentialSlice::new(&block, 2, len).unwrap();
+            let mut out = vec![0.0_f32; 2 * len];
+            let mut output = SequentialSlice::new_mut(&mut out, 2, len).unwrap();
+            convolve(&input, &taps, &mut output, &mut state);
+            block_result[start..start + len].copy_from_slice(&out[..len]);
+            block_result[10 + start..10 + start + len].copy_from_slice(&out[len..]);
+        }
+        assert_eq!(result, block_result);
+    }
+
+    #[test]
+    fn copy_scaled() {
+        let data = [0.25_f32, 0.5, -0.75, 1.0];
+        let src = SequentialSlice::new(&data, 2, 2).unwrap();
+        let mut out = [0_i16; 6];
+        let mut output =
+            crate::number_to_float::InterleavedNumbers::new_mut(&mut out, 2, 3).unwrap();
+        assert_eq!(copy_with_gain(&src, &mut output, 2.0), 3);
+        assert_eq!(out, [16384, -32768, 32767, 32767, 0, 0]);
+    }
+
+    #[test]
+    fn copy_scaled_interleaved() {
+        let data = [0.25_f32, 0.5, -0.75, 1.0];
+        let src = InterleavedSlice::new(&data, 2, 2).unwrap();
+        assert_eq!(src.preferred_iteration(), IterationOrder::FramesOuter);
+        let mut out = [0_i16; 6];
+        let mut output =
+            crate::number_to_float::InterleavedNumbers::new_mut(&mut out, 2, 3).unwrap();
+        assert_eq!(output.preferred_iteration(), IterationOrder::FramesOuter);
+        assert_eq!(copy_with_gain(&src, &mut output, 2.0), 3);
+        assert_eq!(out, [16384, 32767, -32768, 32767, 0, 0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn unsorted_crossovers() {
+        let data = [0.0_f32; 10];
+        let buffer = SequentialSlice::new(&data, 1, 10).unwrap();
+        split_bands(&buffer as &dyn Adapter<f32>, &[1000.0, 100.0], 48000.0);
+    }
+
+    #[test]
+    fn min_max_buckets() {
+        let data = [0.0_f32, 1.0, -1.0, 0.5, 0.25, -0.5, 2.0, 3.0];
+        let buffer = SequentialSlice::new(&data, 2, 4).unwrap();
+        let pairs = decimate_min_max(&buffer, 2);
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(pairs[0], [(0.0, 1.0), (-1.0, 0.5)]);
+        assert_eq!(pairs[1], [(-0.5, 0.25), (2.0, 3.0)]);
+
+        // Uneven division into three buckets.
+        let pairs = decimate_min_max(&buffer, 3);
+        assert_eq!(pairs[0], [(0.0, 0.0), (1.0, 1.0), (-1.0, 0.5)]);
+    }
+
+    #[test]
+    fn min_max_more_buckets_than_frames() {
+        let data = [1.0_f32, -1.0];
+        let buffer = InterleavedSlice::new(&data, 1, 2).unwrap();
+        let pairs = decimate_min_max(&buffer, 4);
+        assert_eq!(
+            pairs[0],
+            [(1.0, 1.0), (1.0, 1.0), (-1.0, -1.0), (-1.0, -1.0)]
+        );
+    }
+
+    #[cfg(not(feature = "reject-zero-dimensions"))]
+    #[test]
+    fn min_max_empty() {
+        let data = [1.0_f32, -1.0];
+        let empty = InterleavedSlice::new(&data, 1, 0).unwrap();
+        assert_eq!(decimate_min_max(&empty, 2), [[(0.0, 0.0), (0.0, 0.0)]]);
+    }
+
+    #[test]
+    fn min_max_inactive_channel() {
+        use crate::direct::SparseSequentialSliceOfVecs;
+
+        let data = vec![vec![1.0_f32, -1.0, 2.0, -2.0], Vec::new()];
+        let mask = [true, false];
+        let buffer = SparseSequentialSliceOfVecs::new(&data, 2, 4, &mask).unwrap();
+        let pairs = decimate_min_max(&buffer, 2);
+        assert_eq!(pairs[0], [(-1.0, 1.0), (-2.0, 2.0)]);
+        assert_eq!(pairs[1], [(0.0, 0.0), (0.0, 0.0)]);
+    }
+
+    #[test]
+    fn split_inactive_channel() {
+        use crate::direct::SparseSequentialSliceOfVecs;
+
+        let sample_rate = 48000.0;
+        let frames = 4800;
+        let data = vec![sine(100.0, sample_rate, frames), Vec::new()];
+        let mask = [true, false];
+        let buffer = SparseSequentialSliceOfVecs::new(&data, 2, frames, &mask).unwrap();
+        let bands = split_bands(&buffer as &dyn Adapter<f32>, &[1000.0], sample_rate);
+        assert!(rms_of_tail(&bands[0], 0, 1000) > 0.69);
+        assert_eq!(rms_of_tail(&bands[0], 1, 0), 0.0);
+        assert_eq!(rms_of_tail(&bands[1], 1, 0), 0.0);
+    }
+
+    #[test]
+    fn convolve_inactive_channel() {
+        use crate::direct::SparseSequentialSliceOfVecs;
+
+        let taps = [1.0_f32, 0.5, 0.25];
+        let data = vec![vec![1.0_f32, 2.0, 3.0, 4.0], Vec::new()];
+        let mask = [true, false];
+        let input = SparseSequentialSliceOfVecs::new(&data, 2, 4, &mask).unwrap();
+        let mut result = vec![9.0_f32; 8];
+        let mut output = SequentialSlice::new_mut(&mut result, 2, 4).unwrap();
+        let mut state = FirState::new(2, taps.len());
+        convolve(&input, &taps, &mut output, &mut state);
+        assert_eq!(result, [1.0, 2.5, 4.25, 6.0, 0.0, 0.0, 0.0, 0.0]);
+
+        // The history of the inactive channel must only contain zeros.
+        let zeros = vec![0.0_f32; 8];
+        let input = SequentialSlice::new(&zeros, 2, 4).unwrap();
+        let mut output = SequentialSlice::new_mut(&mut result, 2, 4).unwrap();
+        convolve(&input, &taps, &mut output, &mut state);
+        assert_eq!(result, [2.75, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+    }
+}
@@ -5,6 +5,26 @@
 //! ## Available wrappers
 //! Wrappers are available for vectors, `Vec<T>`,
 //! with samples stored in _interleaved_ and _sequential_ order.
+//! An `InterleavedOwned` of raw byte samples, such as `I16LE`,
+//! can be created from a vector of bytes using `InterleavedOwned::from_bytes`.
+//! Both wrappers can also take ownership of a boxed slice, `Box<[T]>`.
+//!
+//! For samples that are shared between several owners,
+//! for example in different threads, there is also `SharedInterleaved`,
+//! a read-only wrapper for a reference counted slice, `Arc<[T]>`.
+//! Like the direct wrappers, the samples can be accessed
+//! by indexing with a `(channel, frame)` tuple, as `buffer[(channel, frame)]`,
+//! or as references from the `sample_ref` and `sample_mut` methods.
+//!
+//! ## Inline storage of small buffers
+//! When the `smallvec` feature is enabled, the wrappers store their samples
+//! in a `SmallVec` instead of a `Vec`.
+//! Buffers with at most `INLINE_SAMPLES` samples are then stored inline,
+//! without any heap allocation.
+//! This is useful for the very short buffers used for example
+//! for control-rate signals.
+//! Larger buffers are stored on the heap just like without the feature.
+//! The methods taking and returning vectors work the same with and without the feature.
 //!
 //! ### Example
 //! Wrap a `Vec<i32>` as an interleaved buffer
@@ -36,11 +56,75 @@
 //! ```
 //!
 
+use std::sync::Arc;
+
+use crate::direct::{split_channel_slices, SplitChannels, SplitFrames};
+use crate::direct::{InterleavedSlice, SequentialSlice, SequentialVecOfSlices};
+use crate::sample::BytesSample;
 use crate::SizeError;
 
-use crate::slicetools::copy_within_slice;
-use crate::{check_slice_length, implement_size_getters};
-use crate::{Adapter, AdapterMut};
+#[cfg(feature = "smallvec")]
+use smallvec::SmallVec;
+
+use crate::slicetools::{copy_within_slice, transpose_in_place};
+use crate::{
+    check_slice_length, implement_active_frames, implement_sample_access, implement_size_getters,
+    implement_with_dims,
+};
+use crate::{Adapter, AdapterMut, IterationOrder};
+
+/// The largest number of samples that are stored inline
+/// when the `smallvec` feature is enabled.
+#[cfg(feature = "smallvec")]
+pub const INLINE_SAMPLES: usize = 64;
+
+#[cfg(feature = "smallvec")]
+type Storage<T> = SmallVec<[T; INLINE_SAMPLES]>;
+
+#[cfg(not(feature = "smallvec"))]
+type Storage<T> = Vec<T>;
+
+/// Create new storage filled with `len` copies of `value`.
+fn filled_storage<T: Clone>(value: T, len: usize) -> Storage<T> {
+    core::iter::repeat(value).take(len).collect()
+}
+
+/// Create new storage filled with `len` copies of `value`,
+/// that is guaranteed to be stored on the heap.
+/// This is needed when the address of the samples must not change
+/// when the wrapper is moved.
+#[cfg(feature = "smallvec")]
+fn heap_storage<T: Clone>(value: T, len: usize) -> Storage<T> {
+    // A vector with a capacity larger than the inline capacity stays on the heap.
+    let mut buf = Vec::with_capacity(len.max(INLINE_SAMPLES + 1));
+    buf.resize(len, value);
+    SmallVec::from_vec(buf)
+}
+
+#[cfg(not(feature = "smallvec"))]
+fn heap_storage<T: Clone>(value: T, len: usize) -> Storage<T> {
+    vec![value; len]
+}
+
+#[cfg(feature = "smallvec")]
+fn vec_into_storage<T>(buf: Vec<T>) -> Storage<T> {
+    SmallVec::from_vec(buf)
+}
+
+#[cfg(not(feature = "smallvec"))]
+fn vec_into_storage<T>(buf: Vec<T>) -> Storage<T> {
+    buf
+}
+
+#[cfg(feature = "smallvec")]
+fn storage_into_vec<T>(buf: Storage<T>) -> Vec<T> {
+    buf.into_vec()
+}
+
+#[cfg(not(feature = "smallvec"))]
+fn storage_into_vec<T>(buf: Storage<T>) -> Vec<T> {
+    buf
+}
 
 //
 // =========================== InterleavedOwned ===========================
@@ -53,28 +137,77 @@ use crate::{Adapter, AdapterMut};
 /// For a stereo buffer containing four frames, the order is
 /// `L1, R1, L2, R2, L3, R3, L4, R4`
 pub struct InterleavedOwned<U> {
-    buf: Vec<U>,
+    buf: Storage<U>,
     frames: usize,
     channels: usize,
+    offset: usize,
+    align: usize,
 }
 
 impl<U> InterleavedOwned<U> {
     fn calc_index(&self, channel: usize, frame: usize) -> usize {
-        frame * self.channels + channel
+        self.offset + frame * self.channels + channel
+    }
+
+    /// Get the alignment in bytes that is guaranteed for the first sample.
+    /// This is the alignment requested when creating the buffer with [InterleavedOwned::new_aligned],
+    /// and otherwise the natural alignment of the sample type.
+    pub fn alignment(&self) -> usize {
+        self.align
+    }
+
+    /// Remove any padding at the start of the vector.
+    fn remove_padding(&mut self) {
+        self.buf.drain(..self.offset);
+        self.offset = 0;
+        self.align = core::mem::align_of::<U>();
     }
 }
 
-impl<'a, T> InterleavedOwned<T>
+impl<T> InterleavedOwned<T>
 where
-    T: Clone + 'a,
+    T: Clone,
 {
     /// Create a new `InterleavedOwned` by allocaing a new vector filled with `value`.
     pub fn new(value: T, channels: usize, frames: usize) -> Self {
-        let buf = vec![value; channels * frames];
+        let buf = filled_storage(value, channels * frames);
         Self {
             buf,
             frames,
             channels,
+            offset: 0,
+            align: core::mem::align_of::<T>(),
+        }
+    }
+
+    implement_with_dims!(with_dims = new(value: T) -> Self);
+
+    /// Create a new `InterleavedOwned` by allocating a new vector filled with `value`,
+    /// where the first sample is stored at an address that is a multiple of `align` bytes.
+    /// This is intended for buffers that are used for SIMD processing or DMA.
+    /// The vector is allocated with `align` extra elements, counted in samples and not in bytes,
+    /// and the samples are stored starting at the first suitably aligned element.
+    /// The elements before and after the samples are padding,
+    /// that can't be accessed via the trait methods.
+    ///
+    /// Panics if `align` is not a power of two,
+    /// or if the alignment can't be reached with the size of the sample type.
+    pub fn new_aligned(value: T, channels: usize, frames: usize, align: usize) -> Self {
+        assert!(align.is_power_of_two(), "Alignment must be a power of two");
+        let size = core::mem::size_of::<T>();
+        let align = align.max(core::mem::align_of::<T>());
+        let padding = if size == 0 { 0 } else { align };
+        let buf = heap_storage(value, channels * frames + padding);
+        let start = buf.as_ptr() as usize;
+        let offset = (0..=padding)
+            .find(|n| (start + n * size) % align == 0)
+            .expect("The alignment can't be reached for this sample type");
+        Self {
+            buf,
+            frames,
+            channels,
+            offset,
+            align,
         }
     }
 
@@ -85,15 +218,127 @@ where
     pub fn new_from(buf: Vec<T>, channels: usize, frames: usize) -> Result<Self, SizeError> {
         check_slice_length!(channels, frames, buf.len());
         Ok(Self {
-            buf,
+            buf: vec_into_storage(buf),
             frames,
             channels,
+            offset: 0,
+            align: core::mem::align_of::<T>(),
         })
     }
 
+    implement_with_dims!(with_dims_from = new_from(buf: Vec<T>) -> Result<Self, SizeError>);
+
+    /// Create a new `InterleavedOwned` by taking ownership of an existing boxed slice.
+    /// The slice is converted to a vector without copying the samples.
+    /// The length requirements are the same as for [InterleavedOwned::new_from].
+    pub fn new_from_boxed(
+        buf: Box<[T]>,
+        channels: usize,
+        frames: usize,
+    ) -> Result<Self, SizeError> {
+        Self::new_from(buf.into_vec(), channels, frames)
+    }
+
     /// Take ownership of the data from the `InterleavedOwned`.
-    pub fn take_data(self) -> Vec<T> {
-        self.buf
+    /// For a buffer created with [InterleavedOwned::new_aligned],
+    /// the padding at the start of the vector is removed first,
+    /// meaning that the samples are moved and no longer aligned.
+    pub fn take_data(mut self) -> Vec<T> {
+        self.remove_padding();
+        storage_into_vec(self.buf)
+    }
+
+    /// Convert the `InterleavedOwned` into a [SequentialOwned]
+    /// with the same samples.
+    /// The samples are rearranged in place within the existing vector,
+    /// so no new vector is allocated.
+    /// Any extra values at the end of the vector are left untouched.
+    pub fn into_sequential(mut self) -> SequentialOwned<T> {
+        self.remove_padding();
+        transpose_in_place(&mut self.buf, self.frames, self.channels);
+        SequentialOwned {
+            buf: self.buf,
+            frames: self.frames,
+            channels: self.channels,
+        }
+    }
+
+    /// Convert the `InterleavedOwned` into a [SharedInterleaved]
+    /// with the same samples, that can be cheaply cloned and shared between threads.
+    /// Any padding and extra values at the end of the vector are removed,
+    /// which may require the samples to be moved.
+    pub fn into_shared(mut self) -> SharedInterleaved<T> {
+        self.remove_padding();
+        let mut buf = storage_into_vec(self.buf);
+        buf.truncate(self.frames * self.channels);
+        SharedInterleaved {
+            buf: buf.into(),
+            frames: self.frames,
+            channels: self.channels,
+        }
+    }
+
+    implement_active_frames!(|s| (s.buf.len() - s.offset)
+        .checked_div(s.channels)
+        .unwrap_or(usize::MAX));
+
+    /// Split the buffer into two [InterleavedSlice] buffers with disjoint ranges of frames,
+    /// where the first contains the frames before `frame`,
+    /// and the second contains `frame` and the following frames.
+    /// Returns `None` if `frame` is larger than the number of frames.
+    pub fn split_frames_at_mut(
+        &mut self,
+        frame: usize,
+    ) -> Option<SplitFrames<InterleavedSlice<&mut [T]>>> {
+        if frame > self.frames {
+            return None;
+        }
+        let samples = &mut self.buf[self.offset..self.offset + self.frames * self.channels];
+        let (first, second) = samples.split_at_mut(frame * self.channels);
+        Some((
+            InterleavedSlice::new_mut(first, self.channels, frame).ok()?,
+            InterleavedSlice::new_mut(second, self.channels, self.frames - frame).ok()?,
+        ))
+    }
+}
+
+impl<U> InterleavedOwned<U>
+where
+    U: BytesSample,
+{
+    /// Create a new `InterleavedOwned` from a vector of raw bytes,
+    /// where each sample is stored as `U::BYTES_PER_SAMPLE` bytes.
+    /// The vector length must be at least `frames*channels*U::BYTES_PER_SAMPLE`.
+    /// The byte sample types have no alignment requirements,
+    /// and the bytes of the first `frames*channels` samples
+    /// are copied into a new vector of samples.
+    /// Any extra bytes at the end are dropped.
+    pub fn from_bytes(bytes: Vec<u8>, channels: usize, frames: usize) -> Result<Self, SizeError> {
+        check_slice_length!(channels, frames, bytes.len(), U::BYTES_PER_SAMPLE);
+        let buf = bytes
+            .chunks_exact(U::BYTES_PER_SAMPLE)
+            .take(channels * frames)
+            .map(U::from_slice)
+            .collect();
+        Ok(Self {
+            buf,
+            frames,
+            channels,
+            offset: 0,
+            align: core::mem::align_of::<U>(),
+        })
+    }
+
+    /// Convert the `InterleavedOwned` back into a vector of raw bytes.
+    /// The vector contains the bytes of the `frames*channels` samples of the buffer.
+    /// Any padding, and any extra values at the end of the wrapped vector, are dropped.
+    pub fn into_bytes(self) -> Vec<u8> {
+        let samples = self.frames * self.channels;
+        let mut bytes = Vec::with_capacity(samples * U::BYTES_PER_SAMPLE);
+        for sample in self.buf[self.offset..self.offset + samples].iter() {
+            bytes.extend_from_slice(sample.as_slice());
+        }
+        bytes
     }
 }
 
@@ -108,6 +353,10 @@ where
 
     implement_size_getters!();
 
+    fn preferred_iteration(&self) -> IterationOrder {
+        IterationOrder::FramesOuter
+    }
+
     fn write_from_frame_to_slice(&self, frame: usize, skip: usize, slice: &mut [T]) -> usize {
         if frame >= self.frames || skip >= self.channels {
             return 0;
@@ -122,6 +371,14 @@ where
             .clone_from_slice(&self.buf[buffer_skip..buffer_skip + channels_to_write]);
         channels_to_write
     }
+
+    fn to_interleaved_vec(&self) -> Vec<T>
+    where
+        T: Clone + Default,
+    {
+        let start = self.calc_index(0, 0);
+        self.buf[start..start + self.channels * self.frames].to_vec()
+    }
 }
 
 impl<'a, T> AdapterMut<'a, T> for InterleavedOwned<T>
@@ -158,18 +415,24 @@ where
         if src + count > self.frames || dest + count > self.frames {
             return None;
         }
+        let src_index = self.calc_index(0, src);
+        let dest_index = self.calc_index(0, dest);
         unsafe {
-            copy_within_slice(
-                &mut self.buf,
-                src * self.channels,
-                dest * self.channels,
-                count * self.channels,
-            );
+            copy_within_slice(&mut self.buf, src_index, dest_index, count * self.channels);
         }
         Some(count)
     }
+
+    fn copy_from_interleaved_slice(&mut self, slice: &[T]) -> usize {
+        let samples = (slice.len() / self.channels.max(1)).min(self.frames) * self.channels;
+        let start = self.calc_index(0, 0);
+        self.buf[start..start + samples].clone_from_slice(&slice[..samples]);
+        0
+    }
 }
 
+implement_sample_access!([T] InterleavedOwned<T>, mutable);
+
 //
 // =========================== SequentialOwned ===========================
 //
@@ -181,7 +444,7 @@ where
 /// For a stereo buffer containing four frames, the order is
 /// `L1, L2, L3, L4, R1, R2, R3, R4`
 pub struct SequentialOwned<U> {
-    buf: Vec<U>,
+    buf: Storage<U>,
     frames: usize,
     channels: usize,
 }
@@ -192,13 +455,13 @@ impl<U> SequentialOwned<U> {
     }
 }
 
-impl<'a, T> SequentialOwned<T>
+impl<T> SequentialOwned<T>
 where
-    T: Clone + 'a,
+    T: Clone,
 {
     /// Create a new `SequentialOwned` by allocaing a new vector filled with `value`.
     pub fn new(value: T, channels: usize, frames: usize) -> Self {
-        let buf = vec![value; channels * frames];
+        let buf = filled_storage(value, channels * frames);
         Self {
             buf,
             frames,
@@ -206,6 +469,8 @@ where
         }
     }
 
+    implement_with_dims!(with_dims = new(value: T) -> Self);
+
     /// Create a new `SequentialOwned` by taking ownership of an existing vector.
     /// The vector length must be at least `frames*channels`.
     /// It is allowed to be longer than needed,
@@ -213,15 +478,87 @@ where
     pub fn new_from(buf: Vec<T>, channels: usize, frames: usize) -> Result<Self, SizeError> {
         check_slice_length!(channels, frames, buf.len());
         Ok(Self {
-            buf,
+            buf: vec_into_storage(buf),
             frames,
             channels,
         })
     }
 
+    implement_with_dims!(with_dims_from = new_from(buf: Vec<T>) -> Result<Self, SizeError>);
+
+    /// Create a new `SequentialOwned` by taking ownership of an existing boxed slice.
+    /// The slice is converted to a vector without copying the samples.
+    /// The length requirements are the same as for [SequentialOwned::new_from].
+    pub fn new_from_boxed(
+        buf: Box<[T]>,
+        channels: usize,
+        frames: usize,
+    ) -> Result<Self, SizeError> {
+        Self::new_from(buf.into_vec(), channels, frames)
+    }
+
     /// Take ownership of the data from the `SequentialOwned`.
     pub fn take_data(self) -> Vec<T> {
-        self.buf
+        storage_into_vec(self.buf)
+    }
+
+    /// Convert the `SequentialOwned` into an [InterleavedOwned]
+    /// with the same samples.
+    /// The samples are rearranged in place within the existing vector,
+    /// so no new vector is allocated.
+    /// Any extra values at the end of the vector are left untouched.
+    pub fn into_interleaved(mut self) -> InterleavedOwned<T> {
+        transpose_in_place(&mut self.buf, self.channels, self.frames);
+        InterleavedOwned {
+            buf: self.buf,
+            frames: self.frames,
+            channels: self.channels,
+            offset: 0,
+            align: core::mem::align_of::<T>(),
+        }
+    }
+
+    implement_active_frames!(|s| s.buf.len().checked_div(s.channels).unwrap_or(usize::MAX));
+
+    /// Split the buffer into two [SequentialSlice] buffers with disjoint sets of channels,
+    /// where the first contains the channels before `channel`,
+    /// and the second contains `channel` and the following channels.
+    /// Since the channels do not overlap in memory,
+    /// the two buffers can be processed in parallel, for example in separate threads.
+    /// Returns `None` if `channel` is larger than the number of channels.
+    pub fn split_channels_at_mut(
+        &mut self,
+        channel: usize,
+    ) -> Option<SplitChannels<SequentialSlice<&mut [T]>>> {
+        if channel > self.channels {
+            return None;
+        }
+        let (first, second) = self.buf.split_at_mut(channel * self.frames);
+        Some((
+            SequentialSlice::new_mut(first, channel, self.frames).ok()?,
+            SequentialSlice::new_mut(second, self.channels - channel, self.frames).ok()?,
+        ))
+    }
+
+    /// Split the buffer into two [SequentialVecOfSlices] buffers with disjoint ranges of frames,
+    /// where the first contains the frames before `frame`,
+    /// and the second contains `frame` and the following frames.
+    /// Returns `None` if `frame` is larger than the number of frames.
+    pub fn split_frames_at_mut(
+        &mut self,
+        frame: usize,
+    ) -> Option<SplitFrames<SequentialVecOfSlices<Vec<&mut [T]>>>> {
+        if frame > self.frames {
+            return None;
+        }
+        let frames = self.frames;
+        let mut rest = &mut self.buf[..self.channels * frames];
+        let channels = (0..self.channels).map(move |_| {
+            let (chan, tail) = core::mem::take(&mut rest).split_at_mut(frames);
+            rest = tail;
+            chan
+        });
+        Some(split_channel_slices(channels, frame, frames))
     }
 }
 
@@ -236,6 +573,10 @@ where
 
     implement_size_getters!();
 
+    fn preferred_iteration(&self) -> IterationOrder {
+        IterationOrder::ChannelsOuter
+    }
+
     fn write_from_channel_to_slice(&self, channel: usize, skip: usize, slice: &mut [T]) -> usize {
         if channel >= self.channels || skip >= self.frames {
             return 0;
@@ -296,6 +637,97 @@ where
     }
 }
 
+implement_sample_access!([T] SequentialOwned<T>, mutable);
+
+//
+// =========================== SharedInterleaved ===========================
+//
+
+/// Read-only wrapper for a reference counted slice of length `frames * channels`,
+/// with samples stored in _interleaved_ order.
+/// Cloning the wrapper only increments the reference count,
+/// and the clones share the same samples.
+/// This is intended for data that is loaded once and then read
+/// from many places, possibly in several threads,
+/// such as the samples of a sample library or other preloaded assets.
+pub struct SharedInterleaved<T> {
+    buf: Arc<[T]>,
+    frames: usize,
+    channels: usize,
+}
+
+impl<T> SharedInterleaved<T> {
+    fn calc_index(&self, channel: usize, frame: usize) -> usize {
+        frame * self.channels + channel
+    }
+
+    /// Create a new `SharedInterleaved` to wrap a reference counted slice.
+    /// The slice length must be at least `frames*channels`.
+    /// It is allowed to be longer than needed,
+    /// but these extra values cannot be accessed via the trait methods.
+    pub fn new(buf: Arc<[T]>, channels: usize, frames: usize) -> Result<Self, SizeError> {
+        check_slice_length!(channels, frames, buf.len());
+        Ok(Self {
+            buf,
+            frames,
+            channels,
+        })
+    }
+
+    implement_with_dims!(with_dims = new(buf: Arc<[T]>) -> Result<Self, SizeError>);
+
+    /// Get a reference to the wrapped reference counted slice.
+    pub fn data(&self) -> &Arc<[T]> {
+        &self.buf
+    }
+}
+
+impl<T> Clone for SharedInterleaved<T> {
+    fn clone(&self) -> Self {
+        Self {
+            buf: Arc::clone(&self.buf),
+            frames: self.frames,
+            channels: self.channels,
+        }
+    }
+}
+
+impl<'a, T> Adapter<'a, T> for SharedInterleaved<T>
+where
+    T: Clone + 'a,
+{
+    unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
+        let index = self.calc_index(channel, frame);
+        self.buf.get_unchecked(index).clone()
+    }
+
+    implement_size_getters!();
+
+    fn preferred_iteration(&self) -> IterationOrder {
+        IterationOrder::FramesOuter
+    }
+
+    fn write_from_frame_to_slice(&self, frame: usize, skip: usize, slice: &mut [T]) -> usize {
+        if frame >= self.frames || skip >= self.channels {
+            return 0;
+        }
+        let channels_to_write = (self.channels - skip).min(slice.len());
+        let buffer_skip = self.calc_index(skip, frame);
+        slice[..channels_to_write]
+            .clone_from_slice(&self.buf[buffer_skip..buffer_skip + channels_to_write]);
+        channels_to_write
+    }
+
+    fn to_interleaved_vec(&self) -> Vec<T>
+    where
+        T: Clone + Default,
+    {
+        self.buf[..self.channels * self.frames].to_vec()
+    }
+}
+
+implement_sample_access!([T] SharedInterleaved<T>);
+
 //   _____         _
 //  |_   _|__  ___| |_ ___
 //    | |/ _ \/ __| __/ __|
@@ -305,6 +737,9 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sample::I16LE;
+    use crate::tests::check_copy_within;
+    use crate::AdapterMutAny;
 
     fn insert_data(buffer: &mut dyn AdapterMut<i32>) {
         buffer.write_sample(0, 0, &1);
@@ -405,6 +840,55 @@ mod tests {
         let _data = buffer.take_data();
     }
 
+    #[test]
+    fn interleaved_vec() {
+        let mut buffer = InterleavedOwned::new_aligned(0_i32, 2, 3, 64);
+        assert_eq!(buffer.copy_from_interleaved_slice(&[1, 4, 2, 5, 3]), 0);
+        assert_eq!(buffer.to_interleaved_vec(), vec![1, 4, 2, 5, 0, 0]);
+        buffer.set_active_frames(1).unwrap();
+        assert_eq!(buffer.to_interleaved_vec(), vec![1, 4]);
+    }
+
+    #[test]
+    fn snapshot() {
+        let mut data = [1, 2, 3, 4, 5, 6];
+        let mut buffer = SequentialSlice::new_mut(&mut data, 2, 3).unwrap();
+        let snapshot = buffer.snapshot();
+        buffer.write_sample(0, 0, &7).unwrap();
+        let handle = std::thread::spawn(move || snapshot.read_sample(0, 0));
+        assert_eq!(handle.join().unwrap(), Some(1));
+        assert_eq!(buffer.snapshot().take_data(), vec![7, 4, 2, 5, 3, 6]);
+    }
+
+    #[test]
+    fn split_channels() {
+        let mut buffer = SequentialOwned::new_from(vec![1, 2, 3, 4, 5, 6], 3, 2).unwrap();
+        let (mut first, second) = buffer.split_channels_at_mut(1).unwrap();
+        assert_eq!(first.channels(), 1);
+        assert_eq!(second.channels(), 2);
+        assert_eq!(second.read_sample(1, 1), Some(6));
+        first.write_sample(0, 1, &7).unwrap();
+        assert!(buffer.split_channels_at_mut(4).is_none());
+        assert_eq!(buffer.take_data(), vec![1, 7, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn split_frames() {
+        let mut buffer = InterleavedOwned::new_aligned(0, 2, 3, 64);
+        let (mut first, mut second) = buffer.split_frames_at_mut(1).unwrap();
+        first.fill_with(&1);
+        second.write_sample(1, 1, &2).unwrap();
+        assert!(buffer.split_frames_at_mut(4).is_none());
+        assert_eq!(buffer.to_interleaved_vec(), vec![1, 1, 0, 0, 0, 2]);
+
+        let mut buffer = SequentialOwned::new(0, 2, 3);
+        let (mut first, mut second) = buffer.split_frames_at_mut(2).unwrap();
+        assert_eq!((first.frames(), second.frames()), (2, 1));
+        first.fill_channel_with(1, &1);
+        second.fill_with(&2);
+        assert_eq!(buffer.take_data(), vec![0, 0, 2, 1, 1, 2]);
+    }
+
     // This tests that an Adapter is object safe.
     #[cfg(feature = "std")]
     #[test]
@@ -413,15 +897,80 @@ mod tests {
         assert_eq!(boxed.read_sample(0, 0).unwrap(), 1);
     }
 
+    #[test]
+    fn aligned() {
+        let mut buffer = InterleavedOwned::new_aligned(0_i32, 2, 10, 64);
+        assert_eq!(buffer.alignment(), 64);
+        assert_eq!(buffer.buf[buffer.offset..].as_ptr() as usize % 64, 0);
+        insert_data(&mut buffer);
+        test_get(&mut buffer);
+        test_slice_frame(&mut buffer);
+        test_mut_slice_channel(&mut buffer);
+        let data = buffer.take_data();
+        assert_eq!(data[0..4], [1, 10, 8, 11]);
+
+        let mut buffer = InterleavedOwned::new_aligned(0_u32, 2, 10, 32);
+        check_copy_within(&mut buffer);
+    }
+
+    #[test]
+    fn bytes_roundtrip() {
+        let bytes: Vec<u8> = vec![1, 0, 2, 0, 3, 0, 4, 0, 5, 0, 6, 0, 7];
+        let buffer = InterleavedOwned::<I16LE>::from_bytes(bytes, 2, 3).unwrap();
+        assert_eq!(buffer.read_sample(1, 1).unwrap().to_number(), 4);
+        assert_eq!(buffer.read_sample(0, 2).unwrap().to_number(), 5);
+        assert_eq!(
+            buffer.into_bytes(),
+            vec![1, 0, 2, 0, 3, 0, 4, 0, 5, 0, 6, 0]
+        );
+
+        let bytes: Vec<u8> = vec![0; 11];
+        assert!(InterleavedOwned::<I16LE>::from_bytes(bytes, 2, 3).is_err());
+
+        // The padding of an aligned buffer is not included.
+        let mut buffer = InterleavedOwned::new_aligned(I16LE::from_number(0), 2, 3, 64);
+        buffer.write_sample(1, 2, &I16LE::from_number(7));
+        assert_eq!(
+            buffer.into_bytes(),
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 7, 0]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "smallvec")]
+    fn inline_storage() {
+        let mut buffer = SequentialOwned::new(0_i32, 2, INLINE_SAMPLES / 2);
+        assert!(!buffer.buf.spilled());
+        insert_data(&mut buffer);
+        let buffer = buffer.into_interleaved();
+        assert!(!buffer.buf.spilled());
+        assert_eq!(buffer.read_sample(1, 1), Some(5));
+
+        let buffer = InterleavedOwned::new(0_i32, 2, INLINE_SAMPLES);
+        assert!(buffer.buf.spilled());
+
+        // aligned buffers must stay on the heap, so that moving the buffer keeps the alignment
+        let buffer = InterleavedOwned::new_aligned(0_i32, 1, 2, 16);
+        assert!(buffer.buf.spilled());
+        let moved = Box::new(buffer);
+        assert_eq!(moved.buf[moved.offset..].as_ptr() as usize % 16, 0);
+    }
+
     // Check that a buffer is Send + Sync,
     // meaning it can be sent between threads.
     // This test is not designed to be run, only to compile.
     #[allow(dead_code)]
-    fn test_adapter_send_and_sync<T: Sync + Send + Clone>() {
+    fn test_adapter_send_and_sync() {
         fn is_send<T: Send>() {}
         fn is_sync<T: Sync>() {}
         is_send::<InterleavedOwned<f32>>();
         is_sync::<InterleavedOwned<f32>>();
+        is_send::<SequentialOwned<f32>>();
+        is_sync::<SequentialOwned<f32>>();
+        is_send::<InterleavedOwned<I16LE>>();
+        is_sync::<SequentialOwned<I16LE>>();
+        is_send::<SharedInterleaved<f32>>();
+        is_sync::<SharedInterleaved<f32>>();
     }
 
     #[test]
@@ -443,6 +992,35 @@ mod tests {
         assert_eq!(buffer.read_sample(1, 2).unwrap(), 2.0);
     }
 
+    #[test]
+    fn active_frames() {
+        let mut buffer = InterleavedOwned::new_from(vec![1, 2, 3, 4, 5, 6, 7], 2, 1).unwrap();
+        assert_eq!(buffer.capacity_frames(), 3);
+        buffer.set_active_frames(3).unwrap();
+        assert_eq!(buffer.read_sample(1, 2), Some(6));
+        assert!(buffer.set_active_frames(4).is_err());
+
+        let buffer = InterleavedOwned::new_aligned(0_i32, 2, 5, 64);
+        assert!(buffer.capacity_frames() >= 5);
+
+        let mut buffer = SequentialOwned::new_from(vec![1, 2, 3, 4, 5, 6], 2, 2).unwrap();
+        assert_eq!(buffer.capacity_frames(), 3);
+        buffer.set_active_frames(3).unwrap();
+        assert_eq!(buffer.read_sample(1, 0), Some(4));
+    }
+
+    #[test]
+    fn convert_layout() {
+        let data = vec![1_i32, 4, 2, 5, 3, 6, 7];
+        let interleaved = InterleavedOwned::new_from(data, 2, 3).unwrap();
+        let sequential = interleaved.into_sequential();
+        assert_eq!(sequential.read_sample(0, 2), Some(3));
+        assert_eq!(sequential.read_sample(1, 0), Some(4));
+        let interleaved = sequential.into_interleaved();
+        assert_eq!(interleaved.read_sample(0, 2), Some(3));
+        assert_eq!(interleaved.take_data(), vec![1, 4, 2, 5, 3, 6, 7]);
+    }
+
     #[test]
     fn fill_channel() {
         let mut buffer = InterleavedOwned::new(1, 2, 3);
@@ -469,4 +1047,83 @@ mod tests {
         let data = buffer.take_data();
         assert_eq!(data, expected);
     }
+
+    #[test]
+    fn downcast_boxed() {
+        let mut buffer: Box<dyn AdapterMutAny<i32>> = Box::new(InterleavedOwned::new(0, 2, 3));
+        buffer.write_sample(1, 2, &5);
+        assert!(buffer
+            .as_any()
+            .downcast_ref::<SequentialOwned<i32>>()
+            .is_none());
+        let concrete = buffer
+            .as_any_mut()
+            .downcast_mut::<InterleavedOwned<i32>>()
+            .unwrap();
+        assert_eq!(concrete.read_sample(1, 2), Some(5));
+
+        // Samples that borrow data can still be used via the adapter traits.
+        let text = String::from("abc");
+        let borrowing = SequentialOwned::new(text.as_str(), 1, 2);
+        let adapter: &dyn Adapter<&str> = &borrowing;
+        assert_eq!(adapter.read_sample(0, 1), Some("abc"));
+    }
+
+    #[test]
+    fn index_owned() {
+        let mut buffer = InterleavedOwned::new_aligned(0, 2, 3, 64);
+        buffer[(1, 2)] = 5;
+        assert_eq!(buffer.read_sample(1, 2), Some(5));
+        assert_eq!(buffer[(1, 2)], 5);
+        let mut buffer = SequentialOwned::new(0, 2, 3);
+        buffer[(1, 0)] = 7;
+        assert_eq!(buffer.take_data(), [0, 0, 0, 7, 0, 0]);
+    }
+
+    #[test]
+    fn sample_references_owned() {
+        let mut buffer = InterleavedOwned::new(vec![0_u8; 2], 2, 3);
+        buffer.sample_mut(1, 2).unwrap().push(5);
+        assert_eq!(buffer.sample_ref(1, 2), Some(&vec![0, 0, 5]));
+        assert_eq!(buffer.sample_ref(2, 0), None);
+        let mut buffer = SequentialOwned::new(0, 2, 3);
+        *buffer.sample_mut(0, 1).unwrap() = 3;
+        assert!(buffer.sample_mut(0, 3).is_none());
+        assert_eq!(buffer.take_data(), [0, 3, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn boxed_slices() {
+        let data: Box<[i32]> = vec![1, 2, 3, 4, 5, 6].into_boxed_slice();
+        let buffer = InterleavedOwned::new_from_boxed(data.clone(), 2, 3).unwrap();
+        assert_eq!(buffer.read_sample(1, 0), Some(2));
+        let buffer = SequentialOwned::new_from_boxed(data.clone(), 2, 3).unwrap();
+        assert_eq!(buffer.read_sample(1, 0), Some(4));
+        assert!(SequentialOwned::new_from_boxed(data, 2, 4).is_err());
+    }
+
+    #[test]
+    fn shared_interleaved() {
+        let mut owned = InterleavedOwned::new_aligned(0, 2, 3, 32);
+        owned.write_sample(1, 2, &5);
+        owned.set_active_frames(2).unwrap();
+        let shared = owned.into_shared();
+        assert_eq!(shared.data().len(), 4);
+        let other = shared.clone();
+        assert!(Arc::ptr_eq(shared.data(), other.data()));
+
+        let handle = std::thread::spawn(move || other.read_sample(1, 1));
+        assert_eq!(handle.join().unwrap(), Some(0));
+        assert_eq!(shared.frames(), 2);
+        assert_eq!(shared[(0, 1)], 0);
+
+        let data: Arc<[i32]> = Arc::from([1, 2, 3, 4, 5, 6]);
+        let shared = SharedInterleaved::new(data, 2, 3).unwrap();
+        assert_eq!(shared.to_interleaved_vec(), [1, 2, 3, 4, 5, 6]);
+        let mut frame = [0; 2];
+        shared.write_from_frame_to_slice(2, 0, &mut frame);
+        assert_eq!(frame, [5, 6]);
+        assert_eq!(shared.sample_ref(1, 0), Some(&2));
+        assert!(SharedInterleaved::new(Arc::<[i32]>::from([0; 5]), 2, 3).is_err());
+    }
 }
@@ -0,0 +1,160 @@
+//! # Parallel conversion of buffers
+//!
+//! This module provides [convert_parallel](crate::parallel::convert_parallel),
+//! that converts float samples to a numerical sample format using several threads.
+//! This is intended for offline processing, such as exporting a long render to a file,
+//! where the conversion of a large buffer would otherwise run on a single core.
+//!
+//! The destination buffer is divided into chunks of frames with `split_frames_at_mut`,
+//! and the chunks are converted in parallel using [rayon](https://crates.io/crates/rayon).
+//! The source buffer is shared between the threads,
+//! and must therefore be [Sync].
+//!
+//! This is enabled via the `parallel` Cargo feature.
+//!
+//! ## Example
+//! Convert a stereo float buffer to interleaved 16-bit integers.
+//! ```
+//! use audioadapter::direct::{InterleavedSlice, SequentialSlice};
+//! use audioadapter::parallel::convert_parallel;
+//!
+//! let input = vec![0.5_f32; 2 * 10000];
+//! let mut output = vec![0_i16; 2 * 10000];
+//! let src = SequentialSlice::new(&input, 2, 10000).unwrap();
+//! let mut dst = InterleavedSlice::new_mut(&mut output, 2, 10000).unwrap();
+//!
+//! let clipped = convert_parallel(&src, &mut dst, 1024);
+//! assert_eq!(clipped, 0);
+//! assert!(output.iter().all(|value| *value == 16384));
+//! ```
+
+use crate::direct::InterleavedSlice;
+use crate::sample::RawSample;
+use crate::{Adapter, AdapterMut};
+
+/// Convert the float samples of `src` to the numerical samples of `dst`, using several threads.
+/// The frames are divided into chunks of `chunk_frames` frames,
+/// that are converted in parallel.
+/// Values are converted in the same way as when writing to the converting wrappers,
+/// meaning that values outside the range -1.0 .. +1.0 are clipped for integer formats.
+///
+/// The number of channels and frames converted are the smallest of the two buffers.
+/// Returns the number of values that were clipped.
+///
+/// Panics if `chunk_frames` is zero.
+pub fn convert_parallel<'a, T>(
+    src: &(dyn Adapter<'a, f32> + Sync),
+    dst: &mut InterleavedSlice<&mut [T]>,
+    chunk_frames: usize,
+) -> usize
+where
+    T: RawSample + Clone + Send,
+{
+    assert!(
+        chunk_frames > 0,
+        "The chunk size must be at least one frame"
+    );
+    let frames = src.frames().min(dst.frames());
+    match dst.split_frames_at_mut(frames) {
+        Some((mut head, _)) => convert_chunks(src, 0, &mut head, chunk_frames),
+        None => 0,
+    }
+}
+
+/// Convert the frames of `dst`, reading from `src` starting at frame `offset`.
+/// The frames are split in two halves, at a chunk boundary,
+/// that are converted in parallel until only a single chunk remains.
+fn convert_chunks<'a, T>(
+    src: &(dyn Adapter<'a, f32> + Sync),
+    offset: usize,
+    dst: &mut InterleavedSlice<&mut [T]>,
+    chunk_frames: usize,
+) -> usize
+where
+    T: RawSample + Clone + Send,
+{
+    let frames = dst.frames();
+    if frames <= chunk_frames {
+        return convert_chunk(src, offset, dst);
+    }
+    let middle = (frames / chunk_frames).div_ceil(2) * chunk_frames;
+    // Cannot fail since the middle frame is smaller than the number of frames.
+    let (mut first, mut second) = dst.split_frames_at_mut(middle).unwrap();
+    let (clipped_first, clipped_second) = rayon::join(
+        || convert_chunks(src, offset, &mut first, chunk_frames),
+        || convert_chunks(src, offset + middle, &mut second, chunk_frames),
+    );
+    clipped_first + clipped_second
+}
+
+/// Convert the frames of `dst` on the current thread,
+/// reading from `src` starting at frame `offset`.
+fn convert_chunk<'a, T>(
+    src: &(dyn Adapter<'a, f32> + Sync),
+    offset: usize,
+    dst: &mut InterleavedSlice<&mut [T]>,
+) -> usize
+where
+    T: RawSample + Clone,
+{
+    let channels = src.channels().min(dst.channels());
+    let mut values = vec![0.0_f32; channels];
+    let mut nbr_clipped = 0;
+    for frame in 0..dst.frames() {
+        src.write_from_frame_to_slice(offset + frame, 0, &mut values);
+        for (channel, value) in values.iter().enumerate() {
+            let converted = T::from_scaled_float(*value);
+            unsafe { dst.write_sample_unchecked(channel, frame, &converted.value) };
+            nbr_clipped += converted.clipped as usize;
+        }
+    }
+    nbr_clipped
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direct::SequentialSlice;
+
+    #[test]
+    fn convert_in_chunks() {
+        let frames = 1000;
+        let input: Vec<f32> = (0..2 * frames)
+            .map(|n| (n % 7) as f32 / 4.0 - 0.75)
+            .collect();
+        let src = SequentialSlice::new(&input, 2, frames).unwrap();
+        for chunk_frames in [1, 7, 64, 999, 1000, 5000] {
+            let mut output = vec![0_i16; 2 * frames];
+            let mut dst = InterleavedSlice::new_mut(&mut output, 2, frames).unwrap();
+            // Values of 1.0 and 1.25 are clipped.
+            let expected_clipped = input.iter().filter(|value| **value >= 1.0).count();
+            assert_eq!(
+                convert_parallel(&src, &mut dst, chunk_frames),
+                expected_clipped
+            );
+            for frame in 0..frames {
+                for channel in 0..2 {
+                    let value = input[channel * frames + frame];
+                    let expected = i16::from_scaled_float(value).value;
+                    assert_eq!(output[2 * frame + channel], expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn convert_shorter_source() {
+        let input = [0.5_f32; 3];
+        let mut output = [0_i32; 5];
+        let src = InterleavedSlice::new(&input, 1, 3).unwrap();
+        let mut dst = InterleavedSlice::new_mut(&mut output, 1, 5).unwrap();
+        assert_eq!(convert_parallel(&src, &mut dst, 2), 0);
+        assert_eq!(output, [1 << 30, 1 << 30, 1 << 30, 0, 0]);
+    }
+}
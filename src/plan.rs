@@ -0,0 +1,274 @@
+//! # Precomputed copy plans
+//!
+//! This module provides [CopyPlan](crate::plan::CopyPlan), that copies samples from one buffer to another
+//! according to a channel mapping.
+//! The plan is prepared once for a given pair of buffers,
+//! and can then be executed repeatedly, for example once per processing callback.
+//!
+//! When the plan is created, the mapping is sorted and consecutive channels
+//! are merged into blocks, and the order of the loops is chosen
+//! from the preferred iteration order of the buffers.
+//! Each block is then copied via the slice methods of the buffers,
+//! so that buffers with contiguous storage can use plain memory copies,
+//! and converting wrappers can convert a whole block in one call.
+//! Executing the plan does not allocate.
+//!
+//! ## Example
+//! Copy the two channels of a stereo buffer to channels 2 and 3 of a four channel buffer.
+//! ```
+//! use audioadapter::direct::{InterleavedSlice, SequentialSlice};
+//! use audioadapter::plan::CopyPlan;
+//! use audioadapter::{Adapter, AdapterMut};
+//!
+//! let input = [1, 2, 3, 4, 5, 6];
+//! let mut output = [0; 12];
+//! let src = InterleavedSlice::new(&input, 2, 3).unwrap();
+//! let mut dst = SequentialSlice::new_mut(&mut output, 4, 3).unwrap();
+//!
+//! let mut plan = CopyPlan::new(&src, &dst, &[(0, 2), (1, 3)]).unwrap();
+//! // Execute the plan, this is typically repeated for every new buffer.
+//! assert_eq!(plan.execute(&src, &mut dst), Some(0));
+//! assert_eq!(output, [0, 0, 0, 0, 0, 0, 1, 3, 5, 2, 4, 6]);
+//! ```
+
+use crate::{Adapter, AdapterMut, IterationOrder};
+
+/// A block of consecutive channels,
+/// copied from `src..src+count` to `dst..dst+count`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Block {
+    src: usize,
+    dst: usize,
+    count: usize,
+}
+
+/// A precomputed plan for copying samples between two buffers,
+/// according to a mapping of source channels to destination channels.
+#[derive(Debug, Clone)]
+pub struct CopyPlan<T> {
+    blocks: Vec<Block>,
+    frames: usize,
+    frames_outer: bool,
+    src_channels: usize,
+    dst_channels: usize,
+    scratch: Vec<T>,
+}
+
+impl<T> CopyPlan<T>
+where
+    T: Clone,
+{
+    /// Create a new plan for copying from `src` to `dst`.
+    /// The `mapping` is a list of `(source channel, destination channel)` pairs.
+    /// If several pairs have the same destination channel, the last one is used.
+    /// The number of frames copied is the smallest of the two buffers.
+    ///
+    /// Returns `None` if the mapping contains a channel that does not exist in the buffers.
+    pub fn new<'a>(
+        src: &dyn Adapter<'a, T>,
+        dst: &dyn Adapter<'a, T>,
+        mapping: &[(usize, usize)],
+    ) -> Option<Self>
+    where
+        T: 'a,
+    {
+        if mapping
+            .iter()
+            .any(|(src_chan, dst_chan)| *src_chan >= src.channels() || *dst_chan >= dst.channels())
+        {
+            return None;
+        }
+        let mut pairs = mapping.to_vec();
+        // A stable sort keeps the given order of pairs with the same destination.
+        pairs.sort_by_key(|(_, dst_chan)| *dst_chan);
+        let mut blocks: Vec<Block> = Vec::with_capacity(pairs.len());
+        for (src_chan, dst_chan) in pairs {
+            match blocks.last_mut() {
+                Some(block) if block.dst + block.count - 1 == dst_chan => {
+                    // Same destination as the previous pair, the later pair wins.
+                    if block.count == 1 {
+                        block.src = src_chan;
+                    } else {
+                        block.count -= 1;
+                        blocks.push(Block {
+                            src: src_chan,
+                            dst: dst_chan,
+                            count: 1,
+                        });
+                    }
+                }
+                Some(block)
+                    if block.dst + block.count == dst_chan
+                        && block.src + block.count == src_chan =>
+                {
+                    block.count += 1;
+                }
+                _ => blocks.push(Block {
+                    src: src_chan,
+                    dst: dst_chan,
+                    count: 1,
+                }),
+            }
+        }
+        let frames = src.frames().min(dst.frames());
+        let frames_outer = src.preferred_iteration() == IterationOrder::FramesOuter
+            && dst.preferred_iteration() == IterationOrder::FramesOuter;
+        let scratch_len = if frames_outer {
+            blocks.iter().map(|block| block.count).max().unwrap_or(0)
+        } else {
+            frames
+        };
+        let scratch = match src.read_sample(0, 0) {
+            Some(value) => vec![value; scratch_len],
+            None => Vec::new(),
+        };
+        Some(Self {
+            blocks,
+            frames,
+            frames_outer,
+            src_channels: src.channels(),
+            dst_channels: dst.channels(),
+            scratch,
+        })
+    }
+
+    /// Get the number of frames copied by the plan.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Get the number of copy operations performed for each channel or frame.
+    /// This is the number of blocks of consecutive channels in the mapping.
+    pub fn nbr_blocks(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Copy samples from `src` to `dst` according to the plan.
+    /// The buffers must have at least the number of channels and frames
+    /// of the buffers the plan was created for.
+    ///
+    /// Returns the number of values that were clipped during conversion,
+    /// or `None` if the buffers are too small for the plan.
+    pub fn execute<'a>(
+        &mut self,
+        src: &dyn Adapter<'a, T>,
+        dst: &mut dyn AdapterMut<'a, T>,
+    ) -> Option<usize>
+    where
+        T: 'a,
+    {
+        if src.channels() < self.src_channels
+            || dst.channels() < self.dst_channels
+            || src.frames() < self.frames
+            || dst.frames() < self.frames
+        {
+            return None;
+        }
+        let mut nbr_clipped = 0;
+        if self.frames_outer {
+            for frame in 0..self.frames {
+                for block in self.blocks.iter() {
+                    let values = &mut self.scratch[..block.count];
+                    src.write_from_frame_to_slice(frame, block.src, values);
+                    nbr_clipped += dst.write_from_slice_to_frame(frame, block.dst, values).1;
+                }
+            }
+        } else {
+            let values = &mut self.scratch[..self.frames];
+            for block in self.blocks.iter() {
+                for n in 0..block.count {
+                    src.write_from_channel_to_slice(block.src + n, 0, values);
+                    nbr_clipped += dst.write_from_slice_to_channel(block.dst + n, 0, values).1;
+                }
+            }
+        }
+        Some(nbr_clipped)
+    }
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direct::{InterleavedSlice, SequentialSlice};
+    use crate::number_to_float::InterleavedNumbers;
+
+    #[test]
+    fn merge_blocks() {
+        let src = InterleavedSlice::new(&[0; 12], 4, 3).unwrap();
+        let dst = InterleavedSlice::new(&[0; 18], 6, 3).unwrap();
+        let plan = CopyPlan::new(&src, &dst, &[(1, 3), (0, 2), (2, 4), (3, 0)]).unwrap();
+        assert_eq!(
+            plan.blocks,
+            [
+                Block {
+                    src: 3,
+                    dst: 0,
+                    count: 1
+                },
+                Block {
+                    src: 0,
+                    dst: 2,
+                    count: 3
+                }
+            ]
+        );
+        let plan = CopyPlan::new(&src, &dst, &[(0, 0), (1, 1), (3, 1)]).unwrap();
+        assert_eq!(plan.nbr_blocks(), 2);
+        assert!(CopyPlan::new(&src, &dst, &[(4, 0)]).is_none());
+        assert!(CopyPlan::new(&src, &dst, &[(0, 6)]).is_none());
+    }
+
+    #[test]
+    fn execute_interleaved() {
+        let input = [1, 2, 3, 4, 5, 6];
+        let mut output = [0; 9];
+        let src = InterleavedSlice::new(&input, 2, 3).unwrap();
+        let mut dst = InterleavedSlice::new_mut(&mut output, 3, 3).unwrap();
+        let mut plan = CopyPlan::new(&src, &dst, &[(0, 1), (1, 2), (0, 0)]).unwrap();
+        assert_eq!(plan.nbr_blocks(), 2);
+        for _ in 0..2 {
+            assert_eq!(plan.execute(&src, &mut dst), Some(0));
+        }
+        assert_eq!(output, [1, 1, 2, 3, 3, 4, 5, 5, 6]);
+    }
+
+    #[test]
+    fn execute_sequential() {
+        let input = [1, 2, 3, 4, 5, 6];
+        let mut output = [0; 4];
+        let src = SequentialSlice::new(&input, 3, 2).unwrap();
+        let mut dst = InterleavedSlice::new_mut(&mut output, 2, 2).unwrap();
+        let mut plan = CopyPlan::new(&src, &dst, &[(2, 0), (0, 1)]).unwrap();
+        assert_eq!(plan.frames(), 2);
+        assert_eq!(plan.execute(&src, &mut dst), Some(0));
+        assert_eq!(output, [5, 1, 6, 2]);
+    }
+
+    #[test]
+    fn execute_converting() {
+        let input = [0.5_f32, 2.0, -0.5, -2.0];
+        let mut output = [0_i16; 4];
+        let src = InterleavedSlice::new(&input, 2, 2).unwrap();
+        let mut dst = InterleavedNumbers::<_, f32>::new_mut(&mut output, 2, 2).unwrap();
+        let mut plan = CopyPlan::new(&src, &dst, &[(0, 0), (1, 1)]).unwrap();
+        assert_eq!(plan.execute(&src, &mut dst), Some(2));
+        assert_eq!(output, [1 << 14, i16::MAX, -(1 << 14), i16::MIN]);
+    }
+
+    #[test]
+    fn execute_too_small() {
+        let input = [0; 6];
+        let mut output = [0; 6];
+        let src = InterleavedSlice::new(&input, 2, 3).unwrap();
+        let dst = InterleavedSlice::new(&output, 2, 3).unwrap();
+        let mut plan = CopyPlan::new(&src, &dst, &[(0, 0)]).unwrap();
+        let mut small = InterleavedSlice::new_mut(&mut output, 2, 2).unwrap();
+        assert_eq!(plan.execute(&src, &mut small), None);
+    }
+}
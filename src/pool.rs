@@ -0,0 +1,153 @@
+//! # Pool of reusable buffers
+//!
+//! This module provides a pool of preallocated [InterleavedOwned](crate::owned::InterleavedOwned) buffers,
+//! that all have the same number of channels and frames.
+//! A buffer is checked out from the pool when it is needed,
+//! and returned to the pool when it is no longer used.
+//! Buffers are cleared when they are returned,
+//! so every buffer that is checked out is filled with the initial value.
+//!
+//! All buffers are allocated when the pool is created.
+//! Checking out and returning buffers does not allocate,
+//! which makes the pool suitable for real-time processing
+//! where temporary buffers are needed in every processing callback.
+//!
+//! ## Example
+//! ```
+//! use audioadapter::pool::BufferPool;
+//! use audioadapter::{Adapter, AdapterMut};
+//!
+//! let mut pool = BufferPool::new(0.0_f32, 2, 512, 4);
+//!
+//! let mut buffer = pool.checkout().unwrap();
+//! buffer.write_sample(0, 0, &0.5);
+//! pool.checkin(buffer);
+//!
+//! let buffer = pool.checkout().unwrap();
+//! assert_eq!(buffer.read_sample(0, 0), Some(0.0));
+//! ```
+
+use crate::owned::InterleavedOwned;
+use crate::{Adapter, AdapterMut};
+
+/// A pool of [InterleavedOwned] buffers with a fixed number of channels and frames.
+pub struct BufferPool<T> {
+    free: Vec<InterleavedOwned<T>>,
+    value: T,
+    channels: usize,
+    frames: usize,
+    size: usize,
+}
+
+impl<T> BufferPool<T>
+where
+    T: Clone,
+{
+    /// Create a new pool with `size` buffers,
+    /// each with `channels` channels and `frames` frames, filled with `value`.
+    pub fn new(value: T, channels: usize, frames: usize, size: usize) -> Self {
+        let free = (0..size)
+            .map(|_| InterleavedOwned::new(value.clone(), channels, frames))
+            .collect();
+        Self {
+            free,
+            value,
+            channels,
+            frames,
+            size,
+        }
+    }
+
+    /// Check out a buffer from the pool.
+    /// Returns `None` if all buffers are checked out.
+    pub fn checkout(&mut self) -> Option<InterleavedOwned<T>> {
+        self.free.pop()
+    }
+
+    /// Return a buffer to the pool.
+    /// The number of active frames is restored, and the buffer is filled with the initial value.
+    ///
+    /// Panics if the buffer does not have the shape of the buffers in the pool,
+    /// or if more buffers are returned than the pool was created with.
+    pub fn checkin(&mut self, mut buffer: InterleavedOwned<T>) {
+        assert!(
+            buffer.channels() == self.channels && buffer.capacity_frames() >= self.frames,
+            "The buffer does not match the pool, got {} channels and capacity for {} frames, expected {} channels and {} frames",
+            buffer.channels(),
+            buffer.capacity_frames(),
+            self.channels,
+            self.frames
+        );
+        assert!(
+            self.free.len() < self.size,
+            "More buffers were returned than the pool contains"
+        );
+        // Cannot fail since the capacity was checked above.
+        let _ = buffer.set_active_frames(self.frames);
+        buffer.fill_with(&self.value);
+        self.free.push(buffer);
+    }
+
+    /// Get the number of buffers that are available for checkout.
+    pub fn available(&self) -> usize {
+        self.free.len()
+    }
+
+    /// Get the total number of buffers in the pool,
+    /// including the ones that are currently checked out.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Get the number of channels of the buffers in the pool.
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Get the number of frames of the buffers in the pool.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checkout_and_return() {
+        let mut pool = BufferPool::new(0_i32, 2, 8, 2);
+        assert_eq!(pool.size(), 2);
+        let mut first = pool.checkout().unwrap();
+        let second = pool.checkout().unwrap();
+        assert!(pool.checkout().is_none());
+        assert_eq!(pool.available(), 0);
+
+        first.fill_with(&5);
+        first.set_active_frames(3).unwrap();
+        pool.checkin(first);
+        pool.checkin(second);
+        assert_eq!(pool.available(), 2);
+
+        for _ in 0..2 {
+            let buffer = pool.checkout().unwrap();
+            assert_eq!(buffer.frames(), 8);
+            assert_eq!(buffer.channels(), 2);
+            assert!((0..8).all(|frame| buffer.read_sample(1, frame) == Some(0)));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn return_wrong_shape() {
+        let mut pool = BufferPool::new(0_i32, 2, 8, 1);
+        let _ = pool.checkout();
+        pool.checkin(InterleavedOwned::new(0, 1, 8));
+    }
+}
@@ -0,0 +1,209 @@
+//! # Processors operating on buffers
+//!
+//! This module provides the [Process](crate::process::Process) trait, a common interface for
+//! processors such as filters and resamplers that read their input from one buffer
+//! and write their output to another.
+//! Processors do not depend on a fixed number of frames,
+//! and handle buffers of any length in each call.
+//!
+//! Processors can be combined using [Process::chain](crate::process::Process::chain),
+//! which creates a [Chain](crate::process::Chain) that feeds the output of the first processor
+//! to the input of the second, via an intermediate buffer.
+//!
+//! ## Example
+//! Chain two processors that apply gain.
+//! ```
+//! use audioadapter::direct::InterleavedSlice;
+//! use audioadapter::process::Process;
+//! use audioadapter::{Adapter, AdapterMut};
+//!
+//! struct Gain(f32);
+//!
+//! impl Process for Gain {
+//!     fn process<'a>(
+//!         &mut self,
+//!         input: &dyn Adapter<'a, f32>,
+//!         output: &mut dyn AdapterMut<'a, f32>,
+//!     ) {
+//!         audioadapter::ops::copy_with_gain(input, output, self.0);
+//!     }
+//! }
+//!
+//! let mut chain = Gain(2.0).chain(Gain(0.25), 2, 512);
+//!
+//! let input = [1.0, 2.0, 3.0, 4.0];
+//! let mut output = [0.0; 4];
+//! let src = InterleavedSlice::new(&input, 2, 2).unwrap();
+//! let mut dst = InterleavedSlice::new_mut(&mut output, 2, 2).unwrap();
+//! chain.process(&src, &mut dst);
+//! assert_eq!(output, [0.5, 1.0, 1.5, 2.0]);
+//! ```
+
+use crate::owned::SequentialOwned;
+use crate::{Adapter, AdapterMut};
+
+/// A processor that reads samples from an input buffer
+/// and writes the result to an output buffer.
+pub trait Process {
+    /// Process the samples of `input` and write the result to `output`.
+    /// The number of frames may be different in every call.
+    /// How a processor handles input and output buffers
+    /// of different sizes is up to the implementation.
+    fn process<'a>(&mut self, input: &dyn Adapter<'a, f32>, output: &mut dyn AdapterMut<'a, f32>);
+
+    /// Combine this processor with another one, that processes the output of this one.
+    /// The output of this processor is written to an intermediate buffer
+    /// with `channels` channels, preallocated for `max_frames` frames.
+    /// The intermediate buffer is reallocated if a longer input is processed.
+    fn chain<P>(self, next: P, channels: usize, max_frames: usize) -> Chain<Self, P>
+    where
+        Self: Sized,
+        P: Process,
+    {
+        Chain::new(self, next, channels, max_frames)
+    }
+}
+
+impl<P> Process for &mut P
+where
+    P: Process + ?Sized,
+{
+    fn process<'a>(&mut self, input: &dyn Adapter<'a, f32>, output: &mut dyn AdapterMut<'a, f32>) {
+        (**self).process(input, output)
+    }
+}
+
+impl<P> Process for Box<P>
+where
+    P: Process + ?Sized,
+{
+    fn process<'a>(&mut self, input: &dyn Adapter<'a, f32>, output: &mut dyn AdapterMut<'a, f32>) {
+        (**self).process(input, output)
+    }
+}
+
+/// Two processors combined in series, created by [Process::chain].
+pub struct Chain<A, B> {
+    first: A,
+    second: B,
+    buffer: SequentialOwned<f32>,
+}
+
+impl<A, B> Chain<A, B>
+where
+    A: Process,
+    B: Process,
+{
+    /// Create a new chain, where the output of `first` is processed by `second`.
+    /// The intermediate buffer has `channels` channels,
+    /// and is preallocated for `max_frames` frames.
+    pub fn new(first: A, second: B, channels: usize, max_frames: usize) -> Self {
+        Self {
+            first,
+            second,
+            buffer: SequentialOwned::new(0.0, channels, max_frames),
+        }
+    }
+
+    /// Get references to the two processors of the chain.
+    pub fn processors(&self) -> (&A, &B) {
+        (&self.first, &self.second)
+    }
+
+    /// Get mutable references to the two processors of the chain.
+    pub fn processors_mut(&mut self) -> (&mut A, &mut B) {
+        (&mut self.first, &mut self.second)
+    }
+
+    /// Split the chain into the two processors.
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A, B> Process for Chain<A, B>
+where
+    A: Process,
+    B: Process,
+{
+    fn process<'a>(&mut self, input: &dyn Adapter<'a, f32>, output: &mut dyn AdapterMut<'a, f32>) {
+        let frames = input.frames();
+        if self.buffer.set_active_frames(frames).is_err() {
+            // The input is longer than the preallocated buffer.
+            self.buffer = SequentialOwned::new(0.0, self.buffer.channels(), frames);
+        }
+        self.first.process(input, &mut self.buffer);
+        self.second.process(&self.buffer, output);
+    }
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direct::InterleavedSlice;
+
+    /// Adds an offset, and records the number of frames of each call.
+    struct Offset {
+        offset: f32,
+        calls: Vec<usize>,
+    }
+
+    impl Process for Offset {
+        fn process<'a>(
+            &mut self,
+            input: &dyn Adapter<'a, f32>,
+            output: &mut dyn AdapterMut<'a, f32>,
+        ) {
+            self.calls.push(input.frames());
+            for channel in 0..input.channels().min(output.channels()) {
+                for frame in 0..input.frames().min(output.frames()) {
+                    let value = input.read_sample(channel, frame).unwrap() + self.offset;
+                    output.write_sample(channel, frame, &value);
+                }
+            }
+        }
+    }
+
+    fn offset(offset: f32) -> Offset {
+        Offset {
+            offset,
+            calls: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn chain_varying_lengths() {
+        let mut chain = offset(1.0).chain(offset(10.0), 1, 2);
+        for frames in [2, 1, 4] {
+            let input = vec![0.5; frames];
+            let mut output = vec![0.0; frames];
+            let src = InterleavedSlice::new(&input, 1, frames).unwrap();
+            let mut dst = InterleavedSlice::new_mut(&mut output, 1, frames).unwrap();
+            chain.process(&src, &mut dst);
+            assert_eq!(output, vec![11.5; frames]);
+        }
+        let (first, second) = chain.into_inner();
+        assert_eq!(first.calls, [2, 1, 4]);
+        assert_eq!(second.calls, [2, 1, 4]);
+    }
+
+    #[test]
+    fn chain_boxed() {
+        let first: Box<dyn Process> = Box::new(offset(1.0));
+        let mut second = offset(2.0);
+        let mut chain = first.chain(&mut second, 2, 8).chain(offset(3.0), 2, 8);
+        let input = [0.0; 4];
+        let mut output = [0.0; 4];
+        let src = InterleavedSlice::new(&input, 2, 2).unwrap();
+        let mut dst = InterleavedSlice::new_mut(&mut output, 2, 2).unwrap();
+        chain.process(&src, &mut dst);
+        assert_eq!(output, [6.0; 4]);
+        assert_eq!(chain.processors().0.processors().1.calls, [2]);
+    }
+}
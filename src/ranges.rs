@@ -0,0 +1,222 @@
+use core::ops::{Bound, Range, RangeBounds};
+
+use crate::{Adapter, AdapterMut};
+
+/// Convert a range with any kind of bounds to a `Range`,
+/// where an unbounded end is replaced by `len`.
+/// Returns `None` if the range starts after its end, or ends after `len`.
+fn resolve_range(range: impl RangeBounds<usize>, len: usize) -> Option<Range<usize>> {
+    let start = match range.start_bound() {
+        Bound::Included(start) => *start,
+        Bound::Excluded(start) => start.checked_add(1)?,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(end) => end.checked_add(1)?,
+        Bound::Excluded(end) => *end,
+        Bound::Unbounded => len,
+    };
+    if start > end || end > len {
+        return None;
+    }
+    Some(start..end)
+}
+
+/// A trait providing methods for copying samples from a range of frames or channels
+/// of an [Adapter] to a slice.
+/// The ranges can be given with any of the range types of the standard library,
+/// such as `10..20`, `10..=19`, `10..` or `..`.
+///
+/// These are alternatives to the methods of [Adapter] that take a `skip` argument,
+/// such as [Adapter::write_from_channel_to_slice].
+/// A range that starts after its end, or that extends past the end of the buffer,
+/// is invalid, and then no samples are copied.
+pub trait AdapterRanges<'a, T: 'a> {
+    /// Copy the samples in a range of frames of a channel to a slice.
+    /// If the slice is shorter than the range, only the number of samples that fit are copied.
+    ///
+    /// Returns the number of values written,
+    /// or zero if called with an invalid channel number or range.
+    fn copy_channel_range_to_slice(
+        &self,
+        channel: usize,
+        frames: impl RangeBounds<usize>,
+        slice: &mut [T],
+    ) -> usize;
+
+    /// Copy the samples in a range of channels of a frame to a slice.
+    /// If the slice is shorter than the range, only the number of samples that fit are copied.
+    ///
+    /// Returns the number of values written,
+    /// or zero if called with an invalid frame number or range.
+    fn copy_frame_range_to_slice(
+        &self,
+        frame: usize,
+        channels: impl RangeBounds<usize>,
+        slice: &mut [T],
+    ) -> usize;
+}
+
+impl<'a, T, U> AdapterRanges<'a, T> for U
+where
+    T: Clone + 'a,
+    U: Adapter<'a, T> + ?Sized,
+{
+    fn copy_channel_range_to_slice(
+        &self,
+        channel: usize,
+        frames: impl RangeBounds<usize>,
+        slice: &mut [T],
+    ) -> usize {
+        match resolve_range(frames, self.frames()) {
+            Some(range) => {
+                let len = range.len().min(slice.len());
+                self.write_from_channel_to_slice(channel, range.start, &mut slice[..len])
+            }
+            None => 0,
+        }
+    }
+
+    fn copy_frame_range_to_slice(
+        &self,
+        frame: usize,
+        channels: impl RangeBounds<usize>,
+        slice: &mut [T],
+    ) -> usize {
+        match resolve_range(channels, self.channels()) {
+            Some(range) => {
+                let len = range.len().min(slice.len());
+                self.write_from_frame_to_slice(frame, range.start, &mut slice[..len])
+            }
+            None => 0,
+        }
+    }
+}
+
+/// A trait providing methods for copying samples from a slice
+/// to a range of frames or channels of an [AdapterMut].
+/// The ranges are handled in the same way as for [AdapterRanges].
+pub trait AdapterRangesMut<'a, T: 'a> {
+    /// Copy samples from a slice to a range of frames of a channel.
+    /// If the slice is shorter than the range, only the samples of the slice are copied.
+    ///
+    /// Returns a tuple of two numbers.
+    /// The first is the number of values written,
+    /// and the second is the number of values that were clipped during conversion.
+    /// If called with an invalid channel number or range, (0, 0) is returned.
+    fn copy_slice_to_channel_range(
+        &mut self,
+        channel: usize,
+        frames: impl RangeBounds<usize>,
+        slice: &[T],
+    ) -> (usize, usize);
+
+    /// Copy samples from a slice to a range of channels of a frame.
+    /// If the slice is shorter than the range, only the samples of the slice are copied.
+    ///
+    /// Returns a tuple of two numbers.
+    /// The first is the number of values written,
+    /// and the second is the number of values that were clipped during conversion.
+    /// If called with an invalid frame number or range, (0, 0) is returned.
+    fn copy_slice_to_frame_range(
+        &mut self,
+        frame: usize,
+        channels: impl RangeBounds<usize>,
+        slice: &[T],
+    ) -> (usize, usize);
+}
+
+impl<'a, T, U> AdapterRangesMut<'a, T> for U
+where
+    T: Clone + 'a,
+    U: AdapterMut<'a, T> + ?Sized,
+{
+    fn copy_slice_to_channel_range(
+        &mut self,
+        channel: usize,
+        frames: impl RangeBounds<usize>,
+        slice: &[T],
+    ) -> (usize, usize) {
+        match resolve_range(frames, self.frames()) {
+            Some(range) => {
+                let len = range.len().min(slice.len());
+                self.write_from_slice_to_channel(channel, range.start, &slice[..len])
+            }
+            None => (0, 0),
+        }
+    }
+
+    fn copy_slice_to_frame_range(
+        &mut self,
+        frame: usize,
+        channels: impl RangeBounds<usize>,
+        slice: &[T],
+    ) -> (usize, usize) {
+        match resolve_range(channels, self.channels()) {
+            Some(range) => {
+                let len = range.len().min(slice.len());
+                self.write_from_slice_to_frame(frame, range.start, &slice[..len])
+            }
+            None => (0, 0),
+        }
+    }
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direct::InterleavedSlice;
+
+    #[test]
+    fn resolve() {
+        assert_eq!(resolve_range(2..5, 10), Some(2..5));
+        assert_eq!(resolve_range(2..=5, 10), Some(2..6));
+        assert_eq!(resolve_range(.., 10), Some(0..10));
+        assert_eq!(resolve_range(4.., 10), Some(4..10));
+        assert_eq!(resolve_range(..=9, 10), Some(0..10));
+        assert_eq!(resolve_range(..=10, 10), None);
+        assert_eq!(resolve_range(11.., 10), None);
+        assert_eq!(resolve_range(10..10, 10), Some(10..10));
+    }
+
+    #[test]
+    fn copy_ranges() {
+        let data = [1, 2, 3, 4, 5, 6, 7, 8];
+        let buffer = InterleavedSlice::new(&data, 2, 4).unwrap();
+        let mut values = [0; 4];
+        assert_eq!(buffer.copy_channel_range_to_slice(0, 1..3, &mut values), 2);
+        assert_eq!(values, [3, 5, 0, 0]);
+        assert_eq!(
+            buffer.copy_channel_range_to_slice(1, .., &mut values[..3]),
+            3
+        );
+        assert_eq!(values, [2, 4, 6, 0]);
+        assert_eq!(buffer.copy_channel_range_to_slice(1, 2..5, &mut values), 0);
+        assert_eq!(buffer.copy_channel_range_to_slice(2, .., &mut values), 0);
+        assert_eq!(buffer.copy_frame_range_to_slice(3, 1.., &mut values), 1);
+        assert_eq!(values[0], 8);
+
+        let dyn_buffer: &dyn Adapter<i32> = &buffer;
+        assert_eq!(dyn_buffer.copy_frame_range_to_slice(2, .., &mut values), 2);
+        assert_eq!(values[..2], [5, 6]);
+    }
+
+    #[test]
+    fn copy_ranges_mut() {
+        let mut data = [0; 8];
+        let mut buffer = InterleavedSlice::new_mut(&mut data, 2, 4).unwrap();
+        assert_eq!(
+            buffer.copy_slice_to_channel_range(1, 2.., &[1, 2, 3]),
+            (2, 0)
+        );
+        assert_eq!(buffer.copy_slice_to_frame_range(0, ..=1, &[4, 5]), (2, 0));
+        assert_eq!(buffer.copy_slice_to_frame_range(0, ..=2, &[4, 5]), (0, 0));
+        assert_eq!(data, [4, 5, 0, 0, 0, 1, 0, 2]);
+    }
+}
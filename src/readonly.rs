@@ -0,0 +1,126 @@
+//! # Read-only views
+//!
+//! This module provides a wrapper that gives a read-only view
+//! of a buffer implementing [AdapterMut].
+//! This is useful when code that holds a `&mut dyn AdapterMut`
+//! needs to call a function that takes a `&dyn Adapter`,
+//! since a trait object cannot be converted to a trait object of its supertrait
+//! with the minimum supported Rust version of this crate.
+//!
+//! The view only borrows the buffer immutably, and only for as long as the view is used.
+//! The buffer can be written to again as soon as the view is no longer needed.
+//!
+//! ## Example
+//! ```
+//! use audioadapter::direct::InterleavedSlice;
+//! use audioadapter::readonly::ReadOnly;
+//! use audioadapter::{Adapter, AdapterMut};
+//!
+//! fn sum(buffer: &dyn Adapter<i32>) -> i32 {
+//!     (0..buffer.frames()).map(|frame| buffer.read_sample(0, frame).unwrap()).sum()
+//! }
+//!
+//! let mut data = [1, 2, 3, 4];
+//! let mut slice = InterleavedSlice::new_mut(&mut data, 2, 2).unwrap();
+//! let buffer: &mut dyn AdapterMut<i32> = &mut slice;
+//!
+//! assert_eq!(sum(&ReadOnly::new(buffer)), 4);
+//! buffer.write_sample(0, 1, &5).unwrap();
+//! assert_eq!(sum(&ReadOnly::new(buffer)), 6);
+//! ```
+
+use core::any::Any;
+
+use crate::{Adapter, AdapterMut, IterationOrder};
+
+/// A read-only view of a buffer implementing [AdapterMut].
+/// All reads are passed straight through to the wrapped buffer.
+pub struct ReadOnly<U> {
+    buf: U,
+}
+
+impl<'a, 'b, T> ReadOnly<&'b dyn AdapterMut<'a, T>>
+where
+    T: Clone + 'a,
+{
+    /// Create a new read-only view of a buffer implementing the [AdapterMut] trait.
+    pub fn new(buf: &'b dyn AdapterMut<'a, T>) -> Self {
+        Self { buf }
+    }
+}
+
+impl<'a, 'b, T> Adapter<'b, T> for ReadOnly<&'b dyn AdapterMut<'a, T>>
+where
+    T: Clone + 'a,
+    'a: 'b,
+{
+    unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
+        self.buf.read_sample_unchecked(channel, frame)
+    }
+
+    fn read_sample(&self, channel: usize, frame: usize) -> Option<T> {
+        self.buf.read_sample(channel, frame)
+    }
+
+    fn channels(&self) -> usize {
+        self.buf.channels()
+    }
+
+    fn frames(&self) -> usize {
+        self.buf.frames()
+    }
+
+    fn is_channel_active(&self, channel: usize) -> bool {
+        self.buf.is_channel_active(channel)
+    }
+
+    fn preferred_iteration(&self) -> IterationOrder {
+        self.buf.preferred_iteration()
+    }
+
+    fn metadata(&self) -> Option<&dyn Any> {
+        self.buf.metadata()
+    }
+
+    fn write_from_channel_to_slice(&self, channel: usize, skip: usize, slice: &mut [T]) -> usize {
+        self.buf.write_from_channel_to_slice(channel, skip, slice)
+    }
+
+    fn write_from_frame_to_slice(&self, frame: usize, skip: usize, slice: &mut [T]) -> usize {
+        self.buf.write_from_frame_to_slice(frame, skip, slice)
+    }
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direct::SequentialSlice;
+
+    #[test]
+    fn read_then_write() {
+        let mut data = [1, 2, 3, 4, 5, 6];
+        let mut slice = SequentialSlice::new_mut(&mut data, 2, 3).unwrap();
+        let buffer: &mut dyn AdapterMut<i32> = &mut slice;
+        let view = ReadOnly::new(buffer);
+        let dyn_view: &dyn Adapter<i32> = &view;
+        assert_eq!(dyn_view.channels(), 2);
+        assert_eq!(dyn_view.frames(), 3);
+        assert_eq!(dyn_view.read_sample(1, 2), Some(6));
+        assert_eq!(dyn_view.read_sample(2, 0), None);
+        let mut values = [0; 3];
+        assert_eq!(dyn_view.write_from_channel_to_slice(0, 1, &mut values), 2);
+        assert_eq!(values[..2], [2, 3]);
+        assert_eq!(
+            dyn_view.preferred_iteration(),
+            IterationOrder::ChannelsOuter
+        );
+        buffer.write_sample(1, 2, &7).unwrap();
+        assert_eq!(ReadOnly::new(buffer).read_sample(1, 2), Some(7));
+    }
+}
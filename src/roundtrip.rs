@@ -0,0 +1,264 @@
+//! # Verification of sample formats
+//!
+//! The converting wrappers rely on the [RawSample](crate::sample::RawSample)
+//! and [BytesSample](crate::sample::BytesSample) implementations
+//! of the sample types to store values correctly.
+//! A mistake in one of these, for example mixing up the byte order
+//! in `from_number` and `to_number`, silently corrupts the data.
+//!
+//! This module provides [verify_roundtrip](crate::roundtrip::verify_roundtrip),
+//! that checks that a sample type fulfills the invariants the crate expects.
+//! It is intended to be called from the tests of crates that implement
+//! [BytesSample](crate::sample::BytesSample) for their own sample formats.
+//!
+//! When the `verify-roundtrip` Cargo feature is enabled,
+//! the converting wrappers also check every value they write.
+//! The stored sample is converted back to float,
+//! and converting that float again must give the same value without clipping.
+//! The wrappers panic if this is not the case.
+//! This slows down writing considerably, and is meant for debugging only.
+//!
+//! ## Example
+//! Verify one of the sample formats of the crate.
+//! ```
+//! use audioadapter::roundtrip::verify_roundtrip;
+//! use audioadapter::sample::I24LE;
+//!
+//! assert_eq!(verify_roundtrip::<I24LE<3>>(), Ok(()));
+//! ```
+
+use core::fmt;
+use num_traits::Float;
+
+use crate::sample::{BytesSample, RawSample};
+
+/// The number of test values, spread evenly over the range -1.0 .. +1.0.
+const NBR_TEST_VALUES: usize = 4096;
+
+/// Error returned when a sample type does not fulfill the expected invariants.
+/// The `value` fields hold the float value that was converted to a sample when the problem was found.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RoundtripError {
+    /// The number of bits of sample data is zero or does not fit in the bytes of a sample.
+    Size { bytes: usize, bits: usize },
+    /// Converting a sample to raw bytes and back changed the bytes.
+    Bytes { value: f64 },
+    /// Converting a sample to its numeric type and back changed the number.
+    Number { value: f64 },
+    /// Converting the float value of a stored sample to a sample again gave a different value,
+    /// or was clipped.
+    Unstable { value: f64, stored: f64 },
+    /// The stored sample is further from the written value than the resolution of the format.
+    Inaccurate { value: f64, stored: f64 },
+    /// A larger float value gave a smaller stored value.
+    Order { value: f64, stored: f64 },
+    /// A value in the range -1.0 .. +1.0 was clipped, or a value outside it was not.
+    Clipping { value: f64 },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RoundtripError {}
+
+impl fmt::Display for RoundtripError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RoundtripError::Size { bytes, bits } => write!(
+                f,
+                "Invalid sample size, {} bits of data in {} bytes",
+                bits, bytes
+            ),
+            RoundtripError::Bytes { value } => {
+                write!(f, "Raw bytes do not round-trip, for value: {}", value)
+            }
+            RoundtripError::Number { value } => {
+                write!(f, "Numeric value does not round-trip, for value: {}", value)
+            }
+            RoundtripError::Unstable { value, stored } => write!(
+                f,
+                "Stored value {} does not convert back to the same sample, for value: {}",
+                stored, value
+            ),
+            RoundtripError::Inaccurate { value, stored } => write!(
+                f,
+                "Stored value {} is too far from the written value: {}",
+                stored, value
+            ),
+            RoundtripError::Order { value, stored } => write!(
+                f,
+                "Stored value {} is smaller than for the previous value, for value: {}",
+                stored, value
+            ),
+            RoundtripError::Clipping { value } => {
+                write!(f, "Wrong clipping, for value: {}", value)
+            }
+        }
+    }
+}
+
+/// Check that a sample type fulfills the invariants expected by the converting wrappers.
+/// A range of float values from -1.0 to just below +1.0 are converted to samples,
+/// and each sample is checked so that:
+/// - the raw bytes are unchanged by `from_slice`, `from_raw` and `to_raw`,
+/// - the numeric value is unchanged by `from_number` and `to_number`,
+/// - converting the stored value back to float and then to a sample again gives the same value,
+/// - the stored value is within the resolution of the format from the written value,
+/// - the stored values increase with the written values.
+///
+/// It is also checked that -1.0 is stored exactly, and that values outside
+/// the range -1.0 .. +1.0 are clipped.
+/// Returns the first problem found.
+pub fn verify_roundtrip<U>() -> Result<(), RoundtripError>
+where
+    U: BytesSample + RawSample,
+    U::NumericType: PartialEq,
+{
+    let bytes = U::BYTES_PER_SAMPLE;
+    let bits = U::BITS_PER_SAMPLE;
+    if bits == 0 || bits > 8 * bytes {
+        return Err(RoundtripError::Size { bytes, bits });
+    }
+    let resolution = 2.0_f64.powi(1 - bits as i32).max(f32::EPSILON as f64);
+    let mut previous = f64::NEG_INFINITY;
+    for n in 0..NBR_TEST_VALUES {
+        let value = 2.0 * n as f64 / NBR_TEST_VALUES as f64 - 1.0;
+        let converted = U::from_scaled_float(value);
+        if converted.clipped {
+            return Err(RoundtripError::Clipping { value });
+        }
+        let sample = converted.value;
+        let raw = sample.as_slice();
+        if raw.len() != bytes
+            || U::from_slice(raw).as_slice() != raw
+            || U::from_raw(sample.to_raw()).as_slice() != raw
+        {
+            return Err(RoundtripError::Bytes { value });
+        }
+        if U::from_number(sample.to_number()).to_number() != sample.to_number() {
+            return Err(RoundtripError::Number { value });
+        }
+        let stored: f64 = sample.to_scaled_float();
+        if !is_stable::<f64, U>(&sample) {
+            return Err(RoundtripError::Unstable { value, stored });
+        }
+        if (stored - value).abs() > resolution {
+            return Err(RoundtripError::Inaccurate { value, stored });
+        }
+        if stored < previous {
+            return Err(RoundtripError::Order { value, stored });
+        }
+        previous = stored;
+    }
+    let stored: f64 = U::from_scaled_float(-1.0).value.to_scaled_float();
+    if stored != -1.0 {
+        return Err(RoundtripError::Inaccurate {
+            value: -1.0,
+            stored,
+        });
+    }
+    for value in [-2.0, 2.0] {
+        if !U::from_scaled_float(value).clipped {
+            return Err(RoundtripError::Clipping { value });
+        }
+    }
+    Ok(())
+}
+
+/// Check that converting a stored sample to float and back gives the same value, without clipping.
+/// NaN values cannot be compared, and are always accepted.
+fn is_stable<T: Float, U: RawSample>(stored: &U) -> bool {
+    let value: T = stored.to_scaled_float();
+    if value.is_nan() {
+        return true;
+    }
+    let again = U::from_scaled_float(value);
+    !again.clipped && again.value.to_scaled_float::<T>() == value
+}
+
+/// Panic if a sample written by a converting wrapper does not round-trip.
+/// Only used when the `verify-roundtrip` feature is enabled.
+#[cfg(feature = "verify-roundtrip")]
+#[track_caller]
+pub(crate) fn verify_stored<T: Float, U: RawSample>(stored: &U) {
+    assert!(
+        is_stable::<T, U>(stored),
+        "Stored sample with value {} does not convert back to the same sample",
+        stored.to_scaled_float::<f64>()
+    );
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample::*;
+
+    /// A 16-bit sample where the byte order of `from_number` and `to_number` do not match.
+    #[derive(Debug, Clone, Copy)]
+    struct Broken([u8; 2]);
+
+    impl BytesSample for Broken {
+        type NumericType = i16;
+        type Raw = [u8; 2];
+        const BYTES_PER_SAMPLE: usize = 2;
+        const BITS_PER_SAMPLE: usize = 16;
+
+        fn from_slice(bytes: &[u8]) -> Self {
+            Self([bytes[0], bytes[1]])
+        }
+
+        fn as_slice(&self) -> &[u8] {
+            &self.0
+        }
+
+        fn from_raw(raw: Self::Raw) -> Self {
+            Self(raw)
+        }
+
+        fn to_raw(&self) -> Self::Raw {
+            self.0
+        }
+
+        fn to_number(&self) -> Self::NumericType {
+            i16::from_be_bytes(self.0)
+        }
+
+        fn from_number(value: Self::NumericType) -> Self {
+            Self(value.to_le_bytes())
+        }
+    }
+
+    #[test]
+    fn crate_formats() {
+        assert_eq!(verify_roundtrip::<I16LE>(), Ok(()));
+        assert_eq!(verify_roundtrip::<U16BE>(), Ok(()));
+        assert_eq!(verify_roundtrip::<I24BE<4>>(), Ok(()));
+        assert_eq!(verify_roundtrip::<U24LE<3>>(), Ok(()));
+        assert_eq!(verify_roundtrip::<I32LE>(), Ok(()));
+        assert_eq!(verify_roundtrip::<I64BE>(), Ok(()));
+        assert_eq!(verify_roundtrip::<F32BE>(), Ok(()));
+        assert_eq!(verify_roundtrip::<F64LE>(), Ok(()));
+    }
+
+    #[test]
+    fn broken_format() {
+        assert!(verify_roundtrip::<Broken>().is_err());
+    }
+
+    #[cfg(feature = "verify-roundtrip")]
+    #[test]
+    #[should_panic]
+    fn broken_format_write() {
+        use crate::number_to_float::InterleavedNumbers;
+        use crate::AdapterMut;
+
+        let mut data = [Broken([0, 0]); 4];
+        let mut buffer =
+            InterleavedNumbers::<&mut [Broken], f32>::new_mut(&mut data, 2, 2).unwrap();
+        buffer.write_sample(0, 0, &0.5);
+    }
+}
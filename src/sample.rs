@@ -19,6 +19,50 @@ pub struct U24LE<const N: usize>([u8; N]);
 #[derive(Debug, Clone, Copy)]
 pub struct U24BE<const N: usize>([u8; N]);
 
+/// 24 bit signed integer, little endian, packed as 3 bytes.
+pub type I24LEPacked = I24LE<3>;
+
+/// 24 bit signed integer, little endian, padded to 4 bytes.
+pub type I24LEPadded = I24LE<4>;
+
+/// 24 bit signed integer, big endian, packed as 3 bytes.
+pub type I24BEPacked = I24BE<3>;
+
+/// 24 bit signed integer, big endian, padded to 4 bytes.
+pub type I24BEPadded = I24BE<4>;
+
+/// 24 bit unsigned integer, little endian, packed as 3 bytes.
+pub type U24LEPacked = U24LE<3>;
+
+/// 24 bit unsigned integer, little endian, padded to 4 bytes.
+pub type U24LEPadded = U24LE<4>;
+
+/// 24 bit unsigned integer, big endian, packed as 3 bytes.
+pub type U24BEPacked = U24BE<3>;
+
+/// 24 bit unsigned integer, big endian, padded to 4 bytes.
+pub type U24BEPadded = U24BE<4>;
+
+/// 24 bit signed integer, little endian, stored as 4 bytes with the data in the three most significant bytes.
+/// The least significant byte is padding, and is written as zero.
+#[derive(Debug, Clone, Copy)]
+pub struct I24InHighLE([u8; 4]);
+
+/// 24 bit signed integer, big endian, stored as 4 bytes with the data in the three most significant bytes.
+/// The least significant byte is padding, and is written as zero.
+#[derive(Debug, Clone, Copy)]
+pub struct I24InHighBE([u8; 4]);
+
+/// 24 bit signed integer, little endian, stored as 4 bytes with the data in the three least significant bytes.
+/// The most significant byte is ignored when reading, and is written as the sign extension of the value.
+#[derive(Debug, Clone, Copy)]
+pub struct I24InLowLE([u8; 4]);
+
+/// 24 bit signed integer, big endian, stored as 4 bytes with the data in the three least significant bytes.
+/// The most significant byte is ignored when reading, and is written as the sign extension of the value.
+#[derive(Debug, Clone, Copy)]
+pub struct I24InLowBE([u8; 4]);
+
 /// 32 bit signed integer, little endian. Stored as 4 bytes.
 #[derive(Debug, Clone, Copy)]
 pub struct I32LE([u8; 4]);
@@ -109,13 +153,192 @@ fn to_clamped_int<T: Float, U: PrimInt>(value: T, converted: Option<U>) -> Conve
     }
 }
 
+/// Convert a float to another float type, clamp at -1.0 and +1.0.
+/// NaN values are converted to zero.
+fn to_clamped_unit_float<T: Float, U: Float>(value: T) -> ConversionResult<U> {
+    if value.is_nan() {
+        return ConversionResult {
+            clipped: true,
+            value: U::zero(),
+        };
+    }
+    if value > T::one() {
+        return ConversionResult {
+            clipped: true,
+            value: U::one(),
+        };
+    }
+    if value < -T::one() {
+        return ConversionResult {
+            clipped: true,
+            value: -U::one(),
+        };
+    }
+    ConversionResult {
+        clipped: false,
+        value: U::from(value).unwrap_or(U::zero()),
+    }
+}
+
+/// Convert a float to another float type, clamp at the min and max limits of the new type.
+/// NaN values are converted to zero.
+fn to_clamped_float<T: Float, U: Float>(value: T) -> ConversionResult<U> {
+    if value.is_nan() {
+        return ConversionResult {
+            clipped: true,
+            value: U::zero(),
+        };
+    }
+    match U::from(value) {
+        Some(converted) if converted.is_finite() || value.is_infinite() => ConversionResult {
+            clipped: false,
+            value: converted,
+        },
+        _ if value > T::zero() => ConversionResult {
+            clipped: true,
+            value: U::max_value(),
+        },
+        _ => ConversionResult {
+            clipped: true,
+            value: U::min_value(),
+        },
+    }
+}
+
+/// The rounding used when converting float values to integer samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoundingMode {
+    /// Round towards zero, by dropping the fractional part.
+    /// This is the fastest mode.
+    #[default]
+    Truncate,
+    /// Round to the nearest integer, with ties rounded to the nearest even integer.
+    NearestEven,
+    /// Round to the nearest integer, with ties rounded away from zero.
+    NearestAway,
+}
+
+impl RoundingMode {
+    /// Round a float value to an integer value using this rounding mode.
+    pub fn round<T: Float>(&self, value: T) -> T {
+        match self {
+            RoundingMode::Truncate => value.trunc(),
+            RoundingMode::NearestAway => value.round(),
+            RoundingMode::NearestEven => {
+                let two = T::one() + T::one();
+                if (value - value.trunc()).abs() == T::one() / two {
+                    (value / two).round() * two
+                } else {
+                    value.round()
+                }
+            }
+        }
+    }
+}
+
+/// Round a float in the range -1.0 .. +1.0 to the nearest value
+/// that can be represented exactly by an integer with `bits` bits.
+fn round_to_bits<T: Float>(value: T, bits: usize, rounding: RoundingMode) -> T {
+    if rounding == RoundingMode::Truncate {
+        // The conversion to integer truncates, no need to do anything here.
+        return value;
+    }
+    let scale = T::from(2.0).unwrap().powi(bits as i32 - 1);
+    rounding.round(value * scale) / scale
+}
+
+/// A ceiling for values written by the converting wrappers,
+/// given as a level in dBFS and stored also as a linear amplitude.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Ceiling<T> {
+    dbfs: T,
+    amplitude: T,
+}
+
+impl<T: Float> Ceiling<T> {
+    /// Create a new ceiling at the given level in dBFS.
+    pub(crate) fn from_dbfs(dbfs: T) -> Self {
+        let ten = T::from(10.0).unwrap();
+        let twenty = T::from(20.0).unwrap();
+        Self {
+            dbfs,
+            amplitude: ten.powf(dbfs / twenty),
+        }
+    }
+
+    /// Get the level of the ceiling in dBFS.
+    pub(crate) fn dbfs(&self) -> T {
+        self.dbfs
+    }
+
+    /// Limit a value to the range given by the ceiling.
+    /// Returns the limited value, and a boolean that is true if the value was limited.
+    /// NaN values are passed through unchanged.
+    #[inline]
+    pub(crate) fn limit(&self, value: T) -> (T, bool) {
+        if value > self.amplitude {
+            (self.amplitude, true)
+        } else if value < -self.amplitude {
+            (-self.amplitude, true)
+        } else {
+            (value, false)
+        }
+    }
+}
+
+/// Limit a value to an optional ceiling.
+/// Returns the limited value, and a boolean that is true if the value was limited.
+#[inline]
+pub(crate) fn limit_to_ceiling<T: Float>(value: T, ceiling: Option<Ceiling<T>>) -> (T, bool) {
+    match ceiling {
+        Some(ceiling) => ceiling.limit(value),
+        None => (value, false),
+    }
+}
+
 /// A conversion result, containing the resulting value as `value`
 /// and a boolean `clipped` indicating if the value was clipped during conversion.
+#[must_use = "the result may have been clipped during conversion"]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct ConversionResult<T> {
     pub clipped: bool,
     pub value: T,
 }
 
+impl<T> ConversionResult<T> {
+    /// Get the resulting value, ignoring if it was clipped.
+    pub fn value(self) -> T {
+        self.value
+    }
+
+    /// Check if the value was clipped during conversion.
+    pub fn is_clipped(&self) -> bool {
+        self.clipped
+    }
+
+    /// Apply a function to the resulting value, keeping the `clipped` flag.
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> ConversionResult<U> {
+        ConversionResult {
+            clipped: self.clipped,
+            value: f(self.value),
+        }
+    }
+}
+
+macro_rules! from_conversionresult_for_number {
+    ($($type:ty),*) => {
+        $(
+            impl From<ConversionResult<$type>> for $type {
+                fn from(result: ConversionResult<$type>) -> Self {
+                    result.value
+                }
+            }
+        )*
+    };
+}
+
+from_conversionresult_for_number!(i8, i16, i32, i64, u8, u16, u32, u64, f32, f64);
+
 /// A trait for converting a given sample type to and from floating point values.
 /// The floating point values use the range -1.0 to +1.0.
 /// When converting to/from signed integers, the range does not include +1.0.
@@ -134,7 +357,27 @@ where
 
     /// Convert a float in the range -1.0 .. +1.0 to a sample value.
     /// Values outside the allowed range are clipped to the nearest limit.
+    /// This applies also to float sample types.
     fn from_scaled_float<T: Float>(value: T) -> ConversionResult<Self>;
+
+    /// Convert a float to a sample value, without limiting the value to the range -1.0 .. +1.0.
+    /// This only makes a difference for float sample types,
+    /// where values outside the range are kept as long as they can be represented by the type.
+    /// Values that are too large for the sample type are still clipped to the nearest limit.
+    /// The default implementation calls [RawSample::from_scaled_float].
+    fn from_scaled_float_unclipped<T: Float>(value: T) -> ConversionResult<Self> {
+        Self::from_scaled_float(value)
+    }
+
+    /// Round a float in the range -1.0 .. +1.0 to the nearest value
+    /// that can be represented by the sample type, using the given rounding mode.
+    /// Converting the result with [RawSample::from_scaled_float] then gives
+    /// the rounded sample value.
+    /// The default implementation returns the value unchanged,
+    /// which is used for float sample types.
+    fn round_scaled<T: Float>(value: T, _rounding: RoundingMode) -> T {
+        value
+    }
 }
 
 /// A trait for converting samples stored as raw bytes into a numerical type.
@@ -152,17 +395,46 @@ pub trait BytesSample {
     /// The closest matching numeric type.
     type NumericType;
 
+    /// The byte array holding the raw bytes of a sample value, `[u8; BYTES_PER_SAMPLE]`.
+    type Raw: Copy;
+
     /// The number of bytes making up each sample value.
     const BYTES_PER_SAMPLE: usize;
 
+    /// The number of bits of sample data in each sample value,
+    /// not counting any padding.
+    const BITS_PER_SAMPLE: usize;
+
     /// Create a new ByteSample from a slice of raw bytes.
     /// The slice length must be at least the number of bytes
-    /// for a sample value.
+    /// for a sample value, and any extra bytes are ignored.
+    ///
+    /// Panics if the slice is too short.
+    /// Use [BytesSample::try_from_slice] when the length of the slice is not known to be valid.
     fn from_slice(bytes: &[u8]) -> Self;
 
+    /// Try to create a new ByteSample from a slice of raw bytes.
+    /// Returns `None` if the slice is shorter than the number of bytes
+    /// for a sample value. Any extra bytes are ignored.
+    fn try_from_slice(bytes: &[u8]) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if bytes.len() < Self::BYTES_PER_SAMPLE {
+            return None;
+        }
+        Some(Self::from_slice(bytes))
+    }
+
     /// Return the raw bytes as a slice.
     fn as_slice(&self) -> &[u8];
 
+    /// Create a new ByteSample from an array of raw bytes.
+    fn from_raw(raw: Self::Raw) -> Self;
+
+    /// Return the raw bytes as an array.
+    fn to_raw(&self) -> Self::Raw;
+
     /// Convert the raw bytes to a numerical value.
     fn to_number(&self) -> Self::NumericType;
 
@@ -182,6 +454,10 @@ macro_rules! rawsample_for_int {
                 let converted = scaled.$to();
                 to_clamped_int(scaled, converted)
             }
+
+            fn round_scaled<T: Float>(value: T, rounding: RoundingMode) -> T {
+                round_to_bits(value, 8 * core::mem::size_of::<$type>(), rounding)
+            }
         }
     };
 }
@@ -205,6 +481,10 @@ macro_rules! rawsample_for_uint {
                 let converted = scaled.$to();
                 to_clamped_int(scaled, converted)
             }
+
+            fn round_scaled<T: Float>(value: T, rounding: RoundingMode) -> T {
+                round_to_bits(value, 8 * core::mem::size_of::<$type>(), rounding)
+            }
         }
     };
 }
@@ -215,25 +495,119 @@ rawsample_for_uint!(u32, to_u32);
 rawsample_for_uint!(u64, to_u64);
 
 macro_rules! rawsample_for_float {
-    ($type:ident, $to:ident) => {
+    ($type:ident) => {
         impl RawSample for $type {
             fn to_scaled_float<T: Float>(&self) -> T {
                 T::from(*self).unwrap_or(T::zero())
             }
 
             fn from_scaled_float<T: Float>(value: T) -> ConversionResult<Self> {
-                // TODO clip here
-                ConversionResult {
-                    clipped: false,
-                    value: value.$to().unwrap_or(0.0),
+                to_clamped_unit_float(value)
+            }
+
+            fn from_scaled_float_unclipped<T: Float>(value: T) -> ConversionResult<Self> {
+                to_clamped_float(value)
+            }
+        }
+    };
+}
+
+rawsample_for_float!(f32);
+rawsample_for_float!(f64);
+
+/// Addition of sample values, that saturates instead of overflowing.
+/// This is used when adding values to the samples of a buffer,
+/// for example with [AdapterMut::add_sample](crate::AdapterMut::add_sample).
+pub trait SaturatingSum: Sized {
+    /// Add two values, saturating at the bounds of the type.
+    /// Returns the sum, and a boolean indicating if it was saturated.
+    /// Float values never saturate, and are simply added.
+    fn saturating_sum(&self, other: &Self) -> (Self, bool);
+}
+
+macro_rules! saturatingsum_for_int {
+    ($($type:ident),+) => {
+        $(
+            impl SaturatingSum for $type {
+                fn saturating_sum(&self, other: &Self) -> (Self, bool) {
+                    match self.checked_add(*other) {
+                        Some(sum) => (sum, false),
+                        None => (self.saturating_add(*other), true),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+saturatingsum_for_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! saturatingsum_for_float {
+    ($($type:ident),+) => {
+        $(
+            impl SaturatingSum for $type {
+                fn saturating_sum(&self, other: &Self) -> (Self, bool) {
+                    (*self + *other, false)
                 }
             }
+        )+
+    };
+}
+
+saturatingsum_for_float!(f32, f64);
+
+/// Boolean samples, for gate and trigger signals.
+/// `false` is converted to 0.0 and `true` to 1.0.
+/// When converting from float, values of 0.5 and above become `true`.
+/// Values outside the range 0.0 to 1.0 are counted as clipped,
+/// and NaN is converted to `false`.
+impl RawSample for bool {
+    fn to_scaled_float<T: Float>(&self) -> T {
+        if *self {
+            T::one()
+        } else {
+            T::zero()
+        }
+    }
+
+    fn from_scaled_float<T: Float>(value: T) -> ConversionResult<Self> {
+        ConversionResult {
+            clipped: value.is_nan() || value < T::zero() || value > T::one(),
+            value: value >= T::from(0.5).unwrap(),
+        }
+    }
+}
+
+/// A numeric type whose values can be viewed as arrays of bytes in native byte order.
+/// This is used by the `new_from_numbers` constructors of the wrappers in
+/// the [direct](crate::direct) module.
+///
+/// # Safety
+/// The `Bytes` associated type must be a byte array of the same size as the type,
+/// and every possible bit pattern must be a valid value of the type.
+pub unsafe trait NativeBytes: Copy {
+    /// The byte array holding the bytes of a value, `[u8; size_of::<Self>()]`.
+    type Bytes: Copy;
+}
+
+macro_rules! nativebytes_for_number {
+    ($type:ident) => {
+        unsafe impl NativeBytes for $type {
+            type Bytes = [u8; core::mem::size_of::<$type>()];
         }
     };
 }
 
-rawsample_for_float!(f32, to_f32);
-rawsample_for_float!(f64, to_f64);
+nativebytes_for_number!(i8);
+nativebytes_for_number!(i16);
+nativebytes_for_number!(i32);
+nativebytes_for_number!(i64);
+nativebytes_for_number!(u8);
+nativebytes_for_number!(u16);
+nativebytes_for_number!(u32);
+nativebytes_for_number!(u64);
+nativebytes_for_number!(f32);
+nativebytes_for_number!(f64);
 
 // 24 bit formats, needs more work than others
 // because they don't map directly to a normal numerical type,
@@ -241,7 +615,9 @@ rawsample_for_float!(f64, to_f64);
 /// 24 bit signed integer, little endian, stored as 4 bytes. The data is in the lower 3 bytes and the most significant byte is padding.
 impl BytesSample for I24LE<4> {
     type NumericType = i32;
+    type Raw = [u8; 4];
     const BYTES_PER_SAMPLE: usize = core::mem::size_of::<Self>();
+    const BITS_PER_SAMPLE: usize = 24;
 
     fn from_slice(bytes: &[u8]) -> Self {
         Self(bytes[0..4].try_into().unwrap())
@@ -251,6 +627,14 @@ impl BytesSample for I24LE<4> {
         &self.0
     }
 
+    fn from_raw(raw: Self::Raw) -> Self {
+        Self(raw)
+    }
+
+    fn to_raw(&self) -> Self::Raw {
+        self.0
+    }
+
     fn to_number(&self) -> Self::NumericType {
         let padded = [0, self.0[0], self.0[1], self.0[2]];
         i32::from_le_bytes(padded)
@@ -265,7 +649,9 @@ impl BytesSample for I24LE<4> {
 /// 24 bit signed integer, little endian, stored as 3 bytes without padding.
 impl BytesSample for I24LE<3> {
     type NumericType = i32;
+    type Raw = [u8; 3];
     const BYTES_PER_SAMPLE: usize = core::mem::size_of::<Self>();
+    const BITS_PER_SAMPLE: usize = 24;
 
     fn from_slice(bytes: &[u8]) -> Self {
         Self(bytes[0..3].try_into().unwrap())
@@ -275,6 +661,14 @@ impl BytesSample for I24LE<3> {
         &self.0
     }
 
+    fn from_raw(raw: Self::Raw) -> Self {
+        Self(raw)
+    }
+
+    fn to_raw(&self) -> Self::Raw {
+        self.0
+    }
+
     fn to_number(&self) -> Self::NumericType {
         let padded = [0, self.0[0], self.0[1], self.0[2]];
         i32::from_le_bytes(padded)
@@ -289,7 +683,9 @@ impl BytesSample for I24LE<3> {
 /// 24 bit signed integer, big endian, stored as 4 bytes. The data is in the lower 3 bytes and the most significant byte is padding.
 impl BytesSample for I24BE<4> {
     type NumericType = i32;
+    type Raw = [u8; 4];
     const BYTES_PER_SAMPLE: usize = core::mem::size_of::<Self>();
+    const BITS_PER_SAMPLE: usize = 24;
 
     fn from_slice(bytes: &[u8]) -> Self {
         Self(bytes[0..4].try_into().unwrap())
@@ -299,6 +695,14 @@ impl BytesSample for I24BE<4> {
         &self.0
     }
 
+    fn from_raw(raw: Self::Raw) -> Self {
+        Self(raw)
+    }
+
+    fn to_raw(&self) -> Self::Raw {
+        self.0
+    }
+
     fn to_number(&self) -> Self::NumericType {
         let padded = [self.0[1], self.0[2], self.0[3], 0];
         i32::from_be_bytes(padded)
@@ -313,7 +717,9 @@ impl BytesSample for I24BE<4> {
 /// 24 bit signed integer, big endian, stored as 3 bytes without padding.
 impl BytesSample for I24BE<3> {
     type NumericType = i32;
+    type Raw = [u8; 3];
     const BYTES_PER_SAMPLE: usize = core::mem::size_of::<Self>();
+    const BITS_PER_SAMPLE: usize = 24;
 
     fn from_slice(bytes: &[u8]) -> Self {
         Self(bytes[0..3].try_into().unwrap())
@@ -323,6 +729,14 @@ impl BytesSample for I24BE<3> {
         &self.0
     }
 
+    fn from_raw(raw: Self::Raw) -> Self {
+        Self(raw)
+    }
+
+    fn to_raw(&self) -> Self::Raw {
+        self.0
+    }
+
     fn to_number(&self) -> Self::NumericType {
         let padded = [self.0[0], self.0[1], self.0[2], 0];
         i32::from_be_bytes(padded)
@@ -337,7 +751,9 @@ impl BytesSample for I24BE<3> {
 /// 24 bit unsigned integer, little endian, stored as 4 bytes. The data is in the lower 3 bytes and the most significant byte is padding.
 impl BytesSample for U24LE<4> {
     type NumericType = u32;
+    type Raw = [u8; 4];
     const BYTES_PER_SAMPLE: usize = core::mem::size_of::<Self>();
+    const BITS_PER_SAMPLE: usize = 24;
 
     fn from_slice(bytes: &[u8]) -> Self {
         Self(bytes[0..4].try_into().unwrap())
@@ -347,6 +763,14 @@ impl BytesSample for U24LE<4> {
         &self.0
     }
 
+    fn from_raw(raw: Self::Raw) -> Self {
+        Self(raw)
+    }
+
+    fn to_raw(&self) -> Self::Raw {
+        self.0
+    }
+
     fn to_number(&self) -> Self::NumericType {
         let padded = [0, self.0[0], self.0[1], self.0[2]];
         u32::from_le_bytes(padded)
@@ -361,7 +785,9 @@ impl BytesSample for U24LE<4> {
 /// 24 bit unsigned integer, little endian, stored as 3 bytes without padding.
 impl BytesSample for U24LE<3> {
     type NumericType = u32;
+    type Raw = [u8; 3];
     const BYTES_PER_SAMPLE: usize = core::mem::size_of::<Self>();
+    const BITS_PER_SAMPLE: usize = 24;
 
     fn from_slice(bytes: &[u8]) -> Self {
         Self(bytes[0..3].try_into().unwrap())
@@ -371,6 +797,14 @@ impl BytesSample for U24LE<3> {
         &self.0
     }
 
+    fn from_raw(raw: Self::Raw) -> Self {
+        Self(raw)
+    }
+
+    fn to_raw(&self) -> Self::Raw {
+        self.0
+    }
+
     fn to_number(&self) -> Self::NumericType {
         let padded = [0, self.0[0], self.0[1], self.0[2]];
         u32::from_le_bytes(padded)
@@ -385,7 +819,9 @@ impl BytesSample for U24LE<3> {
 /// 24 bit unsigned integer, big endian, stored as 4 bytes. The data is in the lower 3 bytes and the most significant byte is padding.
 impl BytesSample for U24BE<4> {
     type NumericType = u32;
+    type Raw = [u8; 4];
     const BYTES_PER_SAMPLE: usize = core::mem::size_of::<Self>();
+    const BITS_PER_SAMPLE: usize = 24;
 
     fn from_slice(bytes: &[u8]) -> Self {
         Self(bytes[0..4].try_into().unwrap())
@@ -395,6 +831,14 @@ impl BytesSample for U24BE<4> {
         &self.0
     }
 
+    fn from_raw(raw: Self::Raw) -> Self {
+        Self(raw)
+    }
+
+    fn to_raw(&self) -> Self::Raw {
+        self.0
+    }
+
     fn to_number(&self) -> Self::NumericType {
         let padded = [self.0[1], self.0[2], self.0[3], 0];
         u32::from_be_bytes(padded)
@@ -409,7 +853,9 @@ impl BytesSample for U24BE<4> {
 /// 24 bit unsigned integer, big endian, stored as 3 bytes without padding.
 impl BytesSample for U24BE<3> {
     type NumericType = u32;
+    type Raw = [u8; 3];
     const BYTES_PER_SAMPLE: usize = core::mem::size_of::<Self>();
+    const BITS_PER_SAMPLE: usize = 24;
 
     fn from_slice(bytes: &[u8]) -> Self {
         Self(bytes[0..3].try_into().unwrap())
@@ -419,6 +865,14 @@ impl BytesSample for U24BE<3> {
         &self.0
     }
 
+    fn from_raw(raw: Self::Raw) -> Self {
+        Self(raw)
+    }
+
+    fn to_raw(&self) -> Self::Raw {
+        self.0
+    }
+
     fn to_number(&self) -> Self::NumericType {
         let padded = [self.0[0], self.0[1], self.0[2], 0];
         u32::from_be_bytes(padded)
@@ -430,20 +884,97 @@ impl BytesSample for U24BE<3> {
     }
 }
 
+// 24 bit formats stored in 32 bit words.
+// Values are read as an i32 with the 24 bits of data in the most significant bytes.
+macro_rules! bytessample_for_i24_in_32 {
+    ($newtype:ident, $from_bytes:ident, $to_bytes:ident, |$raw:ident| $to_number:expr, |$value:ident| $from_number:expr) => {
+        impl BytesSample for $newtype {
+            type NumericType = i32;
+            type Raw = [u8; 4];
+            const BYTES_PER_SAMPLE: usize = 4;
+            const BITS_PER_SAMPLE: usize = 24;
+
+            fn from_slice(bytes: &[u8]) -> Self {
+                Self(bytes[0..4].try_into().unwrap())
+            }
+
+            fn as_slice(&self) -> &[u8] {
+                &self.0
+            }
+
+            fn from_raw(raw: Self::Raw) -> Self {
+                Self(raw)
+            }
+
+            fn to_raw(&self) -> Self::Raw {
+                self.0
+            }
+
+            fn to_number(&self) -> Self::NumericType {
+                let $raw = i32::$from_bytes(self.0);
+                $to_number
+            }
+
+            fn from_number($value: Self::NumericType) -> Self {
+                Self(($from_number).$to_bytes())
+            }
+        }
+    };
+}
+
+bytessample_for_i24_in_32!(
+    I24InHighLE,
+    from_le_bytes,
+    to_le_bytes,
+    |raw| raw & !0xff,
+    |value| value & !0xff
+);
+bytessample_for_i24_in_32!(
+    I24InHighBE,
+    from_be_bytes,
+    to_be_bytes,
+    |raw| raw & !0xff,
+    |value| value & !0xff
+);
+bytessample_for_i24_in_32!(
+    I24InLowLE,
+    from_le_bytes,
+    to_le_bytes,
+    |raw| raw << 8,
+    |value| value >> 8
+);
+bytessample_for_i24_in_32!(
+    I24InLowBE,
+    from_be_bytes,
+    to_be_bytes,
+    |raw| raw << 8,
+    |value| value >> 8
+);
+
 macro_rules! bytessample_for_newtype {
     ($type:ident, $newtype:ident, $from:ident, $to:ident) => {
         impl BytesSample for $newtype {
             type NumericType = $type;
+            type Raw = [u8; core::mem::size_of::<$type>()];
             const BYTES_PER_SAMPLE: usize = core::mem::size_of::<$type>();
+            const BITS_PER_SAMPLE: usize = 8 * core::mem::size_of::<$type>();
 
             fn from_slice(bytes: &[u8]) -> Self {
-                Self(bytes.try_into().unwrap())
+                Self(bytes[..Self::BYTES_PER_SAMPLE].try_into().unwrap())
             }
 
             fn as_slice(&self) -> &[u8] {
                 &self.0
             }
 
+            fn from_raw(raw: Self::Raw) -> Self {
+                Self(raw)
+            }
+
+            fn to_raw(&self) -> Self::Raw {
+                self.0
+            }
+
             fn to_number(&self) -> Self::NumericType {
                 $type::$from(self.0)
             }
@@ -492,6 +1023,279 @@ where
             value: V::from_number(value.value),
         }
     }
+
+    fn from_scaled_float_unclipped<T: Float>(value: T) -> ConversionResult<Self> {
+        let value = <V as BytesSample>::NumericType::from_scaled_float_unclipped(value);
+        ConversionResult {
+            clipped: value.clipped,
+            value: V::from_number(value.value),
+        }
+    }
+
+    fn round_scaled<T: Float>(value: T, rounding: RoundingMode) -> T {
+        if V::BITS_PER_SAMPLE == 8 * core::mem::size_of::<<V as BytesSample>::NumericType>() {
+            <V as BytesSample>::NumericType::round_scaled(value, rounding)
+        } else {
+            // The numeric type is larger than the sample data, for example i32 for 24-bit samples.
+            round_to_bits(value, V::BITS_PER_SAMPLE, rounding)
+        }
+    }
+}
+
+/// The byte order of a sample format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+/// A trait describing a sample format at compile time.
+/// It is implemented for all the sample formats stored as raw bytes,
+/// such as [I16LE] and [I24BE].
+/// This makes it possible to write code that is generic over the sample format,
+/// while still having access to the properties of the format.
+pub trait SampleSpec {
+    /// The type used for storing samples of this format.
+    type Sample: BytesSample + RawSample + Copy;
+
+    /// The byte order of the format.
+    const ENDIANNESS: Endianness;
+
+    /// The number of bits of sample data,
+    /// not counting any padding.
+    const BITS: usize;
+}
+
+macro_rules! samplespec_for_newtype {
+    ($newtype:ty, $endianness:ident, $bits:expr) => {
+        impl SampleSpec for $newtype {
+            type Sample = Self;
+            const ENDIANNESS: Endianness = Endianness::$endianness;
+            const BITS: usize = $bits;
+        }
+    };
+}
+
+samplespec_for_newtype!(I16LE, Little, 16);
+samplespec_for_newtype!(I16BE, Big, 16);
+samplespec_for_newtype!(U16LE, Little, 16);
+samplespec_for_newtype!(U16BE, Big, 16);
+samplespec_for_newtype!(I24LE<3>, Little, 24);
+samplespec_for_newtype!(I24LE<4>, Little, 24);
+samplespec_for_newtype!(I24BE<3>, Big, 24);
+samplespec_for_newtype!(I24BE<4>, Big, 24);
+samplespec_for_newtype!(U24LE<3>, Little, 24);
+samplespec_for_newtype!(U24LE<4>, Little, 24);
+samplespec_for_newtype!(U24BE<3>, Big, 24);
+samplespec_for_newtype!(U24BE<4>, Big, 24);
+samplespec_for_newtype!(I24InHighLE, Little, 24);
+samplespec_for_newtype!(I24InHighBE, Big, 24);
+samplespec_for_newtype!(I24InLowLE, Little, 24);
+samplespec_for_newtype!(I24InLowBE, Big, 24);
+samplespec_for_newtype!(I32LE, Little, 32);
+samplespec_for_newtype!(I32BE, Big, 32);
+samplespec_for_newtype!(U32LE, Little, 32);
+samplespec_for_newtype!(U32BE, Big, 32);
+samplespec_for_newtype!(I64LE, Little, 64);
+samplespec_for_newtype!(I64BE, Big, 64);
+samplespec_for_newtype!(U64LE, Little, 64);
+samplespec_for_newtype!(U64BE, Big, 64);
+samplespec_for_newtype!(F32LE, Little, 32);
+samplespec_for_newtype!(F32BE, Big, 32);
+samplespec_for_newtype!(F64LE, Little, 64);
+samplespec_for_newtype!(F64BE, Big, 64);
+
+/// A sample format selected at runtime.
+/// Each variant corresponds to one of the types implementing [SampleSpec].
+/// Use the [with_format](crate::with_format) macro to dispatch
+/// a runtime format value to code that is generic over the sample type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleFormat {
+    I16LE,
+    I16BE,
+    U16LE,
+    U16BE,
+    I24LE3,
+    I24LE4,
+    I24BE3,
+    I24BE4,
+    U24LE3,
+    U24LE4,
+    U24BE3,
+    U24BE4,
+    I32LE,
+    I32BE,
+    U32LE,
+    U32BE,
+    I64LE,
+    I64BE,
+    U64LE,
+    U64BE,
+    F32LE,
+    F32BE,
+    F64LE,
+    F64BE,
+    I24InHighLE,
+    I24InHighBE,
+    I24InLowLE,
+    I24InLowBE,
+}
+
+impl SampleFormat {
+    /// The number of bytes used to store each sample.
+    pub fn bytes_per_sample(&self) -> usize {
+        crate::with_format!(*self, |S| <S as BytesSample>::BYTES_PER_SAMPLE)
+    }
+
+    /// The number of bits of sample data, not counting any padding.
+    pub fn bits(&self) -> usize {
+        crate::with_format!(*self, |S| <S as SampleSpec>::BITS)
+    }
+
+    /// The byte order of the format.
+    pub fn endianness(&self) -> Endianness {
+        crate::with_format!(*self, |S| <S as SampleSpec>::ENDIANNESS)
+    }
+}
+
+/// A macro for dispatching a runtime [SampleFormat](crate::sample::SampleFormat) value
+/// to code that is generic over the sample type.
+/// The body is compiled once for each format,
+/// with the given identifier as an alias for the matching sample type.
+/// All branches must evaluate to the same type.
+///
+/// ```
+/// use audioadapter::number_to_float::InterleavedNumbers;
+/// use audioadapter::sample::SampleFormat;
+/// use audioadapter::{with_format, Adapter};
+///
+/// let data: Vec<u8> = vec![0, 0, 0, 64];
+/// let format = SampleFormat::I16LE;
+/// let value = with_format!(format, |S| {
+///     let buffer = InterleavedNumbers::<&[S], f32>::new_from_bytes(&data, 2, 1).unwrap();
+///     buffer.read_sample(1, 0).unwrap()
+/// });
+/// assert_eq!(value, 0.5);
+/// ```
+#[macro_export]
+macro_rules! with_format {
+    ($format:expr, |$s:ident| $body:expr) => {{
+        use $crate::sample::SampleFormat as __Format;
+        match $format {
+            __Format::I16LE => {
+                type $s = $crate::sample::I16LE;
+                $body
+            }
+            __Format::I16BE => {
+                type $s = $crate::sample::I16BE;
+                $body
+            }
+            __Format::U16LE => {
+                type $s = $crate::sample::U16LE;
+                $body
+            }
+            __Format::U16BE => {
+                type $s = $crate::sample::U16BE;
+                $body
+            }
+            __Format::I24LE3 => {
+                type $s = $crate::sample::I24LE<3>;
+                $body
+            }
+            __Format::I24LE4 => {
+                type $s = $crate::sample::I24LE<4>;
+                $body
+            }
+            __Format::I24BE3 => {
+                type $s = $crate::sample::I24BE<3>;
+                $body
+            }
+            __Format::I24BE4 => {
+                type $s = $crate::sample::I24BE<4>;
+                $body
+            }
+            __Format::U24LE3 => {
+                type $s = $crate::sample::U24LE<3>;
+                $body
+            }
+            __Format::U24LE4 => {
+                type $s = $crate::sample::U24LE<4>;
+                $body
+            }
+            __Format::U24BE3 => {
+                type $s = $crate::sample::U24BE<3>;
+                $body
+            }
+            __Format::U24BE4 => {
+                type $s = $crate::sample::U24BE<4>;
+                $body
+            }
+            __Format::I32LE => {
+                type $s = $crate::sample::I32LE;
+                $body
+            }
+            __Format::I32BE => {
+                type $s = $crate::sample::I32BE;
+                $body
+            }
+            __Format::U32LE => {
+                type $s = $crate::sample::U32LE;
+                $body
+            }
+            __Format::U32BE => {
+                type $s = $crate::sample::U32BE;
+                $body
+            }
+            __Format::I64LE => {
+                type $s = $crate::sample::I64LE;
+                $body
+            }
+            __Format::I64BE => {
+                type $s = $crate::sample::I64BE;
+                $body
+            }
+            __Format::U64LE => {
+                type $s = $crate::sample::U64LE;
+                $body
+            }
+            __Format::U64BE => {
+                type $s = $crate::sample::U64BE;
+                $body
+            }
+            __Format::F32LE => {
+                type $s = $crate::sample::F32LE;
+                $body
+            }
+            __Format::F32BE => {
+                type $s = $crate::sample::F32BE;
+                $body
+            }
+            __Format::F64LE => {
+                type $s = $crate::sample::F64LE;
+                $body
+            }
+            __Format::F64BE => {
+                type $s = $crate::sample::F64BE;
+                $body
+            }
+            __Format::I24InHighLE => {
+                type $s = $crate::sample::I24InHighLE;
+                $body
+            }
+            __Format::I24InHighBE => {
+                type $s = $crate::sample::I24InHighBE;
+                $body
+            }
+            __Format::I24InLowLE => {
+                type $s = $crate::sample::I24InLowLE;
+                $body
+            }
+            __Format::I24InLowBE => {
+                type $s = $crate::sample::I24InLowBE;
+                $body
+            }
+        }
+    }};
 }
 
 // Implement Sample for the audioadapter types
@@ -536,6 +1340,14 @@ impl_sample_for_newtype!(F32BE, 4);
 impl_sample_for_newtype!(F64LE, 8);
 #[cfg(feature = "audio")]
 impl_sample_for_newtype!(F64BE, 8);
+#[cfg(feature = "audio")]
+impl_sample_for_newtype!(I24InHighLE, 4);
+#[cfg(feature = "audio")]
+impl_sample_for_newtype!(I24InHighBE, 4);
+#[cfg(feature = "audio")]
+impl_sample_for_newtype!(I24InLowLE, 4);
+#[cfg(feature = "audio")]
+impl_sample_for_newtype!(I24InLowBE, 4);
 
 #[cfg(feature = "audio")]
 macro_rules! impl_sample_for_generic_newtype {
@@ -937,4 +1749,179 @@ mod tests {
         let wrapped = U24BE(bytes);
         assert_eq!(number, wrapped.to_number());
     }
+
+    #[test]
+    fn i24_in_32_justification() {
+        let number = -0x123456 << 8;
+        // Data in the high bytes, padding in the least significant byte.
+        let high_le = I24InHighLE([0x55, 0xaa, 0xcb, 0xed]);
+        let high_be = I24InHighBE([0xed, 0xcb, 0xaa, 0x55]);
+        assert_eq!(high_le.to_number(), number);
+        assert_eq!(high_be.to_number(), number);
+        assert_eq!(I24InHighLE::from_number(number).0, [0, 0xaa, 0xcb, 0xed]);
+        assert_eq!(I24InHighBE::from_number(number).0, [0xed, 0xcb, 0xaa, 0]);
+
+        // Data in the low bytes, the most significant byte is ignored when reading.
+        let low_le = I24InLowLE([0xaa, 0xcb, 0xed, 0x00]);
+        let low_be = I24InLowBE([0x00, 0xed, 0xcb, 0xaa]);
+        assert_eq!(low_le.to_number(), number);
+        assert_eq!(low_be.to_number(), number);
+        assert_eq!(I24InLowLE::from_number(number).0, [0xaa, 0xcb, 0xed, 0xff]);
+        assert_eq!(I24InLowBE::from_number(number).0, [0xff, 0xed, 0xcb, 0xaa]);
+        assert_eq!(
+            I24InLowLE::from_number(0x123456 << 8).0,
+            [0x56, 0x34, 0x12, 0]
+        );
+    }
+
+    #[test]
+    fn bool_samples() {
+        assert_eq!(true.to_scaled_float::<f32>(), 1.0);
+        assert_eq!(false.to_scaled_float::<f64>(), 0.0);
+        let result = bool::from_scaled_float(0.5_f32);
+        assert!(result.value && !result.clipped);
+        let result = bool::from_scaled_float(0.2_f32);
+        assert!(!result.value && !result.clipped);
+        let result = bool::from_scaled_float(-0.5_f32);
+        assert!(!result.value && result.clipped);
+        let result = bool::from_scaled_float(f32::NAN);
+        assert!(!result.value && result.clipped);
+    }
+
+    #[test]
+    fn conversion_result_helpers() {
+        let result = i16::from_scaled_float(2.0_f32);
+        assert!(result.is_clipped());
+        assert_eq!(result.value(), i16::MAX);
+        let value: i16 = i16::from_scaled_float(0.5_f32).into();
+        assert_eq!(value, 16384);
+        let mapped = result.map(|v| v as i32 + 1);
+        assert_eq!(
+            mapped,
+            ConversionResult {
+                clipped: true,
+                value: 32768
+            }
+        );
+    }
+
+    #[test]
+    fn clip_floats() {
+        let result = f32::from_scaled_float(1.5_f64);
+        assert!(result.clipped);
+        assert_eq!(result.value, 1.0);
+        let result = f32::from_scaled_float(-0.5_f64);
+        assert!(!result.clipped);
+        assert_eq!(result.value, -0.5);
+        let result = f64::from_scaled_float(f64::NAN);
+        assert!(result.clipped);
+        assert_eq!(result.value, 0.0);
+
+        let result = f32::from_scaled_float_unclipped(1.5_f64);
+        assert!(!result.clipped);
+        assert_eq!(result.value, 1.5);
+        let result = f32::from_scaled_float_unclipped(-1.0e300_f64);
+        assert!(result.clipped);
+        assert_eq!(result.value, f32::MIN);
+        let result = F32LE::from_scaled_float(2.0_f32);
+        assert!(result.clipped);
+        assert_eq!(result.value.to_number(), 1.0);
+    }
+
+    #[test]
+    fn rounding_modes() {
+        let values = [2.5_f32, 3.5, -2.5, 2.4, -2.6];
+        let round = |mode: RoundingMode| values.map(|v| mode.round(v));
+        assert_eq!(round(RoundingMode::Truncate), [2.0, 3.0, -2.0, 2.0, -2.0]);
+        assert_eq!(
+            round(RoundingMode::NearestEven),
+            [2.0, 4.0, -2.0, 2.0, -3.0]
+        );
+        assert_eq!(
+            round(RoundingMode::NearestAway),
+            [3.0, 4.0, -3.0, 2.0, -3.0]
+        );
+    }
+
+    #[test]
+    fn round_before_converting() {
+        // 2.5 and -2.5 steps of a 16-bit integer.
+        let value = 2.5_f64 / 32768.0;
+        let convert = |value: f64, mode: RoundingMode| {
+            i16::from_scaled_float(i16::round_scaled(value, mode)).value
+        };
+        assert_eq!(convert(value, RoundingMode::Truncate), 2);
+        assert_eq!(convert(value, RoundingMode::NearestEven), 2);
+        assert_eq!(convert(value, RoundingMode::NearestAway), 3);
+        assert_eq!(convert(-value, RoundingMode::NearestAway), -3);
+        assert_eq!(
+            u8::from_scaled_float(u8::round_scaled(-0.7 / 128.0, RoundingMode::NearestEven)).value,
+            127
+        );
+
+        // 1.5 steps of a 24-bit integer, stored in an i32.
+        let value = 1.5_f64 / 8388608.0;
+        let rounded = I24LE::<3>::round_scaled(value, RoundingMode::NearestEven);
+        assert_eq!(
+            I24LE::<3>::from_scaled_float(rounded).value.to_number(),
+            2 << 8
+        );
+        let rounded = I24LE::<3>::round_scaled(value, RoundingMode::Truncate);
+        assert_eq!(
+            I24LE::<3>::from_scaled_float(rounded).value.to_number(),
+            1 << 8
+        );
+
+        // Floats are not rounded.
+        assert_eq!(
+            f32::round_scaled(0.123_f32, RoundingMode::NearestEven),
+            0.123
+        );
+        assert_eq!(
+            F32LE::round_scaled(0.123_f32, RoundingMode::NearestEven),
+            0.123
+        );
+    }
+
+    #[test]
+    fn try_from_slice() {
+        assert!(I16LE::try_from_slice(&[1]).is_none());
+        assert_eq!(I16LE::try_from_slice(&[1, 2]).unwrap().to_number(), 0x0201);
+        assert_eq!(
+            I16LE::try_from_slice(&[1, 2, 3]).unwrap().to_number(),
+            0x0201
+        );
+        assert!(I24LE::<4>::try_from_slice(&[1, 2, 3]).is_none());
+        assert_eq!(
+            I24LE::<3>::try_from_slice(&[0, 0, 64]).unwrap().to_number(),
+            0x40000000
+        );
+        assert!(F64BE::try_from_slice(&[0; 7]).is_none());
+    }
+
+    #[test]
+    fn sample_format_properties() {
+        assert_eq!(SampleFormat::I16BE.bits(), 16);
+        assert_eq!(SampleFormat::I16BE.bytes_per_sample(), 2);
+        assert_eq!(SampleFormat::I16BE.endianness(), Endianness::Big);
+        assert_eq!(SampleFormat::I24LE4.bits(), 24);
+        assert_eq!(SampleFormat::I24LE4.bytes_per_sample(), 4);
+        assert_eq!(SampleFormat::I24LE3.bytes_per_sample(), 3);
+        assert_eq!(SampleFormat::F64LE.endianness(), Endianness::Little);
+        assert_eq!(SampleFormat::I24InHighBE.bits(), 24);
+        assert_eq!(SampleFormat::I24InLowLE.bytes_per_sample(), 4);
+    }
+
+    #[test]
+    fn dispatch_with_format() {
+        let bytes = [0, 0, 0, 64];
+        let small = 64.0 / 2.0_f32.powi(31);
+        for (format, expected) in [(SampleFormat::I32LE, 0.5), (SampleFormat::I32BE, small)] {
+            let value: f32 = crate::with_format!(format, |S| {
+                let sample = <S as BytesSample>::from_slice(&bytes);
+                sample.to_scaled_float()
+            });
+            assert_eq!(value, expected);
+        }
+    }
 }
@@ -36,6 +36,36 @@ pub unsafe fn copy_within_slice<T: Clone>(slice: &mut [T], src: usize, dest: usi
     }
 }
 
+/// Transpose a matrix stored in row-major order in a slice, in place.
+/// The first `rows * cols` elements of the slice are treated as a matrix
+/// with `rows` rows and `cols` columns, and are rearranged to the
+/// row-major order of the transposed matrix.
+/// Elements are moved by following the cycles of the permutation,
+/// using one bit of extra storage per element to keep track of
+/// which elements have already been moved.
+#[cfg(feature = "std")]
+pub fn transpose_in_place<T>(slice: &mut [T], rows: usize, cols: usize) {
+    if rows <= 1 || cols <= 1 {
+        return;
+    }
+    let len = rows * cols;
+    let destination = |index: usize| (index % cols) * rows + index / cols;
+    let mut visited = vec![0_u64; len.div_ceil(64)];
+    // The first and last elements never move.
+    for start in 1..len - 1 {
+        if visited[start / 64] & (1 << (start % 64)) != 0 {
+            continue;
+        }
+        visited[start / 64] |= 1 << (start % 64);
+        let mut current = destination(start);
+        while current != start {
+            slice.swap(start, current);
+            visited[current / 64] |= 1 << (current % 64);
+            current = destination(current);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -71,4 +101,30 @@ mod tests {
         let expected = vec![1, 2, 3, 2, 3, 4, 5, 6, 9];
         assert_eq!(data, expected);
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn transpose() {
+        let mut data: Vec<i32> = vec![1, 2, 3, 4, 5, 6, 7];
+        transpose_in_place(&mut data, 2, 3);
+        let expected = vec![1, 4, 2, 5, 3, 6, 7];
+        assert_eq!(data, expected);
+        transpose_in_place(&mut data, 3, 2);
+        let expected = vec![1, 2, 3, 4, 5, 6, 7];
+        assert_eq!(data, expected);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn transpose_large() {
+        let rows = 37;
+        let cols = 5;
+        let mut data: Vec<usize> = (0..rows * cols).collect();
+        transpose_in_place(&mut data, rows, cols);
+        for r in 0..rows {
+            for c in 0..cols {
+                assert_eq!(data[c * rows + r], r * cols + c);
+            }
+        }
+    }
 }
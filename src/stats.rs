@@ -1,7 +1,77 @@
 use num_traits::{Num, ToPrimitive};
 
+#[cfg(feature = "std")]
+use crate::filter::Weighting;
 use crate::Adapter;
 
+/// A running sum of `f64` values, using Kahan summation
+/// to reduce the rounding errors when adding many values.
+#[derive(Default)]
+pub(crate) struct KahanSum {
+    sum: f64,
+    compensation: f64,
+}
+
+impl KahanSum {
+    pub(crate) fn add(&mut self, value: f64) {
+        let corrected = value - self.compensation;
+        let new_sum = self.sum + corrected;
+        self.compensation = (new_sum - self.sum) - corrected;
+        self.sum = new_sum;
+    }
+
+    pub(crate) fn value(&self) -> f64 {
+        self.sum
+    }
+}
+
+/// Check if `T` is an integer type, by checking if dividing one by two gives zero.
+fn is_integer<T: Num>() -> bool {
+    T::one() / (T::one() + T::one()) == T::zero()
+}
+
+/// Read all samples of a channel as `f64` values.
+#[cfg(feature = "std")]
+fn channel_values<'a, T, A>(adapter: &A, channel: usize) -> Vec<f64>
+where
+    T: ToPrimitive + 'a,
+    A: Adapter<'a, T> + ?Sized,
+{
+    (0..adapter.frames())
+        .map(|frame| unsafe { adapter.read_sample_unchecked(channel, frame) })
+        .map(|value| value.to_f64().unwrap_or_default())
+        .collect()
+}
+
+/// Calculate the sum of the squares of the samples.
+/// For integer sample types, the squares are summed exactly using a 128-bit accumulator.
+/// Squares that do not fit in the accumulator are instead added to an `f64` sum.
+/// For float sample types, the squares are summed as `f64` using Kahan summation.
+fn sum_of_squares<T>(samples: impl Iterator<Item = T>) -> f64
+where
+    T: ToPrimitive + Num,
+{
+    let mut float_sum = KahanSum::default();
+    if is_integer::<T>() {
+        let mut exact_sum: u128 = 0;
+        for sample in samples {
+            let square = sample
+                .to_i128()
+                .map(|value| value.unsigned_abs())
+                .and_then(|value| value.checked_mul(value));
+            match square.and_then(|square| exact_sum.checked_add(square)) {
+                Some(sum) => exact_sum = sum,
+                None => float_sum.add(sample.to_f64().unwrap_or_default().powi(2)),
+            }
+        }
+        return exact_sum as f64 + float_sum.value();
+    }
+    for sample in samples {
+        float_sum.add(sample.to_f64().unwrap_or_default().powi(2));
+    }
+    float_sum.value()
+}
+
 /// A trait providing methods to calculate the RMS and peak-to-peak values of a channel or frame.
 /// This requires that the samples are of a numerical type, that implement the
 /// [num_traits::ToPrimitive], [num_traits::Num] and [core::cmp::PartialOrd] traits.
@@ -12,37 +82,54 @@ where
 {
     /// Calculate the RMS value of the given channel.
     /// The result is returned as `f64`.
+    /// For integer sample types, the squares of the samples are summed exactly,
+    /// and for float types the sum uses Kahan summation,
+    /// to keep the precision also for long buffers.
     fn channel_rms(&self, channel: usize) -> f64 {
-        let mut square_sum = 0.0;
         if self.frames() == 0 || self.channels() == 0 {
             return 0.0;
         }
+        let square_sum = sum_of_squares(
+            (0..self.frames()).map(|frame| self.read_sample(channel, frame).unwrap_or(T::zero())),
+        );
+        (square_sum / self.frames() as f64).sqrt()
+    }
+
+    /// Calculate the RMS value of the given channel,
+    /// after applying a frequency [Weighting] filter for the given sample rate.
+    /// The weighting filter starts from silence at the first frame,
+    /// and the RMS value includes the initial transient of the filter.
+    /// The result is returned as `f64`.
+    #[cfg(feature = "std")]
+    fn channel_rms_weighted(&self, channel: usize, weighting: Weighting, sample_rate: f64) -> f64 {
+        if channel >= self.channels() || self.frames() == 0 {
+            return 0.0;
+        }
+        let sections = weighting.sections(sample_rate);
+        let mut states = vec![[0.0; 2]; sections.len()];
+        let mut square_sum = KahanSum::default();
         for frame in 0..self.frames() {
-            square_sum += self
-                .read_sample(channel, frame)
-                .unwrap_or(T::zero())
+            let mut value = unsafe { self.read_sample_unchecked(channel, frame) }
                 .to_f64()
-                .unwrap_or_default()
-                .powi(2);
+                .unwrap_or_default();
+            for (section, state) in sections.iter().zip(states.iter_mut()) {
+                value = section.process_sample(state, value);
+            }
+            square_sum.add(value * value);
         }
-        (square_sum / self.frames() as f64).sqrt()
+        (square_sum.value() / self.frames() as f64).sqrt()
     }
 
     /// Calculate the RMS value of the given channel.
     /// The result is returned as `f64`.
     fn frame_rms(&self, frame: usize) -> f64 {
-        let mut square_sum = 0.0;
         if self.frames() == 0 || self.channels() == 0 {
             return 0.0;
         }
-        for channel in 0..self.channels() {
-            square_sum += self
-                .read_sample(channel, frame)
-                .unwrap_or(T::zero())
-                .to_f64()
-                .unwrap_or_default()
-                .powi(2);
-        }
+        let square_sum = sum_of_squares(
+            (0..self.channels())
+                .map(|channel| self.read_sample(channel, frame).unwrap_or(T::zero())),
+        );
         (square_sum / self.frames() as f64).sqrt()
     }
 
@@ -99,6 +186,187 @@ where
         let (min, max) = self.frame_min_and_max(frame);
         max.to_f64().unwrap_or_default() - min.to_f64().unwrap_or_default()
     }
+
+    /// Calculate a histogram of the sample values of the given channel.
+    /// The range from the smallest to the largest value in the channel
+    /// is divided into `bins` bins of equal width,
+    /// and the returned vector contains the number of samples in each bin.
+    /// If all samples have the same value, they are all counted in the first bin.
+    #[cfg(feature = "std")]
+    fn channel_histogram(&self, channel: usize, bins: usize) -> Vec<usize> {
+        let mut histogram = vec![0; bins];
+        if bins == 0 || channel >= self.channels() || self.frames() == 0 {
+            return histogram;
+        }
+        let values = channel_values(self, channel);
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let width = (max - min) / bins as f64;
+        for value in values {
+            let bin = if width > 0.0 {
+                ((value - min) / width) as usize
+            } else {
+                0
+            };
+            histogram[bin.min(bins - 1)] += 1;
+        }
+        histogram
+    }
+
+    /// Calculate the given percentile of the sample values of the given channel.
+    /// The percentile `p` is given in the range 0.0 to 100.0,
+    /// where 0.0 gives the smallest value, 50.0 the median, and 100.0 the largest value.
+    /// Values between two samples are linearly interpolated.
+    /// The result is returned as `f64`.
+    #[cfg(feature = "std")]
+    fn channel_percentile(&self, channel: usize, p: f64) -> f64 {
+        if channel >= self.channels() || self.frames() == 0 {
+            return 0.0;
+        }
+        let mut values = channel_values(self, channel);
+        values.sort_by(f64::total_cmp);
+        let position = p.clamp(0.0, 100.0) / 100.0 * (values.len() - 1) as f64;
+        let lower = position.floor() as usize;
+        let upper = position.ceil() as usize;
+        let fraction = position - lower as f64;
+        values[lower] + fraction * (values[upper] - values[lower])
+    }
+
+    /// Count the number of zero crossings in the given channel.
+    /// A zero crossing is counted each time the sign changes between two consecutive samples,
+    /// where zero is counted as positive.
+    fn channel_zero_crossings(&self, channel: usize) -> usize {
+        if channel >= self.channels() {
+            return 0;
+        }
+        let mut crossings = 0;
+        let mut previous_negative = None;
+        for frame in 0..self.frames() {
+            let negative = unsafe { self.read_sample_unchecked(channel, frame) } < T::zero();
+            if previous_negative.is_some_and(|previous| previous != negative) {
+                crossings += 1;
+            }
+            previous_negative = Some(negative);
+        }
+        crossings
+    }
+
+    /// Estimate the fundamental frequency of the signal in the given channel,
+    /// using the autocorrelation of the signal.
+    /// The `sample_rate` is used to convert the period found in samples to a frequency in Hz.
+    ///
+    /// The period is taken as the position of the first autocorrelation peak that is close to the highest one,
+    /// after the autocorrelation has first dropped below zero.
+    /// The position is refined using parabolic interpolation.
+    /// Only periods up to half the length of the channel can be detected.
+    /// Returns `None` if the channel is silent or no clear periodicity is found.
+    ///
+    /// This is a simple estimator meant for clean signals such as for tuners and diagnostics.
+    /// The calculation time grows with the square of the number of frames.
+    #[cfg(feature = "std")]
+    fn estimate_fundamental(&self, channel: usize, sample_rate: f64) -> Option<f64> {
+        if channel >= self.channels() || self.frames() < 4 {
+            return None;
+        }
+        let values = channel_values(self, channel);
+        let max_lag = values.len() / 2;
+        let correlation: Vec<f64> = (0..=max_lag)
+            .map(|lag| {
+                values[lag..]
+                    .iter()
+                    .zip(values.iter())
+                    .map(|(a, b)| a * b)
+                    .sum::<f64>()
+                    / (values.len() - lag) as f64
+            })
+            .collect();
+        if correlation[0] <= 0.0 {
+            return None;
+        }
+        let first_negative = correlation.iter().position(|r| *r < 0.0)?;
+        let highest = correlation[first_negative..max_lag]
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max);
+        if highest < 0.3 * correlation[0] {
+            return None;
+        }
+        // Multiples of the period give peaks of about the same height,
+        // pick the first peak that is close to the highest one.
+        let peak = (first_negative..max_lag).find(|lag| {
+            let value = correlation[*lag];
+            value >= 0.9 * highest && value >= correlation[lag - 1] && value >= correlation[lag + 1]
+        })?;
+        let peak_value = correlation[peak];
+        let before = correlation[peak - 1];
+        let after = correlation[peak + 1];
+        let curvature = before - 2.0 * peak_value + after;
+        let offset = if curvature < 0.0 {
+            0.5 * (before - after) / curvature
+        } else {
+            0.0
+        };
+        Some(sample_rate / (peak as f64 + offset))
+    }
+
+    /// Calculate the correlation between two channels, as shown by a phase correlation meter.
+    /// The result is in the range -1.0 to 1.0,
+    /// where 1.0 means that the channels are identical apart from the level,
+    /// 0.0 that they are uncorrelated, and -1.0 that they have opposite polarity.
+    /// Returns 0.0 if either channel is silent or does not exist.
+    fn channel_correlation(&self, channel_a: usize, channel_b: usize) -> f64 {
+        if channel_a >= self.channels() || channel_b >= self.channels() {
+            return 0.0;
+        }
+        let mut product_sum = KahanSum::default();
+        let mut square_sum_a = KahanSum::default();
+        let mut square_sum_b = KahanSum::default();
+        for frame in 0..self.frames() {
+            let a = unsafe { self.read_sample_unchecked(channel_a, frame) }
+                .to_f64()
+                .unwrap_or_default();
+            let b = unsafe { self.read_sample_unchecked(channel_b, frame) }
+                .to_f64()
+                .unwrap_or_default();
+            product_sum.add(a * b);
+            square_sum_a.add(a * a);
+            square_sum_b.add(b * b);
+        }
+        let norm = (square_sum_a.value() * square_sum_b.value()).sqrt();
+        if norm == 0.0 {
+            return 0.0;
+        }
+        (product_sum.value() / norm).clamp(-1.0, 1.0)
+    }
+
+    /// Calculate the RMS values of the mid and side signals of a pair of channels,
+    /// where mid is `(a + b) / 2` and side is `(a - b) / 2`.
+    /// These correspond to the vertical and horizontal extent of a Lissajous (goniometer) display,
+    /// and the ratio side / mid is a measure of the stereo width.
+    /// The result is returned as a tuple `(mid, side)`.
+    /// Returns `(0.0, 0.0)` if either channel does not exist.
+    fn channel_mid_side_rms(&self, channel_a: usize, channel_b: usize) -> (f64, f64) {
+        if channel_a >= self.channels() || channel_b >= self.channels() || self.frames() == 0 {
+            return (0.0, 0.0);
+        }
+        let mut mid_sum = KahanSum::default();
+        let mut side_sum = KahanSum::default();
+        for frame in 0..self.frames() {
+            let a = unsafe { self.read_sample_unchecked(channel_a, frame) }
+                .to_f64()
+                .unwrap_or_default();
+            let b = unsafe { self.read_sample_unchecked(channel_b, frame) }
+                .to_f64()
+                .unwrap_or_default();
+            mid_sum.add((0.5 * (a + b)).powi(2));
+            side_sum.add((0.5 * (a - b)).powi(2));
+        }
+        let frames = self.frames() as f64;
+        (
+            (mid_sum.value() / frames).sqrt(),
+            (side_sum.value() / frames).sqrt(),
+        )
+    }
 }
 
 impl<'a, T, U> AdapterStats<'a, T> for U
@@ -108,6 +376,154 @@ where
 {
 }
 
+/// The running statistics of a single channel.
+#[cfg(feature = "std")]
+struct RunningChannel {
+    square_sum: KahanSum,
+    peak: f64,
+    min: f64,
+    max: f64,
+    frames: usize,
+}
+
+#[cfg(feature = "std")]
+impl Default for RunningChannel {
+    fn default() -> Self {
+        Self {
+            square_sum: KahanSum::default(),
+            peak: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            frames: 0,
+        }
+    }
+}
+
+/// Statistics that are updated incrementally from a sequence of buffers.
+///
+/// The [AdapterStats] methods calculate their results from the samples of a single buffer.
+/// A `RunningStats` instead accumulates the RMS and peak values of each channel
+/// over all buffers passed to [RunningStats::update_from],
+/// for example the successive blocks of an audio callback.
+/// Each update only needs to process the new samples.
+///
+/// ## Example
+/// ```
+/// use audioadapter::direct::InterleavedSlice;
+/// use audioadapter::stats::RunningStats;
+///
+/// let mut stats = RunningStats::new(2);
+/// let first = [0.5_f32, 0.1, -0.5, 0.1];
+/// let second = [0.5_f32, -0.25, -0.5, 0.25];
+/// for block in [&first, &second] {
+///     let buffer = InterleavedSlice::new(block, 2, 2).unwrap();
+///     stats.update_from(&buffer);
+/// }
+/// assert_eq!(stats.frames(), 4);
+/// assert_eq!(stats.channel_peak(1), 0.25);
+/// assert!((stats.channel_rms(0) - 0.5).abs() < 1.0e-6);
+/// ```
+#[cfg(feature = "std")]
+pub struct RunningStats {
+    channels: Vec<RunningChannel>,
+    frames: usize,
+}
+
+#[cfg(feature = "std")]
+impl RunningStats {
+    /// Create a new `RunningStats` for the given number of channels.
+    pub fn new(channels: usize) -> Self {
+        Self {
+            channels: (0..channels).map(|_| RunningChannel::default()).collect(),
+            frames: 0,
+        }
+    }
+
+    /// Update the statistics with all the samples of a buffer.
+    /// If the buffer has more channels than the `RunningStats`, the extra channels are ignored.
+    /// If it has fewer, the statistics of the missing channels are left unchanged,
+    /// and their frame counts are not increased.
+    pub fn update_from<'a, T>(&mut self, buffer: &dyn Adapter<'a, T>)
+    where
+        T: Clone + ToPrimitive + 'a,
+    {
+        for (channel, stats) in self.channels.iter_mut().enumerate() {
+            if channel >= buffer.channels() {
+                continue;
+            }
+            for frame in 0..buffer.frames() {
+                let value = unsafe { buffer.read_sample_unchecked(channel, frame) }
+                    .to_f64()
+                    .unwrap_or_default();
+                stats.square_sum.add(value * value);
+                stats.peak = stats.peak.max(value.abs());
+                stats.min = stats.min.min(value);
+                stats.max = stats.max.max(value);
+            }
+            stats.frames += buffer.frames();
+        }
+        self.frames += buffer.frames();
+    }
+
+    /// Get the number of channels.
+    pub fn channels(&self) -> usize {
+        self.channels.len()
+    }
+
+    /// Get the total number of frames processed since the creation or the last reset.
+    /// This includes the frames of buffers that did not have all the channels,
+    /// see [RunningStats::channel_frames] for the number of frames of a single channel.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Get the number of frames processed for the given channel
+    /// since the creation or the last reset.
+    /// This is smaller than [RunningStats::frames] if some of the buffers
+    /// did not have this channel.
+    /// Returns 0 if the channel does not exist.
+    pub fn channel_frames(&self, channel: usize) -> usize {
+        self.channels.get(channel).map_or(0, |stats| stats.frames)
+    }
+
+    /// Get the RMS value of the given channel,
+    /// calculated over the frames processed for this channel.
+    /// Returns 0.0 if no frames have been processed or if the channel does not exist.
+    pub fn channel_rms(&self, channel: usize) -> f64 {
+        match self.channels.get(channel) {
+            Some(stats) if stats.frames > 0 => {
+                (stats.square_sum.value() / stats.frames as f64).sqrt()
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Get the largest absolute sample value of the given channel.
+    /// Returns 0.0 if the channel does not exist.
+    pub fn channel_peak(&self, channel: usize) -> f64 {
+        self.channels.get(channel).map_or(0.0, |stats| stats.peak)
+    }
+
+    /// Get the smallest and largest sample values of the given channel,
+    /// as a tuple `(min, max)`.
+    /// Returns `(0.0, 0.0)` if no frames have been processed for the channel,
+    /// or if the channel does not exist.
+    pub fn channel_min_and_max(&self, channel: usize) -> (f64, f64) {
+        match self.channels.get(channel) {
+            Some(stats) if stats.frames > 0 => (stats.min, stats.max),
+            _ => (0.0, 0.0),
+        }
+    }
+
+    /// Reset the statistics of all channels, and the frame count.
+    pub fn reset(&mut self) {
+        for stats in self.channels.iter_mut() {
+            *stats = RunningChannel::default();
+        }
+        self.frames = 0;
+    }
+}
+
 //   _____         _
 //  |_   _|__  ___| |_ ___
 //    | |/ _ \/ __| __/ __|
@@ -116,9 +532,34 @@ where
 
 #[cfg(test)]
 mod tests {
+    use super::*;
     use crate::direct::SequentialSlice;
     use crate::stats::AdapterStats;
 
+    #[test]
+    fn exact_sums() {
+        assert!(is_integer::<i32>());
+        assert!(is_integer::<u8>());
+        assert!(!is_integer::<f32>());
+        // Summing as f64 would lose all the ones, since they are smaller
+        // than half the distance between consecutive f64 values near 2^62.
+        let values = core::iter::once(1_i64 << 31).chain(core::iter::repeat(1).take(1024));
+        assert_eq!(sum_of_squares(values), ((1_u64 << 62) + 1024) as f64);
+        // Squares of the most negative i64 fit in the accumulator, but not their sum.
+        let values = [i64::MIN; 5];
+        let expected = 5.0 * (i64::MIN as f64).powi(2);
+        assert_eq!(sum_of_squares(values.iter().copied()), expected);
+    }
+
+    #[test]
+    fn compensated_float_sum() {
+        // A large value followed by many small ones,
+        // where naive summation loses all the small contributions.
+        let values = core::iter::once(1.0_f64).chain(core::iter::repeat(1.0e-8).take(1_000_000));
+        let sum = sum_of_squares(values);
+        assert!((sum - (1.0 + 1.0e-10)).abs() < 1.0e-15);
+    }
+
     #[test]
     fn stats_integer() {
         let data = [1_i32, -1, 1, -1, 1, -1, 1, -1];
@@ -128,6 +569,133 @@ mod tests {
         assert_eq!(buffer.channel_peak_to_peak(0), 2.0);
     }
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn histogram() {
+        let data = [0_i16, 1, 2, 3, 4, 5, 6, 7, 8, 10, 7, 7];
+        let buffer = SequentialSlice::new(&data, 2, 6).unwrap();
+        assert_eq!(buffer.channel_histogram(0, 5), vec![1, 1, 1, 1, 2]);
+        assert_eq!(buffer.channel_histogram(0, 0), Vec::<usize>::new());
+        assert_eq!(buffer.channel_histogram(1, 3), vec![4, 1, 1]);
+        let data = [3.0_f32; 4];
+        let buffer = SequentialSlice::new(&data, 1, 4).unwrap();
+        assert_eq!(buffer.channel_histogram(0, 2), vec![4, 0]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn percentile() {
+        let data = [4.0_f32, 1.0, 3.0, 2.0, 5.0];
+        let buffer = SequentialSlice::new(&data, 1, 5).unwrap();
+        assert_eq!(buffer.channel_percentile(0, 0.0), 1.0);
+        assert_eq!(buffer.channel_percentile(0, 50.0), 3.0);
+        assert_eq!(buffer.channel_percentile(0, 100.0), 5.0);
+        assert_eq!(buffer.channel_percentile(0, 87.5), 4.5);
+        assert_eq!(buffer.channel_percentile(1, 50.0), 0.0);
+    }
+
+    #[test]
+    fn correlation_and_mid_side() {
+        let data = [
+            1.0_f64, -1.0, 0.5, -0.5, -2.0, 2.0, -1.0, 1.0, 1.0, 1.0, 0.0, 0.0,
+        ];
+        let buffer = SequentialSlice::new(&data, 6, 2).unwrap();
+        assert!((buffer.channel_correlation(0, 1) - 1.0).abs() < 1e-12);
+        assert!((buffer.channel_correlation(0, 2) + 1.0).abs() < 1e-12);
+        assert!(buffer.channel_correlation(0, 4).abs() < 1e-12);
+        assert_eq!(buffer.channel_correlation(0, 5), 0.0);
+        assert_eq!(buffer.channel_correlation(0, 6), 0.0);
+
+        let (mid, side) = buffer.channel_mid_side_rms(0, 1);
+        assert!((mid - 0.75).abs() < 1e-12);
+        assert!((side - 0.25).abs() < 1e-12);
+        let (mid, side) = buffer.channel_mid_side_rms(0, 3);
+        assert_eq!(mid, 0.0);
+        assert!((side - 1.0).abs() < 1e-12);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn running_stats() {
+        let mut stats = RunningStats::new(2);
+        let first = [3_i16, -1, 4, 1];
+        let second = [0_i16, 2, -5, 0, 0, 0];
+        let buffer = SequentialSlice::new(&first, 2, 2).unwrap();
+        stats.update_from(&buffer);
+        assert_eq!(stats.channel_rms(0), 5.0_f64.sqrt());
+        // a mono buffer only updates the first channel
+        let buffer = SequentialSlice::new(&second, 1, 6).unwrap();
+        stats.update_from(&buffer);
+        assert_eq!(stats.frames(), 8);
+        let combined = SequentialSlice::new(&[3_i16, -1, 0, 2, -5, 0, 0, 0], 1, 8).unwrap();
+        assert_eq!(stats.channel_rms(0), combined.channel_rms(0));
+        assert_eq!(stats.channel_peak(0), 5.0);
+        assert_eq!(stats.channel_min_and_max(0), (-5.0, 3.0));
+        assert_eq!(stats.channel_min_and_max(1), (1.0, 4.0));
+        assert_eq!(stats.channel_frames(1), 2);
+        assert_eq!(stats.channel_rms(1), (17.0_f64 / 2.0).sqrt());
+        assert_eq!(stats.channel_rms(2), 0.0);
+        stats.reset();
+        assert_eq!(stats.frames(), 0);
+        assert_eq!(stats.channel_peak(0), 0.0);
+        assert_eq!(stats.channel_rms(0), 0.0);
+        assert_eq!(stats.channel_min_and_max(0), (0.0, 0.0));
+        let negative = [-2_i16, -3];
+        stats.update_from(&SequentialSlice::new(&negative, 1, 2).unwrap());
+        assert_eq!(stats.channel_min_and_max(0), (-3.0, -2.0));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn weighted_rms() {
+        let sample_rate = 48000.0;
+        let sine = |freq: f64| -> Vec<f64> {
+            (0..48000)
+                .map(|n| (2.0 * core::f64::consts::PI * freq * n as f64 / sample_rate).sin())
+                .collect()
+        };
+        let db = |rms: f64| 20.0 * (rms * 2.0_f64.sqrt()).log10();
+        let data = sine(1000.0);
+        let buffer = SequentialSlice::new(&data, 1, 48000).unwrap();
+        assert!(db(buffer.channel_rms_weighted(0, Weighting::A, sample_rate)).abs() < 0.1);
+        let data = sine(100.0);
+        let buffer = SequentialSlice::new(&data, 1, 48000).unwrap();
+        assert!((db(buffer.channel_rms_weighted(0, Weighting::A, sample_rate)) + 19.1).abs() < 0.2);
+        assert!((db(buffer.channel_rms_weighted(0, Weighting::C, sample_rate)) + 0.3).abs() < 0.2);
+        assert_eq!(
+            buffer.channel_rms_weighted(1, Weighting::A, sample_rate),
+            0.0
+        );
+    }
+
+    #[test]
+    fn zero_crossings() {
+        let data = [1_i32, 2, -1, -2, 0, 3, -3, -1, 1, 1];
+        let buffer = SequentialSlice::new(&data, 2, 5).unwrap();
+        assert_eq!(buffer.channel_zero_crossings(0), 2);
+        assert_eq!(buffer.channel_zero_crossings(1), 2);
+        assert_eq!(buffer.channel_zero_crossings(2), 0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn fundamental() {
+        let sample_rate = 48000.0;
+        let data: Vec<f32> = (0..4800)
+            .map(|n| {
+                let phase = 2.0 * core::f32::consts::PI * 220.0 * n as f32 / sample_rate as f32;
+                phase.sin() + 0.3 * (2.0 * phase).sin()
+            })
+            .collect();
+        let buffer = SequentialSlice::new(&data, 1, 4800).unwrap();
+        let estimate = buffer.estimate_fundamental(0, sample_rate).unwrap();
+        assert!((estimate - 220.0).abs() < 0.5, "estimate: {}", estimate);
+
+        let silence = [0.0_f32; 100];
+        let buffer = SequentialSlice::new(&silence, 1, 100).unwrap();
+        assert_eq!(buffer.estimate_fundamental(0, sample_rate), None);
+    }
+
     #[test]
     fn stats_float() {
         let data = [1.0_f32, -1.0, 1.0, -1.0, 1.0, -1.0, 1.0, -1.0];
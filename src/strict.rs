@@ -0,0 +1,245 @@
+//! # Strict buffers
+//!
+//! This module provides a wrapper for buffers implementing [Adapter] or [AdapterMut],
+//! that panics on out-of-bounds accesses in debug builds.
+//!
+//! The methods for reading and writing single samples return `None`
+//! when the channel or frame is out of bounds,
+//! and the methods for reading and writing slices return zero.
+//! If the calling code ignores these return values,
+//! samples are silently lost, which can be difficult to track down.
+//! Wrapping a buffer in a `StrictAdapter` turns these cases into panics
+//! with a message telling the channel and frame that was accessed.
+//!
+//! The checks are only performed when debug assertions are enabled.
+//! In release builds, the wrapper passes all calls straight through to the wrapped buffer.
+//!
+//! ## Example
+//! ```should_panic
+//! use audioadapter::direct::InterleavedSlice;
+//! use audioadapter::strict::StrictAdapter;
+//! use audioadapter::Adapter;
+//!
+//! let data = [0_i32; 6];
+//! let buffer = InterleavedSlice::new(&data, 2, 3).unwrap();
+//! let strict = StrictAdapter::new(&buffer as &dyn Adapter<i32>);
+//!
+//! // Frame 3 is out of bounds, this panics in debug builds.
+//! let _ = strict.read_sample(0, 3);
+//! # #[cfg(not(debug_assertions))]
+//! # panic!();
+//! ```
+
+use core::any::Any;
+
+use crate::{Adapter, AdapterMut, IterationOrder};
+
+/// A wrapper for an [Adapter] or [AdapterMut] buffer,
+/// that panics in debug builds when a sample outside the buffer is accessed.
+pub struct StrictAdapter<U> {
+    buf: U,
+}
+
+impl<'a, T> StrictAdapter<&'a dyn Adapter<'a, T>>
+where
+    T: Clone + 'a,
+{
+    /// Create a new wrapper for a buffer implementing the [Adapter] trait.
+    pub fn new(buf: &'a dyn Adapter<'a, T>) -> Self {
+        Self { buf }
+    }
+}
+
+impl<'a, T> StrictAdapter<&'a mut dyn AdapterMut<'a, T>>
+where
+    T: Clone + 'a,
+{
+    /// Create a new wrapper for a mutable buffer implementing the [AdapterMut] trait.
+    pub fn new_mut(buf: &'a mut dyn AdapterMut<'a, T>) -> Self {
+        Self { buf }
+    }
+}
+
+/// Panic if the channel or frame is out of bounds, when debug assertions are enabled.
+fn check_sample(operation: &str, channel: usize, frame: usize, channels: usize, frames: usize) {
+    if cfg!(debug_assertions) && (channel >= channels || frame >= frames) {
+        panic!(
+            "{} out of bounds at channel {}, frame {}, the buffer has {} channels and {} frames",
+            operation, channel, frame, channels, frames
+        );
+    }
+}
+
+/// Panic if the index is out of bounds, when debug assertions are enabled.
+fn check_index(operation: &str, kind: &str, index: usize, len: usize) {
+    if cfg!(debug_assertions) && index >= len {
+        panic!(
+            "{} out of bounds at {} {}, the buffer has {} {}s",
+            operation, kind, index, len, kind
+        );
+    }
+}
+
+macro_rules! implement_strict_reads {
+    () => {
+        unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
+            self.buf.read_sample_unchecked(channel, frame)
+        }
+
+        fn read_sample(&self, channel: usize, frame: usize) -> Option<T> {
+            check_sample(
+                "Read",
+                channel,
+                frame,
+                self.buf.channels(),
+                self.buf.frames(),
+            );
+            self.buf.read_sample(channel, frame)
+        }
+
+        fn channels(&self) -> usize {
+            self.buf.channels()
+        }
+
+        fn frames(&self) -> usize {
+            self.buf.frames()
+        }
+
+        fn is_channel_active(&self, channel: usize) -> bool {
+            self.buf.is_channel_active(channel)
+        }
+
+        fn preferred_iteration(&self) -> IterationOrder {
+            self.buf.preferred_iteration()
+        }
+
+        fn metadata(&self) -> Option<&dyn Any> {
+            self.buf.metadata()
+        }
+
+        fn write_from_channel_to_slice(
+            &self,
+            channel: usize,
+            skip: usize,
+            slice: &mut [T],
+        ) -> usize {
+            check_index("Read", "channel", channel, self.buf.channels());
+            self.buf.write_from_channel_to_slice(channel, skip, slice)
+        }
+
+        fn write_from_frame_to_slice(&self, frame: usize, skip: usize, slice: &mut [T]) -> usize {
+            check_index("Read", "frame", frame, self.buf.frames());
+            self.buf.write_from_frame_to_slice(frame, skip, slice)
+        }
+    };
+}
+
+impl<'a, T> Adapter<'a, T> for StrictAdapter<&'a dyn Adapter<'a, T>>
+where
+    T: Clone + 'a,
+{
+    implement_strict_reads!();
+}
+
+impl<'a, T> Adapter<'a, T> for StrictAdapter<&'a mut dyn AdapterMut<'a, T>>
+where
+    T: Clone + 'a,
+{
+    implement_strict_reads!();
+}
+
+impl<'a, T> AdapterMut<'a, T> for StrictAdapter<&'a mut dyn AdapterMut<'a, T>>
+where
+    T: Clone + 'a,
+{
+    unsafe fn write_sample_unchecked(&mut self, channel: usize, frame: usize, value: &T) -> bool {
+        self.buf.write_sample_unchecked(channel, frame, value)
+    }
+
+    fn write_sample(&mut self, channel: usize, frame: usize, value: &T) -> Option<bool> {
+        check_sample(
+            "Write",
+            channel,
+            frame,
+            self.buf.channels(),
+            self.buf.frames(),
+        );
+        self.buf.write_sample(channel, frame, value)
+    }
+
+    fn write_from_slice_to_channel(
+        &mut self,
+        channel: usize,
+        skip: usize,
+        slice: &[T],
+    ) -> (usize, usize) {
+        check_index("Write", "channel", channel, self.buf.channels());
+        self.buf.write_from_slice_to_channel(channel, skip, slice)
+    }
+
+    fn write_from_slice_to_frame(
+        &mut self,
+        frame: usize,
+        skip: usize,
+        slice: &[T],
+    ) -> (usize, usize) {
+        check_index("Write", "frame", frame, self.buf.frames());
+        self.buf.write_from_slice_to_frame(frame, skip, slice)
+    }
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direct::SequentialSlice;
+    use crate::tests::check_copy_within;
+
+    #[test]
+    fn read_and_write_in_bounds() {
+        let mut data = [0_u32; 20];
+        let mut buffer = SequentialSlice::new_mut(&mut data, 2, 10).unwrap();
+        let mut strict = StrictAdapter::new_mut(&mut buffer as &mut dyn AdapterMut<u32>);
+        check_copy_within(&mut strict);
+        assert_eq!(strict.write_sample(1, 9, &5), Some(false));
+        assert_eq!(strict.read_sample(1, 9), Some(5));
+        let mut values = [0_u32; 3];
+        assert_eq!(strict.write_from_channel_to_slice(1, 8, &mut values), 2);
+        assert_eq!(values[1], 5);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Read out of bounds at channel 2, frame 0")]
+    fn read_outside() {
+        let data = [0_u32; 20];
+        let buffer = SequentialSlice::new(&data, 2, 10).unwrap();
+        let strict = StrictAdapter::new(&buffer as &dyn Adapter<u32>);
+        let _ = strict.read_sample(2, 0);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Write out of bounds at channel 1, frame 10")]
+    fn write_outside() {
+        let mut data = [0_u32; 20];
+        let mut buffer = SequentialSlice::new_mut(&mut data, 2, 10).unwrap();
+        let mut strict = StrictAdapter::new_mut(&mut buffer as &mut dyn AdapterMut<u32>);
+        let _ = strict.write_sample(1, 10, &1);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Write out of bounds at frame 10")]
+    fn write_frame_outside() {
+        let mut data = [0_u32; 20];
+        let mut buffer = SequentialSlice::new_mut(&mut data, 2, 10).unwrap();
+        let mut strict = StrictAdapter::new_mut(&mut buffer as &mut dyn AdapterMut<u32>);
+        strict.write_from_slice_to_frame(10, 0, &[1, 2]);
+    }
+}
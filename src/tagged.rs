@@ -0,0 +1,237 @@
+//! # Buffers with metadata
+//!
+//! This module provides a wrapper for buffers implementing [Adapter] or [AdapterMut],
+//! that attaches user metadata to the buffer.
+//! The metadata can be any type, for example a struct with the source device and stream id,
+//! or a map of string tags using the [Tags](crate::tagged::Tags) type.
+//!
+//! The metadata is available from the [Adapter::metadata] method,
+//! so that it follows the buffer also through code that only sees a `&dyn Adapter`.
+//! Wrappers that pass reads straight through to another buffer,
+//! such as [Timestamped](crate::timestamps::Timestamped),
+//! also pass on the metadata of the wrapped buffer.
+//! Use [metadata_of](crate::tagged::metadata_of) to get the metadata
+//! of a trait object as a given type.
+//!
+//! ## Example
+//! Attach the name of the source device to a buffer.
+//! ```
+//! use audioadapter::direct::InterleavedSlice;
+//! use audioadapter::tagged::{metadata_of, Tagged, Tags};
+//! use audioadapter::Adapter;
+//!
+//! fn source_of<'a>(buffer: &'a dyn Adapter<'a, f32>) -> Option<&'a str> {
+//!     metadata_of::<Tags, _>(buffer)?.get("source").map(|source| source.as_str())
+//! }
+//!
+//! let data = vec![0.0_f32; 2 * 100];
+//! let buffer = InterleavedSlice::new(&data, 2, 100).unwrap();
+//! let mut tags = Tags::new();
+//! tags.insert("source".to_string(), "microphone".to_string());
+//! let tagged = Tagged::new(&buffer as &dyn Adapter<f32>, tags);
+//!
+//! assert_eq!(source_of(&tagged), Some("microphone"));
+//! assert_eq!(source_of(&buffer), None);
+//! ```
+
+use core::any::Any;
+
+use crate::{Adapter, AdapterMut, IterationOrder};
+
+/// A map of string tags, for use as metadata with the [Tagged] wrapper.
+#[cfg(feature = "std")]
+pub type Tags = std::collections::BTreeMap<String, String>;
+
+/// Get the metadata of a buffer, if it has metadata of type `M`.
+/// Returns `None` if the buffer has no metadata, or if it is of another type.
+pub fn metadata_of<'a, 'b, M, T>(buf: &'b dyn Adapter<'a, T>) -> Option<&'b M>
+where
+    M: Any,
+    T: Clone + 'a,
+{
+    buf.metadata()?.downcast_ref::<M>()
+}
+
+/// A wrapper for an [Adapter] or [AdapterMut] buffer,
+/// that carries metadata of type `M` along with the buffer.
+/// Reading and writing samples passes straight through to the wrapped buffer.
+pub struct Tagged<U, M> {
+    buf: U,
+    metadata: M,
+}
+
+impl<U, M> Tagged<U, M> {
+    /// Get a reference to the metadata.
+    pub fn tags(&self) -> &M {
+        &self.metadata
+    }
+
+    /// Get a mutable reference to the metadata.
+    pub fn tags_mut(&mut self) -> &mut M {
+        &mut self.metadata
+    }
+
+    /// Replace the metadata.
+    pub fn set_tags(&mut self, metadata: M) {
+        self.metadata = metadata;
+    }
+
+    /// Consume the wrapper and return the wrapped buffer and the metadata.
+    pub fn into_inner(self) -> (U, M) {
+        (self.buf, self.metadata)
+    }
+}
+
+impl<'a, 'b, T, M> Tagged<&'b dyn Adapter<'a, T>, M>
+where
+    T: Clone + 'a,
+{
+    /// Create a new wrapper for a buffer implementing the [Adapter] trait.
+    pub fn new(buf: &'b dyn Adapter<'a, T>, metadata: M) -> Self {
+        Self { buf, metadata }
+    }
+}
+
+impl<'a, 'b, T, M> Tagged<&'b mut dyn AdapterMut<'a, T>, M>
+where
+    T: Clone + 'a,
+{
+    /// Create a new wrapper for a mutable buffer implementing the [AdapterMut] trait.
+    pub fn new_mut(buf: &'b mut dyn AdapterMut<'a, T>, metadata: M) -> Self {
+        Self { buf, metadata }
+    }
+}
+
+macro_rules! implement_tagged_reads {
+    () => {
+        unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
+            self.buf.read_sample_unchecked(channel, frame)
+        }
+
+        fn channels(&self) -> usize {
+            self.buf.channels()
+        }
+
+        fn frames(&self) -> usize {
+            self.buf.frames()
+        }
+
+        fn is_channel_active(&self, channel: usize) -> bool {
+            self.buf.is_channel_active(channel)
+        }
+
+        fn preferred_iteration(&self) -> IterationOrder {
+            self.buf.preferred_iteration()
+        }
+
+        fn metadata(&self) -> Option<&dyn Any> {
+            Some(&self.metadata)
+        }
+
+        fn write_from_channel_to_slice(
+            &self,
+            channel: usize,
+            skip: usize,
+            slice: &mut [T],
+        ) -> usize {
+            self.buf.write_from_channel_to_slice(channel, skip, slice)
+        }
+
+        fn write_from_frame_to_slice(&self, frame: usize, skip: usize, slice: &mut [T]) -> usize {
+            self.buf.write_from_frame_to_slice(frame, skip, slice)
+        }
+    };
+}
+
+impl<'a, T, M> Adapter<'a, T> for Tagged<&dyn Adapter<'a, T>, M>
+where
+    T: Clone + 'a,
+    M: Any,
+{
+    implement_tagged_reads!();
+}
+
+impl<'a, T, M> Adapter<'a, T> for Tagged<&mut dyn AdapterMut<'a, T>, M>
+where
+    T: Clone + 'a,
+    M: Any,
+{
+    implement_tagged_reads!();
+}
+
+impl<'a, T, M> AdapterMut<'a, T> for Tagged<&mut dyn AdapterMut<'a, T>, M>
+where
+    T: Clone + 'a,
+    M: Any,
+{
+    unsafe fn write_sample_unchecked(&mut self, channel: usize, frame: usize, value: &T) -> bool {
+        self.buf.write_sample_unchecked(channel, frame, value)
+    }
+
+    fn write_from_slice_to_channel(
+        &mut self,
+        channel: usize,
+        skip: usize,
+        slice: &[T],
+    ) -> (usize, usize) {
+        self.buf.write_from_slice_to_channel(channel, skip, slice)
+    }
+
+    fn write_from_slice_to_frame(
+        &mut self,
+        frame: usize,
+        skip: usize,
+        slice: &[T],
+    ) -> (usize, usize) {
+        self.buf.write_from_slice_to_frame(frame, skip, slice)
+    }
+
+    fn copy_frames_within(&mut self, src: usize, dest: usize, count: usize) -> Option<usize> {
+        self.buf.copy_frames_within(src, dest, count)
+    }
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direct::SequentialSlice;
+    use crate::timestamps::Timestamped;
+
+    #[derive(Debug, PartialEq)]
+    struct Stream {
+        id: u32,
+    }
+
+    #[test]
+    fn custom_metadata() {
+        let mut data = [0_i32; 20];
+        let mut buffer = SequentialSlice::new_mut(&mut data, 2, 10).unwrap();
+        let mut tagged = Tagged::new_mut(&mut buffer as &mut dyn AdapterMut<i32>, Stream { id: 3 });
+        tagged.write_sample(1, 2, &5).unwrap();
+        tagged.tags_mut().id = 4;
+        assert_eq!(metadata_of::<Stream, _>(&tagged), Some(&Stream { id: 4 }));
+        assert_eq!(metadata_of::<u32, _>(&tagged), None);
+        let (_, stream) = tagged.into_inner();
+        assert_eq!(stream.id, 4);
+        assert_eq!(data[12], 5);
+    }
+
+    #[test]
+    fn through_wrappers() {
+        let data = [0_i32; 20];
+        let buffer = SequentialSlice::new(&data, 2, 10).unwrap();
+        let mut tags = Tags::new();
+        tags.insert("stream".to_string(), "main".to_string());
+        let tagged = Tagged::new(&buffer as &dyn Adapter<i32>, tags);
+        let stamped = Timestamped::new(&tagged as &dyn Adapter<i32>, 0.0, 48000.0);
+        let tags = metadata_of::<Tags, _>(&stamped).unwrap();
+        assert_eq!(tags.get("stream").map(|s| s.as_str()), Some("main"));
+        assert!(buffer.metadata().is_none());
+    }
+}
@@ -0,0 +1,291 @@
+//! # Timestamped buffers
+//!
+//! This module provides a wrapper for buffers implementing [Adapter] or [AdapterMut],
+//! that attaches a start timestamp and a sample rate to the buffer.
+//! This makes it possible to calculate the time of any frame,
+//! to align two buffers in time,
+//! and to measure the drift between two streams with slightly different sample rates.
+//!
+//! Timestamps are given in seconds as `f64`,
+//! with an arbitrary reference point that is the same for all compared buffers.
+//!
+//! ## Example
+//! Align a captured buffer with a buffer that started 10 ms later.
+//! ```
+//! use audioadapter::direct::InterleavedSlice;
+//! use audioadapter::timestamps::Timestamped;
+//! use audioadapter::Adapter;
+//!
+//! let capture_data = vec![0.0_f32; 2 * 1000];
+//! let playback_data = vec![0.0_f32; 2 * 1000];
+//! let capture_buffer = InterleavedSlice::new(&capture_data, 2, 1000).unwrap();
+//! let playback_buffer = InterleavedSlice::new(&playback_data, 2, 1000).unwrap();
+//!
+//! let capture = Timestamped::new(&capture_buffer as &dyn Adapter<f32>, 1.0, 48000.0);
+//! let playback = Timestamped::new(&playback_buffer as &dyn Adapter<f32>, 1.01, 48000.0);
+//!
+//! // Frame 480 of the capture buffer was recorded at the same time
+//! // as frame 0 of the playback buffer was played.
+//! assert_eq!(capture.align_with(&playback), Some((480, 0)));
+//! ```
+
+use core::any::Any;
+
+use crate::{Adapter, AdapterMut, IterationOrder};
+
+/// A wrapper for an [Adapter] or [AdapterMut] buffer,
+/// that carries the timestamp of the first frame
+/// and the sample rate of the data.
+/// Reading and writing samples passes straight through to the wrapped buffer.
+pub struct Timestamped<U> {
+    buf: U,
+    frames: usize,
+    start: f64,
+    sample_rate: f64,
+}
+
+impl<U> Timestamped<U> {
+    /// Get the timestamp of the first frame, in seconds.
+    pub fn start(&self) -> f64 {
+        self.start
+    }
+
+    /// Update the timestamp of the first frame, in seconds.
+    pub fn set_start(&mut self, start: f64) {
+        self.start = start;
+    }
+
+    /// Get the sample rate in Hz.
+    pub fn sample_rate(&self) -> f64 {
+        self.sample_rate
+    }
+
+    /// Get the timestamp of a frame, in seconds.
+    /// The frame is allowed to be outside the buffer.
+    pub fn timestamp_of_frame(&self, frame: usize) -> f64 {
+        self.start + frame as f64 / self.sample_rate
+    }
+
+    /// Get the position of a timestamp, in frames relative to the first frame of the buffer.
+    /// The result is negative for timestamps before the start of the buffer.
+    pub fn frame_position(&self, timestamp: f64) -> f64 {
+        (timestamp - self.start) * self.sample_rate
+    }
+
+    /// Get the drift in frames between this buffer and `other`,
+    /// after `frames` frames of this buffer.
+    /// This is the number of frames that `other` produces during the same time,
+    /// minus `frames`.
+    /// A positive value means that `other` runs faster than this buffer.
+    pub fn drift_frames<V>(&self, other: &Timestamped<V>, frames: usize) -> f64 {
+        frames as f64 * other.sample_rate / self.sample_rate - frames as f64
+    }
+
+    /// Get the timestamp just after the last frame, in seconds.
+    /// This is where the following buffer of a continuous stream would start.
+    pub fn end(&self) -> f64 {
+        self.timestamp_of_frame(self.frames)
+    }
+
+    /// Find the frames of this buffer and of `other` that correspond to the same time.
+    /// Returns a tuple with a frame index in each buffer,
+    /// where one of the two is zero, for the buffer that starts later.
+    /// The position in the other buffer is rounded to the nearest frame.
+    /// Returns `None` if the two buffers do not overlap in time.
+    pub fn align_with<V>(&self, other: &Timestamped<V>) -> Option<(usize, usize)> {
+        if other.start >= self.end() || self.start >= other.end() {
+            return None;
+        }
+        if other.start >= self.start {
+            let frame = round_position(self.frame_position(other.start));
+            if frame >= self.frames {
+                return None;
+            }
+            Some((frame, 0))
+        } else {
+            let frame = round_position(other.frame_position(self.start));
+            if frame >= other.frames {
+                return None;
+            }
+            Some((0, frame))
+        }
+    }
+}
+
+/// Round a non-negative position to the nearest frame.
+fn round_position(position: f64) -> usize {
+    (position + 0.5) as usize
+}
+
+impl<'a, T> Timestamped<&'a dyn Adapter<'a, T>>
+where
+    T: Clone + 'a,
+{
+    /// Create a new wrapper for a buffer implementing the [Adapter] trait.
+    /// The `start` argument is the timestamp of the first frame in seconds,
+    /// and `sample_rate` is the sample rate in Hz.
+    pub fn new(buf: &'a dyn Adapter<'a, T>, start: f64, sample_rate: f64) -> Self {
+        Self {
+            frames: buf.frames(),
+            buf,
+            start,
+            sample_rate,
+        }
+    }
+}
+
+impl<'a, T> Timestamped<&'a mut dyn AdapterMut<'a, T>>
+where
+    T: Clone + 'a,
+{
+    /// Create a new wrapper for a mutable buffer implementing the [AdapterMut] trait.
+    /// The `start` argument is the timestamp of the first frame in seconds,
+    /// and `sample_rate` is the sample rate in Hz.
+    pub fn new_mut(buf: &'a mut dyn AdapterMut<'a, T>, start: f64, sample_rate: f64) -> Self {
+        Self {
+            frames: buf.frames(),
+            buf,
+            start,
+            sample_rate,
+        }
+    }
+}
+
+macro_rules! implement_timestamped_reads {
+    () => {
+        unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
+            self.buf.read_sample_unchecked(channel, frame)
+        }
+
+        fn channels(&self) -> usize {
+            self.buf.channels()
+        }
+
+        fn frames(&self) -> usize {
+            self.buf.frames()
+        }
+
+        fn is_channel_active(&self, channel: usize) -> bool {
+            self.buf.is_channel_active(channel)
+        }
+
+        fn preferred_iteration(&self) -> IterationOrder {
+            self.buf.preferred_iteration()
+        }
+
+        fn metadata(&self) -> Option<&dyn Any> {
+            self.buf.metadata()
+        }
+
+        fn write_from_channel_to_slice(
+            &self,
+            channel: usize,
+            skip: usize,
+            slice: &mut [T],
+        ) -> usize {
+            self.buf.write_from_channel_to_slice(channel, skip, slice)
+        }
+
+        fn write_from_frame_to_slice(&self, frame: usize, skip: usize, slice: &mut [T]) -> usize {
+            self.buf.write_from_frame_to_slice(frame, skip, slice)
+        }
+    };
+}
+
+impl<'a, T> Adapter<'a, T> for Timestamped<&'a dyn Adapter<'a, T>>
+where
+    T: Clone + 'a,
+{
+    implement_timestamped_reads!();
+}
+
+impl<'a, T> Adapter<'a, T> for Timestamped<&'a mut dyn AdapterMut<'a, T>>
+where
+    T: Clone + 'a,
+{
+    implement_timestamped_reads!();
+}
+
+impl<'a, T> AdapterMut<'a, T> for Timestamped<&'a mut dyn AdapterMut<'a, T>>
+where
+    T: Clone + 'a,
+{
+    unsafe fn write_sample_unchecked(&mut self, channel: usize, frame: usize, value: &T) -> bool {
+        self.buf.write_sample_unchecked(channel, frame, value)
+    }
+
+    fn write_from_slice_to_channel(
+        &mut self,
+        channel: usize,
+        skip: usize,
+        slice: &[T],
+    ) -> (usize, usize) {
+        self.buf.write_from_slice_to_channel(channel, skip, slice)
+    }
+
+    fn write_from_slice_to_frame(
+        &mut self,
+        frame: usize,
+        skip: usize,
+        slice: &[T],
+    ) -> (usize, usize) {
+        self.buf.write_from_slice_to_frame(frame, skip, slice)
+    }
+
+    fn copy_frames_within(&mut self, src: usize, dest: usize, count: usize) -> Option<usize> {
+        self.buf.copy_frames_within(src, dest, count)
+    }
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direct::InterleavedSlice;
+
+    #[test]
+    fn frame_timestamps() {
+        let data = [0_i32; 200];
+        let buffer = InterleavedSlice::new(&data, 2, 100).unwrap();
+        let stamped = Timestamped::new(&buffer as &dyn Adapter<i32>, 2.0, 1000.0);
+        assert_eq!(stamped.timestamp_of_frame(0), 2.0);
+        assert!((stamped.timestamp_of_frame(50) - 2.05).abs() < 1.0e-9);
+        assert!((stamped.end() - 2.1).abs() < 1.0e-9);
+        assert!((stamped.frame_position(2.01) - 10.0).abs() < 1.0e-9);
+        assert!((stamped.frame_position(1.99) + 10.0).abs() < 1.0e-9);
+    }
+
+    #[test]
+    fn align_buffers() {
+        let data1 = [0_i32; 100];
+        let mut data2 = [0_i32; 100];
+        let buffer1 = InterleavedSlice::new(&data1, 1, 100).unwrap();
+        let mut buffer2 = InterleavedSlice::new_mut(&mut data2, 1, 100).unwrap();
+        let first = Timestamped::new(&buffer1 as &dyn Adapter<i32>, 1.0, 1000.0);
+        let mut second =
+            Timestamped::new_mut(&mut buffer2 as &mut dyn AdapterMut<i32>, 1.0304, 1000.0);
+        assert_eq!(first.align_with(&second), Some((30, 0)));
+        assert_eq!(second.align_with(&first), Some((0, 30)));
+        second.set_start(1.1);
+        assert_eq!(first.align_with(&second), None);
+        second.set_start(0.95);
+        assert_eq!(first.align_with(&second), Some((0, 50)));
+        second.write_sample(0, 50, &5).unwrap();
+        assert_eq!(second.read_sample(0, 50), Some(5));
+    }
+
+    #[test]
+    fn drift() {
+        let data = [0_i32; 10];
+        let buffer = InterleavedSlice::new(&data, 1, 10).unwrap();
+        let nominal = Timestamped::new(&buffer as &dyn Adapter<i32>, 0.0, 48000.0);
+        let fast = Timestamped::new(&buffer as &dyn Adapter<i32>, 0.0, 48004.8);
+        assert!((nominal.drift_frames(&fast, 48000) - 4.8).abs() < 1.0e-9);
+        assert!((fast.drift_frames(&nominal, 48004) + 4.8).abs() < 1.0e-3);
+    }
+}
@@ -2,8 +2,47 @@
 //!
 //! A set of traits for making it easier to work with buffers of audio data.
 
+use core::any::Any;
+use core::ops::{Mul, Range};
+
+#[cfg(feature = "std")]
+use num_traits::ToPrimitive;
+
+use crate::cursors::{FrameReader, FrameWriter};
+#[cfg(feature = "std")]
+use crate::owned::InterleavedOwned;
+use crate::sample::SaturatingSum;
+#[cfg(feature = "std")]
+use crate::ClipReport;
+
 // -------------------- The main buffer trait --------------------
 
+/// The order of iteration that gives the best performance for a buffer.
+/// Returned by [Adapter::preferred_iteration].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IterationOrder {
+    /// Loop over channels in the outer loop, and over frames in the inner loop.
+    /// This is the best order when the samples of each channel are stored together.
+    ChannelsOuter,
+    /// Loop over frames in the outer loop, and over channels in the inner loop.
+    /// This is the best order when the samples of each frame are stored together.
+    FramesOuter,
+    /// Both orders are equally good.
+    DontCare,
+}
+
+impl IterationOrder {
+    /// Get the preferred order for samples stored in a single slice,
+    /// with the given distances between consecutive channels and frames.
+    pub(crate) fn from_strides(channel_stride: usize, frame_stride: usize) -> Self {
+        match channel_stride.cmp(&frame_stride) {
+            core::cmp::Ordering::Greater => Self::ChannelsOuter,
+            core::cmp::Ordering::Less => Self::FramesOuter,
+            core::cmp::Ordering::Equal => Self::DontCare,
+        }
+    }
+}
+
 /// A trait for reading samples from a buffer.
 /// Samples are accessed indirectly by a `read_sample` method.
 /// Implementations may perform any needed transformation
@@ -37,6 +76,36 @@ pub trait Adapter<'a, T: 'a> {
     /// Get the number of frames stored in this buffer.
     fn frames(&self) -> usize;
 
+    /// Check if a channel contains data.
+    /// Buffers where some channels may be unused, such as
+    /// [SparseSequentialSliceOfVecs](crate::direct::SparseSequentialSliceOfVecs),
+    /// return `false` for the unused channels.
+    /// Reading an inactive channel gives default values, and writing to it does nothing,
+    /// so generic code can use this to skip processing these channels.
+    /// The default implementation returns `true` for every channel.
+    fn is_channel_active(&self, _channel: usize) -> bool {
+        true
+    }
+
+    /// Get the order of iteration over channels and frames
+    /// that gives the best performance for this buffer.
+    /// Generic code that can process the samples in either order
+    /// can use this to pick the one that accesses memory sequentially.
+    /// The default implementation returns [IterationOrder::DontCare].
+    fn preferred_iteration(&self) -> IterationOrder {
+        IterationOrder::DontCare
+    }
+
+    /// Get the user metadata attached to the buffer, as [Any].
+    /// This allows information such as the source device or stream id
+    /// to travel with the buffer through code that only sees a trait object.
+    /// Metadata is attached with the [Tagged](crate::tagged::Tagged) wrapper,
+    /// and wrappers that pass reads through to another buffer also pass on its metadata.
+    /// The default implementation returns `None`.
+    fn metadata(&self) -> Option<&dyn Any> {
+        None
+    }
+
     /// Write values from a channel of the buffer to a slice.
     /// The `skip` argument is the offset into the buffer channel
     /// where the first value will be read from.
@@ -86,6 +155,64 @@ pub trait Adapter<'a, T: 'a> {
         }
         channels_to_write
     }
+
+    /// Get a [FrameReader] cursor for reading frames from the buffer
+    /// one at a time, starting at the first frame.
+    fn frame_reader(&self) -> FrameReader<'a, '_, T>
+    where
+        Self: Sized,
+        T: Clone,
+    {
+        FrameReader::new(self)
+    }
+
+    /// Copy the contents of the buffer to a new vector of vectors.
+    /// The returned vector contains one vector per channel,
+    /// each holding the samples of all frames of that channel.
+    #[cfg(feature = "std")]
+    fn to_vecs(&self) -> Vec<Vec<T>>
+    where
+        T: Clone + Default,
+    {
+        (0..self.channels())
+            .map(|channel| {
+                let mut values = vec![T::default(); self.frames()];
+                self.write_from_channel_to_slice(channel, 0, &mut values);
+                values
+            })
+            .collect()
+    }
+
+    /// Copy the contents of the buffer to a new vector,
+    /// with the samples stored in _interleaved_ order.
+    #[cfg(feature = "std")]
+    fn to_interleaved_vec(&self) -> Vec<T>
+    where
+        T: Clone + Default,
+    {
+        let channels = self.channels();
+        let mut values = vec![T::default(); channels * self.frames()];
+        if channels > 0 {
+            for (frame, chunk) in values.chunks_mut(channels).enumerate() {
+                self.write_from_frame_to_slice(frame, 0, chunk);
+            }
+        }
+        values
+    }
+
+    /// Copy the current contents of the buffer to a new [InterleavedOwned] buffer.
+    /// The copy owns its data, and can be sent to and shared between threads
+    /// as long as the sample type allows it,
+    /// for example to hand a consistent copy to an analysis thread
+    /// while the original buffer keeps being written.
+    #[cfg(feature = "std")]
+    fn snapshot(&self) -> InterleavedOwned<T>
+    where
+        T: Clone + Default,
+    {
+        InterleavedOwned::new_from(self.to_interleaved_vec(), self.channels(), self.frames())
+            .expect("vector length matches the buffer size")
+    }
 }
 
 /// A trait for writing samples to a buffer.
@@ -196,6 +323,79 @@ where
         (channels_to_read, nbr_clipped)
     }
 
+    /// Add a value to the sample at the
+    /// given combination of frame and channel,
+    /// instead of overwriting it.
+    /// Integer sums saturate at the bounds of the sample type.
+    /// Returns a boolean indicating if the sum
+    /// was saturated or clipped during conversion.
+    /// The default implementation reads the sample, adds the value
+    /// with [SaturatingSum::saturating_sum],
+    /// and writes the sum with [AdapterMut::write_sample_unchecked].
+    ///
+    /// # Safety
+    ///
+    /// This method performs no bounds checking.
+    /// Calling it with an out-of-bound value for frame or channel
+    /// results in undefined behavior,
+    /// for example returning an invalid value or panicking.
+    unsafe fn add_sample_unchecked(&mut self, channel: usize, frame: usize, value: &T) -> bool
+    where
+        T: SaturatingSum,
+    {
+        let (sum, saturated) = self
+            .read_sample_unchecked(channel, frame)
+            .saturating_sum(value);
+        let clipped = self.write_sample_unchecked(channel, frame, &sum);
+        clipped || saturated
+    }
+
+    /// Add a value to the sample at the
+    /// given combination of frame and channel,
+    /// instead of overwriting it.
+    /// Integer sums saturate at the bounds of the sample type.
+    /// Returns a boolean indicating if the sum
+    /// was saturated or clipped during conversion.
+    /// Returns `None` if the frame or channel is
+    /// out of bounds of the buffer.
+    fn add_sample(&mut self, channel: usize, frame: usize, value: &T) -> Option<bool>
+    where
+        T: SaturatingSum,
+    {
+        if channel >= self.channels() || frame >= self.frames() {
+            return None;
+        }
+        Some(unsafe { self.add_sample_unchecked(channel, frame, value) })
+    }
+
+    /// Add values from a slice to a channel of the buffer,
+    /// for example to mix a signal into the buffer.
+    /// This works like [AdapterMut::write_from_slice_to_channel],
+    /// but adds the values to the existing samples instead of overwriting them.
+    ///
+    /// Returns a tuple of two numbers.
+    /// The first is the number of values added,
+    /// and the second is the number of sums that were saturated or clipped during conversion.
+    fn add_from_slice_to_channel(
+        &mut self,
+        channel: usize,
+        skip: usize,
+        slice: &[T],
+    ) -> (usize, usize)
+    where
+        T: SaturatingSum,
+    {
+        if channel >= self.channels() || skip >= self.frames() {
+            return (0, 0);
+        }
+        let frames_to_read = (self.frames() - skip).min(slice.len());
+        let mut nbr_clipped = 0;
+        for (n, item) in slice.iter().enumerate().take(frames_to_read) {
+            unsafe { nbr_clipped += self.add_sample_unchecked(channel, skip + n, item) as usize };
+        }
+        (frames_to_read, nbr_clipped)
+    }
+
     /// Copy values from a channel of another buffer to self.
     /// The `self_skip` and `other_skip` arguments are the offsets
     /// in frames for where copying starts in the two buffers.
@@ -235,6 +435,111 @@ where
         Some(nbr_clipped)
     }
 
+    /// Copy values from a channel of another buffer to self,
+    /// while multiplying each value by `gain`.
+    /// This works like [AdapterMut::write_from_other_to_channel],
+    /// but applies the gain in the same pass as the copy.
+    ///
+    /// Returns the number of values that were clipped during conversion,
+    /// or `None` if the channels or ranges are invalid.
+    #[allow(clippy::too_many_arguments)]
+    fn write_from_other_to_channel_with_gain(
+        &mut self,
+        other: &dyn Adapter<'a, T>,
+        other_channel: usize,
+        self_channel: usize,
+        other_skip: usize,
+        self_skip: usize,
+        take: usize,
+        gain: T,
+    ) -> Option<usize>
+    where
+        T: Mul<Output = T>,
+    {
+        if self_channel >= self.channels()
+            || take + self_skip > self.frames()
+            || other_channel >= other.channels()
+            || take + other_skip > other.frames()
+        {
+            return None;
+        }
+        let mut nbr_clipped = 0;
+        for n in 0..take {
+            unsafe {
+                let value =
+                    other.read_sample_unchecked(other_channel, n + other_skip) * gain.clone();
+                nbr_clipped +=
+                    self.write_sample_unchecked(self_channel, n + self_skip, &value) as usize
+            };
+        }
+        Some(nbr_clipped)
+    }
+
+    /// Copy as many frames as possible from another buffer to self.
+    /// Frames are read from `other` starting at frame `src_start`,
+    /// and written to self starting at frame `dst_start`.
+    /// At most `max_frames` frames are copied,
+    /// and fewer if either buffer runs out of frames before that.
+    /// All channels that exist in both buffers are copied.
+    ///
+    /// Unlike [AdapterMut::write_from_other_to_channel],
+    /// this never fails because the buffers are too short.
+    /// This is intended for streaming, where the sizes of the buffers may differ,
+    /// and the remaining frames are copied in a later call.
+    ///
+    /// Returns the number of frames that were copied,
+    /// which is zero if a start frame is beyond the end of its buffer.
+    fn copy_frames_from_other(
+        &mut self,
+        other: &dyn Adapter<'a, T>,
+        src_start: usize,
+        dst_start: usize,
+        max_frames: usize,
+    ) -> usize {
+        let frames = max_frames
+            .min(other.frames().saturating_sub(src_start))
+            .min(self.frames().saturating_sub(dst_start));
+        if frames == 0 {
+            return 0;
+        }
+        for channel in 0..self.channels().min(other.channels()) {
+            self.write_from_other_to_channel(other, channel, channel, src_start, dst_start, frames);
+        }
+        frames
+    }
+
+    /// Swap frames between self and another buffer.
+    /// The `count` frames starting at frame `self_start` of self are exchanged
+    /// with the `count` frames starting at frame `other_start` of `other`,
+    /// without using a temporary buffer.
+    /// All channels that exist in both buffers are swapped.
+    /// This can be used to exchange a processed block with an unprocessed one.
+    ///
+    /// Returns the number of frames that were swapped,
+    /// or `None` if either of the buffers is too short to swap `count` frames.
+    fn swap_frames_with(
+        &mut self,
+        other: &mut dyn AdapterMut<'a, T>,
+        self_start: usize,
+        other_start: usize,
+        count: usize,
+    ) -> Option<usize> {
+        if count + self_start > self.frames() || count + other_start > other.frames() {
+            return None;
+        }
+        for channel in 0..self.channels().min(other.channels()) {
+            for n in 0..count {
+                unsafe {
+                    let own = self.read_sample_unchecked(channel, self_start + n);
+                    let others = other.read_sample_unchecked(channel, other_start + n);
+                    self.write_sample_unchecked(channel, self_start + n, &others);
+                    other.write_sample_unchecked(channel, other_start + n, &own);
+                }
+            }
+        }
+        Some(count)
+    }
+
     /// Write the provided value to every sample in a channel.
     /// Can be used to clear a channel by writing zeroes,
     /// or to initialize each sample to a certain value.
@@ -268,7 +573,7 @@ where
     /// or to initialize each sample to a certain value.
     /// Returns `None` if called with a too large range.
     fn fill_frames_with(&mut self, start: usize, count: usize, value: &T) -> Option<usize> {
-        if start + count >= self.frames() {
+        if start + count > self.frames() {
             return None;
         }
         for channel in 0..self.channels() {
@@ -279,6 +584,33 @@ where
         Some(count)
     }
 
+    /// Write the provided value to every sample in a range of channels and frames.
+    /// The ranges are validated in the same way by all implementations:
+    /// an empty range, such as `2..2`, is allowed and writes nothing,
+    /// while a range that ends after the last channel or frame,
+    /// or that starts after its end, returns `None` without writing anything.
+    /// Returns the number of frames filled in each channel.
+    fn fill_range_with(
+        &mut self,
+        channels: Range<usize>,
+        frames: Range<usize>,
+        value: &T,
+    ) -> Option<usize> {
+        if channels.start > channels.end
+            || frames.start > frames.end
+            || channels.end > self.channels()
+            || frames.end > self.frames()
+        {
+            return None;
+        }
+        for channel in channels {
+            for frame in frames.clone() {
+                unsafe { self.write_sample_unchecked(channel, frame, value) };
+            }
+        }
+        Some(frames.len())
+    }
+
     /// Write the provided value to every sample in the entire buffer.
     /// Can be used to clear a buffer by writing zeroes,
     /// or to initialize each sample to a certain value.
@@ -288,6 +620,198 @@ where
         }
     }
 
+    /// Get a [FrameWriter] cursor for writing frames to the buffer
+    /// one at a time, starting at the first frame.
+    fn frame_writer(&mut self) -> FrameWriter<'a, '_, T>
+    where
+        Self: Sized,
+    {
+        FrameWriter::new(self)
+    }
+
+    /// Copy the contents of a slice of vectors to the buffer.
+    /// Each vector holds the samples of one channel.
+    /// The number of channels and frames copied are the smallest
+    /// of the buffer and the vectors,
+    /// and any remaining samples of the buffer are left unchanged.
+    ///
+    /// Returns the number of values that were clipped during conversion.
+    #[cfg(feature = "std")]
+    fn copy_from_vecs(&mut self, vecs: &[Vec<T>]) -> usize {
+        vecs.iter()
+            .take(self.channels())
+            .enumerate()
+            .map(|(channel, values)| self.write_from_slice_to_channel(channel, 0, values).1)
+            .sum()
+    }
+
+    /// Copy samples stored in _interleaved_ order from a slice to the buffer.
+    /// The slice is read as frames of [Adapter::channels] samples each.
+    /// The number of frames copied is the smallest of the buffer and the slice,
+    /// and an incomplete frame at the end of the slice is ignored.
+    ///
+    /// Returns the number of values that were clipped during conversion.
+    #[cfg(feature = "std")]
+    fn copy_from_interleaved_slice(&mut self, slice: &[T]) -> usize {
+        let channels = self.channels();
+        if channels == 0 {
+            return 0;
+        }
+        slice
+            .chunks_exact(channels)
+            .take(self.frames())
+            .enumerate()
+            .map(|(frame, values)| self.write_from_slice_to_frame(frame, 0, values).1)
+            .sum()
+    }
+
+    /// Write values from a slice into a channel of the buffer,
+    /// and return a [ClipReport] of the clipping.
+    /// This works like [AdapterMut::write_from_slice_to_channel],
+    /// but the report tells the number of written values in its `written` field.
+    #[cfg(feature = "std")]
+    fn write_from_slice_to_channel_report(
+        &mut self,
+        channel: usize,
+        skip: usize,
+        slice: &[T],
+    ) -> ClipReport
+    where
+        T: ToPrimitive,
+    {
+        let mut report = ClipReport::new(self.channels());
+        if channel >= self.channels() || skip >= self.frames() {
+            return report;
+        }
+        for (n, item) in slice.iter().enumerate().take(self.frames() - skip) {
+            let clipped = unsafe { self.write_sample_unchecked(channel, skip + n, item) };
+            report.add(channel, item, clipped);
+        }
+        report
+    }
+
+    /// Write values from a slice into a frame of the buffer,
+    /// and return a [ClipReport] of the clipping.
+    /// This works like [AdapterMut::write_from_slice_to_frame],
+    /// but the report tells the number of written values in its `written` field.
+    #[cfg(feature = "std")]
+    fn write_from_slice_to_frame_report(
+        &mut self,
+        frame: usize,
+        skip: usize,
+        slice: &[T],
+    ) -> ClipReport
+    where
+        T: ToPrimitive,
+    {
+        let mut report = ClipReport::new(self.channels());
+        if frame >= self.frames() || skip >= self.channels() {
+            return report;
+        }
+        for (n, item) in slice.iter().enumerate().take(self.channels() - skip) {
+            let clipped = unsafe { self.write_sample_unchecked(skip + n, frame, item) };
+            report.add(skip + n, item, clipped);
+        }
+        report
+    }
+
+    /// Copy values from a channel of another buffer to self,
+    /// and return a [ClipReport] of the clipping.
+    /// This works like [AdapterMut::write_from_other_to_channel],
+    /// and returns `None` if the channels or ranges are invalid.
+    #[cfg(feature = "std")]
+    fn write_from_other_to_channel_report(
+        &mut self,
+        other: &dyn Adapter<'a, T>,
+        other_channel: usize,
+        self_channel: usize,
+        other_skip: usize,
+        self_skip: usize,
+        take: usize,
+    ) -> Option<ClipReport>
+    where
+        T: ToPrimitive,
+    {
+        if self_channel >= self.channels()
+            || take + self_skip > self.frames()
+            || other_channel >= other.channels()
+            || take + other_skip > other.frames()
+        {
+            return None;
+        }
+        let mut report = ClipReport::new(self.channels());
+        for n in 0..take {
+            unsafe {
+                let value = other.read_sample_unchecked(other_channel, n + other_skip);
+                let clipped = self.write_sample_unchecked(self_channel, n + self_skip, &value);
+                report.add(self_channel, &value, clipped);
+            };
+        }
+        Some(report)
+    }
+
+    /// Copy samples from a slice of vectors to the buffer,
+    /// and return a [ClipReport] of the clipping.
+    /// This works like [AdapterMut::copy_from_vecs].
+    #[cfg(feature = "std")]
+    fn copy_from_vecs_report(&mut self, vecs: &[Vec<T>]) -> ClipReport
+    where
+        T: ToPrimitive,
+    {
+        let mut report = ClipReport::new(self.channels());
+        for (channel, values) in vecs.iter().take(self.channels()).enumerate() {
+            for (frame, value) in values.iter().take(self.frames()).enumerate() {
+                let clipped = unsafe { self.write_sample_unchecked(channel, frame, value) };
+                report.add(channel, value, clipped);
+            }
+        }
+        report
+    }
+
+    /// Copy samples stored in _interleaved_ order from a slice to the buffer,
+    /// and return a [ClipReport] of the clipping.
+    /// This works like [AdapterMut::copy_from_interleaved_slice].
+    #[cfg(feature = "std")]
+    fn copy_from_interleaved_slice_report(&mut self, slice: &[T]) -> ClipReport
+    where
+        T: ToPrimitive,
+    {
+        let channels = self.channels();
+        let mut report = ClipReport::new(channels);
+        if channels == 0 {
+            return report;
+        }
+        for (frame, values) in slice.chunks_exact(channels).take(self.frames()).enumerate() {
+            for (channel, value) in values.iter().enumerate() {
+                let clipped = unsafe { self.write_sample_unchecked(channel, frame, value) };
+                report.add(channel, value, clipped);
+            }
+        }
+        report
+    }
+
+    /// Shift the contents of the buffer in time by `offset` frames,
+    /// and fill the vacated frames with the provided value.
+    /// A positive offset moves the contents towards later frames, delaying the signal,
+    /// while a negative offset moves them towards earlier frames.
+    /// Frames that are moved past either end of the buffer are discarded,
+    /// and an offset larger than the number of frames fills the whole buffer.
+    /// This is implemented with [AdapterMut::copy_frames_within]
+    /// and [AdapterMut::fill_frames_with],
+    /// and uses the optimized implementations of these where available.
+    fn shift_frames(&mut self, offset: isize, fill: &T) {
+        let frames = self.frames();
+        let distance = offset.unsigned_abs().min(frames);
+        let remaining = frames - distance;
+        if offset > 0 {
+            self.copy_frames_within(0, distance, remaining);
+            self.fill_frames_with(0, distance, fill);
+        } else if offset < 0 {
+            self.copy_frames_within(distance, 0, remaining);
+            self.fill_frames_with(remaining, distance, fill);
+        }
+    }
+
     /// Copy frames within the buffer.
     /// Copying is performed for all channels.
     /// Copies (by cloning) `count` frames, from the range `src..src+count`,
@@ -326,3 +850,76 @@ where
         Some(count)
     }
 }
+
+// -------------------- Downcasting --------------------
+
+/// A helper trait for downcasting [Adapter] trait objects to their concrete types,
+/// for example to use a fast path that depends on the data layout.
+/// It is implemented for all buffers that are [Any],
+/// which means that they may not borrow any data.
+/// Hold the buffer as a `dyn AdapterAny` instead of a `dyn Adapter`
+/// to be able to downcast it.
+#[cfg(feature = "std")]
+pub trait AdapterAny<'a, T>: Adapter<'a, T>
+where
+    T: Clone + 'a,
+{
+    /// Get a reference to the buffer as [Any].
+    fn as_any(&self) -> &dyn Any;
+}
+
+#[cfg(feature = "std")]
+impl<'a, T, U> AdapterAny<'a, T> for U
+where
+    T: Clone + 'a,
+    U: Adapter<'a, T> + Any,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// A helper trait for downcasting [AdapterMut] trait objects to their concrete types.
+/// This works like [AdapterAny], and also gives mutable access.
+#[cfg(feature = "std")]
+pub trait AdapterMutAny<'a, T>: AdapterMut<'a, T> + AdapterAny<'a, T>
+where
+    T: Clone + 'a,
+{
+    /// Get a mutable reference to the buffer as [Any].
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+#[cfg(feature = "std")]
+impl<'a, T, U> AdapterMutAny<'a, T> for U
+where
+    T: Clone + 'a,
+    U: AdapterMut<'a, T> + Any,
+{
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// -------------------- Borrowing reads --------------------
+
+/// A trait for buffers that store the samples as values of type `T`,
+/// and can therefore give references to them.
+/// Reading via [AdapterRef::read_sample_ref] avoids cloning the sample,
+/// which is useful when the sample type is expensive to copy,
+/// for example `[u8; N]` groups of bytes that are only inspected.
+///
+/// The trait is implemented by the wrappers in [direct](crate::direct)
+/// and [owned](crate::owned).
+/// Converting wrappers create new values when reading,
+/// and do not implement it.
+pub trait AdapterRef<'a, T>: Adapter<'a, T>
+where
+    T: Clone + 'a,
+{
+    /// Get a reference to the sample at
+    /// a given combination of frame and channel.
+    /// Returns `None` if the frame or channel is
+    /// out of bounds of the buffer.
+    fn read_sample_ref(&self, channel: usize, frame: usize) -> Option<&T>;
+}
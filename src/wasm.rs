@@ -0,0 +1,332 @@
+//! # WebAssembly and JavaScript interop
+//!
+//! This module provides wrappers for JavaScript typed arrays,
+//! and functions for copying data to and from Web Audio `AudioBuffer` objects.
+//! This makes it possible to use the crate directly from for example
+//! Web Audio worklets compiled to WebAssembly.
+//!
+//! The typed arrays live in JavaScript memory,
+//! meaning that every read and write goes through a call to JavaScript.
+//! The methods reading and writing slices of channels or frames are implemented
+//! using a single call per slice, and are much faster than accessing samples one by one.
+//!
+//! The functions in this module can only be called when running in a JavaScript environment,
+//! and panic when called from a native target.
+//!
+//! ## Example
+//! Copy the contents of an `AudioBuffer` to a wrapped `Float32Array`.
+//! ```no_run
+//! use audioadapter::wasm::{copy_from_audio_buffer, InterleavedTypedArray};
+//! use js_sys::Float32Array;
+//! use web_sys::AudioBuffer;
+//!
+//! fn to_interleaved(audio_buffer: &AudioBuffer) -> Float32Array {
+//!     let channels = audio_buffer.number_of_channels() as usize;
+//!     let frames = audio_buffer.length() as usize;
+//!     let array = Float32Array::new_with_length((channels * frames) as u32);
+//!     let mut buffer = InterleavedTypedArray::new_mut(array.clone(), channels, frames).unwrap();
+//!     copy_from_audio_buffer(audio_buffer, &mut buffer).unwrap();
+//!     array
+//! }
+//! ```
+
+use js_sys::wasm_bindgen::JsValue;
+use js_sys::{Float32Array, Int16Array};
+use web_sys::AudioBuffer;
+
+use crate::{check_slice_length, implement_size_getters};
+use crate::{Adapter, AdapterMut, IterationOrder, SizeError};
+
+/// A JavaScript typed array that can be wrapped by
+/// [InterleavedTypedArray] and [SequentialTypedArray].
+pub trait TypedArray {
+    /// The type of the elements of the array.
+    type Sample: Copy + Default;
+
+    /// Get the number of elements in the array.
+    fn nbr_elements(&self) -> usize;
+
+    /// Read the element at `index`.
+    fn read_element(&self, index: usize) -> Self::Sample;
+
+    /// Write the element at `index`.
+    fn write_element(&self, index: usize, value: Self::Sample);
+
+    /// Copy consecutive elements, starting at `start`, into a slice.
+    fn read_elements(&self, start: usize, slice: &mut [Self::Sample]);
+
+    /// Copy consecutive elements from a slice, starting at `start`.
+    fn write_elements(&self, start: usize, slice: &[Self::Sample]);
+}
+
+macro_rules! implement_typed_array {
+    ($array:ty, $sample:ty) => {
+        impl TypedArray for $array {
+            type Sample = $sample;
+
+            fn nbr_elements(&self) -> usize {
+                self.length() as usize
+            }
+
+            fn read_element(&self, index: usize) -> $sample {
+                self.get_index(index as u32)
+            }
+
+            fn write_element(&self, index: usize, value: $sample) {
+                self.set_index(index as u32, value);
+            }
+
+            fn read_elements(&self, start: usize, slice: &mut [$sample]) {
+                self.subarray(start as u32, (start + slice.len()) as u32)
+                    .copy_to(slice);
+            }
+
+            fn write_elements(&self, start: usize, slice: &[$sample]) {
+                self.subarray(start as u32, (start + slice.len()) as u32)
+                    .copy_from(slice);
+            }
+        }
+    };
+}
+
+implement_typed_array!(Float32Array, f32);
+implement_typed_array!(Int16Array, i16);
+
+/// A wrapper for a JavaScript typed array containing interleaved samples.
+///
+/// The typed array handles are cheap to clone,
+/// so the wrapper takes ownership of a handle to the array.
+pub struct InterleavedTypedArray<A> {
+    array: A,
+    frames: usize,
+    channels: usize,
+}
+
+/// A wrapper for a JavaScript typed array containing sequential samples.
+///
+/// The typed array handles are cheap to clone,
+/// so the wrapper takes ownership of a handle to the array.
+pub struct SequentialTypedArray<A> {
+    array: A,
+    frames: usize,
+    channels: usize,
+}
+
+impl<A: TypedArray> InterleavedTypedArray<A> {
+    /// Create a new wrapper for a typed array containing interleaved samples.
+    /// The array length must be at least `frames*channels`.
+    pub fn new(array: A, channels: usize, frames: usize) -> Result<Self, SizeError> {
+        check_slice_length!(channels, frames, array.nbr_elements());
+        Ok(Self {
+            array,
+            frames,
+            channels,
+        })
+    }
+
+    /// Create a new wrapper for a typed array containing interleaved samples,
+    /// for both reading and writing.
+    /// The array length must be at least `frames*channels`.
+    pub fn new_mut(array: A, channels: usize, frames: usize) -> Result<Self, SizeError> {
+        Self::new(array, channels, frames)
+    }
+
+    /// Get a reference to the wrapped array.
+    pub fn array(&self) -> &A {
+        &self.array
+    }
+
+    fn calc_index(&self, channel: usize, frame: usize) -> usize {
+        frame * self.channels + channel
+    }
+}
+
+impl<A: TypedArray> SequentialTypedArray<A> {
+    /// Create a new wrapper for a typed array containing sequential samples.
+    /// The array length must be at least `frames*channels`.
+    pub fn new(array: A, channels: usize, frames: usize) -> Result<Self, SizeError> {
+        check_slice_length!(channels, frames, array.nbr_elements());
+        Ok(Self {
+            array,
+            frames,
+            channels,
+        })
+    }
+
+    /// Create a new wrapper for a typed array containing sequential samples,
+    /// for both reading and writing.
+    /// The array length must be at least `frames*channels`.
+    pub fn new_mut(array: A, channels: usize, frames: usize) -> Result<Self, SizeError> {
+        Self::new(array, channels, frames)
+    }
+
+    /// Get a reference to the wrapped array.
+    pub fn array(&self) -> &A {
+        &self.array
+    }
+
+    fn calc_index(&self, channel: usize, frame: usize) -> usize {
+        channel * self.frames + frame
+    }
+}
+
+impl<'a, A> Adapter<'a, A::Sample> for InterleavedTypedArray<A>
+where
+    A: TypedArray,
+    A::Sample: 'a,
+{
+    unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> A::Sample {
+        self.array.read_element(self.calc_index(channel, frame))
+    }
+
+    implement_size_getters!();
+
+    fn preferred_iteration(&self) -> IterationOrder {
+        IterationOrder::FramesOuter
+    }
+
+    fn write_from_frame_to_slice(
+        &self,
+        frame: usize,
+        skip: usize,
+        slice: &mut [A::Sample],
+    ) -> usize {
+        if frame >= self.frames || skip >= self.channels {
+            return 0;
+        }
+        let samples_to_write = (self.channels - skip).min(slice.len());
+        self.array
+            .read_elements(self.calc_index(skip, frame), &mut slice[..samples_to_write]);
+        samples_to_write
+    }
+}
+
+impl<'a, A> AdapterMut<'a, A::Sample> for InterleavedTypedArray<A>
+where
+    A: TypedArray,
+    A::Sample: 'a,
+{
+    unsafe fn write_sample_unchecked(
+        &mut self,
+        channel: usize,
+        frame: usize,
+        value: &A::Sample,
+    ) -> bool {
+        self.array
+            .write_element(self.calc_index(channel, frame), *value);
+        false
+    }
+
+    fn write_from_slice_to_frame(
+        &mut self,
+        frame: usize,
+        skip: usize,
+        slice: &[A::Sample],
+    ) -> (usize, usize) {
+        if frame >= self.frames || skip >= self.channels {
+            return (0, 0);
+        }
+        let samples_to_read = (self.channels - skip).min(slice.len());
+        self.array
+            .write_elements(self.calc_index(skip, frame), &slice[..samples_to_read]);
+        (samples_to_read, 0)
+    }
+}
+
+impl<'a, A> Adapter<'a, A::Sample> for SequentialTypedArray<A>
+where
+    A: TypedArray,
+    A::Sample: 'a,
+{
+    unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> A::Sample {
+        self.array.read_element(self.calc_index(channel, frame))
+    }
+
+    implement_size_getters!();
+
+    fn preferred_iteration(&self) -> IterationOrder {
+        IterationOrder::ChannelsOuter
+    }
+
+    fn write_from_channel_to_slice(
+        &self,
+        channel: usize,
+        skip: usize,
+        slice: &mut [A::Sample],
+    ) -> usize {
+        if channel >= self.channels || skip >= self.frames {
+            return 0;
+        }
+        let frames_to_write = (self.frames - skip).min(slice.len());
+        self.array.read_elements(
+            self.calc_index(channel, skip),
+            &mut slice[..frames_to_write],
+        );
+        frames_to_write
+    }
+}
+
+impl<'a, A> AdapterMut<'a, A::Sample> for SequentialTypedArray<A>
+where
+    A: TypedArray,
+    A::Sample: 'a,
+{
+    unsafe fn write_sample_unchecked(
+        &mut self,
+        channel: usize,
+        frame: usize,
+        value: &A::Sample,
+    ) -> bool {
+        self.array
+            .write_element(self.calc_index(channel, frame), *value);
+        false
+    }
+
+    fn write_from_slice_to_channel(
+        &mut self,
+        channel: usize,
+        skip: usize,
+        slice: &[A::Sample],
+    ) -> (usize, usize) {
+        if channel >= self.channels || skip >= self.frames {
+            return (0, 0);
+        }
+        let frames_to_read = (self.frames - skip).min(slice.len());
+        self.array
+            .write_elements(self.calc_index(channel, skip), &slice[..frames_to_read]);
+        (frames_to_read, 0)
+    }
+}
+
+/// Copy the channel data of an `AudioBuffer` to a buffer.
+/// The number of channels and frames copied are the smallest of the two buffers.
+/// Returns the number of frames copied,
+/// or the JavaScript exception if reading the `AudioBuffer` failed.
+pub fn copy_from_audio_buffer(
+    src: &AudioBuffer,
+    dst: &mut dyn AdapterMut<f32>,
+) -> Result<usize, JsValue> {
+    let channels = (src.number_of_channels() as usize).min(dst.channels());
+    let frames = (src.length() as usize).min(dst.frames());
+    let mut values = vec![0.0_f32; frames];
+    for channel in 0..channels {
+        src.copy_from_channel(&mut values, channel as i32)?;
+        dst.write_from_slice_to_channel(channel, 0, &values);
+    }
+    Ok(frames)
+}
+
+/// Copy the samples of a buffer to the channel data of an `AudioBuffer`.
+/// The number of channels and frames copied are the smallest of the two buffers.
+/// Returns the number of frames copied,
+/// or the JavaScript exception if writing the `AudioBuffer` failed.
+pub fn copy_to_audio_buffer(src: &dyn Adapter<f32>, dst: &AudioBuffer) -> Result<usize, JsValue> {
+    let channels = src.channels().min(dst.number_of_channels() as usize);
+    let frames = src.frames().min(dst.length() as usize);
+    let mut values = vec![0.0_f32; frames];
+    for channel in 0..channels {
+        src.write_from_channel_to_slice(channel, 0, &mut values);
+        dst.copy_to_channel(&values, channel as i32)?;
+    }
+    Ok(frames)
+}
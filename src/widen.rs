@@ -0,0 +1,202 @@
+//! # Lossless widening of integer samples
+//!
+//! This module provides [WidenAdapter](crate::widen::WidenAdapter),
+//! a wrapper that presents a buffer of integer samples as a buffer of a wider integer type,
+//! for example an `Adapter<i16>` as an `Adapter<i32>`.
+//! The values are shifted to the scale of the wider type,
+//! so that full scale of the wrapped samples is also full scale of the wider type.
+//! Unlike the converting wrappers in [adapter_to_float](crate::adapter_to_float),
+//! the values do not pass through a float type, and the conversion is exact.
+//! This is useful for bit-transparent pipelines that work with a single sample type.
+//!
+//! Writing narrows the values back to the wrapped type by shifting them back.
+//! The low bits that do not fit in the narrower type are truncated, without rounding.
+//! Narrowing never clips, and writes therefore never report clipping.
+//!
+//! ## Example
+//! Read 16-bit samples as 32-bit samples.
+//! ```
+//! use audioadapter::direct::InterleavedSlice;
+//! use audioadapter::widen::WidenAdapter;
+//! use audioadapter::Adapter;
+//!
+//! let data: [i16; 4] = [1, -1, i16::MAX, i16::MIN];
+//! let buffer = InterleavedSlice::new(&data, 2, 2).unwrap();
+//! let wide = WidenAdapter::<_, i32>::new(&buffer as &dyn Adapter<i16>);
+//! assert_eq!(wide.read_sample(0, 0), Some(1 << 16));
+//! assert_eq!(wide.read_sample(1, 1), Some(i32::MIN));
+//! ```
+
+use core::any::Any;
+use core::marker::PhantomData;
+
+use crate::{Adapter, AdapterMut, IterationOrder};
+
+/// A trait for integer types that can be widened to the integer type `T` without loss.
+/// Widening shifts the value up to the scale of `T`,
+/// and narrowing shifts it back down, discarding the lowest bits.
+pub trait Widen<T>: Sized {
+    /// Convert the value to the wider type.
+    fn widen(self) -> T;
+
+    /// Convert a value of the wider type to this type,
+    /// truncating the low bits that do not fit.
+    /// Every value of the wider type has a narrowed value,
+    /// so this never clips.
+    fn narrow(value: T) -> Self;
+}
+
+macro_rules! impl_widen {
+    ($narrow:ty, $($wide:ty),+) => {
+        $(
+            impl Widen<$wide> for $narrow {
+                fn widen(self) -> $wide {
+                    (self as $wide) << (8 * (core::mem::size_of::<$wide>() - core::mem::size_of::<$narrow>()))
+                }
+
+                fn narrow(value: $wide) -> Self {
+                    (value >> (8 * (core::mem::size_of::<$wide>() - core::mem::size_of::<$narrow>()))) as $narrow
+                }
+            }
+        )+
+    };
+}
+
+impl_widen!(i8, i16, i32, i64);
+impl_widen!(i16, i32, i64);
+impl_widen!(i32, i64);
+impl_widen!(u8, u16, u32, u64);
+impl_widen!(u16, u32, u64);
+impl_widen!(u32, u64);
+
+/// A wrapper for an [Adapter] or [AdapterMut] buffer of integer samples,
+/// that enables reading and writing the samples as the wider integer type `T`.
+///
+/// The wrapped buffer is accessed via a trait object without a [Send] or [Sync] bound,
+/// and therefore the wrapper is neither [Send] nor [Sync].
+pub struct WidenAdapter<U, T> {
+    buf: U,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<'a, T, S> WidenAdapter<&'a dyn Adapter<'a, S>, T>
+where
+    S: Widen<T> + Clone + 'a,
+{
+    /// Create a new wrapper for a buffer implementing the [Adapter] trait.
+    pub fn new(buf: &'a dyn Adapter<'a, S>) -> Self {
+        Self {
+            buf,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, S> WidenAdapter<&'a mut dyn AdapterMut<'a, S>, T>
+where
+    S: Widen<T> + Clone + 'a,
+{
+    /// Create a new wrapper for a mutable buffer implementing the [AdapterMut] trait.
+    pub fn new_mut(buf: &'a mut dyn AdapterMut<'a, S>) -> Self {
+        Self {
+            buf,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+macro_rules! implement_widened_reads {
+    () => {
+        unsafe fn read_sample_unchecked(&self, channel: usize, frame: usize) -> T {
+            self.buf.read_sample_unchecked(channel, frame).widen()
+        }
+
+        fn channels(&self) -> usize {
+            self.buf.channels()
+        }
+
+        fn frames(&self) -> usize {
+            self.buf.frames()
+        }
+
+        fn is_channel_active(&self, channel: usize) -> bool {
+            self.buf.is_channel_active(channel)
+        }
+
+        fn preferred_iteration(&self) -> IterationOrder {
+            self.buf.preferred_iteration()
+        }
+
+        fn metadata(&self) -> Option<&dyn Any> {
+            self.buf.metadata()
+        }
+    };
+}
+
+impl<'a, T, S> Adapter<'a, T> for WidenAdapter<&'a dyn Adapter<'a, S>, T>
+where
+    T: Clone + 'a,
+    S: Widen<T> + Clone + 'a,
+{
+    implement_widened_reads!();
+}
+
+impl<'a, T, S> Adapter<'a, T> for WidenAdapter<&'a mut dyn AdapterMut<'a, S>, T>
+where
+    T: Clone + 'a,
+    S: Widen<T> + Clone + 'a,
+{
+    implement_widened_reads!();
+}
+
+impl<'a, T, S> AdapterMut<'a, T> for WidenAdapter<&'a mut dyn AdapterMut<'a, S>, T>
+where
+    T: Clone + 'a,
+    S: Widen<T> + Clone + 'a,
+{
+    unsafe fn write_sample_unchecked(&mut self, channel: usize, frame: usize, value: &T) -> bool {
+        self.buf
+            .write_sample_unchecked(channel, frame, &S::narrow(value.clone()))
+    }
+
+    fn copy_frames_within(&mut self, src: usize, dest: usize, count: usize) -> Option<usize> {
+        self.buf.copy_frames_within(src, dest, count)
+    }
+}
+
+//   _____         _
+//  |_   _|__  ___| |_ ___
+//    | |/ _ \/ __| __/ __|
+//    | |  __/\__ \ |_\__ \
+//    |_|\___||___/\__|___/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direct::SequentialSlice;
+
+    #[test]
+    fn widen_and_narrow() {
+        assert_eq!(Widen::<i32>::widen(-1_i16), -65536);
+        assert_eq!(Widen::<i64>::widen(i8::MIN), i64::MIN);
+        assert_eq!(<i16 as Widen<i32>>::narrow(0x1234_5678), 0x1234);
+        assert_eq!(<i16 as Widen<i32>>::narrow(-1), -1);
+        assert_eq!(Widen::<u16>::widen(128_u8), 32768);
+        assert_eq!(<u8 as Widen<u32>>::narrow(u32::MAX), u8::MAX);
+    }
+
+    #[test]
+    fn read_and_write() {
+        let mut data: [i16; 4] = [1, 2, -3, -4];
+        let mut buffer = SequentialSlice::new_mut(&mut data, 2, 2).unwrap();
+        let mut wide = WidenAdapter::<_, i32>::new_mut(&mut buffer as &mut dyn AdapterMut<i16>);
+        assert_eq!(wide.read_sample(1, 0), Some(-3 << 16));
+        let mut values = [0; 2];
+        assert_eq!(wide.write_from_channel_to_slice(0, 0, &mut values), 2);
+        assert_eq!(values, [1 << 16, 2 << 16]);
+        assert_eq!(wide.write_sample(1, 1, &((5 << 16) + 0xffff)), Some(false));
+        assert_eq!(wide.read_sample(1, 1), Some(5 << 16));
+        assert_eq!(wide.write_sample(2, 0, &0), None);
+        assert_eq!(data, [1, 2, -3, 5]);
+    }
+}